@@ -1,7 +1,7 @@
 -- =============================================================================
--- Gianged Attendance - PostgreSQL Database Schema
+-- Gianged Attendance - Migration 001: Baseline schema
 -- =============================================================================
--- Version: 1.1.0
+-- Version: 1.1.0 (the schema installed by hand before versioned migrations)
 -- Description: Mini ERP for staff, department, and attendance management
 --
 -- Naming Conventions:
@@ -0,0 +1,50 @@
+-- =============================================================================
+-- Gianged Attendance - Migration 002: Sync history
+-- =============================================================================
+
+-- -----------------------------------------------------------------------------
+-- Table: app.sync_runs
+-- Description: History of device sync operations with per-run statistics
+-- -----------------------------------------------------------------------------
+CREATE TABLE IF NOT EXISTS app.sync_runs (
+    id               BIGSERIAL,
+    device           VARCHAR(100) NOT NULL,
+    started_at       TIMESTAMPTZ NOT NULL,
+    finished_at      TIMESTAMPTZ NOT NULL,
+    status           VARCHAR(20) NOT NULL,
+    downloaded       INTEGER NOT NULL DEFAULT 0,
+    inserted         INTEGER NOT NULL DEFAULT 0,
+    skipped          INTEGER NOT NULL DEFAULT 0,
+    device_cleared   BOOLEAN NOT NULL DEFAULT false,
+    duration_secs    DOUBLE PRECISION NOT NULL DEFAULT 0,
+    clock_drift_secs INTEGER,
+    error_message    TEXT,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Primary Key
+    CONSTRAINT pk_sync_runs PRIMARY KEY (id)
+);
+
+-- Recreated so installs that ran an earlier script.sql get the 'cancelled' status
+ALTER TABLE app.sync_runs DROP CONSTRAINT IF EXISTS ck_sync_runs_status;
+ALTER TABLE app.sync_runs
+    ADD CONSTRAINT ck_sync_runs_status CHECK (status IN ('success', 'failed', 'cancelled'));
+
+COMMENT ON TABLE app.sync_runs IS 'History of device sync operations with per-run statistics';
+COMMENT ON COLUMN app.sync_runs.id IS 'Primary key, auto-increment';
+COMMENT ON COLUMN app.sync_runs.device IS 'Device address the sync ran against';
+COMMENT ON COLUMN app.sync_runs.started_at IS 'Sync start timestamp';
+COMMENT ON COLUMN app.sync_runs.finished_at IS 'Sync finish timestamp';
+COMMENT ON COLUMN app.sync_runs.status IS 'Run outcome: success, failed, cancelled';
+COMMENT ON COLUMN app.sync_runs.downloaded IS 'Records downloaded from device';
+COMMENT ON COLUMN app.sync_runs.inserted IS 'Records inserted into attendance_logs';
+COMMENT ON COLUMN app.sync_runs.skipped IS 'Records skipped as duplicates';
+COMMENT ON COLUMN app.sync_runs.device_cleared IS 'Whether device attendance memory was cleared';
+COMMENT ON COLUMN app.sync_runs.duration_secs IS 'Run duration in seconds';
+COMMENT ON COLUMN app.sync_runs.clock_drift_secs IS 'Device clock minus workstation clock in seconds (NULL if unavailable)';
+COMMENT ON COLUMN app.sync_runs.error_message IS 'Error text for failed runs';
+COMMENT ON COLUMN app.sync_runs.created_at IS 'Record creation timestamp';
+
+CREATE INDEX IF NOT EXISTS idx_sync_runs_started_at ON app.sync_runs(started_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_sync_runs_status ON app.sync_runs(status);
@@ -0,0 +1,21 @@
+-- =============================================================================
+-- Gianged Attendance - Migration 003: Unmatched punches view
+-- =============================================================================
+
+-- -----------------------------------------------------------------------------
+-- View: app.v_unmatched_punches
+-- Description: Scanner UIDs with punches but no matching employee
+-- -----------------------------------------------------------------------------
+CREATE OR REPLACE VIEW app.v_unmatched_punches AS
+SELECT
+    al.scanner_uid,
+    COUNT(*) AS punch_count,
+    MIN(al.check_time) AS first_seen,
+    MAX(al.check_time) AS last_seen
+FROM app.attendance_logs al
+WHERE NOT EXISTS (
+    SELECT 1 FROM app.employees e WHERE e.scanner_uid = al.scanner_uid
+)
+GROUP BY al.scanner_uid;
+
+COMMENT ON VIEW app.v_unmatched_punches IS 'Scanner UIDs with punches but no matching employee';
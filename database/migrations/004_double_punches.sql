@@ -0,0 +1,64 @@
+-- =============================================================================
+-- Gianged Attendance - Migration 004: Double punch flag
+-- =============================================================================
+
+ALTER TABLE app.attendance_logs ADD COLUMN IF NOT EXISTS is_duplicate BOOLEAN NOT NULL DEFAULT false;
+
+COMMENT ON COLUMN app.attendance_logs.is_duplicate IS 'Repeat punch within the debounce window of the previous punch (excluded from reports)';
+
+-- -----------------------------------------------------------------------------
+-- View: app.v_attendance_details
+-- Description: Attendance logs with employee and department names
+-- Note: Dropped first because is_duplicate is added before created_at
+-- -----------------------------------------------------------------------------
+DROP VIEW IF EXISTS app.v_attendance_details;
+
+CREATE VIEW app.v_attendance_details AS
+SELECT
+    al.id,
+    al.scanner_uid,
+    e.id AS employee_id,
+    e.employee_code,
+    e.full_name,
+    e.department_id,
+    d.name AS department_name,
+    al.check_time,
+    al.verify_type,
+    CASE al.verify_type
+        WHEN 2 THEN 'fingerprint'
+        WHEN 101 THEN 'card'
+        ELSE 'unknown'
+    END AS verify_type_name,
+    al.status,
+    al.source,
+    al.is_duplicate,
+    al.created_at
+FROM app.attendance_logs al
+LEFT JOIN app.employees e ON al.scanner_uid = e.scanner_uid
+LEFT JOIN app.departments d ON e.department_id = d.id;
+
+COMMENT ON VIEW app.v_attendance_details IS 'Attendance logs with employee and department names';
+
+-- -----------------------------------------------------------------------------
+-- View: app.v_daily_attendance
+-- Description: Daily attendance summary per employee
+-- -----------------------------------------------------------------------------
+CREATE OR REPLACE VIEW app.v_daily_attendance AS
+SELECT
+    e.id AS employee_id,
+    e.employee_code,
+    e.full_name,
+    e.department_id,
+    d.name AS department_name,
+    DATE(al.check_time) AS work_date,
+    MIN(al.check_time) AS first_check,
+    MAX(al.check_time) AS last_check,
+    COUNT(*) AS check_count,
+    (EXTRACT(EPOCH FROM (MAX(al.check_time) - MIN(al.check_time))) / 3600.0)::float8 AS work_hours
+FROM app.attendance_logs al
+JOIN app.employees e ON al.scanner_uid = e.scanner_uid
+LEFT JOIN app.departments d ON e.department_id = d.id
+WHERE NOT al.is_duplicate
+GROUP BY e.id, e.employee_code, e.full_name, e.department_id, d.name, DATE(al.check_time);
+
+COMMENT ON VIEW app.v_daily_attendance IS 'Daily attendance summary per employee (double punches excluded)';
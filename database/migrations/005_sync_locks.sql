@@ -0,0 +1,31 @@
+-- =============================================================================
+-- Gianged Attendance - Migration 005: Sync lock holders
+-- =============================================================================
+
+-- -----------------------------------------------------------------------------
+-- Table: app.sync_locks
+-- Description: Current holder of each per-device sync lock
+-- Note: The lock itself is a PostgreSQL advisory lock; this row is informational
+-- -----------------------------------------------------------------------------
+CREATE TABLE IF NOT EXISTS app.sync_locks (
+    device          VARCHAR(100) NOT NULL,
+    holder_host     VARCHAR(255) NOT NULL,
+    holder_pid      INTEGER NOT NULL,
+    operation       VARCHAR(20) NOT NULL,
+    acquired_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    heartbeat_at    TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Primary Key
+    CONSTRAINT pk_sync_locks PRIMARY KEY (device),
+
+    -- Check Constraints
+    CONSTRAINT ck_sync_locks_operation CHECK (operation IN ('sync', 'clear'))
+);
+
+COMMENT ON TABLE app.sync_locks IS 'Current holder of each per-device sync lock (advisory lock heartbeat)';
+COMMENT ON COLUMN app.sync_locks.device IS 'Device address the lock protects';
+COMMENT ON COLUMN app.sync_locks.holder_host IS 'Hostname of the workstation holding the lock';
+COMMENT ON COLUMN app.sync_locks.holder_pid IS 'Process ID of the holder';
+COMMENT ON COLUMN app.sync_locks.operation IS 'Operation in progress: sync, clear';
+COMMENT ON COLUMN app.sync_locks.acquired_at IS 'Lock acquisition timestamp';
+COMMENT ON COLUMN app.sync_locks.heartbeat_at IS 'Last heartbeat from the holder';
@@ -0,0 +1,50 @@
+-- =============================================================================
+-- Gianged Attendance - Migration 006: Business timezone
+-- =============================================================================
+-- Attendance days were computed in three places with three timezones: a
+-- hardcoded 'Asia/Ho_Chi_Minh' in system.fn_date_from_timestamptz, the session
+-- timezone in v_daily_attendance, and the workstation timezone in the
+-- application. The application now sets the session timezone to the configured
+-- business timezone on every connection, and the views derive the day from it.
+
+-- -----------------------------------------------------------------------------
+-- Function: system.fn_date_from_timestamptz(TIMESTAMPTZ, TEXT)
+-- Description: Extract date from timestamptz in the given timezone
+-- Note: Replaces the one-argument version with its hardcoded timezone; the
+--       expression index on it was never used by attendance queries
+-- -----------------------------------------------------------------------------
+DROP INDEX IF EXISTS app.idx_attendance_logs_date;
+DROP FUNCTION IF EXISTS system.fn_date_from_timestamptz(TIMESTAMPTZ);
+
+CREATE OR REPLACE FUNCTION system.fn_date_from_timestamptz(ts TIMESTAMPTZ, tz TEXT)
+RETURNS DATE AS $$
+    SELECT (ts AT TIME ZONE tz)::DATE;
+$$ LANGUAGE sql IMMUTABLE STRICT;
+
+COMMENT ON FUNCTION system.fn_date_from_timestamptz(TIMESTAMPTZ, TEXT) IS 'Extract date from timestamptz in the given timezone';
+
+-- -----------------------------------------------------------------------------
+-- View: app.v_daily_attendance
+-- Description: Daily attendance summary per employee
+-- Note: work_date is the day in the session timezone (the business timezone)
+-- -----------------------------------------------------------------------------
+CREATE OR REPLACE VIEW app.v_daily_attendance AS
+SELECT
+    e.id AS employee_id,
+    e.employee_code,
+    e.full_name,
+    e.department_id,
+    d.name AS department_name,
+    system.fn_date_from_timestamptz(al.check_time, current_setting('TimeZone')) AS work_date,
+    MIN(al.check_time) AS first_check,
+    MAX(al.check_time) AS last_check,
+    COUNT(*) AS check_count,
+    (EXTRACT(EPOCH FROM (MAX(al.check_time) - MIN(al.check_time))) / 3600.0)::float8 AS work_hours
+FROM app.attendance_logs al
+JOIN app.employees e ON al.scanner_uid = e.scanner_uid
+LEFT JOIN app.departments d ON e.department_id = d.id
+WHERE NOT al.is_duplicate
+GROUP BY e.id, e.employee_code, e.full_name, e.department_id, d.name,
+    system.fn_date_from_timestamptz(al.check_time, current_setting('TimeZone'));
+
+COMMENT ON VIEW app.v_daily_attendance IS 'Daily attendance summary per employee in the session (business) timezone, double punches excluded';
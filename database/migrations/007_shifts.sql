@@ -0,0 +1,227 @@
+-- =============================================================================
+-- Gianged Attendance - Migration 007: Shifts and schedules
+-- =============================================================================
+
+-- -----------------------------------------------------------------------------
+-- Table: app.shifts
+-- Description: Shift definitions (expected working hours)
+-- -----------------------------------------------------------------------------
+CREATE TABLE IF NOT EXISTS app.shifts (
+    id                  SERIAL,
+    name                VARCHAR(100) NOT NULL,
+    start_time          TIME NOT NULL,
+    end_time            TIME NOT NULL,
+    break_minutes       INTEGER NOT NULL DEFAULT 0,
+    late_grace_minutes  INTEGER NOT NULL DEFAULT 0,
+    early_grace_minutes INTEGER NOT NULL DEFAULT 0,
+    crosses_midnight    BOOLEAN NOT NULL DEFAULT false,
+    is_active           BOOLEAN NOT NULL DEFAULT true,
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Primary Key
+    CONSTRAINT pk_shifts PRIMARY KEY (id),
+
+    -- Unique Constraints
+    CONSTRAINT uq_shifts_name UNIQUE (name),
+
+    -- Check Constraints
+    CONSTRAINT ck_shifts_crosses_midnight CHECK (crosses_midnight = (end_time <= start_time)),
+    CONSTRAINT ck_shifts_break_minutes CHECK (break_minutes >= 0),
+    CONSTRAINT ck_shifts_grace_minutes CHECK (late_grace_minutes >= 0 AND early_grace_minutes >= 0)
+);
+
+COMMENT ON TABLE app.shifts IS 'Shift definitions (expected working hours)';
+COMMENT ON COLUMN app.shifts.id IS 'Primary key, auto-increment';
+COMMENT ON COLUMN app.shifts.name IS 'Shift name';
+COMMENT ON COLUMN app.shifts.start_time IS 'Shift start (business timezone wall clock)';
+COMMENT ON COLUMN app.shifts.end_time IS 'Shift end (business timezone wall clock)';
+COMMENT ON COLUMN app.shifts.break_minutes IS 'Unpaid break length in minutes';
+COMMENT ON COLUMN app.shifts.late_grace_minutes IS 'Minutes after start before a check-in counts as late';
+COMMENT ON COLUMN app.shifts.early_grace_minutes IS 'Minutes before end before a check-out counts as early';
+COMMENT ON COLUMN app.shifts.crosses_midnight IS 'Shift ends on the day after it starts (end_time <= start_time)';
+COMMENT ON COLUMN app.shifts.is_active IS 'Soft delete flag';
+COMMENT ON COLUMN app.shifts.created_at IS 'Record creation timestamp';
+COMMENT ON COLUMN app.shifts.updated_at IS 'Record last update timestamp';
+
+-- -----------------------------------------------------------------------------
+-- Table: app.shift_patterns
+-- Description: Rotation patterns repeating every cycle_days days
+-- -----------------------------------------------------------------------------
+CREATE TABLE IF NOT EXISTS app.shift_patterns (
+    id              SERIAL,
+    name            VARCHAR(100) NOT NULL,
+    cycle_days      INTEGER NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Primary Key
+    CONSTRAINT pk_shift_patterns PRIMARY KEY (id),
+
+    -- Unique Constraints
+    CONSTRAINT uq_shift_patterns_name UNIQUE (name),
+
+    -- Check Constraints
+    CONSTRAINT ck_shift_patterns_cycle_days CHECK (cycle_days BETWEEN 1 AND 366)
+);
+
+COMMENT ON TABLE app.shift_patterns IS 'Rotation patterns repeating every cycle_days days';
+COMMENT ON COLUMN app.shift_patterns.id IS 'Primary key, auto-increment';
+COMMENT ON COLUMN app.shift_patterns.name IS 'Pattern name';
+COMMENT ON COLUMN app.shift_patterns.cycle_days IS 'Cycle length in days (7 for a weekly schedule)';
+COMMENT ON COLUMN app.shift_patterns.created_at IS 'Record creation timestamp';
+COMMENT ON COLUMN app.shift_patterns.updated_at IS 'Record last update timestamp';
+
+-- -----------------------------------------------------------------------------
+-- Table: app.shift_pattern_days
+-- Description: Shift worked on each day of a pattern cycle
+-- Note: Days without a row are days off
+-- -----------------------------------------------------------------------------
+CREATE TABLE IF NOT EXISTS app.shift_pattern_days (
+    pattern_id      INTEGER NOT NULL,
+    day_index       INTEGER NOT NULL,
+    shift_id        INTEGER NOT NULL,
+
+    -- Primary Key
+    CONSTRAINT pk_shift_pattern_days PRIMARY KEY (pattern_id, day_index),
+
+    -- Foreign Keys
+    CONSTRAINT fk_shift_pattern_days_pattern FOREIGN KEY (pattern_id)
+        REFERENCES app.shift_patterns(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    CONSTRAINT fk_shift_pattern_days_shift FOREIGN KEY (shift_id)
+        REFERENCES app.shifts(id)
+        ON DELETE RESTRICT
+        ON UPDATE CASCADE,
+
+    -- Check Constraints
+    CONSTRAINT ck_shift_pattern_days_day_index CHECK (day_index >= 0)
+);
+
+COMMENT ON TABLE app.shift_pattern_days IS 'Shift worked on each day of a pattern cycle (missing days are days off)';
+COMMENT ON COLUMN app.shift_pattern_days.pattern_id IS 'FK to shift_patterns';
+COMMENT ON COLUMN app.shift_pattern_days.day_index IS 'Zero-based day within the cycle, counted from the assignment start date';
+COMMENT ON COLUMN app.shift_pattern_days.shift_id IS 'FK to shifts';
+
+-- -----------------------------------------------------------------------------
+-- Table: app.shift_assignments
+-- Description: Pattern assigned to an employee or a department for a date range
+-- Note: Employee assignments take precedence over department assignments; the
+--       nearest ancestor department applies when a department has none
+-- -----------------------------------------------------------------------------
+CREATE TABLE IF NOT EXISTS app.shift_assignments (
+    id              SERIAL,
+    employee_id     INTEGER,
+    department_id   INTEGER,
+    pattern_id      INTEGER NOT NULL,
+    start_date      DATE NOT NULL,
+    end_date        DATE,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Primary Key
+    CONSTRAINT pk_shift_assignments PRIMARY KEY (id),
+
+    -- Foreign Keys
+    CONSTRAINT fk_shift_assignments_employee FOREIGN KEY (employee_id)
+        REFERENCES app.employees(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    CONSTRAINT fk_shift_assignments_department FOREIGN KEY (department_id)
+        REFERENCES app.departments(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    CONSTRAINT fk_shift_assignments_pattern FOREIGN KEY (pattern_id)
+        REFERENCES app.shift_patterns(id)
+        ON DELETE RESTRICT
+        ON UPDATE CASCADE,
+
+    -- Check Constraints
+    CONSTRAINT ck_shift_assignments_target CHECK ((employee_id IS NULL) <> (department_id IS NULL)),
+    CONSTRAINT ck_shift_assignments_dates CHECK (end_date IS NULL OR end_date >= start_date)
+);
+
+COMMENT ON TABLE app.shift_assignments IS 'Pattern assigned to an employee or a department for a date range';
+COMMENT ON COLUMN app.shift_assignments.id IS 'Primary key, auto-increment';
+COMMENT ON COLUMN app.shift_assignments.employee_id IS 'FK to employees (set for employee assignments)';
+COMMENT ON COLUMN app.shift_assignments.department_id IS 'FK to departments (set for department assignments)';
+COMMENT ON COLUMN app.shift_assignments.pattern_id IS 'FK to shift_patterns';
+COMMENT ON COLUMN app.shift_assignments.start_date IS 'First effective date, also day 0 of the pattern cycle';
+COMMENT ON COLUMN app.shift_assignments.end_date IS 'Last effective date (NULL = open-ended)';
+COMMENT ON COLUMN app.shift_assignments.created_at IS 'Record creation timestamp';
+COMMENT ON COLUMN app.shift_assignments.updated_at IS 'Record last update timestamp';
+
+CREATE INDEX IF NOT EXISTS idx_shift_assignments_employee ON app.shift_assignments(employee_id)
+    WHERE employee_id IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_shift_assignments_department ON app.shift_assignments(department_id)
+    WHERE department_id IS NOT NULL;
+
+-- -----------------------------------------------------------------------------
+-- Table: app.shift_rosters
+-- Description: Per-day roster entries overriding assignments
+-- -----------------------------------------------------------------------------
+CREATE TABLE IF NOT EXISTS app.shift_rosters (
+    id              SERIAL,
+    employee_id     INTEGER NOT NULL,
+    work_date       DATE NOT NULL,
+    shift_id        INTEGER,
+    note            VARCHAR(200),
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Primary Key
+    CONSTRAINT pk_shift_rosters PRIMARY KEY (id),
+
+    -- Foreign Keys
+    CONSTRAINT fk_shift_rosters_employee FOREIGN KEY (employee_id)
+        REFERENCES app.employees(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    CONSTRAINT fk_shift_rosters_shift FOREIGN KEY (shift_id)
+        REFERENCES app.shifts(id)
+        ON DELETE RESTRICT
+        ON UPDATE CASCADE,
+
+    -- Unique Constraints
+    CONSTRAINT uq_shift_rosters_employee_date UNIQUE (employee_id, work_date)
+);
+
+COMMENT ON TABLE app.shift_rosters IS 'Per-day roster entries overriding shift assignments';
+COMMENT ON COLUMN app.shift_rosters.id IS 'Primary key, auto-increment';
+COMMENT ON COLUMN app.shift_rosters.employee_id IS 'FK to employees';
+COMMENT ON COLUMN app.shift_rosters.work_date IS 'Day the entry applies to (the shift start date)';
+COMMENT ON COLUMN app.shift_rosters.shift_id IS 'FK to shifts (NULL = day off)';
+COMMENT ON COLUMN app.shift_rosters.note IS 'Optional note';
+COMMENT ON COLUMN app.shift_rosters.created_at IS 'Record creation timestamp';
+COMMENT ON COLUMN app.shift_rosters.updated_at IS 'Record last update timestamp';
+
+CREATE INDEX IF NOT EXISTS idx_shift_rosters_work_date ON app.shift_rosters(work_date);
+
+-- -----------------------------------------------------------------------------
+-- Triggers: Auto-update updated_at
+-- -----------------------------------------------------------------------------
+DROP TRIGGER IF EXISTS trg_shifts_update_timestamp ON app.shifts;
+CREATE TRIGGER trg_shifts_update_timestamp
+    BEFORE UPDATE ON app.shifts
+    FOR EACH ROW
+    EXECUTE FUNCTION system.fn_update_timestamp();
+
+DROP TRIGGER IF EXISTS trg_shift_patterns_update_timestamp ON app.shift_patterns;
+CREATE TRIGGER trg_shift_patterns_update_timestamp
+    BEFORE UPDATE ON app.shift_patterns
+    FOR EACH ROW
+    EXECUTE FUNCTION system.fn_update_timestamp();
+
+DROP TRIGGER IF EXISTS trg_shift_assignments_update_timestamp ON app.shift_assignments;
+CREATE TRIGGER trg_shift_assignments_update_timestamp
+    BEFORE UPDATE ON app.shift_assignments
+    FOR EACH ROW
+    EXECUTE FUNCTION system.fn_update_timestamp();
+
+DROP TRIGGER IF EXISTS trg_shift_rosters_update_timestamp ON app.shift_rosters;
+CREATE TRIGGER trg_shift_rosters_update_timestamp
+    BEFORE UPDATE ON app.shift_rosters
+    FOR EACH ROW
+    EXECUTE FUNCTION system.fn_update_timestamp();
@@ -0,0 +1,162 @@
+-- =============================================================================
+-- Gianged Attendance - Migration 008: Work day attribution
+-- =============================================================================
+-- v_daily_attendance grouped punches by calendar date, so an overnight shift
+-- (e.g. 22:00-06:00) became two half-days. Punches are now attributed to a work
+-- day: after an overnight shift the morning punches belong to the day the shift
+-- started, otherwise punches before the configured day start (session setting
+-- app.day_start, set by the application) belong to the previous day.
+-- The same rule is implemented in Rust by models::shift::work_date.
+
+-- -----------------------------------------------------------------------------
+-- Function: system.fn_scheduled_shift(INTEGER, DATE)
+-- Description: Shift an employee is scheduled to start on a day
+-- Note: Precedence matches models::shift::Schedule: roster entry, then the
+--       employee's assignment, then the nearest department assignment up the
+--       department tree. Among overlapping assignments the latest start wins.
+-- -----------------------------------------------------------------------------
+CREATE OR REPLACE FUNCTION system.fn_scheduled_shift(p_employee_id INTEGER, p_date DATE)
+RETURNS INTEGER AS $$
+DECLARE
+    v_shift_id      INTEGER;
+    v_found         BOOLEAN;
+    v_department_id INTEGER;
+    v_depth         INTEGER := 0;
+BEGIN
+    -- Roster entry (a NULL shift is a rostered day off)
+    SELECT r.shift_id, true INTO v_shift_id, v_found
+    FROM app.shift_rosters r
+    WHERE r.employee_id = p_employee_id AND r.work_date = p_date;
+    IF v_found THEN
+        RETURN v_shift_id;
+    END IF;
+
+    -- Pattern assigned to the employee
+    SELECT pd.shift_id, true INTO v_shift_id, v_found
+    FROM (
+        SELECT a.pattern_id, a.start_date
+        FROM app.shift_assignments a
+        WHERE a.employee_id = p_employee_id
+            AND a.start_date <= p_date
+            AND (a.end_date IS NULL OR p_date <= a.end_date)
+        ORDER BY a.start_date DESC, a.id DESC
+        LIMIT 1
+    ) a
+    JOIN app.shift_patterns p ON p.id = a.pattern_id
+    LEFT JOIN app.shift_pattern_days pd
+        ON pd.pattern_id = p.id AND pd.day_index = (p_date - a.start_date) % p.cycle_days;
+    IF v_found THEN
+        RETURN v_shift_id;
+    END IF;
+
+    -- Pattern assigned to the department or a parent department; the depth cap
+    -- guards against cycles
+    SELECT e.department_id INTO v_department_id FROM app.employees e WHERE e.id = p_employee_id;
+    WHILE v_department_id IS NOT NULL AND v_depth < 32 LOOP
+        SELECT pd.shift_id, true INTO v_shift_id, v_found
+        FROM (
+            SELECT a.pattern_id, a.start_date
+            FROM app.shift_assignments a
+            WHERE a.department_id = v_department_id
+                AND a.start_date <= p_date
+                AND (a.end_date IS NULL OR p_date <= a.end_date)
+            ORDER BY a.start_date DESC, a.id DESC
+            LIMIT 1
+        ) a
+        JOIN app.shift_patterns p ON p.id = a.pattern_id
+        LEFT JOIN app.shift_pattern_days pd
+            ON pd.pattern_id = p.id AND pd.day_index = (p_date - a.start_date) % p.cycle_days;
+        IF v_found THEN
+            RETURN v_shift_id;
+        END IF;
+
+        SELECT d.parent_id INTO v_department_id FROM app.departments d WHERE d.id = v_department_id;
+        v_depth := v_depth + 1;
+    END LOOP;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql STABLE;
+
+COMMENT ON FUNCTION system.fn_scheduled_shift(INTEGER, DATE) IS 'Shift an employee is scheduled to start on a day (roster, employee, then department assignment)';
+
+-- -----------------------------------------------------------------------------
+-- Function: system.fn_work_date(INTEGER, TIMESTAMPTZ, TEXT, TIME)
+-- Description: Work day a punch belongs to
+-- Note: If the shift scheduled the previous day crosses midnight, punches up to
+--       the middle of the gap between its end and the next start belong to the
+--       previous day (before 14:00 for a 22:00-06:00 shift). Otherwise punches
+--       before day_start belong to the previous day.
+-- -----------------------------------------------------------------------------
+CREATE OR REPLACE FUNCTION system.fn_work_date(
+    p_employee_id INTEGER,
+    p_check_time TIMESTAMPTZ,
+    tz TEXT,
+    day_start TIME
+)
+RETURNS DATE AS $$
+DECLARE
+    v_local     TIMESTAMP := p_check_time AT TIME ZONE tz;
+    v_date      DATE := v_local::DATE;
+    v_start     TIME;
+    v_end       TIME;
+BEGIN
+    SELECT s.start_time, s.end_time INTO v_start, v_end
+    FROM app.shifts s
+    WHERE s.id = system.fn_scheduled_shift(p_employee_id, v_date - 1)
+        AND s.crosses_midnight;
+
+    IF FOUND THEN
+        IF v_local::TIME < v_end + (v_start - v_end) / 2 THEN
+            RETURN v_date - 1;
+        END IF;
+        RETURN v_date;
+    END IF;
+
+    IF v_local::TIME < day_start THEN
+        RETURN v_date - 1;
+    END IF;
+    RETURN v_date;
+END;
+$$ LANGUAGE plpgsql STABLE;
+
+COMMENT ON FUNCTION system.fn_work_date(INTEGER, TIMESTAMPTZ, TEXT, TIME) IS 'Work day a punch belongs to, following overnight shifts and the work day start';
+
+-- -----------------------------------------------------------------------------
+-- View: app.v_daily_attendance
+-- Description: Daily attendance summary per employee
+-- Note: work_date is the work day (see system.fn_work_date) in the session
+--       timezone, so an overnight shift is a single record
+-- -----------------------------------------------------------------------------
+CREATE OR REPLACE VIEW app.v_daily_attendance AS
+WITH punches AS (
+    SELECT
+        e.id AS employee_id,
+        al.check_time,
+        system.fn_work_date(
+            e.id,
+            al.check_time,
+            current_setting('TimeZone'),
+            COALESCE(NULLIF(current_setting('app.day_start', true), ''), '00:00')::TIME
+        ) AS work_date
+    FROM app.attendance_logs al
+    JOIN app.employees e ON al.scanner_uid = e.scanner_uid
+    WHERE NOT al.is_duplicate
+)
+SELECT
+    e.id AS employee_id,
+    e.employee_code,
+    e.full_name,
+    e.department_id,
+    d.name AS department_name,
+    p.work_date,
+    MIN(p.check_time) AS first_check,
+    MAX(p.check_time) AS last_check,
+    COUNT(*) AS check_count,
+    (EXTRACT(EPOCH FROM (MAX(p.check_time) - MIN(p.check_time))) / 3600.0)::float8 AS work_hours
+FROM punches p
+JOIN app.employees e ON p.employee_id = e.id
+LEFT JOIN app.departments d ON e.department_id = d.id
+GROUP BY e.id, e.employee_code, e.full_name, e.department_id, d.name, p.work_date;
+
+COMMENT ON VIEW app.v_daily_attendance IS 'Daily attendance summary per employee by work day in the session (business) timezone, double punches excluded';
@@ -0,0 +1,46 @@
+-- =============================================================================
+-- Gianged Attendance - Migration 009: Punch sessions
+-- =============================================================================
+-- Worked time is now the sum of paired in/out sessions, computed in Rust from
+-- each work day's punches. The detail view exposes the work day of every punch
+-- so a page of punches can be paired with the rest of its day.
+
+-- -----------------------------------------------------------------------------
+-- View: app.v_attendance_details
+-- Description: Attendance logs with employee and department names
+-- Note: work_date (see system.fn_work_date) is appended; NULL for punches of
+--       unknown scanner UIDs
+-- -----------------------------------------------------------------------------
+CREATE OR REPLACE VIEW app.v_attendance_details AS
+SELECT
+    al.id,
+    al.scanner_uid,
+    e.id AS employee_id,
+    e.employee_code,
+    e.full_name,
+    e.department_id,
+    d.name AS department_name,
+    al.check_time,
+    al.verify_type,
+    CASE al.verify_type
+        WHEN 2 THEN 'fingerprint'
+        WHEN 101 THEN 'card'
+        ELSE 'unknown'
+    END AS verify_type_name,
+    al.status,
+    al.source,
+    al.is_duplicate,
+    al.created_at,
+    CASE WHEN e.id IS NOT NULL THEN
+        system.fn_work_date(
+            e.id,
+            al.check_time,
+            current_setting('TimeZone'),
+            COALESCE(NULLIF(current_setting('app.day_start', true), ''), '00:00')::TIME
+        )
+    END AS work_date
+FROM app.attendance_logs al
+LEFT JOIN app.employees e ON al.scanner_uid = e.scanner_uid
+LEFT JOIN app.departments d ON e.department_id = d.id;
+
+COMMENT ON VIEW app.v_attendance_details IS 'Attendance logs with employee and department names and the work day of each punch';
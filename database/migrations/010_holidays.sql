@@ -0,0 +1,48 @@
+-- =============================================================================
+-- Gianged Attendance - Migration 010: Holidays
+-- =============================================================================
+
+-- -----------------------------------------------------------------------------
+-- Table: app.holidays
+-- Description: Public holidays and company days off
+-- -----------------------------------------------------------------------------
+CREATE TABLE IF NOT EXISTS app.holidays (
+    id              SERIAL,
+    holiday_date    DATE NOT NULL,
+    name            VARCHAR(100) NOT NULL,
+    is_paid         BOOLEAN NOT NULL DEFAULT true,
+    department_id   INTEGER,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Primary Key
+    CONSTRAINT pk_holidays PRIMARY KEY (id),
+
+    -- Foreign Keys
+    CONSTRAINT fk_holidays_department FOREIGN KEY (department_id)
+        REFERENCES app.departments(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE
+);
+
+COMMENT ON TABLE app.holidays IS 'Public holidays and company days off';
+COMMENT ON COLUMN app.holidays.id IS 'Primary key, auto-increment';
+COMMENT ON COLUMN app.holidays.holiday_date IS 'Day off';
+COMMENT ON COLUMN app.holidays.name IS 'Holiday name';
+COMMENT ON COLUMN app.holidays.is_paid IS 'Paid day off';
+COMMENT ON COLUMN app.holidays.department_id IS 'FK to departments; applies to its sub-departments too (NULL = whole company)';
+COMMENT ON COLUMN app.holidays.created_at IS 'Record creation timestamp';
+COMMENT ON COLUMN app.holidays.updated_at IS 'Record last update timestamp';
+
+-- One entry per day and scope; COALESCE since NULL scopes are otherwise distinct
+CREATE UNIQUE INDEX IF NOT EXISTS uq_holidays_date_department
+    ON app.holidays(holiday_date, COALESCE(department_id, 0));
+
+-- -----------------------------------------------------------------------------
+-- Triggers: Auto-update updated_at
+-- -----------------------------------------------------------------------------
+DROP TRIGGER IF EXISTS trg_holidays_update_timestamp ON app.holidays;
+CREATE TRIGGER trg_holidays_update_timestamp
+    BEFORE UPDATE ON app.holidays
+    FOR EACH ROW
+    EXECUTE FUNCTION system.fn_update_timestamp();
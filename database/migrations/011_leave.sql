@@ -0,0 +1,162 @@
+-- =============================================================================
+-- Gianged Attendance - Migration 011: Leave
+-- =============================================================================
+
+-- -----------------------------------------------------------------------------
+-- Table: app.leave_types
+-- Description: Kinds of leave (annual, sick, unpaid, ...)
+-- -----------------------------------------------------------------------------
+CREATE TABLE IF NOT EXISTS app.leave_types (
+    id              SERIAL,
+    name            VARCHAR(50) NOT NULL,
+    is_paid         BOOLEAN NOT NULL DEFAULT true,
+    default_days    DOUBLE PRECISION,
+    is_active       BOOLEAN NOT NULL DEFAULT true,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Primary Key
+    CONSTRAINT pk_leave_types PRIMARY KEY (id),
+
+    -- Unique Constraints
+    CONSTRAINT uq_leave_types_name UNIQUE (name),
+
+    -- Check Constraints
+    CONSTRAINT ck_leave_types_default_days CHECK (default_days IS NULL OR default_days >= 0)
+);
+
+COMMENT ON TABLE app.leave_types IS 'Kinds of leave (annual, sick, unpaid, ...)';
+COMMENT ON COLUMN app.leave_types.id IS 'Primary key, auto-increment';
+COMMENT ON COLUMN app.leave_types.name IS 'Leave type name';
+COMMENT ON COLUMN app.leave_types.is_paid IS 'Paid leave';
+COMMENT ON COLUMN app.leave_types.default_days IS 'Yearly entitlement when an employee has none set (NULL = no limit, no balance)';
+COMMENT ON COLUMN app.leave_types.is_active IS 'Soft delete flag';
+COMMENT ON COLUMN app.leave_types.created_at IS 'Record creation timestamp';
+COMMENT ON COLUMN app.leave_types.updated_at IS 'Record last update timestamp';
+
+-- Annual leave is 12 days a year under the Labour Code
+INSERT INTO app.leave_types (name, is_paid, default_days) VALUES
+    ('Annual', true, 12),
+    ('Sick', true, NULL),
+    ('Unpaid', false, NULL)
+ON CONFLICT (name) DO NOTHING;
+
+-- -----------------------------------------------------------------------------
+-- Table: app.leave_entitlements
+-- Description: Per-employee yearly leave entitlement
+-- -----------------------------------------------------------------------------
+CREATE TABLE IF NOT EXISTS app.leave_entitlements (
+    id              SERIAL,
+    employee_id     INTEGER NOT NULL,
+    leave_type_id   INTEGER NOT NULL,
+    year            INTEGER NOT NULL,
+    days            DOUBLE PRECISION NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Primary Key
+    CONSTRAINT pk_leave_entitlements PRIMARY KEY (id),
+
+    -- Foreign Keys
+    CONSTRAINT fk_leave_entitlements_employee FOREIGN KEY (employee_id)
+        REFERENCES app.employees(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    CONSTRAINT fk_leave_entitlements_leave_type FOREIGN KEY (leave_type_id)
+        REFERENCES app.leave_types(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+
+    -- Unique Constraints
+    CONSTRAINT uq_leave_entitlements_employee_type_year UNIQUE (employee_id, leave_type_id, year),
+
+    -- Check Constraints
+    CONSTRAINT ck_leave_entitlements_days CHECK (days >= 0)
+);
+
+COMMENT ON TABLE app.leave_entitlements IS 'Per-employee yearly leave entitlement, overriding the leave type default';
+COMMENT ON COLUMN app.leave_entitlements.id IS 'Primary key, auto-increment';
+COMMENT ON COLUMN app.leave_entitlements.employee_id IS 'FK to employees';
+COMMENT ON COLUMN app.leave_entitlements.leave_type_id IS 'FK to leave_types';
+COMMENT ON COLUMN app.leave_entitlements.year IS 'Calendar year';
+COMMENT ON COLUMN app.leave_entitlements.days IS 'Days entitled in the year';
+COMMENT ON COLUMN app.leave_entitlements.created_at IS 'Record creation timestamp';
+COMMENT ON COLUMN app.leave_entitlements.updated_at IS 'Record last update timestamp';
+
+-- -----------------------------------------------------------------------------
+-- Table: app.leave_requests
+-- Description: Leave requested by employees and its approval
+-- -----------------------------------------------------------------------------
+CREATE TABLE IF NOT EXISTS app.leave_requests (
+    id              SERIAL,
+    employee_id     INTEGER NOT NULL,
+    leave_type_id   INTEGER NOT NULL,
+    start_date      DATE NOT NULL,
+    end_date        DATE NOT NULL,
+    half_day        BOOLEAN NOT NULL DEFAULT false,
+    days            DOUBLE PRECISION NOT NULL,
+    status          VARCHAR(20) NOT NULL DEFAULT 'pending',
+    approver        VARCHAR(100),
+    note            VARCHAR(500),
+    decided_at      TIMESTAMPTZ,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Primary Key
+    CONSTRAINT pk_leave_requests PRIMARY KEY (id),
+
+    -- Foreign Keys
+    CONSTRAINT fk_leave_requests_employee FOREIGN KEY (employee_id)
+        REFERENCES app.employees(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    CONSTRAINT fk_leave_requests_leave_type FOREIGN KEY (leave_type_id)
+        REFERENCES app.leave_types(id)
+        ON DELETE RESTRICT
+        ON UPDATE CASCADE,
+
+    -- Check Constraints
+    CONSTRAINT ck_leave_requests_dates CHECK (end_date >= start_date),
+    CONSTRAINT ck_leave_requests_half_day CHECK (NOT half_day OR start_date = end_date),
+    CONSTRAINT ck_leave_requests_days CHECK (days >= 0),
+    CONSTRAINT ck_leave_requests_status CHECK (status IN ('pending', 'approved', 'rejected', 'cancelled'))
+);
+
+COMMENT ON TABLE app.leave_requests IS 'Leave requested by employees and its approval';
+COMMENT ON COLUMN app.leave_requests.id IS 'Primary key, auto-increment';
+COMMENT ON COLUMN app.leave_requests.employee_id IS 'FK to employees';
+COMMENT ON COLUMN app.leave_requests.leave_type_id IS 'FK to leave_types';
+COMMENT ON COLUMN app.leave_requests.start_date IS 'First day of leave';
+COMMENT ON COLUMN app.leave_requests.end_date IS 'Last day of leave (inclusive)';
+COMMENT ON COLUMN app.leave_requests.half_day IS 'Half a day of leave (single-day requests only)';
+COMMENT ON COLUMN app.leave_requests.days IS 'Working days taken, excluding holidays and days off, computed when requested';
+COMMENT ON COLUMN app.leave_requests.status IS 'pending, approved, rejected or cancelled';
+COMMENT ON COLUMN app.leave_requests.approver IS 'Who approved or rejected the request';
+COMMENT ON COLUMN app.leave_requests.note IS 'Optional note';
+COMMENT ON COLUMN app.leave_requests.decided_at IS 'When the request was approved or rejected';
+COMMENT ON COLUMN app.leave_requests.created_at IS 'Record creation timestamp';
+COMMENT ON COLUMN app.leave_requests.updated_at IS 'Record last update timestamp';
+
+CREATE INDEX IF NOT EXISTS idx_leave_requests_employee_dates ON app.leave_requests(employee_id, start_date, end_date);
+CREATE INDEX IF NOT EXISTS idx_leave_requests_status ON app.leave_requests(status);
+
+-- -----------------------------------------------------------------------------
+-- Triggers: Auto-update updated_at
+-- -----------------------------------------------------------------------------
+DROP TRIGGER IF EXISTS trg_leave_types_update_timestamp ON app.leave_types;
+CREATE TRIGGER trg_leave_types_update_timestamp
+    BEFORE UPDATE ON app.leave_types
+    FOR EACH ROW
+    EXECUTE FUNCTION system.fn_update_timestamp();
+
+DROP TRIGGER IF EXISTS trg_leave_entitlements_update_timestamp ON app.leave_entitlements;
+CREATE TRIGGER trg_leave_entitlements_update_timestamp
+    BEFORE UPDATE ON app.leave_entitlements
+    FOR EACH ROW
+    EXECUTE FUNCTION system.fn_update_timestamp();
+
+DROP TRIGGER IF EXISTS trg_leave_requests_update_timestamp ON app.leave_requests;
+CREATE TRIGGER trg_leave_requests_update_timestamp
+    BEFORE UPDATE ON app.leave_requests
+    FOR EACH ROW
+    EXECUTE FUNCTION system.fn_update_timestamp();
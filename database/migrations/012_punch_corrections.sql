@@ -0,0 +1,161 @@
+-- =============================================================================
+-- Gianged Attendance - Migration 012: Punch corrections
+-- =============================================================================
+-- Forgotten punches are added by hand and wrong ones corrected or voided, each
+-- with a reason and the acting user. Punch rows are never edited or deleted:
+-- a correction voids the original and adds a manual replacement, and voided
+-- punches are left out of reports like double punches.
+
+ALTER TABLE app.attendance_logs ADD COLUMN IF NOT EXISTS is_voided BOOLEAN NOT NULL DEFAULT false;
+
+COMMENT ON COLUMN app.attendance_logs.is_voided IS 'Voided or replaced by a punch correction (excluded from reports)';
+
+-- Manual punches carry no verification
+ALTER TABLE app.attendance_logs DROP CONSTRAINT IF EXISTS ck_attendance_logs_verify_type;
+ALTER TABLE app.attendance_logs
+    ADD CONSTRAINT ck_attendance_logs_verify_type CHECK (verify_type IN (0, 2, 101));
+
+COMMENT ON COLUMN app.attendance_logs.verify_type IS 'Verification method: 0=none (manual entry), 2=fingerprint, 101=card';
+
+-- Device and imported punches stay unique so re-downloads (including of voided
+-- punches) are skipped; a manual punch may share the time of the punch it
+-- corrects, e.g. when only the in/out state was wrong
+ALTER TABLE app.attendance_logs DROP CONSTRAINT IF EXISTS uq_attendance_logs_scanner_time;
+CREATE UNIQUE INDEX IF NOT EXISTS uq_attendance_logs_scanner_time
+    ON app.attendance_logs(scanner_uid, check_time)
+    WHERE source <> 'manual';
+
+-- -----------------------------------------------------------------------------
+-- Table: app.punch_corrections
+-- Description: Manual additions, corrections and voids of punches
+-- -----------------------------------------------------------------------------
+CREATE TABLE IF NOT EXISTS app.punch_corrections (
+    id              SERIAL,
+    action          VARCHAR(10) NOT NULL,
+    original_log_id BIGINT,
+    new_log_id      BIGINT,
+    reason          VARCHAR(500) NOT NULL,
+    acted_by        VARCHAR(100) NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Primary Key
+    CONSTRAINT pk_punch_corrections PRIMARY KEY (id),
+
+    -- Foreign Keys
+    CONSTRAINT fk_punch_corrections_original_log FOREIGN KEY (original_log_id)
+        REFERENCES app.attendance_logs(id)
+        ON DELETE CASCADE,
+    CONSTRAINT fk_punch_corrections_new_log FOREIGN KEY (new_log_id)
+        REFERENCES app.attendance_logs(id)
+        ON DELETE CASCADE,
+
+    -- Check Constraints
+    CONSTRAINT ck_punch_corrections_action CHECK (action IN ('add', 'edit', 'void')),
+    CONSTRAINT ck_punch_corrections_logs CHECK (
+        (action = 'add' AND original_log_id IS NULL AND new_log_id IS NOT NULL)
+        OR (action = 'edit' AND original_log_id IS NOT NULL AND new_log_id IS NOT NULL)
+        OR (action = 'void' AND original_log_id IS NOT NULL AND new_log_id IS NULL)
+    ),
+    CONSTRAINT ck_punch_corrections_reason CHECK (btrim(reason) <> ''),
+    CONSTRAINT ck_punch_corrections_acted_by CHECK (btrim(acted_by) <> '')
+);
+
+COMMENT ON TABLE app.punch_corrections IS 'Manual additions, corrections and voids of punches (append-only)';
+COMMENT ON COLUMN app.punch_corrections.id IS 'Primary key, auto-increment';
+COMMENT ON COLUMN app.punch_corrections.action IS 'add, edit or void';
+COMMENT ON COLUMN app.punch_corrections.original_log_id IS 'Punch voided by an edit or void';
+COMMENT ON COLUMN app.punch_corrections.new_log_id IS 'Manual punch added by an add or edit';
+COMMENT ON COLUMN app.punch_corrections.reason IS 'Why the punch was added, corrected or voided';
+COMMENT ON COLUMN app.punch_corrections.acted_by IS 'Who made the correction';
+COMMENT ON COLUMN app.punch_corrections.created_at IS 'When the correction was made';
+
+CREATE INDEX IF NOT EXISTS idx_punch_corrections_original_log ON app.punch_corrections(original_log_id);
+CREATE INDEX IF NOT EXISTS idx_punch_corrections_new_log ON app.punch_corrections(new_log_id);
+
+-- -----------------------------------------------------------------------------
+-- View: app.v_attendance_details
+-- Description: Attendance logs with employee and department names
+-- Note: is_voided and the latest correction touching each punch are appended
+-- -----------------------------------------------------------------------------
+CREATE OR REPLACE VIEW app.v_attendance_details AS
+SELECT
+    al.id,
+    al.scanner_uid,
+    e.id AS employee_id,
+    e.employee_code,
+    e.full_name,
+    e.department_id,
+    d.name AS department_name,
+    al.check_time,
+    al.verify_type,
+    CASE al.verify_type
+        WHEN 0 THEN 'manual'
+        WHEN 2 THEN 'fingerprint'
+        WHEN 101 THEN 'card'
+        ELSE 'unknown'
+    END AS verify_type_name,
+    al.status,
+    al.source,
+    al.is_duplicate,
+    al.created_at,
+    CASE WHEN e.id IS NOT NULL THEN
+        system.fn_work_date(
+            e.id,
+            al.check_time,
+            current_setting('TimeZone'),
+            COALESCE(NULLIF(current_setting('app.day_start', true), ''), '00:00')::TIME
+        )
+    END AS work_date,
+    al.is_voided,
+    pc.reason AS correction_reason,
+    pc.acted_by AS corrected_by
+FROM app.attendance_logs al
+LEFT JOIN app.employees e ON al.scanner_uid = e.scanner_uid
+LEFT JOIN app.departments d ON e.department_id = d.id
+LEFT JOIN LATERAL (
+    SELECT c.reason, c.acted_by
+    FROM app.punch_corrections c
+    WHERE c.original_log_id = al.id OR c.new_log_id = al.id
+    ORDER BY c.id DESC
+    LIMIT 1
+) pc ON true;
+
+COMMENT ON VIEW app.v_attendance_details IS 'Attendance logs with employee and department names, the work day of each punch and its latest correction';
+
+-- -----------------------------------------------------------------------------
+-- View: app.v_daily_attendance
+-- Description: Daily attendance summary per employee
+-- Note: Voided punches are excluded like double punches
+-- -----------------------------------------------------------------------------
+CREATE OR REPLACE VIEW app.v_daily_attendance AS
+WITH punches AS (
+    SELECT
+        e.id AS employee_id,
+        al.check_time,
+        system.fn_work_date(
+            e.id,
+            al.check_time,
+            current_setting('TimeZone'),
+            COALESCE(NULLIF(current_setting('app.day_start', true), ''), '00:00')::TIME
+        ) AS work_date
+    FROM app.attendance_logs al
+    JOIN app.employees e ON al.scanner_uid = e.scanner_uid
+    WHERE NOT al.is_duplicate AND NOT al.is_voided
+)
+SELECT
+    e.id AS employee_id,
+    e.employee_code,
+    e.full_name,
+    e.department_id,
+    d.name AS department_name,
+    p.work_date,
+    MIN(p.check_time) AS first_check,
+    MAX(p.check_time) AS last_check,
+    COUNT(*) AS check_count,
+    (EXTRACT(EPOCH FROM (MAX(p.check_time) - MIN(p.check_time))) / 3600.0)::float8 AS work_hours
+FROM punches p
+JOIN app.employees e ON p.employee_id = e.id
+LEFT JOIN app.departments d ON e.department_id = d.id
+GROUP BY e.id, e.employee_code, e.full_name, e.department_id, d.name, p.work_date;
+
+COMMENT ON VIEW app.v_daily_attendance IS 'Daily attendance summary per employee by work day in the session (business) timezone, double and voided punches excluded';
@@ -0,0 +1,42 @@
+-- =============================================================================
+-- Gianged Attendance - Migration 013: Audit log
+-- =============================================================================
+-- Who changed what and when: employee and department changes, punch
+-- corrections, bulk attendance deletes and device clears, with the row as it
+-- was before and after. Rows are only ever appended.
+
+-- -----------------------------------------------------------------------------
+-- Table: app.audit_log
+-- Description: Record of data changes with actor and before/after values
+-- -----------------------------------------------------------------------------
+CREATE TABLE IF NOT EXISTS app.audit_log (
+    id          BIGSERIAL,
+    actor       VARCHAR(100) NOT NULL,
+    entity      VARCHAR(30) NOT NULL,
+    entity_id   VARCHAR(100),
+    action      VARCHAR(20) NOT NULL,
+    before_data JSONB,
+    after_data  JSONB,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Primary Key
+    CONSTRAINT pk_audit_log PRIMARY KEY (id),
+
+    -- Check Constraints
+    CONSTRAINT ck_audit_log_action CHECK (action IN ('create', 'update', 'delete', 'void', 'clear')),
+    CONSTRAINT ck_audit_log_actor CHECK (btrim(actor) <> '')
+);
+
+COMMENT ON TABLE app.audit_log IS 'Record of data changes with actor and before/after values (append-only)';
+COMMENT ON COLUMN app.audit_log.id IS 'Primary key, auto-increment';
+COMMENT ON COLUMN app.audit_log.actor IS 'Who made the change';
+COMMENT ON COLUMN app.audit_log.entity IS 'Kind of record changed: employee, department, punch, attendance, device';
+COMMENT ON COLUMN app.audit_log.entity_id IS 'ID of the record changed (device address for device operations, NULL for bulk changes)';
+COMMENT ON COLUMN app.audit_log.action IS 'create, update, delete, void or clear';
+COMMENT ON COLUMN app.audit_log.before_data IS 'Record before the change (NULL when created)';
+COMMENT ON COLUMN app.audit_log.after_data IS 'Record after the change (NULL when deleted)';
+COMMENT ON COLUMN app.audit_log.created_at IS 'When the change was made';
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_created_at ON app.audit_log(created_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_entity ON app.audit_log(entity, entity_id);
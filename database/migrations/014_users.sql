@@ -0,0 +1,60 @@
+-- =============================================================================
+-- Gianged Attendance - Migration 014: User accounts
+-- =============================================================================
+-- People sign in to the app with their own account; the role decides which
+-- panels and actions they get. Passwords are stored as argon2 hashes only.
+
+-- -----------------------------------------------------------------------------
+-- Table: app.users
+-- Description: Application user accounts with role
+-- -----------------------------------------------------------------------------
+CREATE TABLE IF NOT EXISTS app.users (
+    id              SERIAL,
+    username        VARCHAR(50) NOT NULL,
+    display_name    VARCHAR(100),
+    password_hash   VARCHAR(255) NOT NULL,
+    role            VARCHAR(20) NOT NULL,
+    department_id   INTEGER,
+    is_active       BOOLEAN NOT NULL DEFAULT true,
+    last_login_at   TIMESTAMPTZ,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Primary Key
+    CONSTRAINT pk_users PRIMARY KEY (id),
+
+    -- Foreign Keys
+    CONSTRAINT fk_users_department FOREIGN KEY (department_id)
+        REFERENCES app.departments(id)
+        ON DELETE RESTRICT
+        ON UPDATE CASCADE,
+
+    -- Check Constraints
+    CONSTRAINT ck_users_username CHECK (btrim(username) <> ''),
+    CONSTRAINT ck_users_role CHECK (role IN ('admin', 'hr', 'manager', 'viewer')),
+    CONSTRAINT ck_users_manager_department CHECK (role <> 'manager' OR department_id IS NOT NULL)
+);
+
+-- Usernames are matched without regard to case
+CREATE UNIQUE INDEX IF NOT EXISTS uq_users_username ON app.users(lower(username));
+
+COMMENT ON TABLE app.users IS 'Application user accounts with role';
+COMMENT ON COLUMN app.users.id IS 'Primary key, auto-increment';
+COMMENT ON COLUMN app.users.username IS 'Login name (unique, case-insensitive)';
+COMMENT ON COLUMN app.users.display_name IS 'Name shown in the app and recorded on changes';
+COMMENT ON COLUMN app.users.password_hash IS 'argon2 password hash in PHC string format';
+COMMENT ON COLUMN app.users.role IS 'admin, hr, manager (own department subtree) or viewer';
+COMMENT ON COLUMN app.users.department_id IS 'Department a manager is responsible for, with its sub-departments';
+COMMENT ON COLUMN app.users.is_active IS 'Inactive users cannot sign in';
+COMMENT ON COLUMN app.users.last_login_at IS 'Last successful sign-in';
+COMMENT ON COLUMN app.users.created_at IS 'Record creation timestamp';
+COMMENT ON COLUMN app.users.updated_at IS 'Record last update timestamp';
+
+-- -----------------------------------------------------------------------------
+-- Triggers: Auto-update updated_at
+-- -----------------------------------------------------------------------------
+DROP TRIGGER IF EXISTS trg_users_update_timestamp ON app.users;
+CREATE TRIGGER trg_users_update_timestamp
+    BEFORE UPDATE ON app.users
+    FOR EACH ROW
+    EXECUTE FUNCTION system.fn_update_timestamp();
@@ -0,0 +1,261 @@
+-- =============================================================================
+-- Gianged Attendance - Migration 015: Employee assignments
+-- =============================================================================
+-- employees.department_id only holds the current department, so a transfer
+-- moved all past attendance to the new department. Each department and
+-- position an employee held is now kept with its effective dates, and reports
+-- attribute a work day to the department the employee was in on that day.
+-- Days before the first assignment fall back to the current department.
+
+-- Current job title; earlier ones are kept in app.employee_assignments
+ALTER TABLE app.employees ADD COLUMN IF NOT EXISTS position VARCHAR(100);
+
+COMMENT ON COLUMN app.employees.position IS 'Current job title';
+
+-- -----------------------------------------------------------------------------
+-- Table: app.employee_assignments
+-- Description: Department and position held by an employee over a date range
+-- -----------------------------------------------------------------------------
+CREATE TABLE IF NOT EXISTS app.employee_assignments (
+    id              SERIAL,
+    employee_id     INTEGER NOT NULL,
+    department_id   INTEGER,
+    position        VARCHAR(100),
+    effective_from  DATE NOT NULL,
+    effective_to    DATE,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Primary Key
+    CONSTRAINT pk_employee_assignments PRIMARY KEY (id),
+
+    -- Foreign Keys
+    CONSTRAINT fk_employee_assignments_employee FOREIGN KEY (employee_id)
+        REFERENCES app.employees(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    CONSTRAINT fk_employee_assignments_department FOREIGN KEY (department_id)
+        REFERENCES app.departments(id)
+        ON DELETE SET NULL
+        ON UPDATE CASCADE,
+
+    -- Unique Constraints
+    CONSTRAINT uq_employee_assignments_employee_from UNIQUE (employee_id, effective_from),
+
+    -- Check Constraints
+    CONSTRAINT ck_employee_assignments_dates CHECK (effective_to IS NULL OR effective_to >= effective_from)
+);
+
+CREATE INDEX IF NOT EXISTS idx_employee_assignments_department ON app.employee_assignments(department_id);
+
+COMMENT ON TABLE app.employee_assignments IS 'Department and position held by an employee over a date range';
+COMMENT ON COLUMN app.employee_assignments.id IS 'Primary key, auto-increment';
+COMMENT ON COLUMN app.employee_assignments.employee_id IS 'FK to employees';
+COMMENT ON COLUMN app.employee_assignments.department_id IS 'FK to departments (NULL = no department)';
+COMMENT ON COLUMN app.employee_assignments.position IS 'Job title held';
+COMMENT ON COLUMN app.employee_assignments.effective_from IS 'First day of the assignment';
+COMMENT ON COLUMN app.employee_assignments.effective_to IS 'Last day of the assignment (NULL = current)';
+COMMENT ON COLUMN app.employee_assignments.created_at IS 'Record creation timestamp';
+COMMENT ON COLUMN app.employee_assignments.updated_at IS 'Record last update timestamp';
+
+-- Existing employees start with their current department from their start date
+INSERT INTO app.employee_assignments (employee_id, department_id, effective_from)
+SELECT e.id, e.department_id, e.start_date
+FROM app.employees e
+WHERE NOT EXISTS (SELECT 1 FROM app.employee_assignments a WHERE a.employee_id = e.id);
+
+-- -----------------------------------------------------------------------------
+-- Triggers: Auto-update updated_at
+-- -----------------------------------------------------------------------------
+DROP TRIGGER IF EXISTS trg_employee_assignments_update_timestamp ON app.employee_assignments;
+CREATE TRIGGER trg_employee_assignments_update_timestamp
+    BEFORE UPDATE ON app.employee_assignments
+    FOR EACH ROW
+    EXECUTE FUNCTION system.fn_update_timestamp();
+
+-- -----------------------------------------------------------------------------
+-- Function: system.fn_department_on(INTEGER, DATE)
+-- Description: Department an employee was in on a day
+-- Note: Matches models::employee::EmploymentHistory. Among overlapping
+--       assignments the latest start wins; without one covering the day the
+--       employee's current department is used.
+-- -----------------------------------------------------------------------------
+CREATE OR REPLACE FUNCTION system.fn_department_on(p_employee_id INTEGER, p_date DATE)
+RETURNS INTEGER AS $$
+    SELECT CASE WHEN a.id IS NOT NULL THEN a.department_id ELSE e.department_id END
+    FROM app.employees e
+    LEFT JOIN LATERAL (
+        SELECT ea.id, ea.department_id
+        FROM app.employee_assignments ea
+        WHERE ea.employee_id = e.id
+            AND ea.effective_from <= p_date
+            AND (ea.effective_to IS NULL OR p_date <= ea.effective_to)
+        ORDER BY ea.effective_from DESC
+        LIMIT 1
+    ) a ON true
+    WHERE e.id = p_employee_id;
+$$ LANGUAGE sql STABLE;
+
+COMMENT ON FUNCTION system.fn_department_on(INTEGER, DATE) IS 'Department an employee was in on a day, from their assignments';
+
+-- -----------------------------------------------------------------------------
+-- Function: system.fn_scheduled_shift(INTEGER, DATE)
+-- Description: Shift an employee is scheduled to start on a day
+-- Note: Department assignments are looked up from the department the employee
+--       was in on that day
+-- -----------------------------------------------------------------------------
+CREATE OR REPLACE FUNCTION system.fn_scheduled_shift(p_employee_id INTEGER, p_date DATE)
+RETURNS INTEGER AS $$
+DECLARE
+    v_shift_id      INTEGER;
+    v_found         BOOLEAN;
+    v_department_id INTEGER;
+    v_depth         INTEGER := 0;
+BEGIN
+    -- Roster entry (a NULL shift is a rostered day off)
+    SELECT r.shift_id, true INTO v_shift_id, v_found
+    FROM app.shift_rosters r
+    WHERE r.employee_id = p_employee_id AND r.work_date = p_date;
+    IF v_found THEN
+        RETURN v_shift_id;
+    END IF;
+
+    -- Pattern assigned to the employee
+    SELECT pd.shift_id, true INTO v_shift_id, v_found
+    FROM (
+        SELECT a.pattern_id, a.start_date
+        FROM app.shift_assignments a
+        WHERE a.employee_id = p_employee_id
+            AND a.start_date <= p_date
+            AND (a.end_date IS NULL OR p_date <= a.end_date)
+        ORDER BY a.start_date DESC, a.id DESC
+        LIMIT 1
+    ) a
+    JOIN app.shift_patterns p ON p.id = a.pattern_id
+    LEFT JOIN app.shift_pattern_days pd
+        ON pd.pattern_id = p.id AND pd.day_index = (p_date - a.start_date) % p.cycle_days;
+    IF v_found THEN
+        RETURN v_shift_id;
+    END IF;
+
+    -- Pattern assigned to the department the employee was in that day or a
+    -- parent department; the depth cap guards against cycles
+    v_department_id := system.fn_department_on(p_employee_id, p_date);
+    WHILE v_department_id IS NOT NULL AND v_depth < 32 LOOP
+        SELECT pd.shift_id, true INTO v_shift_id, v_found
+        FROM (
+            SELECT a.pattern_id, a.start_date
+            FROM app.shift_assignments a
+            WHERE a.department_id = v_department_id
+                AND a.start_date <= p_date
+                AND (a.end_date IS NULL OR p_date <= a.end_date)
+            ORDER BY a.start_date DESC, a.id DESC
+            LIMIT 1
+        ) a
+        JOIN app.shift_patterns p ON p.id = a.pattern_id
+        LEFT JOIN app.shift_pattern_days pd
+            ON pd.pattern_id = p.id AND pd.day_index = (p_date - a.start_date) % p.cycle_days;
+        IF v_found THEN
+            RETURN v_shift_id;
+        END IF;
+
+        SELECT d.parent_id INTO v_department_id FROM app.departments d WHERE d.id = v_department_id;
+        v_depth := v_depth + 1;
+    END LOOP;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql STABLE;
+
+-- -----------------------------------------------------------------------------
+-- View: app.v_attendance_details
+-- Description: Attendance logs with employee and department names
+-- Note: department_id is the department on the punch's work day
+-- -----------------------------------------------------------------------------
+CREATE OR REPLACE VIEW app.v_attendance_details AS
+SELECT
+    al.id,
+    al.scanner_uid,
+    e.id AS employee_id,
+    e.employee_code,
+    e.full_name,
+    wd.department_id,
+    d.name AS department_name,
+    al.check_time,
+    al.verify_type,
+    CASE al.verify_type
+        WHEN 0 THEN 'manual'
+        WHEN 2 THEN 'fingerprint'
+        WHEN 101 THEN 'card'
+        ELSE 'unknown'
+    END AS verify_type_name,
+    al.status,
+    al.source,
+    al.is_duplicate,
+    al.created_at,
+    wd.work_date,
+    al.is_voided,
+    pc.reason AS correction_reason,
+    pc.acted_by AS corrected_by
+FROM app.attendance_logs al
+LEFT JOIN app.employees e ON al.scanner_uid = e.scanner_uid
+LEFT JOIN LATERAL (
+    SELECT w.work_date, system.fn_department_on(e.id, w.work_date) AS department_id
+    FROM (
+        SELECT system.fn_work_date(
+            e.id,
+            al.check_time,
+            current_setting('TimeZone'),
+            COALESCE(NULLIF(current_setting('app.day_start', true), ''), '00:00')::TIME
+        ) AS work_date
+    ) w
+    WHERE e.id IS NOT NULL
+) wd ON true
+LEFT JOIN app.departments d ON wd.department_id = d.id
+LEFT JOIN LATERAL (
+    SELECT c.reason, c.acted_by
+    FROM app.punch_corrections c
+    WHERE c.original_log_id = al.id OR c.new_log_id = al.id
+    ORDER BY c.id DESC
+    LIMIT 1
+) pc ON true;
+
+COMMENT ON VIEW app.v_attendance_details IS 'Attendance logs with employee and department names, the work day of each punch and its latest correction';
+
+-- -----------------------------------------------------------------------------
+-- View: app.v_daily_attendance
+-- Description: Daily attendance summary per employee
+-- Note: department_id is the department on the work day
+-- -----------------------------------------------------------------------------
+CREATE OR REPLACE VIEW app.v_daily_attendance AS
+WITH punches AS (
+    SELECT
+        e.id AS employee_id,
+        al.check_time,
+        system.fn_work_date(
+            e.id,
+            al.check_time,
+            current_setting('TimeZone'),
+            COALESCE(NULLIF(current_setting('app.day_start', true), ''), '00:00')::TIME
+        ) AS work_date
+    FROM app.attendance_logs al
+    JOIN app.employees e ON al.scanner_uid = e.scanner_uid
+    WHERE NOT al.is_duplicate AND NOT al.is_voided
+)
+SELECT
+    e.id AS employee_id,
+    e.employee_code,
+    e.full_name,
+    system.fn_department_on(e.id, p.work_date) AS department_id,
+    d.name AS department_name,
+    p.work_date,
+    MIN(p.check_time) AS first_check,
+    MAX(p.check_time) AS last_check,
+    COUNT(*) AS check_count,
+    (EXTRACT(EPOCH FROM (MAX(p.check_time) - MIN(p.check_time))) / 3600.0)::float8 AS work_hours
+FROM punches p
+JOIN app.employees e ON p.employee_id = e.id
+LEFT JOIN app.departments d ON d.id = system.fn_department_on(e.id, p.work_date)
+GROUP BY e.id, e.employee_code, e.full_name, d.id, d.name, p.work_date;
+
+COMMENT ON VIEW app.v_daily_attendance IS 'Daily attendance summary per employee by work day in the session (business) timezone and department on that day, double and voided punches excluded';
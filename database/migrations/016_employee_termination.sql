@@ -0,0 +1,22 @@
+-- =============================================================================
+-- Gianged Attendance - Migration 016: Employee termination
+-- =============================================================================
+-- Employees only had a start date and an active flag, so a leaver either kept
+-- showing as absent or vanished from historical reports once deactivated. The
+-- last working day and the reason for leaving are now recorded, and reports
+-- only expect attendance inside each employee's employment window.
+
+ALTER TABLE app.employees ADD COLUMN IF NOT EXISTS end_date DATE;
+ALTER TABLE app.employees ADD COLUMN IF NOT EXISTS termination_reason VARCHAR(255);
+
+ALTER TABLE app.employees DROP CONSTRAINT IF EXISTS ck_employees_end_date;
+ALTER TABLE app.employees ADD CONSTRAINT ck_employees_end_date CHECK (end_date IS NULL OR end_date >= start_date);
+
+COMMENT ON COLUMN app.employees.end_date IS 'Last working day (NULL = still employed)';
+COMMENT ON COLUMN app.employees.termination_reason IS 'Reason for leaving';
+
+-- Offboarding removes the leaver from the device under the device lock
+ALTER TABLE app.sync_locks DROP CONSTRAINT IF EXISTS ck_sync_locks_operation;
+ALTER TABLE app.sync_locks ADD CONSTRAINT ck_sync_locks_operation CHECK (operation IN ('sync', 'clear', 'remove_user'));
+
+COMMENT ON COLUMN app.sync_locks.operation IS 'Operation in progress: sync, clear, remove_user';
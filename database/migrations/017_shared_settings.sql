@@ -0,0 +1,69 @@
+-- =============================================================================
+-- Gianged Attendance - Migration 017: Shared settings
+-- =============================================================================
+-- The double-punch window lived in each workstation's config.toml while the
+-- flags it produces are shared in the database, so two workstations with
+-- different windows kept re-flagging each other's punches. The window is now
+-- a single database setting. Punches stored before migration 004 were never
+-- evaluated and kept the column default; they are flagged here once.
+
+-- -----------------------------------------------------------------------------
+-- Table: app.settings
+-- Description: Settings shared by every workstation
+-- Note: Single row (id = 1), created by this migration
+-- -----------------------------------------------------------------------------
+CREATE TABLE IF NOT EXISTS app.settings (
+    id                          SMALLINT NOT NULL DEFAULT 1,
+    double_punch_window_secs    INTEGER NOT NULL DEFAULT 30,
+    created_at                  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at                  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Primary Key
+    CONSTRAINT pk_settings PRIMARY KEY (id),
+
+    -- Check Constraints
+    CONSTRAINT ck_settings_single_row CHECK (id = 1),
+    CONSTRAINT ck_settings_double_punch_window CHECK (double_punch_window_secs BETWEEN 0 AND 3600)
+);
+
+INSERT INTO app.settings (id) VALUES (1) ON CONFLICT (id) DO NOTHING;
+
+COMMENT ON TABLE app.settings IS 'Settings shared by every workstation (single row)';
+COMMENT ON COLUMN app.settings.id IS 'Always 1';
+COMMENT ON COLUMN app.settings.double_punch_window_secs IS 'Repeat punches by the same UID within this many seconds are double punches (0 disables)';
+COMMENT ON COLUMN app.settings.created_at IS 'Record creation timestamp';
+COMMENT ON COLUMN app.settings.updated_at IS 'Record last update timestamp';
+
+-- -----------------------------------------------------------------------------
+-- Triggers: Auto-update updated_at
+-- -----------------------------------------------------------------------------
+DROP TRIGGER IF EXISTS trg_settings_update_timestamp ON app.settings;
+CREATE TRIGGER trg_settings_update_timestamp
+    BEFORE UPDATE ON app.settings
+    FOR EACH ROW
+    EXECUTE FUNCTION system.fn_update_timestamp();
+
+-- -----------------------------------------------------------------------------
+-- Backfill: app.attendance_logs.is_duplicate
+-- Description: Flag double punches across all logs with the shared window
+-- Note: Same rule as flag_double_punches in the application; voided and
+--       manual punches are never flagged nor count as predecessors
+-- -----------------------------------------------------------------------------
+WITH ordered AS (
+    SELECT
+        al.id,
+        COALESCE(
+            al.check_time - LAG(al.check_time) OVER (PARTITION BY al.scanner_uid ORDER BY al.check_time)
+                <= make_interval(secs => s.double_punch_window_secs),
+            false
+        ) AS is_duplicate
+    FROM app.attendance_logs al
+    CROSS JOIN app.settings s
+    WHERE NOT al.is_voided
+        AND al.source <> 'manual'
+)
+UPDATE app.attendance_logs al
+SET is_duplicate = o.is_duplicate
+FROM ordered o
+WHERE al.id = o.id
+  AND al.is_duplicate <> o.is_duplicate;
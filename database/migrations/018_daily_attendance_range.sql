@@ -0,0 +1,74 @@
+-- =============================================================================
+-- Gianged Attendance - Migration 018: Daily attendance by date range
+-- =============================================================================
+-- v_daily_attendance derives work_date with system.fn_work_date, so a filter on
+-- work_date cannot reach the attendance_logs scan and the function ran for
+-- every punch ever stored before the filter was applied. Summaries for a date
+-- range now go through a function that first narrows the punches by
+-- check_time, using the index on it.
+
+-- -----------------------------------------------------------------------------
+-- Function: system.fn_daily_attendance(DATE, DATE)
+-- Description: Daily attendance summary per employee for work days in a range
+-- Note: Same rows as app.v_daily_attendance filtered on work_date. A punch
+--       belongs to its calendar day or the day before, so only punches within
+--       a day either side of the range are evaluated.
+-- -----------------------------------------------------------------------------
+CREATE OR REPLACE FUNCTION system.fn_daily_attendance(p_start DATE, p_end DATE)
+RETURNS TABLE (
+    employee_id     INTEGER,
+    employee_code   VARCHAR,
+    full_name       VARCHAR,
+    department_id   INTEGER,
+    department_name VARCHAR,
+    work_date       DATE,
+    first_check     TIMESTAMPTZ,
+    last_check      TIMESTAMPTZ,
+    check_count     BIGINT,
+    work_hours      FLOAT8
+) AS $$
+    WITH punches AS (
+        SELECT
+            e.id AS employee_id,
+            al.check_time,
+            system.fn_work_date(
+                e.id,
+                al.check_time,
+                current_setting('TimeZone'),
+                COALESCE(NULLIF(current_setting('app.day_start', true), ''), '00:00')::TIME
+            ) AS work_date
+        FROM app.attendance_logs al
+        JOIN app.employees e ON al.scanner_uid = e.scanner_uid
+        WHERE NOT al.is_duplicate AND NOT al.is_voided
+            AND al.check_time >= (p_start - 1)::TIMESTAMP AT TIME ZONE current_setting('TimeZone')
+            AND al.check_time < (p_end + 2)::TIMESTAMP AT TIME ZONE current_setting('TimeZone')
+    ),
+    days AS (
+        SELECT
+            p.employee_id,
+            p.work_date,
+            system.fn_department_on(p.employee_id, p.work_date) AS department_id,
+            MIN(p.check_time) AS first_check,
+            MAX(p.check_time) AS last_check,
+            COUNT(*) AS check_count
+        FROM punches p
+        WHERE p.work_date BETWEEN p_start AND p_end
+        GROUP BY p.employee_id, p.work_date
+    )
+    SELECT
+        e.id,
+        e.employee_code,
+        e.full_name,
+        dy.department_id,
+        d.name,
+        dy.work_date,
+        dy.first_check,
+        dy.last_check,
+        dy.check_count,
+        (EXTRACT(EPOCH FROM (dy.last_check - dy.first_check)) / 3600.0)::float8
+    FROM days dy
+    JOIN app.employees e ON e.id = dy.employee_id
+    LEFT JOIN app.departments d ON d.id = dy.department_id;
+$$ LANGUAGE sql STABLE;
+
+COMMENT ON FUNCTION system.fn_daily_attendance(DATE, DATE) IS 'Daily attendance summary per employee for work days in a range (v_daily_attendance narrowed by check_time first)';
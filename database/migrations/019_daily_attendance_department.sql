@@ -0,0 +1,54 @@
+-- =============================================================================
+-- Gianged Attendance - Migration 019: Daily attendance department lookup
+-- =============================================================================
+-- v_daily_attendance called system.fn_department_on twice per punch, once for
+-- the department_id column and again in the join to departments. The
+-- department is now looked up once per employee and work day.
+
+-- -----------------------------------------------------------------------------
+-- View: app.v_daily_attendance
+-- Description: Daily attendance summary per employee
+-- Note: department_id is the department on the work day
+-- -----------------------------------------------------------------------------
+CREATE OR REPLACE VIEW app.v_daily_attendance AS
+WITH punches AS (
+    SELECT
+        e.id AS employee_id,
+        al.check_time,
+        system.fn_work_date(
+            e.id,
+            al.check_time,
+            current_setting('TimeZone'),
+            COALESCE(NULLIF(current_setting('app.day_start', true), ''), '00:00')::TIME
+        ) AS work_date
+    FROM app.attendance_logs al
+    JOIN app.employees e ON al.scanner_uid = e.scanner_uid
+    WHERE NOT al.is_duplicate AND NOT al.is_voided
+),
+days AS (
+    SELECT
+        p.employee_id,
+        p.work_date,
+        system.fn_department_on(p.employee_id, p.work_date) AS department_id,
+        MIN(p.check_time) AS first_check,
+        MAX(p.check_time) AS last_check,
+        COUNT(*) AS check_count
+    FROM punches p
+    GROUP BY p.employee_id, p.work_date
+)
+SELECT
+    e.id AS employee_id,
+    e.employee_code,
+    e.full_name,
+    dy.department_id,
+    d.name AS department_name,
+    dy.work_date,
+    dy.first_check,
+    dy.last_check,
+    dy.check_count,
+    (EXTRACT(EPOCH FROM (dy.last_check - dy.first_check)) / 3600.0)::float8 AS work_hours
+FROM days dy
+JOIN app.employees e ON e.id = dy.employee_id
+LEFT JOIN app.departments d ON d.id = dy.department_id;
+
+COMMENT ON VIEW app.v_daily_attendance IS 'Daily attendance summary per employee by work day in the session (business) timezone and department on that day, double and voided punches excluded';
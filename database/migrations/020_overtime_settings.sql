@@ -0,0 +1,53 @@
+-- =============================================================================
+-- Gianged Attendance - Migration 020: Shared overtime rules
+-- =============================================================================
+-- Overtime rules lived in each workstation's config.toml, so two workstations
+-- with different rules reported different overtime and paid hours for the
+-- same punches. The rules are now shared settings, like the double-punch
+-- window. Defaults match the previous config defaults.
+
+-- -----------------------------------------------------------------------------
+-- Table: app.settings
+-- Description: Overtime rules
+-- Note: Multipliers and the night premium are fractions, minutes are whole
+--       minutes; a night window with equal start and end is disabled
+-- -----------------------------------------------------------------------------
+ALTER TABLE app.settings
+    ADD COLUMN IF NOT EXISTS overtime_threshold_minutes     INTEGER NOT NULL DEFAULT 480,
+    ADD COLUMN IF NOT EXISTS overtime_weekday_multiplier    DOUBLE PRECISION NOT NULL DEFAULT 1.5,
+    ADD COLUMN IF NOT EXISTS overtime_weekend_multiplier    DOUBLE PRECISION NOT NULL DEFAULT 2.0,
+    ADD COLUMN IF NOT EXISTS overtime_holiday_multiplier    DOUBLE PRECISION NOT NULL DEFAULT 3.0,
+    ADD COLUMN IF NOT EXISTS overtime_night_start           TIME NOT NULL DEFAULT '22:00',
+    ADD COLUMN IF NOT EXISTS overtime_night_end             TIME NOT NULL DEFAULT '06:00',
+    ADD COLUMN IF NOT EXISTS overtime_night_premium         DOUBLE PRECISION NOT NULL DEFAULT 0.3,
+    ADD COLUMN IF NOT EXISTS overtime_min_block_minutes     INTEGER NOT NULL DEFAULT 30,
+    ADD COLUMN IF NOT EXISTS overtime_rounding_minutes      INTEGER NOT NULL DEFAULT 15;
+
+ALTER TABLE app.settings DROP CONSTRAINT IF EXISTS ck_settings_overtime_threshold;
+ALTER TABLE app.settings ADD CONSTRAINT ck_settings_overtime_threshold
+    CHECK (overtime_threshold_minutes BETWEEN 0 AND 1440);
+
+ALTER TABLE app.settings DROP CONSTRAINT IF EXISTS ck_settings_overtime_rates;
+ALTER TABLE app.settings ADD CONSTRAINT ck_settings_overtime_rates
+    CHECK (overtime_weekday_multiplier >= 0
+        AND overtime_weekend_multiplier >= 0
+        AND overtime_holiday_multiplier >= 0
+        AND overtime_night_premium >= 0);
+
+ALTER TABLE app.settings DROP CONSTRAINT IF EXISTS ck_settings_overtime_min_block;
+ALTER TABLE app.settings ADD CONSTRAINT ck_settings_overtime_min_block
+    CHECK (overtime_min_block_minutes BETWEEN 0 AND 480);
+
+ALTER TABLE app.settings DROP CONSTRAINT IF EXISTS ck_settings_overtime_rounding;
+ALTER TABLE app.settings ADD CONSTRAINT ck_settings_overtime_rounding
+    CHECK (overtime_rounding_minutes BETWEEN 0 AND 60);
+
+COMMENT ON COLUMN app.settings.overtime_threshold_minutes IS 'Minutes worked on a working day before overtime starts';
+COMMENT ON COLUMN app.settings.overtime_weekday_multiplier IS 'Pay multiplier for overtime on working days';
+COMMENT ON COLUMN app.settings.overtime_weekend_multiplier IS 'Pay multiplier for time worked on weekends and scheduled days off';
+COMMENT ON COLUMN app.settings.overtime_holiday_multiplier IS 'Pay multiplier for time worked on holidays';
+COMMENT ON COLUMN app.settings.overtime_night_start IS 'Start of the night window (business timezone)';
+COMMENT ON COLUMN app.settings.overtime_night_end IS 'End of the night window; equal to the start disables it';
+COMMENT ON COLUMN app.settings.overtime_night_premium IS 'Extra pay per overtime hour at night, as a fraction of an hour';
+COMMENT ON COLUMN app.settings.overtime_min_block_minutes IS 'Overtime shorter than this many minutes is not counted';
+COMMENT ON COLUMN app.settings.overtime_rounding_minutes IS 'Overtime is rounded down to a multiple of this many minutes (0 disables)';
@@ -0,0 +1,753 @@
+//! Headless command-line interface.
+//!
+//! Runs syncs, device operations, exports and imports without the GUI, for
+//! Task Scheduler, cron or SSH. Every command prints a single JSON object to
+//! stdout and exits with one of the [`exit_code`] values.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{Datelike, NaiveDate};
+use clap::{Args, Subcommand};
+use sea_orm::DatabaseConnection;
+use serde_json::{Value, json};
+
+use crate::config::{AppConfig, ConfigLoadResult};
+use crate::db::{self, migration};
+use crate::error::{AppError, Result};
+use crate::export;
+use crate::models::overtime;
+use crate::models::user::{CreateUser, UpdateUser, user_role};
+use crate::models::{DailyStatus, DayStatus};
+use crate::serve;
+use crate::sync::SyncService;
+use crate::timezone;
+use crate::zk::{ZkError, ZkTcpClient};
+
+/// Process exit codes.
+///
+/// Code 2 is left to clap for usage errors.
+pub mod exit_code {
+    pub const SUCCESS: i32 = 0;
+    /// Any failure not covered below.
+    pub const FAILURE: i32 = 1;
+    /// Config file missing or invalid.
+    pub const CONFIG: i32 = 3;
+    /// Database unreachable or query failed.
+    pub const DATABASE: i32 = 4;
+    /// Device unreachable or protocol error.
+    pub const DEVICE: i32 = 5;
+    /// Device locked by another workstation.
+    pub const DEVICE_LOCKED: i32 = 6;
+    /// Database schema is newer than this build.
+    pub const SCHEMA: i32 = 7;
+    /// Interrupted with Ctrl+C or SIGTERM.
+    pub const CANCELLED: i32 = 130;
+}
+
+/// Headless commands.
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Download attendance from the device into the database
+    Sync {
+        /// Device address, overriding config.toml
+        #[arg(long)]
+        device: Option<String>,
+    },
+    /// Query or clear the device
+    Device {
+        #[command(subcommand)]
+        action: DeviceCommand,
+        /// Device address, overriding config.toml
+        #[arg(long, global = true)]
+        device: Option<String>,
+    },
+    /// Export reports to Excel
+    Export {
+        #[command(subcommand)]
+        report: ExportCommand,
+    },
+    /// Import attendance from a file, or the bundled public holidays
+    Import {
+        #[command(subcommand)]
+        source: ImportCommand,
+    },
+    /// Database maintenance
+    Db {
+        #[command(subcommand)]
+        action: DbCommand,
+    },
+    /// Manage application user accounts
+    User {
+        #[command(subcommand)]
+        action: UserCommand,
+    },
+    /// Run scheduled syncs, spool replay and daily reports until stopped
+    Serve,
+}
+
+/// Device subcommands.
+#[derive(Debug, Subcommand)]
+pub enum DeviceCommand {
+    /// Show reachability, storage capacity and clock
+    Info,
+    /// Show storage capacity
+    Capacity,
+    /// Delete all attendance records from the device
+    Clear {
+        /// Confirm clearing the device
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Show the device clock and its drift from this machine
+    Time,
+}
+
+/// Export subcommands.
+#[derive(Debug, Subcommand)]
+pub enum ExportCommand {
+    /// Daily attendance summary
+    Summary(ReportArgs),
+    /// Individual check records
+    Detail {
+        #[command(flatten)]
+        report: ReportArgs,
+        /// Include double and voided punches
+        #[arg(long)]
+        include_duplicates: bool,
+    },
+    /// Late arrival, early departure and absence per employee-day
+    Status(ReportArgs),
+    /// Overtime totals per employee and month
+    Overtime(ReportArgs),
+    /// Monthly timesheet with one column per day, for printing and sign-off
+    Timesheet {
+        /// Month (YYYY-MM)
+        #[arg(long, value_parser = parse_month)]
+        month: NaiveDate,
+        /// Department ID
+        #[arg(long)]
+        dept: Option<i32>,
+        /// Output file (default: timestamped file in the current directory)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Employee list
+    Employees {
+        /// Department ID
+        #[arg(long)]
+        dept: Option<i32>,
+        /// Output file (default: timestamped file in the current directory)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+}
+
+/// Date range and output for report exports.
+#[derive(Debug, Args)]
+pub struct ReportArgs {
+    /// First day (YYYY-MM-DD)
+    #[arg(long)]
+    from: NaiveDate,
+    /// Last day (YYYY-MM-DD, default: same as --from)
+    #[arg(long)]
+    to: Option<NaiveDate>,
+    /// Department ID
+    #[arg(long)]
+    dept: Option<i32>,
+    /// Output file (default: timestamped file in the current directory)
+    #[arg(long)]
+    out: Option<PathBuf>,
+}
+
+impl ReportArgs {
+    /// Validated inclusive date range.
+    fn range(&self) -> Result<(NaiveDate, NaiveDate)> {
+        let to = self.to.unwrap_or(self.from);
+        if to < self.from {
+            return Err(AppError::validation("--to must not be before --from"));
+        }
+        Ok((self.from, to))
+    }
+}
+
+/// Parse a YYYY-MM month into its first day.
+fn parse_month(value: &str) -> std::result::Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(&format!("{value}-01"), "%Y-%m-%d").map_err(|_| format!("'{value}' is not YYYY-MM"))
+}
+
+/// Import subcommands.
+#[derive(Debug, Subcommand)]
+pub enum ImportCommand {
+    /// attlog.dat exported from the device USB menu
+    Attlog {
+        /// Path to the attlog file
+        file: PathBuf,
+    },
+    /// Bundled Vietnamese public holidays for a year
+    Holidays {
+        /// Calendar year (default: current year)
+        #[arg(long)]
+        year: Option<i32>,
+    },
+}
+
+/// Database subcommands.
+#[derive(Debug, Subcommand)]
+pub enum DbCommand {
+    /// Check connectivity and show server version, schema version and table counts
+    Check,
+    /// Apply pending schema migrations
+    Migrate,
+}
+
+/// User account subcommands.
+///
+/// Passwords are read from the first line of stdin so they stay out of the
+/// shell history.
+#[derive(Debug, Subcommand)]
+pub enum UserCommand {
+    /// List user accounts
+    List,
+    /// Create an account
+    Add {
+        /// Login name
+        username: String,
+        /// Role: admin, hr, manager or viewer
+        #[arg(long, default_value = user_role::VIEWER, value_parser = user_role::ALL)]
+        role: String,
+        /// Department ID a manager is responsible for
+        #[arg(long)]
+        dept: Option<i32>,
+        /// Display name
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Set a new password, e.g. for a locked-out admin
+    Password {
+        /// Login name
+        username: String,
+    },
+}
+
+/// Run a command and return the process exit code.
+pub fn run(command: Command, config_path: &Path) -> i32 {
+    let result = match AppConfig::try_load(config_path) {
+        ConfigLoadResult::Loaded(config) => {
+            if let Some(tz) = config.general.timezone() {
+                timezone::init(tz);
+            }
+            if let Some(day_start) = config.general.day_start() {
+                timezone::init_day_start(day_start);
+            }
+            match tokio::runtime::Runtime::new() {
+                Ok(rt) => rt.block_on(execute(command, config)),
+                Err(e) => Err(AppError::Io(e)),
+            }
+        }
+        ConfigLoadResult::Missing => Err(AppError::config(format!(
+            "Config file not found: {}",
+            config_path.display()
+        ))),
+        ConfigLoadResult::Invalid(e) => Err(AppError::config(e.to_string())),
+    };
+
+    match result {
+        Ok(mut output) => {
+            output["ok"] = json!(true);
+            println!("{output}");
+            exit_code::SUCCESS
+        }
+        Err(e) => {
+            let (kind, code) = classify(&e);
+            tracing::error!("Command failed: {e}");
+            println!("{}", json!({ "ok": false, "error": kind, "message": e.to_string() }));
+            code
+        }
+    }
+}
+
+/// Map an error to its JSON `error` kind and exit code.
+fn classify(error: &AppError) -> (&'static str, i32) {
+    match error {
+        e if e.is_cancelled() => ("cancelled", exit_code::CANCELLED),
+        AppError::Config(_) => ("config", exit_code::CONFIG),
+        AppError::Database(_) => ("database", exit_code::DATABASE),
+        AppError::DeviceLocked(_) => ("device_locked", exit_code::DEVICE_LOCKED),
+        AppError::SchemaTooNew { .. } => ("schema", exit_code::SCHEMA),
+        AppError::Http(_) | AppError::DeviceLoginFailed | AppError::DeviceTimeout(_) | AppError::ZkProtocol(_) => {
+            ("device", exit_code::DEVICE)
+        }
+        AppError::Validation(_) => ("validation", exit_code::FAILURE),
+        _ => ("failure", exit_code::FAILURE),
+    }
+}
+
+async fn execute(command: Command, mut config: AppConfig) -> Result<Value> {
+    match command {
+        Command::Sync { device } => {
+            if let Some(device) = device {
+                config.device.url = device;
+            }
+            sync(config).await
+        }
+        Command::Device { action, device } => {
+            if let Some(device) = device {
+                config.device.url = device;
+            }
+            match action {
+                DeviceCommand::Info => device_info(&config).await,
+                DeviceCommand::Capacity => device_capacity(&config).await,
+                DeviceCommand::Time => device_time(&config).await,
+                DeviceCommand::Clear { yes } => device_clear(config, yes).await,
+            }
+        }
+        Command::Export { report } => {
+            let (pool, _) = connect(&config).await?;
+            match report {
+                ExportCommand::Summary(args) => export_summary(&pool, &args).await,
+                ExportCommand::Detail {
+                    report,
+                    include_duplicates,
+                } => export_detail(&pool, &report, include_duplicates).await,
+                ExportCommand::Status(args) => export_status(&pool, &args).await,
+                ExportCommand::Overtime(args) => export_overtime(&pool, &args).await,
+                ExportCommand::Timesheet { month, dept, out } => export_timesheet(&pool, month, dept, out).await,
+                ExportCommand::Employees { dept, out } => export_employees(&pool, dept, out).await,
+            }
+        }
+        Command::Import {
+            source: ImportCommand::Attlog { file },
+        } => {
+            let (pool, _) = connect(&config).await?;
+            let result = SyncService::new(config, pool).import_attlog(&file).await?;
+            Ok(json!({
+                "command": "import",
+                "file": file.display().to_string(),
+                "parsed": result.parsed,
+                "inserted": result.inserted,
+                "skipped": result.skipped,
+                "invalid_lines": result.invalid_lines,
+                "out_of_range": result.out_of_range,
+                "unknown_uids": result.unknown_uids,
+            }))
+        }
+        Command::Import {
+            source: ImportCommand::Holidays { year },
+        } => {
+            let (pool, _) = connect(&config).await?;
+            let year = year.unwrap_or_else(|| timezone::today().year());
+            let added = db::holiday::import_public_holidays(&pool, year).await?;
+            Ok(json!({
+                "command": "import holidays",
+                "year": year,
+                "added": added,
+            }))
+        }
+        Command::Db { action } => match action {
+            DbCommand::Check => db_check(&config).await,
+            DbCommand::Migrate => {
+                let applied = connect(&config).await?.1;
+                Ok(json!({
+                    "command": "db migrate",
+                    "applied": applied,
+                    "schema_version": migration::latest_version(),
+                }))
+            }
+        },
+        Command::User { action } => {
+            let (pool, _) = connect(&config).await?;
+            match action {
+                UserCommand::List => user_list(&pool).await,
+                UserCommand::Add {
+                    username,
+                    role,
+                    dept,
+                    name,
+                } => user_add(&pool, username, role, dept, name).await,
+                UserCommand::Password { username } => user_password(&pool, &username).await,
+            }
+        }
+        Command::Serve => {
+            let stats = serve::run(config, serve::shutdown_on_signal()).await?;
+            Ok(json!({
+                "command": "serve",
+                "syncs": stats.syncs,
+                "failed_syncs": stats.failed_syncs,
+                "inserted": stats.inserted,
+                "spool_replayed": stats.spool_replayed,
+                "reports": stats.reports,
+            }))
+        }
+    }
+}
+
+/// Connect and bring the schema up to date, returning the migrations applied.
+async fn connect(config: &AppConfig) -> Result<(DatabaseConnection, Vec<i32>)> {
+    let pool = db::connect(&config.database.connection_string()).await?;
+    let applied = migration::migrate(&pool).await?;
+    Ok((pool, applied))
+}
+
+async fn sync(config: AppConfig) -> Result<Value> {
+    let device = config.device.device_ip().to_string();
+    let (pool, _) = connect(&config).await?;
+    let service = SyncService::new(config, pool);
+
+    // Ctrl+C cancels between chunks like the GUI cancel button
+    let cancel = serve::shutdown_on_signal();
+    let result = service
+        .sync_with_progress(&cancel, |_, message| tracing::info!("{message}"))
+        .await?;
+
+    Ok(json!({
+        "command": "sync",
+        "device": device,
+        "downloaded": result.downloaded,
+        "inserted": result.inserted,
+        "skipped": result.skipped,
+        "device_cleared": result.device_cleared,
+        "clock_drift_secs": result.clock_drift_secs,
+        "duration_secs": result.duration_secs,
+    }))
+}
+
+/// Run a read-only query against the device.
+///
+/// Does not touch the database, so device checks work while it is down.
+async fn query_device<T, F>(config: &AppConfig, query: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut ZkTcpClient) -> std::result::Result<T, ZkError> + Send + 'static,
+{
+    let addr = format!("{}:4370", config.device.device_ip());
+    tokio::task::spawn_blocking(move || {
+        let mut client = ZkTcpClient::connect(&addr)?;
+        query(&mut client)
+    })
+    .await
+    .map_err(|e| AppError::parse(format!("Task join error: {e}")))?
+    .map_err(AppError::from)
+}
+
+async fn device_info(config: &AppConfig) -> Result<Value> {
+    let (capacity, time) = query_device(config, |client| Ok((client.get_capacity()?, client.get_time()?))).await?;
+    let now = timezone::now();
+
+    Ok(json!({
+        "command": "device info",
+        "device": config.device.device_ip(),
+        "records": capacity.records,
+        "records_cap": capacity.records_cap,
+        "records_av": capacity.records_av,
+        "device_time": time.to_rfc3339(),
+        "clock_drift_secs": (time - now).num_seconds(),
+    }))
+}
+
+async fn device_capacity(config: &AppConfig) -> Result<Value> {
+    let capacity = query_device(config, |client| client.get_capacity()).await?;
+
+    Ok(json!({
+        "command": "device capacity",
+        "device": config.device.device_ip(),
+        "records": capacity.records,
+        "records_cap": capacity.records_cap,
+        "records_av": capacity.records_av,
+    }))
+}
+
+async fn device_time(config: &AppConfig) -> Result<Value> {
+    let time = query_device(config, |client| client.get_time()).await?;
+    let now = timezone::now();
+
+    Ok(json!({
+        "command": "device time",
+        "device": config.device.device_ip(),
+        "device_time": time.to_rfc3339(),
+        "local_time": now.to_rfc3339(),
+        "timezone": now.timezone().name(),
+        "clock_drift_secs": (time - now).num_seconds(),
+    }))
+}
+
+async fn device_clear(config: AppConfig, confirmed: bool) -> Result<Value> {
+    if !confirmed {
+        return Err(AppError::validation(
+            "Clearing deletes all records on the device; pass --yes to confirm",
+        ));
+    }
+
+    let device = config.device.device_ip().to_string();
+    let (pool, _) = connect(&config).await?;
+    SyncService::new(config, pool).clear_device().await?;
+
+    Ok(json!({ "command": "device clear", "device": device }))
+}
+
+/// Output path for an export, defaulting to a timestamped file in the current directory.
+fn export_path(out: Option<PathBuf>, prefix: &str) -> PathBuf {
+    out.unwrap_or_else(|| PathBuf::from(export::generate_export_filename(prefix)))
+}
+
+async fn export_summary(pool: &DatabaseConnection, args: &ReportArgs) -> Result<Value> {
+    let (from, to) = args.range()?;
+    let path = export_path(args.out.clone(), "attendance_summary");
+
+    let data = db::attendance::get_all_daily_summary_for_export(pool, from, to, args.dept).await?;
+    export::export_attendance_summary_to_excel(&data, &path).map_err(|e| AppError::Export(e.to_string()))?;
+
+    Ok(json!({
+        "command": "export summary",
+        "path": path.display().to_string(),
+        "rows": data.len(),
+        "missing_punch": data.iter().filter(|d| d.missing_punch()).count(),
+    }))
+}
+
+async fn export_status(pool: &DatabaseConnection, args: &ReportArgs) -> Result<Value> {
+    let (from, to) = args.range()?;
+    let path = export_path(args.out.clone(), "attendance_status");
+
+    let data = db::attendance::get_daily_status(pool, from, to, args.dept).await?;
+    export::export_attendance_status_to_excel(&data, &path).map_err(|e| AppError::Export(e.to_string()))?;
+
+    Ok(json!({
+        "command": "export status",
+        "path": path.display().to_string(),
+        "rows": data.len(),
+        "absent": data.iter().filter(|d| d.status == DayStatus::Absent).count(),
+        "late": data.iter().filter(|d| d.late_minutes > 0).count(),
+        "early_leave": data.iter().filter(|d| d.early_minutes > 0).count(),
+        "leave_days": data.iter().map(DailyStatus::leave_days).sum::<f64>(),
+    }))
+}
+
+async fn export_overtime(pool: &DatabaseConnection, args: &ReportArgs) -> Result<Value> {
+    let (from, to) = args.range()?;
+    let path = export_path(args.out.clone(), "overtime");
+
+    let data = db::attendance::get_all_daily_summary_for_export(pool, from, to, args.dept).await?;
+    export::export_overtime_to_excel(&data, &path).map_err(|e| AppError::Export(e.to_string()))?;
+
+    let totals = overtime::monthly_totals(&data);
+    Ok(json!({
+        "command": "export overtime",
+        "path": path.display().to_string(),
+        "rows": totals.len(),
+        "overtime_hours": totals.iter().map(|t| t.minutes()).sum::<i64>() as f64 / 60.0,
+        "paid_hours": totals.iter().map(|t| t.paid_hours).sum::<f64>(),
+    }))
+}
+
+async fn export_timesheet(
+    pool: &DatabaseConnection,
+    month: NaiveDate,
+    dept: Option<i32>,
+    out: Option<PathBuf>,
+) -> Result<Value> {
+    let path = export_path(out, "timesheet");
+    let (first, last) = export::month_bounds(month);
+
+    let data = db::attendance::get_daily_status(pool, first, last, dept).await?;
+    export::export_timesheet_to_excel(&data, first, &path).map_err(|e| AppError::Export(e.to_string()))?;
+
+    let employees: std::collections::HashSet<i32> = data.iter().map(|d| d.employee_id).collect();
+    Ok(json!({
+        "command": "export timesheet",
+        "path": path.display().to_string(),
+        "month": first.format("%Y-%m").to_string(),
+        "employees": employees.len(),
+    }))
+}
+
+async fn export_detail(pool: &DatabaseConnection, args: &ReportArgs, include_duplicates: bool) -> Result<Value> {
+    let (from, to) = args.range()?;
+    let path = export_path(args.out.clone(), "attendance_detail");
+
+    let data =
+        db::attendance::get_all_attendance_details_for_export(pool, from, to, args.dept, include_duplicates).await?;
+    export::export_attendance_detail_to_excel(&data, &path).map_err(|e| AppError::Export(e.to_string()))?;
+
+    Ok(json!({
+        "command": "export detail",
+        "path": path.display().to_string(),
+        "rows": data.len(),
+    }))
+}
+
+async fn export_employees(pool: &DatabaseConnection, dept: Option<i32>, out: Option<PathBuf>) -> Result<Value> {
+    let path = export_path(out, "employees");
+
+    let employees = match dept {
+        Some(dept_id) => db::employee::list_by_department(pool, dept_id).await?,
+        None => db::employee::list_all(pool).await?,
+    };
+    let departments = db::department::list_all(pool).await?;
+    export::export_employees_to_excel(&employees, &departments, &path).map_err(|e| AppError::Export(e.to_string()))?;
+
+    Ok(json!({
+        "command": "export employees",
+        "path": path.display().to_string(),
+        "rows": employees.len(),
+    }))
+}
+
+async fn user_list(pool: &DatabaseConnection) -> Result<Value> {
+    let users = db::user::list_all(pool).await?;
+    let users: Vec<Value> = users
+        .iter()
+        .map(|u| {
+            json!({
+                "id": u.id,
+                "username": u.username,
+                "display_name": u.display_name,
+                "role": u.role,
+                "department_id": u.department_id,
+                "is_active": u.is_active,
+                "last_login_at": u.last_login_at.map(|t| t.to_rfc3339()),
+            })
+        })
+        .collect();
+
+    Ok(json!({
+        "command": "user list",
+        "users": users,
+    }))
+}
+
+async fn user_add(
+    pool: &DatabaseConnection,
+    username: String,
+    role: String,
+    dept: Option<i32>,
+    name: Option<String>,
+) -> Result<Value> {
+    let password = read_password()?;
+    let user = db::user::create(
+        pool,
+        CreateUser {
+            username,
+            display_name: name,
+            password,
+            role,
+            department_id: dept,
+        },
+    )
+    .await?;
+
+    Ok(json!({
+        "command": "user add",
+        "id": user.id,
+        "username": user.username,
+        "role": user.role,
+    }))
+}
+
+async fn user_password(pool: &DatabaseConnection, username: &str) -> Result<Value> {
+    let user = db::user::get_by_username(pool, username)
+        .await?
+        .ok_or_else(|| AppError::not_found(format!("User '{username}' not found")))?;
+    let password = read_password()?;
+    db::user::update(
+        pool,
+        user.id,
+        UpdateUser {
+            password: Some(password),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    Ok(json!({
+        "command": "user password",
+        "username": user.username,
+    }))
+}
+
+/// Read a password from the first line of stdin.
+fn read_password() -> Result<String> {
+    let mut line = String::new();
+    std::io::stdin().read_line(&mut line)?;
+    let password = line.trim_end_matches(['\r', '\n']).to_string();
+    if password.is_empty() {
+        return Err(AppError::validation("Pass the password on stdin"));
+    }
+    Ok(password)
+}
+
+/// Report database state without migrating.
+async fn db_check(config: &AppConfig) -> Result<Value> {
+    let pool = db::connect(&config.database.connection_string()).await?;
+    db::test_connection(&pool).await?;
+    let version = db::get_version(&pool).await?;
+    let schema = migration::status(&pool).await?;
+    schema.ensure_supported()?;
+    let counts = if schema.current > 0 && schema.pending() == 0 {
+        Some(db::get_table_counts(&pool).await?)
+    } else {
+        None
+    };
+
+    Ok(json!({
+        "command": "db check",
+        "version": version,
+        "schema_version": schema.current,
+        "schema_latest": schema.latest,
+        "pending_migrations": schema.pending(),
+        "departments": counts.as_ref().map(|c| c.departments),
+        "employees": counts.as_ref().map(|c| c.employees),
+        "attendance_logs": counts.as_ref().map(|c| c.attendance_logs),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_exit_codes() {
+        assert_eq!(classify(&AppError::config("missing")).1, exit_code::CONFIG);
+        assert_eq!(
+            classify(&AppError::Database(sea_orm::DbErr::Custom("down".into()))).1,
+            exit_code::DATABASE
+        );
+        assert_eq!(
+            classify(&AppError::DeviceLocked("busy".into())).1,
+            exit_code::DEVICE_LOCKED
+        );
+        assert_eq!(
+            classify(&AppError::ZkProtocol(ZkError::Cancelled)).1,
+            exit_code::CANCELLED
+        );
+        assert_eq!(classify(&AppError::DeviceTimeout("slow".into())).1, exit_code::DEVICE);
+        assert_eq!(
+            classify(&AppError::validation("bad")),
+            ("validation", exit_code::FAILURE)
+        );
+    }
+
+    #[test]
+    fn test_report_range_defaults_and_validates() {
+        let day = NaiveDate::from_ymd_opt(2025, 11, 10).unwrap();
+        let mut args = ReportArgs {
+            from: day,
+            to: None,
+            dept: None,
+            out: None,
+        };
+        assert_eq!(args.range().unwrap(), (day, day));
+
+        args.to = day.pred_opt();
+        assert!(args.range().is_err());
+    }
+
+    #[test]
+    fn test_parse_month() {
+        assert_eq!(parse_month("2025-02"), Ok(NaiveDate::from_ymd_opt(2025, 2, 1).unwrap()));
+        assert!(parse_month("2025-13").is_err());
+        assert!(parse_month("Feb 2025").is_err());
+
+        let (first, last) = export::month_bounds(NaiveDate::from_ymd_opt(2024, 2, 17).unwrap());
+        assert_eq!((first.day(), last.day()), (1, 29));
+    }
+}
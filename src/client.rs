@@ -2,7 +2,7 @@
 
 use crate::error::{AppError, Result};
 use crate::models::attendance::CreateAttendanceLog;
-use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
 use reqwest::{Client, cookie::Jar};
 use std::sync::Arc;
 
@@ -160,11 +160,9 @@ impl ZkClient {
         let naive_dt = NaiveDateTime::parse_from_str(timestamp_str, "%Y-%m-%d %H:%M:%S")
             .map_err(|e| AppError::parse(format!("Invalid timestamp '{timestamp_str}': {e}")))?;
 
-        // Device returns local time, convert to UTC
-        let local_dt = Local
-            .from_local_datetime(&naive_dt)
-            .single()
-            .ok_or_else(|| AppError::parse(format!("Ambiguous local time: {timestamp_str}")))?;
+        // Device returns business wall-clock time, convert to UTC
+        let local_dt = crate::timezone::from_wall_clock(&naive_dt)
+            .ok_or_else(|| AppError::parse(format!("Non-existent local time: {timestamp_str}")))?;
 
         Ok(local_dt.with_timezone(&Utc))
     }
@@ -1,11 +1,16 @@
 //! Configuration management module.
 
+use chrono::NaiveTime;
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 /// Configuration load result.
+///
+/// Returned once per load, so the size difference between variants does not matter.
 #[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum ConfigLoadResult {
     /// Config loaded successfully.
     Loaded(AppConfig),
@@ -34,10 +39,47 @@ pub enum ConfigError {
 /// Main application configuration.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AppConfig {
+    #[serde(default)]
+    pub general: GeneralConfig,
     pub device: DeviceConfig,
     pub database: DatabaseConfig,
     pub sync: SyncConfig,
     pub ui: UiConfig,
+    #[serde(default)]
+    pub serve: ServeConfig,
+}
+
+/// Business-wide settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneralConfig {
+    /// IANA timezone the business operates in. Attendance days, device clocks
+    /// and reports use it regardless of the workstation or server timezone.
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
+    /// Time of day (HH:MM, business timezone) a work day starts. Punches before
+    /// it count towards the previous day unless an overnight shift decides.
+    #[serde(default = "default_day_start")]
+    pub day_start: String,
+}
+
+fn default_timezone() -> String {
+    crate::timezone::DEFAULT_TIMEZONE.to_string()
+}
+
+fn default_day_start() -> String {
+    "00:00".to_string()
+}
+
+impl GeneralConfig {
+    /// Parsed business timezone.
+    pub fn timezone(&self) -> Option<Tz> {
+        self.timezone.trim().parse().ok()
+    }
+
+    /// Parsed work day start.
+    pub fn day_start(&self) -> Option<NaiveTime> {
+        NaiveTime::parse_from_str(self.day_start.trim(), "%H:%M").ok()
+    }
 }
 
 /// ZKTeco device connection settings.
@@ -92,6 +134,28 @@ pub struct UiConfig {
     pub minimize_to_tray: bool,
 }
 
+/// Background service (`serve`) settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServeConfig {
+    /// Directory for the daily summary report of the previous day (empty disables).
+    #[serde(default)]
+    pub report_dir: String,
+    /// Time (HH:MM, business timezone) after which the daily report is written.
+    #[serde(default = "default_report_time")]
+    pub report_time: String,
+}
+
+fn default_report_time() -> String {
+    "07:00".to_string()
+}
+
+impl ServeConfig {
+    /// Parsed daily report time.
+    pub fn report_time(&self) -> Option<NaiveTime> {
+        NaiveTime::parse_from_str(self.report_time.trim(), "%H:%M").ok()
+    }
+}
+
 impl AppConfig {
     /// Get config file path (same directory as executable).
     pub fn default_path() -> PathBuf {
@@ -122,6 +186,12 @@ impl AppConfig {
 
     /// Validate configuration values.
     pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.general.timezone().is_none() {
+            return Err(ConfigError::Validation(format!(
+                "Unknown timezone '{}' (expected an IANA name such as Asia/Ho_Chi_Minh)",
+                self.general.timezone
+            )));
+        }
         if self.database.host.trim().is_empty() {
             return Err(ConfigError::Validation("Database host cannot be empty".to_string()));
         }
@@ -150,6 +220,12 @@ impl AppConfig {
                 "Sync interval must be at least 1 minute".to_string(),
             ));
         }
+        if self.general.day_start().is_none() {
+            return Err(ConfigError::Validation("Day start must be HH:MM".to_string()));
+        }
+        if self.serve.report_time().is_none() {
+            return Err(ConfigError::Validation("Report time must be HH:MM".to_string()));
+        }
         Ok(())
     }
 
@@ -190,6 +266,15 @@ impl DeviceConfig {
     }
 }
 
+impl Default for GeneralConfig {
+    fn default() -> Self {
+        Self {
+            timezone: default_timezone(),
+            day_start: default_day_start(),
+        }
+    }
+}
+
 impl Default for DeviceConfig {
     fn default() -> Self {
         Self {
@@ -226,6 +311,15 @@ impl Default for SyncConfig {
     }
 }
 
+impl Default for ServeConfig {
+    fn default() -> Self {
+        Self {
+            report_dir: String::new(),
+            report_time: default_report_time(),
+        }
+    }
+}
+
 impl Default for UiConfig {
     fn default() -> Self {
         Self {
@@ -291,4 +385,55 @@ mod tests {
         config.sync.days = 30;
         assert!(config.validate().is_ok());
     }
+
+    #[test]
+    fn test_legacy_debounce_secs_ignored() {
+        // The double-punch window moved to the database; old files still load
+        let config: SyncConfig = toml::from_str(
+            r#"
+            days = 30
+            max_user_id = 300
+            auto_enabled = false
+            interval_minutes = 60
+            debounce_secs = 45
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.days, 30);
+    }
+
+    #[test]
+    fn test_validation_timezone() {
+        let mut config = AppConfig::default();
+        assert_eq!(config.general.timezone(), Some(chrono_tz::Asia::Ho_Chi_Minh));
+
+        config.general.timezone = "UTC".to_string();
+        assert!(config.validate().is_ok());
+
+        config.general.timezone = "Saigon".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_day_start() {
+        let mut config = AppConfig::default();
+        assert_eq!(config.general.day_start(), NaiveTime::from_hms_opt(0, 0, 0));
+
+        config.general.day_start = "04:30".to_string();
+        assert!(config.validate().is_ok());
+
+        config.general.day_start = "4am".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_report_time() {
+        let mut config = AppConfig::default();
+
+        config.serve.report_time = "23:59".to_string();
+        assert!(config.validate().is_ok());
+
+        config.serve.report_time = "7am".to_string();
+        assert!(config.validate().is_err());
+    }
 }
@@ -1,8 +1,19 @@
 //! Database connection pool and operations.
 
 pub mod attendance;
+pub mod audit;
 pub mod connection;
 pub mod department;
 pub mod employee;
+pub mod holiday;
+pub mod leave;
+pub mod migration;
+pub mod provision;
+pub mod punch_correction;
+pub mod settings;
+pub mod shift;
+pub mod sync_lock;
+pub mod sync_run;
+pub mod user;
 
 pub use connection::{TableCounts, connect, get_table_counts, get_version, test_connection};
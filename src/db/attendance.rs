@@ -1,10 +1,21 @@
 //! Attendance repository for sync and reporting operations.
 
+use super::audit;
 use crate::entities::{attendance_logs, prelude::*};
-use crate::models::attendance::{AttendanceDetail, CreateAttendanceLog, DailyAttendance};
+use crate::models::attendance::{
+    AttendanceDetail, CreateAttendanceLog, DailyAttendance, InsertedBatch, UnmatchedPunches,
+};
+use crate::models::audit::{audit_action, audit_entity};
+use crate::models::day_status::{DailyStatus, daily_statuses};
+use crate::models::overtime;
+use crate::models::punch_session::{Punch, pair_punches, punch_roles};
+use crate::timezone;
 use chrono::{DateTime, NaiveDate, Utc};
-use sea_orm::sea_query::OnConflict;
+use sea_orm::sea_query::{Expr, OnConflict};
 use sea_orm::*;
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::ops::ControlFlow;
 
 /// Batch size for bulk inserts.
 /// 500 records x 5 fields = 2,500 params (well under PostgreSQL's 65,535 limit).
@@ -14,28 +25,28 @@ const INSERT_BATCH_SIZE: usize = 500;
 ///
 /// Uses ON CONFLICT DO NOTHING to skip duplicates based on (scanner_uid, check_time).
 /// Processes records in chunks of 500 for optimal performance.
-/// Returns the count of records processed (duplicates are silently skipped).
+/// Returns the count of records stored (duplicates are silently skipped).
 pub async fn insert_batch(db: &DatabaseConnection, records: &[CreateAttendanceLog]) -> Result<usize, DbErr> {
-    insert_batch_with_progress(db, records, |_, _| {}).await
+    let batch = insert_batch_with_progress(db, records, |_, _| ControlFlow::Continue(())).await?;
+    Ok(batch.inserted)
 }
 
 /// Insert a batch of attendance logs with progress reporting.
 ///
 /// Calls `on_progress(processed, total)` after each chunk is inserted.
+/// Returning `ControlFlow::Break` stops before the next chunk. Returns how many
+/// records were processed and how many of those were new. A database error is returned as is; chunks
+/// inserted before it stay, and inserting them again skips them as duplicates.
 pub async fn insert_batch_with_progress<F>(
     db: &DatabaseConnection,
     records: &[CreateAttendanceLog],
     mut on_progress: F,
-) -> Result<usize, DbErr>
+) -> Result<InsertedBatch, DbErr>
 where
-    F: FnMut(usize, usize),
+    F: FnMut(usize, usize) -> ControlFlow<()>,
 {
-    if records.is_empty() {
-        return Ok(0);
-    }
-
     let total = records.len();
-    let mut processed = 0;
+    let mut batch = InsertedBatch::default();
 
     for chunk in records.chunks(INSERT_BATCH_SIZE) {
         let models: Vec<attendance_logs::ActiveModel> = chunk
@@ -51,21 +62,25 @@ where
             .collect();
 
         // Use insert_many for bulk insert with ON CONFLICT DO NOTHING
-        AttendanceLogs::insert_many(models)
+        // Duplicates are skipped, so the affected row count is the number stored
+        let inserted = AttendanceLogs::insert_many(models)
             .on_conflict(
                 OnConflict::columns([attendance_logs::Column::ScannerUid, attendance_logs::Column::CheckTime])
+                    .target_and_where(Expr::col(attendance_logs::Column::Source).ne("manual"))
                     .do_nothing()
                     .to_owned(),
             )
-            .exec(db)
-            .await
-            .ok(); // Ignore errors from empty inserts (all duplicates)
+            .exec_without_returning(db)
+            .await?;
 
-        processed += chunk.len();
-        on_progress(processed, total);
+        batch.processed += chunk.len();
+        batch.inserted += inserted as usize;
+        if on_progress(batch.processed, total).is_break() {
+            break;
+        }
     }
 
-    Ok(processed)
+    Ok(batch)
 }
 
 /// Insert a single attendance log.
@@ -91,35 +106,36 @@ pub async fn insert_one(
     }
 }
 
-/// Get attendance logs within a date range.
+/// Get attendance logs on the days `start_date..=end_date` in the business timezone.
 pub async fn get_by_date_range(
     db: &DatabaseConnection,
     start_date: NaiveDate,
     end_date: NaiveDate,
 ) -> Result<Vec<attendance_logs::Model>, DbErr> {
-    let start = start_date.and_hms_opt(0, 0, 0).unwrap().and_utc();
-    let end = end_date.and_hms_opt(23, 59, 59).unwrap().and_utc();
+    let (start, end) = timezone::day_range(start_date, end_date);
 
     AttendanceLogs::find()
-        .filter(attendance_logs::Column::CheckTime.between(start, end))
+        .filter(attendance_logs::Column::CheckTime.gte(start))
+        .filter(attendance_logs::Column::CheckTime.lt(end))
         .order_by_desc(attendance_logs::Column::CheckTime)
         .all(db)
         .await
 }
 
-/// Get attendance logs for a specific scanner UID within a date range.
+/// Get attendance logs for a specific scanner UID on the days
+/// `start_date..=end_date` in the business timezone.
 pub async fn get_by_scanner_uid(
     db: &DatabaseConnection,
     scanner_uid: i32,
     start_date: NaiveDate,
     end_date: NaiveDate,
 ) -> Result<Vec<attendance_logs::Model>, DbErr> {
-    let start = start_date.and_hms_opt(0, 0, 0).unwrap().and_utc();
-    let end = end_date.and_hms_opt(23, 59, 59).unwrap().and_utc();
+    let (start, end) = timezone::day_range(start_date, end_date);
 
     AttendanceLogs::find()
         .filter(attendance_logs::Column::ScannerUid.eq(scanner_uid))
-        .filter(attendance_logs::Column::CheckTime.between(start, end))
+        .filter(attendance_logs::Column::CheckTime.gte(start))
+        .filter(attendance_logs::Column::CheckTime.lt(end))
         .order_by_desc(attendance_logs::Column::CheckTime)
         .all(db)
         .await
@@ -131,7 +147,7 @@ pub async fn get_daily_summary(
     start_date: NaiveDate,
     end_date: NaiveDate,
 ) -> Result<Vec<DailyAttendance>, DbErr> {
-    DailyAttendance::find_by_statement(Statement::from_sql_and_values(
+    let mut rows = DailyAttendance::find_by_statement(Statement::from_sql_and_values(
         DbBackend::Postgres,
         r#"
         SELECT
@@ -145,14 +161,16 @@ pub async fn get_daily_summary(
             last_check,
             check_count,
             work_hours
-        FROM app.v_daily_attendance
-        WHERE work_date BETWEEN $1 AND $2
+        FROM system.fn_daily_attendance($1, $2)
         ORDER BY work_date DESC, employee_code
         "#,
         [start_date.into(), end_date.into()],
     ))
     .all(db)
-    .await
+    .await?;
+
+    attach_sessions(db, &mut rows, None).await?;
+    Ok(rows)
 }
 
 /// Get daily attendance summary filtered by department.
@@ -162,7 +180,7 @@ pub async fn get_daily_summary_by_department(
     start_date: NaiveDate,
     end_date: NaiveDate,
 ) -> Result<Vec<DailyAttendance>, DbErr> {
-    DailyAttendance::find_by_statement(Statement::from_sql_and_values(
+    let mut rows = DailyAttendance::find_by_statement(Statement::from_sql_and_values(
         DbBackend::Postgres,
         r#"
         SELECT
@@ -176,24 +194,32 @@ pub async fn get_daily_summary_by_department(
             last_check,
             check_count,
             work_hours
-        FROM app.v_daily_attendance
-        WHERE department_id = $1 AND work_date BETWEEN $2 AND $3
+        FROM system.fn_daily_attendance($2, $3)
+        WHERE department_id = $1
         ORDER BY work_date DESC, employee_code
         "#,
         [department_id.into(), start_date.into(), end_date.into()],
     ))
     .all(db)
-    .await
+    .await?;
+
+    attach_sessions(db, &mut rows, Some(department_id)).await?;
+    Ok(rows)
 }
 
 /// Get attendance details from the view.
 /// Returns individual check records with employee info.
+///
+/// Punches are selected by work day like the daily summary, so an overnight
+/// check-out lists under the day its shift started. Punches of unknown UIDs
+/// have no work day and use their calendar day.
 pub async fn get_attendance_details(
     db: &DatabaseConnection,
     start_date: NaiveDate,
     end_date: NaiveDate,
+    include_duplicates: bool,
 ) -> Result<Vec<AttendanceDetail>, DbErr> {
-    AttendanceDetail::find_by_statement(Statement::from_sql_and_values(
+    let mut rows = AttendanceDetail::find_by_statement(Statement::from_sql_and_values(
         DbBackend::Postgres,
         r#"
         SELECT
@@ -207,15 +233,26 @@ pub async fn get_attendance_details(
             check_time,
             verify_type,
             verify_type_name,
-            source
+            status,
+            source,
+            is_duplicate,
+            work_date,
+            is_voided,
+            correction_reason,
+            corrected_by
         FROM app.v_attendance_details
-        WHERE DATE(check_time) BETWEEN $1 AND $2
+        WHERE check_time >= ($1::DATE - 1) AND check_time < ($2::DATE + 2)
+            AND COALESCE(work_date, DATE(check_time)) BETWEEN $1 AND $2
+            AND ($3 OR NOT (is_duplicate OR is_voided))
         ORDER BY check_time DESC
         "#,
-        [start_date.into(), end_date.into()],
+        [start_date.into(), end_date.into(), include_duplicates.into()],
     ))
     .all(db)
-    .await
+    .await?;
+
+    attach_punch_roles(db, &mut rows, None).await?;
+    Ok(rows)
 }
 
 /// Get attendance details filtered by department.
@@ -224,8 +261,9 @@ pub async fn get_attendance_details_by_department(
     department_id: i32,
     start_date: NaiveDate,
     end_date: NaiveDate,
+    include_duplicates: bool,
 ) -> Result<Vec<AttendanceDetail>, DbErr> {
-    AttendanceDetail::find_by_statement(Statement::from_sql_and_values(
+    let mut rows = AttendanceDetail::find_by_statement(Statement::from_sql_and_values(
         DbBackend::Postgres,
         r#"
         SELECT
@@ -239,17 +277,220 @@ pub async fn get_attendance_details_by_department(
             check_time,
             verify_type,
             verify_type_name,
-            source
+            status,
+            source,
+            is_duplicate,
+            work_date,
+            is_voided,
+            correction_reason,
+            corrected_by
         FROM app.v_attendance_details
-        WHERE department_id = $1 AND DATE(check_time) BETWEEN $2 AND $3
+        WHERE department_id = $1
+            AND check_time >= ($2::DATE - 1) AND check_time < ($3::DATE + 2)
+            AND COALESCE(work_date, DATE(check_time)) BETWEEN $2 AND $3
+            AND ($4 OR NOT (is_duplicate OR is_voided))
         ORDER BY check_time DESC
         "#,
-        [department_id.into(), start_date.into(), end_date.into()],
+        [
+            department_id.into(),
+            start_date.into(),
+            end_date.into(),
+            include_duplicates.into(),
+        ],
+    ))
+    .all(db)
+    .await?;
+
+    attach_punch_roles(db, &mut rows, Some(department_id)).await?;
+    Ok(rows)
+}
+
+/// Load non-duplicate, non-voided punches of work days in a range, keyed by employee and
+/// work day, ordered by time.
+async fn get_work_day_punches(
+    db: &DatabaseConnection,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    department_id: Option<i32>,
+) -> Result<HashMap<(i32, NaiveDate), Vec<Punch>>, DbErr> {
+    #[derive(Debug, FromQueryResult)]
+    struct WorkDayPunch {
+        id: i64,
+        employee_id: i32,
+        work_date: NaiveDate,
+        check_time: DateTime<Utc>,
+        status: i32,
+    }
+
+    // A work day's punches lie within a day either side of its date
+    let rows = WorkDayPunch::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        r#"
+        SELECT id, employee_id, work_date, check_time, status
+        FROM app.v_attendance_details
+        WHERE employee_id IS NOT NULL
+            AND check_time >= ($1::DATE - 1) AND check_time < ($2::DATE + 2)
+            AND ($3::INTEGER IS NULL OR department_id = $3)
+            AND NOT is_duplicate
+            AND NOT is_voided
+        ORDER BY check_time
+        "#,
+        [start_date.into(), end_date.into(), department_id.into()],
+    ))
+    .all(db)
+    .await?;
+
+    let mut punches: HashMap<(i32, NaiveDate), Vec<Punch>> = HashMap::new();
+    for row in rows {
+        punches
+            .entry((row.employee_id, row.work_date))
+            .or_default()
+            .push(Punch {
+                id: row.id,
+                check_time: row.check_time,
+                state: row.status,
+            });
+    }
+    Ok(punches)
+}
+
+/// Pair each summary row's punches into in/out sessions and compute overtime.
+async fn attach_sessions(
+    db: &DatabaseConnection,
+    rows: &mut [DailyAttendance],
+    department_id: Option<i32>,
+) -> Result<(), DbErr> {
+    let dates = rows.iter().map(|r| r.work_date);
+    let (Some(start), Some(end)) = (dates.clone().min(), dates.max()) else {
+        return Ok(());
+    };
+
+    let punches = get_work_day_punches(db, start, end, department_id).await?;
+    for row in rows.iter_mut() {
+        if let Some(day) = punches.get(&(row.employee_id, row.work_date)) {
+            row.sessions = pair_punches(day);
+        }
+    }
+
+    let schedule = super::shift::load_schedule(db, start, end).await?;
+    let holidays = super::holiday::load_calendar(db, start, end).await?;
+    let rules = super::settings::overtime_rules(db).await?;
+    for row in rows {
+        row.break_minutes = row.unpunched_break_minutes(&schedule);
+        row.overtime = overtime::day_overtime(&rules, &schedule, &holidays, row);
+    }
+    Ok(())
+}
+
+/// Mark each detail row with its role in its work day's sessions.
+async fn attach_punch_roles(
+    db: &DatabaseConnection,
+    rows: &mut [AttendanceDetail],
+    department_id: Option<i32>,
+) -> Result<(), DbErr> {
+    let dates = rows.iter().filter_map(|r| r.work_date);
+    let (Some(start), Some(end)) = (dates.clone().min(), dates.max()) else {
+        return Ok(());
+    };
+
+    let roles: HashMap<i64, _> = get_work_day_punches(db, start, end, department_id)
+        .await?
+        .values()
+        .flat_map(|day| punch_roles(day))
+        .collect();
+    for row in rows {
+        row.punch_role = roles.get(&row.id).copied();
+    }
+    Ok(())
+}
+
+/// Flag repeat punches within `window_secs` of the same UID's previous punch.
+///
+/// Voided and manual punches are never flagged nor count as predecessors.
+/// Only rows at or after `since` are re-evaluated (all rows when `None`), but
+/// earlier punches are still considered as predecessors. Rows outside the window
+/// are unflagged, so changing the window and re-running is safe.
+/// Returns the number of rows whose flag changed.
+pub async fn flag_double_punches<C: ConnectionTrait>(
+    db: &C,
+    window_secs: u32,
+    since: Option<DateTime<Utc>>,
+) -> Result<u64, DbErr> {
+    let result = db
+        .execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            r#"
+            WITH ordered AS (
+                SELECT
+                    id,
+                    check_time,
+                    COALESCE(
+                        check_time - LAG(check_time) OVER (PARTITION BY scanner_uid ORDER BY check_time)
+                            <= make_interval(secs => $1::float8),
+                        false
+                    ) AS is_duplicate
+                FROM app.attendance_logs
+                WHERE NOT is_voided
+                    AND source <> 'manual'
+                    AND ($2::timestamptz IS NULL OR check_time >= $2 - make_interval(secs => $1::float8))
+            )
+            UPDATE app.attendance_logs al
+            SET is_duplicate = o.is_duplicate
+            FROM ordered o
+            WHERE al.id = o.id
+              AND ($2::timestamptz IS NULL OR o.check_time >= $2)
+              AND al.is_duplicate <> o.is_duplicate
+            "#,
+            [(window_secs as i32).into(), since.into()],
+        ))
+        .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Get scanner UIDs that have punches but no employee, most recently seen first.
+pub async fn get_unmatched_punches(db: &DatabaseConnection) -> Result<Vec<UnmatchedPunches>, DbErr> {
+    UnmatchedPunches::find_by_statement(Statement::from_string(
+        DbBackend::Postgres,
+        r#"
+        SELECT
+            scanner_uid,
+            punch_count,
+            first_seen,
+            last_seen
+        FROM app.v_unmatched_punches
+        ORDER BY last_seen DESC
+        "#,
     ))
     .all(db)
     .await
 }
 
+/// Get the (scanner_uid, check_time) keys already stored within a time range.
+///
+/// Used to diff device downloads against the database without inserting.
+/// Manual punches are left out since they never stop a device punch.
+pub async fn get_existing_keys(
+    db: &DatabaseConnection,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<HashSet<(i32, DateTime<Utc>)>, DbErr> {
+    let rows: Vec<(i32, sea_orm::prelude::DateTimeWithTimeZone)> = AttendanceLogs::find()
+        .filter(attendance_logs::Column::CheckTime.between(start, end))
+        .filter(attendance_logs::Column::Source.ne("manual"))
+        .select_only()
+        .column(attendance_logs::Column::ScannerUid)
+        .column(attendance_logs::Column::CheckTime)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(uid, time)| (uid, time.with_timezone(&Utc)))
+        .collect())
+}
+
 /// Get the latest check time for incremental sync.
 pub async fn get_latest_check_time(db: &DatabaseConnection) -> Result<Option<DateTime<Utc>>, DbErr> {
     let result = AttendanceLogs::find()
@@ -260,16 +501,16 @@ pub async fn get_latest_check_time(db: &DatabaseConnection) -> Result<Option<Dat
     Ok(result.map(|r| r.check_time.with_timezone(&Utc)))
 }
 
-/// Get count of unique employees who checked in today.
+/// Get count of unique employees who checked in today (business timezone).
 pub async fn get_today_count(db: &DatabaseConnection) -> Result<u64, DbErr> {
     use sea_orm::sea_query::Expr;
 
-    let today = chrono::Utc::now().date_naive();
-    let start = today.and_hms_opt(0, 0, 0).unwrap().and_utc();
-    let end = today.and_hms_opt(23, 59, 59).unwrap().and_utc();
+    let today = timezone::today();
+    let (start, end) = timezone::day_range(today, today);
 
     let result: Option<i64> = AttendanceLogs::find()
-        .filter(attendance_logs::Column::CheckTime.between(start, end))
+        .filter(attendance_logs::Column::CheckTime.gte(start))
+        .filter(attendance_logs::Column::CheckTime.lt(end))
         .select_only()
         .column_as(Expr::col(attendance_logs::Column::ScannerUid).count_distinct(), "count")
         .into_tuple()
@@ -279,14 +520,26 @@ pub async fn get_today_count(db: &DatabaseConnection) -> Result<u64, DbErr> {
     Ok(result.unwrap_or(0) as u64)
 }
 
-/// Delete attendance logs before a given date.
+/// Delete attendance logs before the start of a given date in the business timezone.
 pub async fn delete_before(db: &DatabaseConnection, before_date: NaiveDate) -> Result<u64, DbErr> {
-    let before = before_date.and_hms_opt(0, 0, 0).unwrap().and_utc();
+    let before = timezone::start_of_day(before_date).with_timezone(&Utc);
 
+    let txn = db.begin().await?;
     let result = AttendanceLogs::delete_many()
         .filter(attendance_logs::Column::CheckTime.lt(before))
-        .exec(db)
+        .exec(&txn)
         .await?;
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::ATTENDANCE,
+        None,
+        audit_action::DELETE,
+        Some(json!({ "before": before_date, "deleted": result.rows_affected })),
+        None,
+    )
+    .await?;
+    txn.commit().await?;
 
     Ok(result.rows_affected)
 }
@@ -342,16 +595,15 @@ pub async fn count_daily_summary(
         Some(dept_id) => (
             r#"
             SELECT COUNT(*) as count
-            FROM app.v_daily_attendance
-            WHERE department_id = $1 AND work_date BETWEEN $2 AND $3
+            FROM system.fn_daily_attendance($2, $3)
+            WHERE department_id = $1
             "#,
             vec![dept_id.into(), start_date.into(), end_date.into()],
         ),
         None => (
             r#"
             SELECT COUNT(*) as count
-            FROM app.v_daily_attendance
-            WHERE work_date BETWEEN $1 AND $2
+            FROM system.fn_daily_attendance($1, $2)
             "#,
             vec![start_date.into(), end_date.into()],
         ),
@@ -389,8 +641,8 @@ pub async fn get_daily_summary_paginated(
                 last_check,
                 check_count,
                 work_hours
-            FROM app.v_daily_attendance
-            WHERE department_id = $1 AND work_date BETWEEN $2 AND $3
+            FROM system.fn_daily_attendance($2, $3)
+            WHERE department_id = $1
             ORDER BY work_date DESC, employee_code
             LIMIT $4 OFFSET $5
             "#,
@@ -415,8 +667,7 @@ pub async fn get_daily_summary_paginated(
                 last_check,
                 check_count,
                 work_hours
-            FROM app.v_daily_attendance
-            WHERE work_date BETWEEN $1 AND $2
+            FROM system.fn_daily_attendance($1, $2)
             ORDER BY work_date DESC, employee_code
             LIMIT $3 OFFSET $4
             "#,
@@ -424,9 +675,12 @@ pub async fn get_daily_summary_paginated(
         ),
     };
 
-    DailyAttendance::find_by_statement(Statement::from_sql_and_values(DbBackend::Postgres, sql, params))
+    let mut rows = DailyAttendance::find_by_statement(Statement::from_sql_and_values(DbBackend::Postgres, sql, params))
         .all(db)
-        .await
+        .await?;
+
+    attach_sessions(db, &mut rows, department_id).await?;
+    Ok(rows)
 }
 
 /// Count attendance detail records in a date range.
@@ -435,6 +689,7 @@ pub async fn count_attendance_details(
     start_date: NaiveDate,
     end_date: NaiveDate,
     department_id: Option<i32>,
+    include_duplicates: bool,
 ) -> Result<u64, DbErr> {
     #[derive(Debug, FromQueryResult)]
     struct CountResult {
@@ -446,17 +701,27 @@ pub async fn count_attendance_details(
             r#"
             SELECT COUNT(*) as count
             FROM app.v_attendance_details
-            WHERE department_id = $1 AND DATE(check_time) BETWEEN $2 AND $3
+            WHERE department_id = $1
+                AND check_time >= ($2::DATE - 1) AND check_time < ($3::DATE + 2)
+                AND COALESCE(work_date, DATE(check_time)) BETWEEN $2 AND $3
+                AND ($4 OR NOT (is_duplicate OR is_voided))
             "#,
-            vec![dept_id.into(), start_date.into(), end_date.into()],
+            vec![
+                dept_id.into(),
+                start_date.into(),
+                end_date.into(),
+                include_duplicates.into(),
+            ],
         ),
         None => (
             r#"
             SELECT COUNT(*) as count
             FROM app.v_attendance_details
-            WHERE DATE(check_time) BETWEEN $1 AND $2
+            WHERE check_time >= ($1::DATE - 1) AND check_time < ($2::DATE + 2)
+                AND COALESCE(work_date, DATE(check_time)) BETWEEN $1 AND $2
+                AND ($3 OR NOT (is_duplicate OR is_voided))
             "#,
-            vec![start_date.into(), end_date.into()],
+            vec![start_date.into(), end_date.into(), include_duplicates.into()],
         ),
     };
 
@@ -473,6 +738,7 @@ pub async fn get_attendance_details_paginated(
     start_date: NaiveDate,
     end_date: NaiveDate,
     department_id: Option<i32>,
+    include_duplicates: bool,
     pagination: Pagination,
 ) -> Result<Vec<AttendanceDetail>, DbErr> {
     let offset = pagination.offset() as i64;
@@ -492,9 +758,18 @@ pub async fn get_attendance_details_paginated(
                 check_time,
                 verify_type,
                 verify_type_name,
-                source
+                status,
+                source,
+                is_duplicate,
+                work_date,
+                is_voided,
+                correction_reason,
+                corrected_by
             FROM app.v_attendance_details
-            WHERE department_id = $1 AND DATE(check_time) BETWEEN $2 AND $3
+            WHERE department_id = $1
+                AND check_time >= ($2::DATE - 1) AND check_time < ($3::DATE + 2)
+                AND COALESCE(work_date, DATE(check_time)) BETWEEN $2 AND $3
+                AND ($6 OR NOT (is_duplicate OR is_voided))
             ORDER BY check_time DESC
             LIMIT $4 OFFSET $5
             "#,
@@ -504,6 +779,7 @@ pub async fn get_attendance_details_paginated(
                 end_date.into(),
                 limit.into(),
                 offset.into(),
+                include_duplicates.into(),
             ],
         ),
         None => (
@@ -519,19 +795,37 @@ pub async fn get_attendance_details_paginated(
                 check_time,
                 verify_type,
                 verify_type_name,
-                source
+                status,
+                source,
+                is_duplicate,
+                work_date,
+                is_voided,
+                correction_reason,
+                corrected_by
             FROM app.v_attendance_details
-            WHERE DATE(check_time) BETWEEN $1 AND $2
+            WHERE check_time >= ($1::DATE - 1) AND check_time < ($2::DATE + 2)
+                AND COALESCE(work_date, DATE(check_time)) BETWEEN $1 AND $2
+                AND ($5 OR NOT (is_duplicate OR is_voided))
             ORDER BY check_time DESC
             LIMIT $3 OFFSET $4
             "#,
-            vec![start_date.into(), end_date.into(), limit.into(), offset.into()],
+            vec![
+                start_date.into(),
+                end_date.into(),
+                limit.into(),
+                offset.into(),
+                include_duplicates.into(),
+            ],
         ),
     };
 
-    AttendanceDetail::find_by_statement(Statement::from_sql_and_values(DbBackend::Postgres, sql, params))
-        .all(db)
-        .await
+    let mut rows =
+        AttendanceDetail::find_by_statement(Statement::from_sql_and_values(DbBackend::Postgres, sql, params))
+            .all(db)
+            .await?;
+
+    attach_punch_roles(db, &mut rows, department_id).await?;
+    Ok(rows)
 }
 
 /// Load all records for export (streams in chunks internally).
@@ -556,9 +850,49 @@ pub async fn get_all_attendance_details_for_export(
     start_date: NaiveDate,
     end_date: NaiveDate,
     department_id: Option<i32>,
+    include_duplicates: bool,
 ) -> Result<Vec<AttendanceDetail>, DbErr> {
     match department_id {
-        Some(dept_id) => get_attendance_details_by_department(db, dept_id, start_date, end_date).await,
-        None => get_attendance_details(db, start_date, end_date).await,
+        Some(dept_id) => {
+            get_attendance_details_by_department(db, dept_id, start_date, end_date, include_duplicates).await
+        }
+        None => get_attendance_details(db, start_date, end_date, include_duplicates).await,
+    }
+}
+
+/// Classify every employee-day in the range against the assigned shifts.
+///
+/// Days after today are left out since they cannot be absent yet. With a
+/// department, only the days employees spent in it are included.
+pub async fn get_daily_status(
+    db: &DatabaseConnection,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    department_id: Option<i32>,
+) -> Result<Vec<DailyStatus>, DbErr> {
+    let end_date = end_date.min(crate::timezone::today());
+    if end_date < start_date {
+        return Ok(Vec::new());
+    }
+
+    let employees = Employees::find().all(db).await?;
+    let history = super::employee::load_history(db, start_date, end_date).await?;
+    let schedule = super::shift::load_schedule(db, start_date, end_date).await?;
+    let attendance = get_all_daily_summary_for_export(db, start_date, end_date, department_id).await?;
+    let holidays = super::holiday::load_calendar(db, start_date, end_date).await?;
+    let leave = super::leave::load_leave_days(db, start_date, end_date).await?;
+
+    let mut rows = daily_statuses(
+        &employees,
+        &history,
+        &schedule,
+        &attendance,
+        &holidays,
+        &leave,
+        start_date..=end_date,
+    );
+    if department_id.is_some() {
+        rows.retain(|row| row.department_id == department_id);
     }
+    Ok(rows)
 }
@@ -0,0 +1,103 @@
+//! Audit log of data changes.
+//!
+//! Mutations call [`record`] in the same transaction as the change, so an
+//! entry exists exactly when the change was committed. The acting user is
+//! process-wide: the OS user at startup, replaced by the signed-in user.
+
+use std::sync::RwLock;
+
+use sea_orm::sea_query::{Expr, Func};
+use sea_orm::*;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::entities::{audit_log, prelude::*};
+use crate::models::audit::AuditFilter;
+
+/// Actor recorded when nobody is set, e.g. for unattended runs.
+const SYSTEM_ACTOR: &str = "system";
+
+static ACTOR: RwLock<String> = RwLock::new(String::new());
+
+/// Set the acting user from the OS login name.
+pub fn init_actor() {
+    let name = std::env::var("USERNAME")
+        .or_else(|_| std::env::var("USER"))
+        .unwrap_or_default();
+    set_actor(&name);
+}
+
+/// Set the acting user recorded on changes from now on.
+pub fn set_actor(name: &str) {
+    *ACTOR.write().unwrap_or_else(|e| e.into_inner()) = name.trim().to_string();
+}
+
+/// The acting user recorded on changes.
+pub fn actor() -> String {
+    let actor = ACTOR.read().unwrap_or_else(|e| e.into_inner());
+    if actor.is_empty() {
+        SYSTEM_ACTOR.to_string()
+    } else {
+        actor.clone()
+    }
+}
+
+/// Snapshot of a record for the before/after columns.
+pub fn snapshot<T: Serialize>(value: &T) -> Option<Value> {
+    serde_json::to_value(value).ok()
+}
+
+/// Record a change made by `actor`.
+pub async fn record<C: ConnectionTrait>(
+    db: &C,
+    actor: &str,
+    entity: &str,
+    entity_id: Option<String>,
+    action: &str,
+    before: Option<Value>,
+    after: Option<Value>,
+) -> Result<audit_log::Model, DbErr> {
+    audit_log::ActiveModel {
+        actor: Set(actor.to_string()),
+        entity: Set(entity.to_string()),
+        entity_id: Set(entity_id),
+        action: Set(action.to_string()),
+        before_data: Set(before),
+        after_data: Set(after),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+}
+
+/// List audit entries matching the filter, newest first.
+pub async fn list(db: &DatabaseConnection, filter: &AuditFilter) -> Result<Vec<audit_log::Model>, DbErr> {
+    let mut query = AuditLog::find();
+
+    if let Some(entity) = &filter.entity {
+        query = query.filter(audit_log::Column::Entity.eq(entity.as_str()));
+    }
+    if let Some(entity_id) = &filter.entity_id {
+        query = query.filter(audit_log::Column::EntityId.eq(entity_id.as_str()));
+    }
+    if let Some(action) = &filter.action {
+        query = query.filter(audit_log::Column::Action.eq(action.as_str()));
+    }
+    if let Some(actor) = filter.actor.as_deref().map(str::trim).filter(|a| !a.is_empty()) {
+        let pattern = format!("%{}%", actor.to_lowercase());
+        query = query.filter(Expr::expr(Func::lower(Expr::col(audit_log::Column::Actor))).like(pattern));
+    }
+    if let Some(since) = filter.since {
+        query = query.filter(audit_log::Column::CreatedAt.gte(since));
+    }
+    if let Some(until) = filter.until {
+        query = query.filter(audit_log::Column::CreatedAt.lt(until));
+    }
+
+    query
+        .order_by_desc(audit_log::Column::CreatedAt)
+        .order_by_desc(audit_log::Column::Id)
+        .limit(filter.limit)
+        .all(db)
+        .await
+}
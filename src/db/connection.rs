@@ -1,23 +1,37 @@
 //! Database connection pool and utility functions.
 
-use sea_orm::sqlx::Executor;
 use sea_orm::sqlx::postgres::PgPoolOptions;
+use sea_orm::sqlx::{self, Executor};
 use sea_orm::{ConnectionTrait, DatabaseConnection, DbErr, PaginatorTrait, SqlxPostgresConnector, Statement};
 use std::time::Duration;
 
 /// Create a new database connection with configured pool settings.
-/// Uses after_connect callback to set search_path on each connection.
+/// Uses after_connect callback to set search_path, the business timezone and
+/// the work day start (`app.day_start`) on each connection, so the work dates
+/// in views and queries match the attendance day computed in Rust.
 pub async fn connect(database_url: &str) -> Result<DatabaseConnection, DbErr> {
+    let timezone = crate::timezone::business().name();
+    let day_start = crate::timezone::day_start().format("%H:%M:%S").to_string();
+
     // Build sqlx pool with after_connect callback
     let sqlx_pool = PgPoolOptions::new()
         .max_connections(5)
         .min_connections(1)
         .acquire_timeout(Duration::from_secs(10))
         .idle_timeout(Duration::from_secs(300))
-        .after_connect(|conn, _meta| {
+        .after_connect(move |conn, _meta| {
+            let day_start = day_start.clone();
             Box::pin(async move {
                 // Set search_path for each new connection
                 conn.execute("SET search_path TO app, system, public").await?;
+                sqlx::query("SELECT set_config('TimeZone', $1, false)")
+                    .bind(timezone)
+                    .execute(&mut *conn)
+                    .await?;
+                sqlx::query("SELECT set_config('app.day_start', $1, false)")
+                    .bind(day_start)
+                    .execute(&mut *conn)
+                    .await?;
                 Ok(())
             })
         })
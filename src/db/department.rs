@@ -1,6 +1,8 @@
 //! Department repository with CRUD operations.
 
+use super::audit;
 use crate::entities::{departments, prelude::*};
+use crate::models::audit::{audit_action, audit_entity};
 use crate::models::department::{CreateDepartment, UpdateDepartment};
 use sea_orm::*;
 
@@ -36,7 +38,21 @@ pub async fn create(db: &DatabaseConnection, data: CreateDepartment) -> Result<d
         display_order: Set(data.display_order),
         ..Default::default()
     };
-    model.insert(db).await
+
+    let txn = db.begin().await?;
+    let created = model.insert(&txn).await?;
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::DEPARTMENT,
+        Some(created.id.to_string()),
+        audit_action::CREATE,
+        None,
+        audit::snapshot(&created),
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(created)
 }
 
 /// Update an existing department.
@@ -45,10 +61,12 @@ pub async fn update(
     id: i32,
     data: UpdateDepartment,
 ) -> Result<Option<departments::Model>, DbErr> {
-    let existing = Departments::find_by_id(id).one(db).await?;
+    let txn = db.begin().await?;
+    let existing = Departments::find_by_id(id).one(&txn).await?;
 
     match existing {
         Some(model) => {
+            let before = audit::snapshot(&model);
             let mut active: departments::ActiveModel = model.into();
 
             if let Some(name) = data.name {
@@ -64,7 +82,18 @@ pub async fn update(
                 active.is_active = Set(is_active);
             }
 
-            let updated = active.update(db).await?;
+            let updated = active.update(&txn).await?;
+            audit::record(
+                &txn,
+                &audit::actor(),
+                audit_entity::DEPARTMENT,
+                Some(id.to_string()),
+                audit_action::UPDATE,
+                before,
+                audit::snapshot(&updated),
+            )
+            .await?;
+            txn.commit().await?;
             Ok(Some(updated))
         }
         None => Ok(None),
@@ -73,8 +102,24 @@ pub async fn update(
 
 /// Delete a department by ID.
 pub async fn delete(db: &DatabaseConnection, id: i32) -> Result<bool, DbErr> {
-    let result = Departments::delete_by_id(id).exec(db).await?;
-    Ok(result.rows_affected > 0)
+    let txn = db.begin().await?;
+    let Some(existing) = Departments::find_by_id(id).one(&txn).await? else {
+        return Ok(false);
+    };
+
+    Departments::delete_by_id(id).exec(&txn).await?;
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::DEPARTMENT,
+        Some(id.to_string()),
+        audit_action::DELETE,
+        audit::snapshot(&existing),
+        None,
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(true)
 }
 
 /// Check if department name exists (for validation).
@@ -1,7 +1,14 @@
 //! Employee repository with CRUD operations.
 
-use crate::entities::{employees, prelude::*};
-use crate::models::employee::{CreateEmployee, UpdateEmployee};
+use super::audit;
+use crate::entities::{departments, employee_assignments, employees, prelude::*};
+use crate::models::audit::{audit_action, audit_entity};
+use crate::models::employee::{
+    CreateEmployee, EmploymentHistory, OffboardEmployee, Reassignment, Rehire, RehireEmployee, UpdateEmployee,
+    plan_reassignment, plan_rehire, plan_start_date,
+};
+use chrono::NaiveDate;
+use sea_orm::sea_query::Expr;
 use sea_orm::*;
 
 /// List all employees ordered by employee_code.
@@ -71,21 +78,56 @@ pub async fn create(db: &DatabaseConnection, data: CreateEmployee) -> Result<emp
         employee_code: Set(data.employee_code),
         full_name: Set(data.full_name),
         department_id: Set(data.department_id),
+        position: Set(data.position),
         scanner_uid: Set(data.scanner_uid),
         gender: Set(data.gender),
         birth_date: Set(data.birth_date),
         start_date: Set(data.start_date),
         ..Default::default()
     };
-    model.insert(db).await
+
+    let txn = db.begin().await?;
+    let created = model.insert(&txn).await?;
+    employee_assignments::ActiveModel {
+        employee_id: Set(created.id),
+        department_id: Set(created.department_id),
+        position: Set(created.position.clone()),
+        effective_from: Set(created.start_date),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::EMPLOYEE,
+        Some(created.id.to_string()),
+        audit_action::CREATE,
+        None,
+        audit::snapshot(&created),
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(created)
 }
 
 /// Update an existing employee.
+///
+/// A department or position change closes the current assignment the day
+/// before `effective_from` and opens a new one, so earlier attendance stays
+/// with the old department (see [`plan_reassignment`]). The change defaults to
+/// today, or the last working day of a leaver, and cannot take effect after
+/// it. A new start date moves the first assignment (see [`plan_start_date`]).
+/// Leaving and coming back go through [`offboard`] and [`rehire`].
 pub async fn update(db: &DatabaseConnection, id: i32, data: UpdateEmployee) -> Result<Option<employees::Model>, DbErr> {
-    let existing = Employees::find_by_id(id).one(db).await?;
+    let txn = db.begin().await?;
+    let existing = Employees::find_by_id(id).one(&txn).await?;
 
     match existing {
         Some(model) => {
+            let before = audit::snapshot(&model);
+            let previous = (model.department_id, model.position.clone());
+            let previous_start = model.start_date;
             let mut active: employees::ActiveModel = model.into();
 
             if let Some(employee_code) = data.employee_code {
@@ -97,6 +139,9 @@ pub async fn update(db: &DatabaseConnection, id: i32, data: UpdateEmployee) -> R
             if let Some(department_id) = data.department_id {
                 active.department_id = Set(department_id);
             }
+            if let Some(position) = data.position {
+                active.position = Set(position);
+            }
             if let Some(scanner_uid) = data.scanner_uid {
                 active.scanner_uid = Set(scanner_uid);
             }
@@ -113,17 +158,289 @@ pub async fn update(db: &DatabaseConnection, id: i32, data: UpdateEmployee) -> R
                 active.is_active = Set(is_active);
             }
 
-            let updated = active.update(db).await?;
+            let updated = active.update(&txn).await?;
+            if updated.start_date != previous_start {
+                move_start_date(&txn, &updated).await?;
+            }
+            if previous != (updated.department_id, updated.position.clone()) {
+                let today = crate::timezone::today();
+                let effective_from = data
+                    .effective_from
+                    .unwrap_or_else(|| updated.end_date.map_or(today, |end_date| end_date.min(today)));
+                if let Some(end_date) = updated.end_date
+                    && effective_from > end_date
+                {
+                    return Err(DbErr::Custom(format!(
+                        "{} left on {end_date}; the change must take effect by then",
+                        updated.full_name
+                    )));
+                }
+                reassign(&txn, &updated, effective_from).await?;
+            }
+            audit::record(
+                &txn,
+                &audit::actor(),
+                audit_entity::EMPLOYEE,
+                Some(id.to_string()),
+                audit_action::UPDATE,
+                before,
+                audit::snapshot(&updated),
+            )
+            .await?;
+            txn.commit().await?;
             Ok(Some(updated))
         }
         None => Ok(None),
     }
 }
 
+/// Offboard a leaver: record the last working day and reason, deactivate the
+/// employee and end their assignments on that day.
+///
+/// Transfers dated after the last working day never take effect, so their
+/// assignments are removed and the employee keeps the department and position
+/// they left from.
+pub async fn offboard(
+    db: &DatabaseConnection,
+    id: i32,
+    data: OffboardEmployee,
+) -> Result<Option<employees::Model>, DbErr> {
+    let txn = db.begin().await?;
+    let Some(model) = Employees::find_by_id(id).one(&txn).await? else {
+        return Ok(None);
+    };
+    if data.end_date < model.start_date {
+        return Err(DbErr::Custom(format!(
+            "End date must not be before the start date {}",
+            model.start_date
+        )));
+    }
+
+    EmployeeAssignments::delete_many()
+        .filter(employee_assignments::Column::EmployeeId.eq(id))
+        .filter(employee_assignments::Column::EffectiveFrom.gt(data.end_date))
+        .exec(&txn)
+        .await?;
+    EmployeeAssignments::update_many()
+        .col_expr(employee_assignments::Column::EffectiveTo, Expr::value(data.end_date))
+        .filter(employee_assignments::Column::EmployeeId.eq(id))
+        .filter(
+            Condition::any()
+                .add(employee_assignments::Column::EffectiveTo.is_null())
+                .add(employee_assignments::Column::EffectiveTo.gt(data.end_date)),
+        )
+        .exec(&txn)
+        .await?;
+    let last = EmployeeAssignments::find()
+        .filter(employee_assignments::Column::EmployeeId.eq(id))
+        .order_by_desc(employee_assignments::Column::EffectiveFrom)
+        .one(&txn)
+        .await?;
+
+    let before = audit::snapshot(&model);
+    let mut active: employees::ActiveModel = model.into();
+    if let Some(last) = last {
+        active.department_id = Set(last.department_id);
+        active.position = Set(last.position);
+    }
+    active.end_date = Set(Some(data.end_date));
+    active.termination_reason = Set(data.reason);
+    active.is_active = Set(false);
+    let updated = active.update(&txn).await?;
+
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::EMPLOYEE,
+        Some(id.to_string()),
+        audit_action::UPDATE,
+        before,
+        audit::snapshot(&updated),
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(Some(updated))
+}
+
+/// Rehire a leaver from `data.start_date`, clearing the end date and reason.
+///
+/// See [`plan_rehire`] for when the last assignment continues and when a new
+/// one is opened with the department and position they left from.
+pub async fn rehire(db: &DatabaseConnection, id: i32, data: RehireEmployee) -> Result<Option<employees::Model>, DbErr> {
+    let txn = db.begin().await?;
+    let Some(model) = Employees::find_by_id(id).one(&txn).await? else {
+        return Ok(None);
+    };
+    let Some(previous_end) = model.end_date else {
+        return Err(DbErr::Custom(format!("{} has not left", model.full_name)));
+    };
+    if data.start_date <= previous_end {
+        return Err(DbErr::Custom(format!(
+            "Rehire date must be after the last working day {previous_end}"
+        )));
+    }
+
+    let assignments = EmployeeAssignments::find()
+        .filter(employee_assignments::Column::EmployeeId.eq(id))
+        .all(&txn)
+        .await?;
+    match plan_rehire(&assignments, previous_end, data.start_date) {
+        Rehire::Reopen(assignment_id) => {
+            if let Some(existing) = assignments.into_iter().find(|a| a.id == assignment_id) {
+                let mut active: employee_assignments::ActiveModel = existing.into();
+                active.effective_to = Set(None);
+                active.update(&txn).await?;
+            }
+        }
+        Rehire::Open(effective_from) => {
+            employee_assignments::ActiveModel {
+                employee_id: Set(id),
+                department_id: Set(model.department_id),
+                position: Set(model.position.clone()),
+                effective_from: Set(effective_from),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?;
+        }
+    }
+
+    let before = audit::snapshot(&model);
+    let mut active: employees::ActiveModel = model.into();
+    active.end_date = Set(None);
+    active.termination_reason = Set(None);
+    active.is_active = Set(true);
+    let updated = active.update(&txn).await?;
+
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::EMPLOYEE,
+        Some(id.to_string()),
+        audit_action::UPDATE,
+        before,
+        audit::snapshot(&updated),
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(Some(updated))
+}
+
+/// Move the first assignment to the employee's start date.
+async fn move_start_date<C: ConnectionTrait>(db: &C, employee: &employees::Model) -> Result<(), DbErr> {
+    let assignments = EmployeeAssignments::find()
+        .filter(employee_assignments::Column::EmployeeId.eq(employee.id))
+        .all(db)
+        .await?;
+    let Some(id) = plan_start_date(&assignments, employee.start_date).map_err(DbErr::Custom)? else {
+        return Ok(());
+    };
+
+    if let Some(first) = assignments.into_iter().find(|a| a.id == id) {
+        let mut active: employee_assignments::ActiveModel = first.into();
+        active.effective_from = Set(employee.start_date);
+        active.update(db).await?;
+    }
+    Ok(())
+}
+
+/// Record the employee's current department and position from `effective_from`.
+///
+/// See [`plan_reassignment`] for when an assignment is corrected in place
+/// instead of a new one being added.
+async fn reassign<C: ConnectionTrait>(
+    db: &C,
+    employee: &employees::Model,
+    effective_from: NaiveDate,
+) -> Result<(), DbErr> {
+    let assignments = EmployeeAssignments::find()
+        .filter(employee_assignments::Column::EmployeeId.eq(employee.id))
+        .all(db)
+        .await?;
+    let find = |id: i32| assignments.iter().find(|a| a.id == id).cloned();
+
+    match plan_reassignment(&assignments, effective_from) {
+        Reassignment::Amend(id) => {
+            if let Some(existing) = find(id) {
+                let mut active: employee_assignments::ActiveModel = existing.into();
+                active.department_id = Set(employee.department_id);
+                active.position = Set(employee.position.clone());
+                active.update(db).await?;
+            }
+        }
+        Reassignment::Split { close, effective_to } => {
+            if let Some(existing) = close.and_then(find) {
+                let mut active: employee_assignments::ActiveModel = existing.into();
+                active.effective_to = Set(effective_from.pred_opt());
+                active.update(db).await?;
+            }
+            employee_assignments::ActiveModel {
+                employee_id: Set(employee.id),
+                department_id: Set(employee.department_id),
+                position: Set(employee.position.clone()),
+                effective_from: Set(effective_from),
+                effective_to: Set(effective_to),
+                ..Default::default()
+            }
+            .insert(db)
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+/// List an employee's assignments, latest first.
+pub async fn list_assignments(
+    db: &DatabaseConnection,
+    employee_id: i32,
+) -> Result<Vec<employee_assignments::Model>, DbErr> {
+    EmployeeAssignments::find()
+        .filter(employee_assignments::Column::EmployeeId.eq(employee_id))
+        .order_by_desc(employee_assignments::Column::EffectiveFrom)
+        .all(db)
+        .await
+}
+
+/// Load the assignments overlapping a date range.
+pub async fn load_history<C: ConnectionTrait>(
+    db: &C,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<EmploymentHistory, DbErr> {
+    let assignments = EmployeeAssignments::find()
+        .filter(employee_assignments::Column::EffectiveFrom.lte(end))
+        .filter(
+            Condition::any()
+                .add(employee_assignments::Column::EffectiveTo.is_null())
+                .add(employee_assignments::Column::EffectiveTo.gte(start)),
+        )
+        .all(db)
+        .await?;
+    let departments: Vec<departments::Model> = Departments::find().all(db).await?;
+
+    Ok(EmploymentHistory::new(assignments, &departments))
+}
+
 /// Delete an employee by ID.
 pub async fn delete(db: &DatabaseConnection, id: i32) -> Result<bool, DbErr> {
-    let result = Employees::delete_by_id(id).exec(db).await?;
-    Ok(result.rows_affected > 0)
+    let txn = db.begin().await?;
+    let Some(existing) = Employees::find_by_id(id).one(&txn).await? else {
+        return Ok(false);
+    };
+
+    Employees::delete_by_id(id).exec(&txn).await?;
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::EMPLOYEE,
+        Some(id.to_string()),
+        audit_action::DELETE,
+        audit::snapshot(&existing),
+        None,
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(true)
 }
 
 /// Check if employee code exists (for validation).
@@ -0,0 +1,174 @@
+//! Holiday calendar repository.
+
+use std::collections::HashSet;
+
+use chrono::NaiveDate;
+use sea_orm::*;
+
+use super::audit;
+use crate::entities::{departments, holidays, prelude::*};
+use crate::models::audit::{audit_action, audit_entity};
+use crate::models::holiday::{CreateHoliday, HolidayCalendar, UpdateHoliday, public_holidays};
+
+/// List holidays in a year ordered by date.
+pub async fn list_holidays(db: &DatabaseConnection, year: i32) -> Result<Vec<holidays::Model>, DbErr> {
+    let (Some(start), Some(end)) = (
+        NaiveDate::from_ymd_opt(year, 1, 1),
+        NaiveDate::from_ymd_opt(year, 12, 31),
+    ) else {
+        return Ok(Vec::new());
+    };
+    Holidays::find()
+        .filter(holidays::Column::HolidayDate.between(start, end))
+        .order_by_asc(holidays::Column::HolidayDate)
+        .order_by_asc(holidays::Column::Id)
+        .all(db)
+        .await
+}
+
+/// Create a new holiday.
+pub async fn create_holiday(db: &DatabaseConnection, data: CreateHoliday) -> Result<holidays::Model, DbErr> {
+    let model = holidays::ActiveModel {
+        holiday_date: Set(data.holiday_date),
+        name: Set(data.name),
+        is_paid: Set(data.is_paid),
+        department_id: Set(data.department_id),
+        ..Default::default()
+    };
+
+    let txn = db.begin().await?;
+    let created = model.insert(&txn).await?;
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::HOLIDAY,
+        Some(created.id.to_string()),
+        audit_action::CREATE,
+        None,
+        audit::snapshot(&created),
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(created)
+}
+
+/// Update an existing holiday.
+pub async fn update_holiday(
+    db: &DatabaseConnection,
+    id: i32,
+    data: UpdateHoliday,
+) -> Result<Option<holidays::Model>, DbErr> {
+    let txn = db.begin().await?;
+    let Some(model) = Holidays::find_by_id(id).one(&txn).await? else {
+        return Ok(None);
+    };
+
+    let before = audit::snapshot(&model);
+    let mut active: holidays::ActiveModel = model.into();
+    if let Some(holiday_date) = data.holiday_date {
+        active.holiday_date = Set(holiday_date);
+    }
+    if let Some(name) = data.name {
+        active.name = Set(name);
+    }
+    if let Some(is_paid) = data.is_paid {
+        active.is_paid = Set(is_paid);
+    }
+    if let Some(department_id) = data.department_id {
+        active.department_id = Set(department_id);
+    }
+
+    let updated = active.update(&txn).await?;
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::HOLIDAY,
+        Some(id.to_string()),
+        audit_action::UPDATE,
+        before,
+        audit::snapshot(&updated),
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(Some(updated))
+}
+
+/// Delete a holiday by ID.
+pub async fn delete_holiday(db: &DatabaseConnection, id: i32) -> Result<bool, DbErr> {
+    let txn = db.begin().await?;
+    let Some(existing) = Holidays::find_by_id(id).one(&txn).await? else {
+        return Ok(false);
+    };
+
+    Holidays::delete_by_id(id).exec(&txn).await?;
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::HOLIDAY,
+        Some(id.to_string()),
+        audit_action::DELETE,
+        audit::snapshot(&existing),
+        None,
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(true)
+}
+
+/// Import the bundled public holidays for a year.
+///
+/// Days that already have a company-wide holiday are left as they are, so the
+/// import can be repeated after editing names. Returns the number added.
+pub async fn import_public_holidays(db: &DatabaseConnection, year: i32) -> Result<usize, DbErr> {
+    let existing: HashSet<NaiveDate> = list_holidays(db, year)
+        .await?
+        .into_iter()
+        .filter(|h| h.department_id.is_none())
+        .map(|h| h.holiday_date)
+        .collect();
+
+    let models: Vec<holidays::ActiveModel> = public_holidays(year)
+        .into_iter()
+        .filter(|h| !existing.contains(&h.holiday_date))
+        .map(|h| holidays::ActiveModel {
+            holiday_date: Set(h.holiday_date),
+            name: Set(h.name),
+            is_paid: Set(h.is_paid),
+            department_id: Set(h.department_id),
+            ..Default::default()
+        })
+        .collect();
+
+    let count = models.len();
+    let txn = db.begin().await?;
+    for model in models {
+        let created = model.insert(&txn).await?;
+        audit::record(
+            &txn,
+            &audit::actor(),
+            audit_entity::HOLIDAY,
+            Some(created.id.to_string()),
+            audit_action::CREATE,
+            None,
+            audit::snapshot(&created),
+        )
+        .await?;
+    }
+    txn.commit().await?;
+    Ok(count)
+}
+
+/// Load holidays in `start..=end` for resolving days off per department.
+pub async fn load_calendar<C: ConnectionTrait>(
+    db: &C,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<HolidayCalendar, DbErr> {
+    let holidays = Holidays::find()
+        .filter(holidays::Column::HolidayDate.between(start, end))
+        .all(db)
+        .await?;
+    let departments: Vec<departments::Model> = Departments::find().all(db).await?;
+
+    Ok(HolidayCalendar::new(holidays, &departments))
+}
@@ -0,0 +1,382 @@
+//! Leave types, entitlements and requests repository.
+
+use chrono::{NaiveDate, Utc};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::*;
+
+use super::audit;
+use crate::entities::{leave_entitlements, leave_requests, leave_types, prelude::*};
+use crate::models::audit::{audit_action, audit_entity};
+use crate::models::leave::{
+    CreateLeaveRequest, CreateLeaveType, LeaveBalance, LeaveDays, SetLeaveEntitlement, UpdateLeaveRequest,
+    UpdateLeaveType, count_leave_days, leave_balances, leave_status,
+};
+
+/// List all leave types ordered by name.
+pub async fn list_leave_types(db: &DatabaseConnection) -> Result<Vec<leave_types::Model>, DbErr> {
+    LeaveTypes::find().order_by_asc(leave_types::Column::Name).all(db).await
+}
+
+/// Create a new leave type.
+pub async fn create_leave_type(db: &DatabaseConnection, data: CreateLeaveType) -> Result<leave_types::Model, DbErr> {
+    let model = leave_types::ActiveModel {
+        name: Set(data.name),
+        is_paid: Set(data.is_paid),
+        default_days: Set(data.default_days),
+        is_active: Set(true),
+        ..Default::default()
+    };
+
+    let txn = db.begin().await?;
+    let created = model.insert(&txn).await?;
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::LEAVE_TYPE,
+        Some(created.id.to_string()),
+        audit_action::CREATE,
+        None,
+        audit::snapshot(&created),
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(created)
+}
+
+/// Update an existing leave type.
+pub async fn update_leave_type(
+    db: &DatabaseConnection,
+    id: i32,
+    data: UpdateLeaveType,
+) -> Result<Option<leave_types::Model>, DbErr> {
+    let txn = db.begin().await?;
+    let Some(model) = LeaveTypes::find_by_id(id).one(&txn).await? else {
+        return Ok(None);
+    };
+
+    let before = audit::snapshot(&model);
+    let mut active: leave_types::ActiveModel = model.into();
+    if let Some(name) = data.name {
+        active.name = Set(name);
+    }
+    if let Some(is_paid) = data.is_paid {
+        active.is_paid = Set(is_paid);
+    }
+    if let Some(default_days) = data.default_days {
+        active.default_days = Set(default_days);
+    }
+    if let Some(is_active) = data.is_active {
+        active.is_active = Set(is_active);
+    }
+
+    let updated = active.update(&txn).await?;
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::LEAVE_TYPE,
+        Some(id.to_string()),
+        audit_action::UPDATE,
+        before,
+        audit::snapshot(&updated),
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(Some(updated))
+}
+
+/// List entitlements set for a year.
+pub async fn list_entitlements(db: &DatabaseConnection, year: i32) -> Result<Vec<leave_entitlements::Model>, DbErr> {
+    LeaveEntitlements::find()
+        .filter(leave_entitlements::Column::Year.eq(year))
+        .all(db)
+        .await
+}
+
+/// Set an employee's entitlement for a year, replacing any existing one.
+pub async fn set_entitlement(db: &DatabaseConnection, data: SetLeaveEntitlement) -> Result<(), DbErr> {
+    let txn = db.begin().await?;
+    let existing = find_entitlement(&txn, data.employee_id, data.leave_type_id, data.year).await?;
+
+    let model = leave_entitlements::ActiveModel {
+        employee_id: Set(data.employee_id),
+        leave_type_id: Set(data.leave_type_id),
+        year: Set(data.year),
+        days: Set(data.days),
+        ..Default::default()
+    };
+    let saved = LeaveEntitlements::insert(model)
+        .on_conflict(
+            OnConflict::columns([
+                leave_entitlements::Column::EmployeeId,
+                leave_entitlements::Column::LeaveTypeId,
+                leave_entitlements::Column::Year,
+            ])
+            .update_column(leave_entitlements::Column::Days)
+            .to_owned(),
+        )
+        .exec_with_returning(&txn)
+        .await?;
+    let action = if existing.is_some() {
+        audit_action::UPDATE
+    } else {
+        audit_action::CREATE
+    };
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::LEAVE_ENTITLEMENT,
+        Some(saved.id.to_string()),
+        action,
+        existing.as_ref().and_then(audit::snapshot),
+        audit::snapshot(&saved),
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(())
+}
+
+/// Remove an employee's entitlement for a year, reverting to the type default.
+pub async fn clear_entitlement(
+    db: &DatabaseConnection,
+    employee_id: i32,
+    leave_type_id: i32,
+    year: i32,
+) -> Result<bool, DbErr> {
+    let txn = db.begin().await?;
+    let Some(existing) = find_entitlement(&txn, employee_id, leave_type_id, year).await? else {
+        return Ok(false);
+    };
+
+    LeaveEntitlements::delete_by_id(existing.id).exec(&txn).await?;
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::LEAVE_ENTITLEMENT,
+        Some(existing.id.to_string()),
+        audit_action::DELETE,
+        audit::snapshot(&existing),
+        None,
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(true)
+}
+
+/// An employee's entitlement for a leave type and year.
+async fn find_entitlement<C: ConnectionTrait>(
+    db: &C,
+    employee_id: i32,
+    leave_type_id: i32,
+    year: i32,
+) -> Result<Option<leave_entitlements::Model>, DbErr> {
+    LeaveEntitlements::find()
+        .filter(leave_entitlements::Column::EmployeeId.eq(employee_id))
+        .filter(leave_entitlements::Column::LeaveTypeId.eq(leave_type_id))
+        .filter(leave_entitlements::Column::Year.eq(year))
+        .one(db)
+        .await
+}
+
+/// List leave requests overlapping `start..=end`, newest first.
+pub async fn list_leave_requests(
+    db: &DatabaseConnection,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<Vec<leave_requests::Model>, DbErr> {
+    LeaveRequests::find()
+        .filter(leave_requests::Column::StartDate.lte(end))
+        .filter(leave_requests::Column::EndDate.gte(start))
+        .order_by_desc(leave_requests::Column::StartDate)
+        .order_by_desc(leave_requests::Column::Id)
+        .all(db)
+        .await
+}
+
+/// Leave days a request for `start..=end` takes for an employee.
+async fn request_days<C: ConnectionTrait>(
+    db: &C,
+    employee_id: i32,
+    start: NaiveDate,
+    end: NaiveDate,
+    half_day: bool,
+) -> Result<f64, DbErr> {
+    let Some(employee) = Employees::find_by_id(employee_id).one(db).await? else {
+        return Err(DbErr::RecordNotFound(format!("Employee {employee_id}")));
+    };
+    let schedule = super::shift::load_schedule(db, start, end).await?;
+    let holidays = super::holiday::load_calendar(db, start, end).await?;
+
+    Ok(count_leave_days(
+        &schedule,
+        &holidays,
+        employee.id,
+        employee.department_id,
+        start,
+        end,
+        half_day,
+    ))
+}
+
+/// Create a pending leave request, counting its leave days against the schedule.
+pub async fn create_leave_request(
+    db: &DatabaseConnection,
+    data: CreateLeaveRequest,
+) -> Result<leave_requests::Model, DbErr> {
+    let txn = db.begin().await?;
+    let days = request_days(&txn, data.employee_id, data.start_date, data.end_date, data.half_day).await?;
+
+    let model = leave_requests::ActiveModel {
+        employee_id: Set(data.employee_id),
+        leave_type_id: Set(data.leave_type_id),
+        start_date: Set(data.start_date),
+        end_date: Set(data.end_date),
+        half_day: Set(data.half_day),
+        days: Set(days),
+        status: Set(leave_status::PENDING.to_string()),
+        note: Set(data.note),
+        ..Default::default()
+    };
+    let created = model.insert(&txn).await?;
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::LEAVE_REQUEST,
+        Some(created.id.to_string()),
+        audit_action::CREATE,
+        None,
+        audit::snapshot(&created),
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(created)
+}
+
+/// Update a leave request, recounting its leave days.
+pub async fn update_leave_request(
+    db: &DatabaseConnection,
+    id: i32,
+    data: UpdateLeaveRequest,
+) -> Result<Option<leave_requests::Model>, DbErr> {
+    let txn = db.begin().await?;
+    let Some(model) = LeaveRequests::find_by_id(id).one(&txn).await? else {
+        return Ok(None);
+    };
+
+    let before = audit::snapshot(&model);
+    let start_date = data.start_date.unwrap_or(model.start_date);
+    let end_date = data.end_date.unwrap_or(model.end_date);
+    let half_day = data.half_day.unwrap_or(model.half_day);
+    let days = request_days(&txn, model.employee_id, start_date, end_date, half_day).await?;
+
+    let mut active: leave_requests::ActiveModel = model.into();
+    if let Some(leave_type_id) = data.leave_type_id {
+        active.leave_type_id = Set(leave_type_id);
+    }
+    if let Some(note) = data.note {
+        active.note = Set(note);
+    }
+    active.start_date = Set(start_date);
+    active.end_date = Set(end_date);
+    active.half_day = Set(half_day);
+    active.days = Set(days);
+
+    let updated = active.update(&txn).await?;
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::LEAVE_REQUEST,
+        Some(id.to_string()),
+        audit_action::UPDATE,
+        before,
+        audit::snapshot(&updated),
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(Some(updated))
+}
+
+/// Set the status of a leave request (see [`leave_status`]),
+/// recording who decided and when.
+pub async fn set_leave_status(
+    db: &DatabaseConnection,
+    id: i32,
+    status: &str,
+    approver: Option<String>,
+) -> Result<Option<leave_requests::Model>, DbErr> {
+    let txn = db.begin().await?;
+    let Some(model) = LeaveRequests::find_by_id(id).one(&txn).await? else {
+        return Ok(None);
+    };
+
+    let before = audit::snapshot(&model);
+    let mut active: leave_requests::ActiveModel = model.into();
+    active.status = Set(status.to_string());
+    active.approver = Set(approver);
+    active.decided_at = Set(Some(Utc::now().fixed_offset()));
+
+    let updated = active.update(&txn).await?;
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::LEAVE_REQUEST,
+        Some(id.to_string()),
+        audit_action::UPDATE,
+        before,
+        audit::snapshot(&updated),
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(Some(updated))
+}
+
+/// Delete a leave request by ID.
+pub async fn delete_leave_request(db: &DatabaseConnection, id: i32) -> Result<bool, DbErr> {
+    let txn = db.begin().await?;
+    let Some(existing) = LeaveRequests::find_by_id(id).one(&txn).await? else {
+        return Ok(false);
+    };
+
+    LeaveRequests::delete_by_id(id).exec(&txn).await?;
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::LEAVE_REQUEST,
+        Some(id.to_string()),
+        audit_action::DELETE,
+        audit::snapshot(&existing),
+        None,
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(true)
+}
+
+/// Leave balances of active employees for every active leave type in a year.
+pub async fn get_leave_balances(db: &DatabaseConnection, year: i32) -> Result<Vec<LeaveBalance>, DbErr> {
+    let (Some(start), Some(end)) = (
+        NaiveDate::from_ymd_opt(year, 1, 1),
+        NaiveDate::from_ymd_opt(year, 12, 31),
+    ) else {
+        return Ok(Vec::new());
+    };
+
+    let employees = super::employee::list_active(db).await?;
+    let types = list_leave_types(db).await?;
+    let entitlements = list_entitlements(db, year).await?;
+    let requests = list_leave_requests(db, start, end).await?;
+
+    Ok(leave_balances(&employees, &types, &entitlements, &requests, year))
+}
+
+/// Load approved leave in `start..=end` for excusing days in attendance.
+pub async fn load_leave_days<C: ConnectionTrait>(db: &C, start: NaiveDate, end: NaiveDate) -> Result<LeaveDays, DbErr> {
+    let requests = LeaveRequests::find()
+        .filter(leave_requests::Column::Status.eq(leave_status::APPROVED))
+        .filter(leave_requests::Column::StartDate.lte(end))
+        .filter(leave_requests::Column::EndDate.gte(start))
+        .all(db)
+        .await?;
+    let types = LeaveTypes::find().all(db).await?;
+
+    Ok(LeaveDays::new(&requests, &types))
+}
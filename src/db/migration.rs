@@ -0,0 +1,305 @@
+//! Embedded, versioned schema migrations.
+//!
+//! Migrations are the SQL files in `database/migrations`, compiled into the
+//! binary and applied in order. Applied versions are recorded in
+//! `app.schema_version`. Databases installed by hand from the old
+//! `script.sql` have the tables but no version table; they are adopted at the
+//! baseline version and upgraded from there.
+
+use crate::entities::{prelude::*, schema_version};
+use crate::error::{AppError, Result};
+use sea_orm::*;
+use tracing::info;
+
+/// A schema migration.
+#[derive(Debug)]
+pub struct Migration {
+    pub version: i32,
+    pub description: &'static str,
+    sql: &'static str,
+}
+
+/// All migrations in version order.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "Baseline schema",
+        sql: include_str!("../../database/migrations/001_baseline.sql"),
+    },
+    Migration {
+        version: 2,
+        description: "Sync history",
+        sql: include_str!("../../database/migrations/002_sync_history.sql"),
+    },
+    Migration {
+        version: 3,
+        description: "Unmatched punches view",
+        sql: include_str!("../../database/migrations/003_unmatched_punches.sql"),
+    },
+    Migration {
+        version: 4,
+        description: "Double punch flag",
+        sql: include_str!("../../database/migrations/004_double_punches.sql"),
+    },
+    Migration {
+        version: 5,
+        description: "Sync lock holders",
+        sql: include_str!("../../database/migrations/005_sync_locks.sql"),
+    },
+    Migration {
+        version: 6,
+        description: "Business timezone",
+        sql: include_str!("../../database/migrations/006_business_timezone.sql"),
+    },
+    Migration {
+        version: 7,
+        description: "Shifts and schedules",
+        sql: include_str!("../../database/migrations/007_shifts.sql"),
+    },
+    Migration {
+        version: 8,
+        description: "Work day attribution",
+        sql: include_str!("../../database/migrations/008_work_day_attribution.sql"),
+    },
+    Migration {
+        version: 9,
+        description: "Punch sessions",
+        sql: include_str!("../../database/migrations/009_punch_sessions.sql"),
+    },
+    Migration {
+        version: 10,
+        description: "Holidays",
+        sql: include_str!("../../database/migrations/010_holidays.sql"),
+    },
+    Migration {
+        version: 11,
+        description: "Leave",
+        sql: include_str!("../../database/migrations/011_leave.sql"),
+    },
+    Migration {
+        version: 12,
+        description: "Punch corrections",
+        sql: include_str!("../../database/migrations/012_punch_corrections.sql"),
+    },
+    Migration {
+        version: 13,
+        description: "Audit log",
+        sql: include_str!("../../database/migrations/013_audit_log.sql"),
+    },
+    Migration {
+        version: 14,
+        description: "User accounts",
+        sql: include_str!("../../database/migrations/014_users.sql"),
+    },
+    Migration {
+        version: 15,
+        description: "Employee assignments",
+        sql: include_str!("../../database/migrations/015_employee_assignments.sql"),
+    },
+    Migration {
+        version: 16,
+        description: "Employee termination",
+        sql: include_str!("../../database/migrations/016_employee_termination.sql"),
+    },
+    Migration {
+        version: 17,
+        description: "Shared settings",
+        sql: include_str!("../../database/migrations/017_shared_settings.sql"),
+    },
+    Migration {
+        version: 18,
+        description: "Daily attendance by date range",
+        sql: include_str!("../../database/migrations/018_daily_attendance_range.sql"),
+    },
+    Migration {
+        version: 19,
+        description: "Daily attendance department lookup",
+        sql: include_str!("../../database/migrations/019_daily_attendance_department.sql"),
+    },
+    Migration {
+        version: 20,
+        description: "Shared overtime rules",
+        sql: include_str!("../../database/migrations/020_overtime_settings.sql"),
+    },
+];
+
+/// Version of the baseline migration adopted for hand-installed databases.
+const BASELINE_VERSION: i32 = 1;
+
+/// Advisory lock key serialising concurrent migrators.
+const MIGRATION_LOCK_KEY: i64 = 0x5A4B_5343; // "ZKSC"
+
+const CREATE_VERSION_TABLE: &str = r#"
+CREATE SCHEMA IF NOT EXISTS app;
+
+CREATE TABLE IF NOT EXISTS app.schema_version (
+    version         INTEGER NOT NULL,
+    description     VARCHAR(200) NOT NULL,
+    applied_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Primary Key
+    CONSTRAINT pk_schema_version PRIMARY KEY (version)
+);
+
+COMMENT ON TABLE app.schema_version IS 'Applied schema migrations';
+COMMENT ON COLUMN app.schema_version.version IS 'Migration version number';
+COMMENT ON COLUMN app.schema_version.description IS 'Migration description';
+COMMENT ON COLUMN app.schema_version.applied_at IS 'Migration application timestamp';
+"#;
+
+/// Latest schema version this build knows about.
+pub fn latest_version() -> i32 {
+    MIGRATIONS.last().map_or(0, |m| m.version)
+}
+
+/// Schema state of a database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SchemaStatus {
+    /// Applied version (0 for an empty database).
+    pub current: i32,
+    /// Latest version known to this build.
+    pub latest: i32,
+    /// Tables exist but were installed by hand without a version table.
+    pub unversioned: bool,
+}
+
+impl SchemaStatus {
+    /// Number of migrations not yet applied.
+    pub fn pending(&self) -> usize {
+        MIGRATIONS.iter().filter(|m| m.version > self.current).count()
+    }
+
+    /// Whether the database was migrated by a newer build.
+    pub fn is_newer(&self) -> bool {
+        self.current > self.latest
+    }
+
+    /// Fail with `AppError::SchemaTooNew` if the database is ahead of this build.
+    pub fn ensure_supported(&self) -> Result<()> {
+        if self.is_newer() {
+            return Err(AppError::SchemaTooNew {
+                found: self.current,
+                supported: self.latest,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Read the schema state without changing anything.
+pub async fn status<C: ConnectionTrait>(db: &C) -> Result<SchemaStatus> {
+    let row = db
+        .query_one(Statement::from_string(
+            DbBackend::Postgres,
+            "SELECT to_regclass('app.schema_version') IS NOT NULL AS versioned, \
+                    to_regclass('app.attendance_logs') IS NOT NULL AS installed",
+        ))
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound("schema state".to_string()))?;
+    let versioned: bool = row.try_get("", "versioned")?;
+    let installed: bool = row.try_get("", "installed")?;
+
+    let current = if versioned {
+        SchemaVersion::find()
+            .select_only()
+            .column_as(schema_version::Column::Version.max(), "version")
+            .into_tuple::<Option<i32>>()
+            .one(db)
+            .await?
+            .flatten()
+            .unwrap_or(0)
+    } else if installed {
+        BASELINE_VERSION
+    } else {
+        0
+    };
+
+    Ok(SchemaStatus {
+        current,
+        latest: latest_version(),
+        unversioned: installed && !versioned,
+    })
+}
+
+/// Apply pending migrations and return the versions applied.
+///
+/// Runs in a single transaction under an advisory lock, so concurrent
+/// workstations starting at once apply each migration exactly once and a
+/// failed migration leaves the schema untouched. Fails with
+/// `AppError::SchemaTooNew` if the database is ahead of this build.
+pub async fn migrate(db: &DatabaseConnection) -> Result<Vec<i32>> {
+    let txn = db.begin().await?;
+
+    txn.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "SELECT pg_advisory_xact_lock($1)",
+        [MIGRATION_LOCK_KEY.into()],
+    ))
+    .await?;
+
+    let status = status(&txn).await?;
+    status.ensure_supported()?;
+    if status.pending() == 0 && !status.unversioned {
+        return Ok(Vec::new());
+    }
+
+    txn.execute_unprepared(CREATE_VERSION_TABLE).await?;
+
+    if status.unversioned {
+        info!("Adopting hand-installed schema at version {BASELINE_VERSION}");
+        record(&txn, &MIGRATIONS[0]).await?;
+    }
+
+    let mut applied = Vec::new();
+    for migration in MIGRATIONS.iter().filter(|m| m.version > status.current) {
+        info!("Applying migration {:03}: {}", migration.version, migration.description);
+        txn.execute_unprepared(migration.sql).await?;
+        record(&txn, migration).await?;
+        applied.push(migration.version);
+    }
+
+    txn.commit().await?;
+    Ok(applied)
+}
+
+async fn record(txn: &DatabaseTransaction, migration: &Migration) -> Result<()> {
+    schema_version::ActiveModel {
+        version: Set(migration.version),
+        description: Set(migration.description.to_string()),
+        ..Default::default()
+    }
+    .insert(txn)
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrations_are_ordered() {
+        assert_eq!(MIGRATIONS[0].version, BASELINE_VERSION);
+        for (index, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.version, index as i32 + 1);
+        }
+    }
+
+    #[test]
+    fn test_schema_status() {
+        let status = SchemaStatus {
+            current: 3,
+            latest: latest_version(),
+            unversioned: false,
+        };
+        assert_eq!(status.pending(), MIGRATIONS.len() - 3);
+        assert!(status.ensure_supported().is_ok());
+
+        let newer = SchemaStatus {
+            current: latest_version() + 1,
+            ..status
+        };
+        assert_eq!(newer.pending(), 0);
+        assert!(matches!(newer.ensure_supported(), Err(AppError::SchemaTooNew { .. })));
+    }
+}
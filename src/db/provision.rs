@@ -0,0 +1,240 @@
+//! First-run database provisioning.
+//!
+//! Creates the application role and database from a superuser connection to
+//! the maintenance database, then applies the schema migrations as the
+//! application role so it owns every object. Anything created here is dropped
+//! again if a later step fails.
+
+use crate::config::DatabaseConfig;
+use crate::db::{self, department, migration};
+use crate::entities::prelude::*;
+use crate::error::Result;
+use crate::models::department::CreateDepartment;
+use sea_orm::*;
+use tracing::{info, warn};
+
+/// Provisioning parameters.
+#[derive(Debug, Clone)]
+pub struct ProvisionRequest {
+    /// Target database and application role (created if missing).
+    pub database: DatabaseConfig,
+    /// Superuser for creating the role and database.
+    pub admin_username: String,
+    pub admin_password: String,
+    /// Database to connect to as the superuser.
+    pub maintenance_db: String,
+    /// Department created when the database has none.
+    pub seed_department: Option<String>,
+}
+
+/// What provisioning did.
+#[derive(Debug, Clone, Default)]
+pub struct ProvisionReport {
+    pub role_created: bool,
+    pub database_created: bool,
+    /// Migration versions applied.
+    pub migrations: Vec<i32>,
+    pub department_seeded: bool,
+}
+
+/// Objects created so far, for rollback.
+#[derive(Default)]
+struct Created {
+    role: bool,
+    database: bool,
+    grants: bool,
+}
+
+/// Create the role and database if missing, apply migrations and seed data.
+///
+/// Calls `on_progress(fraction, message)` before each step.
+pub async fn provision<F>(request: &ProvisionRequest, mut on_progress: F) -> Result<ProvisionReport>
+where
+    F: FnMut(f32, &str),
+{
+    let target = &request.database;
+    let mut report = ProvisionReport::default();
+
+    // An existing, reachable database needs no superuser
+    on_progress(0.05, "Connecting to database...");
+    if let Ok(pool) = db::connect(&target.connection_string()).await {
+        finish(&pool, request, &mut report, &mut on_progress).await?;
+        return Ok(report);
+    }
+
+    on_progress(0.15, "Connecting to maintenance database as administrator...");
+    let admin_config = DatabaseConfig {
+        name: request.maintenance_db.clone(),
+        username: request.admin_username.clone(),
+        password: request.admin_password.clone(),
+        ..target.clone()
+    };
+    let admin = db::connect(&admin_config.connection_string()).await?;
+
+    let mut created = Created::default();
+    let result = async {
+        create_objects(&admin, request, &mut created, &mut on_progress).await?;
+        report.role_created = created.role;
+        report.database_created = created.database;
+
+        let pool = db::connect(&target.connection_string()).await?;
+        let result = finish(&pool, request, &mut report, &mut on_progress).await;
+        pool.close().await?;
+        result
+    }
+    .await;
+
+    if let Err(e) = result {
+        on_progress(1.0, "Provisioning failed, rolling back...");
+        rollback(&admin, target, &created).await;
+        return Err(e);
+    }
+
+    Ok(report)
+}
+
+/// Create the role and database and grant access, recording what was created.
+async fn create_objects<F>(
+    admin: &DatabaseConnection,
+    request: &ProvisionRequest,
+    created: &mut Created,
+    on_progress: &mut F,
+) -> Result<()>
+where
+    F: FnMut(f32, &str),
+{
+    let target = &request.database;
+    let is_admin = target.username == request.admin_username;
+
+    if !is_admin && !exists(admin, "SELECT 1 FROM pg_roles WHERE rolname = $1", &target.username).await? {
+        on_progress(0.25, &format!("Creating role {}...", target.username));
+        let sql = format_sql(
+            admin,
+            "CREATE ROLE %I LOGIN PASSWORD %L",
+            &[&target.username, &target.password],
+        )
+        .await?;
+        admin.execute_unprepared(&sql).await?;
+        created.role = true;
+        info!("Created role {}", target.username);
+    }
+
+    if !exists(admin, "SELECT 1 FROM pg_database WHERE datname = $1", &target.name).await? {
+        on_progress(0.35, &format!("Creating database {}...", target.name));
+        let sql = format_sql(admin, "CREATE DATABASE %I OWNER %I", &[&target.name, &target.username]).await?;
+        admin.execute_unprepared(&sql).await?;
+        created.database = true;
+        info!("Created database {}", target.name);
+    } else if !is_admin {
+        on_progress(0.35, "Granting access to existing database...");
+        let sql = format_sql(
+            admin,
+            "GRANT CONNECT, CREATE ON DATABASE %I TO %I",
+            &[&target.name, &target.username],
+        )
+        .await?;
+        admin.execute_unprepared(&sql).await?;
+        created.grants = true;
+    }
+
+    on_progress(0.45, "Setting search path...");
+    let sql = format_sql(
+        admin,
+        "ALTER ROLE %I IN DATABASE %I SET search_path TO app, system, public",
+        &[&target.username, &target.name],
+    )
+    .await?;
+    admin.execute_unprepared(&sql).await?;
+
+    Ok(())
+}
+
+/// Apply migrations and seed data on the target database.
+async fn finish<F>(
+    pool: &DatabaseConnection,
+    request: &ProvisionRequest,
+    report: &mut ProvisionReport,
+    on_progress: &mut F,
+) -> Result<()>
+where
+    F: FnMut(f32, &str),
+{
+    on_progress(0.6, "Applying schema migrations...");
+    report.migrations = migration::migrate(pool).await?;
+
+    if let Some(name) = request
+        .seed_department
+        .as_deref()
+        .map(str::trim)
+        .filter(|n| !n.is_empty())
+        && Departments::find().count(pool).await? == 0
+    {
+        on_progress(0.9, &format!("Creating department {name}..."));
+        department::create(
+            pool,
+            CreateDepartment {
+                name: name.to_string(),
+                parent_id: None,
+                display_order: 0,
+            },
+        )
+        .await?;
+        report.department_seeded = true;
+    }
+
+    on_progress(1.0, "Database ready");
+    Ok(())
+}
+
+/// Drop what provisioning created, newest first. Failures are only logged.
+async fn rollback(admin: &DatabaseConnection, target: &DatabaseConfig, created: &Created) {
+    let mut statements = Vec::new();
+    if created.database {
+        statements.push(("DROP DATABASE IF EXISTS %I WITH (FORCE)", vec![target.name.as_str()]));
+    }
+    if created.grants {
+        statements.push((
+            "REVOKE ALL ON DATABASE %I FROM %I",
+            vec![target.name.as_str(), target.username.as_str()],
+        ));
+    }
+    if created.role {
+        statements.push(("DROP ROLE IF EXISTS %I", vec![target.username.as_str()]));
+    }
+
+    for (template, args) in statements {
+        let result = match format_sql(admin, template, &args).await {
+            Ok(sql) => admin.execute_unprepared(&sql).await.map(|_| ()).map_err(Into::into),
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => info!("Rollback: {template} {args:?}"),
+            Err(e) => warn!("Rollback step failed ({template} {args:?}): {e}"),
+        }
+    }
+}
+
+async fn exists(db: &DatabaseConnection, sql: &str, value: &str) -> Result<bool> {
+    Ok(db
+        .query_one(Statement::from_sql_and_values(DbBackend::Postgres, sql, [value.into()]))
+        .await?
+        .is_some())
+}
+
+/// Build a DDL statement with server-side identifier/literal quoting.
+///
+/// DDL cannot take bind parameters, so `%I`/`%L` placeholders are filled in
+/// by PostgreSQL's `format()`.
+async fn format_sql(db: &DatabaseConnection, template: &str, args: &[&str]) -> Result<String> {
+    let placeholders: Vec<String> = (2..=args.len() + 1).map(|i| format!("${i}::text")).collect();
+    let sql = format!("SELECT format($1::text, {}) AS sql", placeholders.join(", "));
+
+    let mut values: Vec<Value> = vec![template.into()];
+    values.extend(args.iter().map(|arg| (*arg).into()));
+
+    let row = db
+        .query_one(Statement::from_sql_and_values(DbBackend::Postgres, sql, values))
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound("format()".to_string()))?;
+    Ok(row.try_get("", "sql")?)
+}
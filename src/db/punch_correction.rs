@@ -0,0 +1,203 @@
+//! Manual punch entry and correction.
+//!
+//! Punch rows are never updated or deleted here beyond their void flag: a
+//! correction voids the original and inserts a manual replacement, and every
+//! change is recorded in `app.punch_corrections` with its reason and the
+//! acting user, and in the audit log. Voiding a punch re-flags the double
+//! punches after it in the same transaction, since it may have been the
+//! predecessor that made the next punch a double punch.
+
+use chrono::{DateTime, Utc};
+use sea_orm::*;
+
+use super::{attendance, audit, settings};
+use crate::entities::{attendance_logs, prelude::*, punch_corrections};
+use crate::models::attendance::verify_type;
+use crate::models::audit::{audit_action, audit_entity};
+use crate::models::punch_correction::{AddPunch, CorrectPunch, VoidPunch, correction_action};
+
+/// Add a forgotten punch for an employee.
+pub async fn add_punch(db: &DatabaseConnection, data: AddPunch) -> Result<attendance_logs::Model, DbErr> {
+    let txn = db.begin().await?;
+
+    let employee = Employees::find_by_id(data.employee_id)
+        .one(&txn)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("Employee {} not found", data.employee_id)))?;
+    let scanner_uid = employee
+        .scanner_uid
+        .ok_or_else(|| DbErr::Custom(format!("{} has no scanner UID", employee.full_name)))?;
+
+    let punch = insert_manual(&txn, scanner_uid, data.check_time, data.status).await?;
+    record_correction(
+        &txn,
+        correction_action::ADD,
+        None,
+        Some(punch.id),
+        &data.reason,
+        &data.acted_by,
+    )
+    .await?;
+    audit::record(
+        &txn,
+        data.acted_by.trim(),
+        audit_entity::PUNCH,
+        Some(punch.id.to_string()),
+        audit_action::CREATE,
+        None,
+        audit::snapshot(&punch),
+    )
+    .await?;
+
+    txn.commit().await?;
+    Ok(punch)
+}
+
+/// Replace a punch with a manual one at another time or state.
+///
+/// Returns the replacement, or `None` if the punch does not exist.
+pub async fn correct_punch(
+    db: &DatabaseConnection,
+    id: i64,
+    data: CorrectPunch,
+) -> Result<Option<attendance_logs::Model>, DbErr> {
+    let txn = db.begin().await?;
+
+    let Some((original, _)) = void(&txn, id).await? else {
+        return Ok(None);
+    };
+    let punch = insert_manual(&txn, original.scanner_uid, data.check_time, data.status).await?;
+    reflag_double_punches(&txn, &original).await?;
+    record_correction(
+        &txn,
+        correction_action::EDIT,
+        Some(id),
+        Some(punch.id),
+        &data.reason,
+        &data.acted_by,
+    )
+    .await?;
+    audit::record(
+        &txn,
+        data.acted_by.trim(),
+        audit_entity::PUNCH,
+        Some(id.to_string()),
+        audit_action::UPDATE,
+        audit::snapshot(&original),
+        audit::snapshot(&punch),
+    )
+    .await?;
+
+    txn.commit().await?;
+    Ok(Some(punch))
+}
+
+/// Void a punch so reports leave it out.
+///
+/// Returns `false` if the punch does not exist.
+pub async fn void_punch(db: &DatabaseConnection, id: i64, data: VoidPunch) -> Result<bool, DbErr> {
+    let txn = db.begin().await?;
+
+    let Some((original, voided)) = void(&txn, id).await? else {
+        return Ok(false);
+    };
+    reflag_double_punches(&txn, &original).await?;
+    record_correction(
+        &txn,
+        correction_action::VOID,
+        Some(id),
+        None,
+        &data.reason,
+        &data.acted_by,
+    )
+    .await?;
+    audit::record(
+        &txn,
+        data.acted_by.trim(),
+        audit_entity::PUNCH,
+        Some(id.to_string()),
+        audit_action::VOID,
+        audit::snapshot(&original),
+        audit::snapshot(&voided),
+    )
+    .await?;
+
+    txn.commit().await?;
+    Ok(true)
+}
+
+/// Set the void flag of a punch that is not voided yet.
+///
+/// Returns the punch before and after.
+async fn void<C: ConnectionTrait>(
+    db: &C,
+    id: i64,
+) -> Result<Option<(attendance_logs::Model, attendance_logs::Model)>, DbErr> {
+    let Some(punch) = AttendanceLogs::find_by_id(id).one(db).await? else {
+        return Ok(None);
+    };
+    if punch.is_voided {
+        return Err(DbErr::Custom("Punch is already voided".to_string()));
+    }
+
+    let mut active: attendance_logs::ActiveModel = punch.clone().into();
+    active.is_voided = Set(true);
+    let voided = active.update(db).await?;
+    Ok(Some((punch, voided)))
+}
+
+/// Re-evaluate double-punch flags from a voided punch onwards.
+async fn reflag_double_punches<C: ConnectionTrait>(db: &C, voided: &attendance_logs::Model) -> Result<u64, DbErr> {
+    let window = settings::double_punch_window(db).await?;
+    attendance::flag_double_punches(db, window, Some(voided.check_time.with_timezone(&Utc))).await
+}
+
+/// Insert a manual punch, refusing one at the time of another active punch.
+async fn insert_manual<C: ConnectionTrait>(
+    db: &C,
+    scanner_uid: i32,
+    check_time: DateTime<Utc>,
+    status: i32,
+) -> Result<attendance_logs::Model, DbErr> {
+    let existing = AttendanceLogs::find()
+        .filter(attendance_logs::Column::ScannerUid.eq(scanner_uid))
+        .filter(attendance_logs::Column::CheckTime.eq(check_time))
+        .filter(attendance_logs::Column::IsVoided.eq(false))
+        .count(db)
+        .await?;
+    if existing > 0 {
+        return Err(DbErr::Custom("There is already a punch at that time".to_string()));
+    }
+
+    attendance_logs::ActiveModel {
+        scanner_uid: Set(scanner_uid),
+        check_time: Set(check_time.into()),
+        verify_type: Set(verify_type::NONE),
+        status: Set(status),
+        source: Set("manual".to_string()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+}
+
+/// Record a correction.
+async fn record_correction<C: ConnectionTrait>(
+    db: &C,
+    action: &str,
+    original_log_id: Option<i64>,
+    new_log_id: Option<i64>,
+    reason: &str,
+    acted_by: &str,
+) -> Result<punch_corrections::Model, DbErr> {
+    punch_corrections::ActiveModel {
+        action: Set(action.to_string()),
+        original_log_id: Set(original_log_id),
+        new_log_id: Set(new_log_id),
+        reason: Set(reason.trim().to_string()),
+        acted_by: Set(acted_by.trim().to_string()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+}
@@ -0,0 +1,110 @@
+//! Settings shared by every workstation.
+//!
+//! Settings whose effect is stored in the database live in `app.settings`
+//! rather than the per-workstation config file, so every workstation applies
+//! the same values.
+
+use sea_orm::*;
+
+use super::audit::{self, snapshot};
+use crate::entities::{prelude::*, settings};
+use crate::models::audit::{audit_action, audit_entity};
+use crate::models::overtime::OvertimeRules;
+
+/// Id of the single settings row.
+const SETTINGS_ID: i16 = 1;
+
+/// Largest double-punch window accepted, in seconds.
+pub const MAX_DOUBLE_PUNCH_WINDOW_SECS: u32 = 3600;
+
+/// Load the shared settings.
+pub async fn get<C: ConnectionTrait>(db: &C) -> Result<settings::Model, DbErr> {
+    Settings::find_by_id(SETTINGS_ID)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound("Shared settings row is missing".to_string()))
+}
+
+/// Double-punch window in seconds (0 disables).
+pub async fn double_punch_window<C: ConnectionTrait>(db: &C) -> Result<u32, DbErr> {
+    Ok(get(db).await?.double_punch_window_secs.max(0) as u32)
+}
+
+/// Change the double-punch window.
+///
+/// Only the setting is stored; callers re-flag punches with
+/// [`super::attendance::flag_double_punches`] afterwards.
+pub async fn set_double_punch_window(db: &DatabaseConnection, secs: u32) -> Result<settings::Model, DbErr> {
+    if secs > MAX_DOUBLE_PUNCH_WINDOW_SECS {
+        return Err(DbErr::Custom(format!(
+            "Double-punch window cannot exceed {MAX_DOUBLE_PUNCH_WINDOW_SECS} seconds"
+        )));
+    }
+
+    let txn = db.begin().await?;
+    let model = get(&txn).await?;
+    let before = snapshot(&model);
+    let mut active: settings::ActiveModel = model.into();
+    active.double_punch_window_secs = Set(secs as i32);
+    let updated = active.update(&txn).await?;
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::SETTINGS,
+        None,
+        audit_action::UPDATE,
+        before,
+        snapshot(&updated),
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(updated)
+}
+
+/// Overtime rules used by reports and the overtime export.
+pub async fn overtime_rules<C: ConnectionTrait>(db: &C) -> Result<OvertimeRules, DbErr> {
+    let model = get(db).await?;
+    Ok(OvertimeRules {
+        daily_threshold_minutes: i64::from(model.overtime_threshold_minutes),
+        weekday_multiplier: model.overtime_weekday_multiplier,
+        weekend_multiplier: model.overtime_weekend_multiplier,
+        holiday_multiplier: model.overtime_holiday_multiplier,
+        night_start: model.overtime_night_start,
+        night_end: model.overtime_night_end,
+        night_premium: model.overtime_night_premium,
+        min_block_minutes: i64::from(model.overtime_min_block_minutes),
+        rounding_minutes: i64::from(model.overtime_rounding_minutes),
+    })
+}
+
+/// Change the overtime rules.
+pub async fn set_overtime_rules(db: &DatabaseConnection, rules: &OvertimeRules) -> Result<settings::Model, DbErr> {
+    rules.validate().map_err(DbErr::Custom)?;
+
+    let txn = db.begin().await?;
+    let model = get(&txn).await?;
+    let before = snapshot(&model);
+    let mut active: settings::ActiveModel = model.into();
+    active.overtime_threshold_minutes = Set(rules.daily_threshold_minutes as i32);
+    active.overtime_weekday_multiplier = Set(rules.weekday_multiplier);
+    active.overtime_weekend_multiplier = Set(rules.weekend_multiplier);
+    active.overtime_holiday_multiplier = Set(rules.holiday_multiplier);
+    active.overtime_night_start = Set(rules.night_start);
+    active.overtime_night_end = Set(rules.night_end);
+    active.overtime_night_premium = Set(rules.night_premium);
+    active.overtime_min_block_minutes = Set(rules.min_block_minutes as i32);
+    active.overtime_rounding_minutes = Set(rules.rounding_minutes as i32);
+    let updated = active.update(&txn).await?;
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::SETTINGS,
+        None,
+        audit_action::UPDATE,
+        before,
+        snapshot(&updated),
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(updated)
+}
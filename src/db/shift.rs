@@ -0,0 +1,408 @@
+//! Shift, pattern, assignment and roster repository.
+
+use chrono::NaiveDate;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::*;
+
+use super::audit;
+use crate::entities::{
+    departments, prelude::*, shift_assignments, shift_pattern_days, shift_patterns, shift_rosters, shifts,
+};
+use crate::models::audit::{audit_action, audit_entity};
+use crate::models::shift::{
+    CreateShift, CreateShiftAssignment, SaveShiftPattern, Schedule, SetRosterEntry, ShiftPattern, UpdateShift,
+    crosses_midnight,
+};
+
+/// List all shifts ordered by start time and name.
+pub async fn list_shifts(db: &DatabaseConnection) -> Result<Vec<shifts::Model>, DbErr> {
+    Shifts::find()
+        .order_by_asc(shifts::Column::StartTime)
+        .order_by_asc(shifts::Column::Name)
+        .all(db)
+        .await
+}
+
+/// Create a new shift.
+pub async fn create_shift(db: &DatabaseConnection, data: CreateShift) -> Result<shifts::Model, DbErr> {
+    let model = shifts::ActiveModel {
+        name: Set(data.name),
+        start_time: Set(data.start_time),
+        end_time: Set(data.end_time),
+        break_minutes: Set(data.break_minutes),
+        late_grace_minutes: Set(data.late_grace_minutes),
+        early_grace_minutes: Set(data.early_grace_minutes),
+        crosses_midnight: Set(crosses_midnight(data.start_time, data.end_time)),
+        ..Default::default()
+    };
+
+    let txn = db.begin().await?;
+    let created = model.insert(&txn).await?;
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::SHIFT,
+        Some(created.id.to_string()),
+        audit_action::CREATE,
+        None,
+        audit::snapshot(&created),
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(created)
+}
+
+/// Update an existing shift.
+pub async fn update_shift(db: &DatabaseConnection, id: i32, data: UpdateShift) -> Result<Option<shifts::Model>, DbErr> {
+    let txn = db.begin().await?;
+    let Some(model) = Shifts::find_by_id(id).one(&txn).await? else {
+        return Ok(None);
+    };
+
+    let start_time = data.start_time.unwrap_or(model.start_time);
+    let end_time = data.end_time.unwrap_or(model.end_time);
+    let before = audit::snapshot(&model);
+    let mut active: shifts::ActiveModel = model.into();
+
+    if let Some(name) = data.name {
+        active.name = Set(name);
+    }
+    active.start_time = Set(start_time);
+    active.end_time = Set(end_time);
+    active.crosses_midnight = Set(crosses_midnight(start_time, end_time));
+    if let Some(break_minutes) = data.break_minutes {
+        active.break_minutes = Set(break_minutes);
+    }
+    if let Some(late) = data.late_grace_minutes {
+        active.late_grace_minutes = Set(late);
+    }
+    if let Some(early) = data.early_grace_minutes {
+        active.early_grace_minutes = Set(early);
+    }
+    if let Some(is_active) = data.is_active {
+        active.is_active = Set(is_active);
+    }
+
+    let updated = active.update(&txn).await?;
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::SHIFT,
+        Some(id.to_string()),
+        audit_action::UPDATE,
+        before,
+        audit::snapshot(&updated),
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(Some(updated))
+}
+
+/// Delete a shift by ID. Fails while a pattern or roster entry uses it.
+pub async fn delete_shift(db: &DatabaseConnection, id: i32) -> Result<bool, DbErr> {
+    let txn = db.begin().await?;
+    let Some(existing) = Shifts::find_by_id(id).one(&txn).await? else {
+        return Ok(false);
+    };
+
+    Shifts::delete_by_id(id).exec(&txn).await?;
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::SHIFT,
+        Some(id.to_string()),
+        audit_action::DELETE,
+        audit::snapshot(&existing),
+        None,
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(true)
+}
+
+/// List all patterns with their cycle days, ordered by name.
+pub async fn list_patterns<C: ConnectionTrait>(db: &C) -> Result<Vec<ShiftPattern>, DbErr> {
+    let patterns = ShiftPatterns::find()
+        .order_by_asc(shift_patterns::Column::Name)
+        .all(db)
+        .await?;
+    let days = ShiftPatternDays::find().all(db).await?;
+
+    Ok(patterns
+        .into_iter()
+        .map(|p| {
+            let mut cycle = vec![None; p.cycle_days.max(0) as usize];
+            for day in days.iter().filter(|d| d.pattern_id == p.id) {
+                if let Some(slot) = cycle.get_mut(day.day_index as usize) {
+                    *slot = Some(day.shift_id);
+                }
+            }
+            ShiftPattern {
+                id: p.id,
+                name: p.name,
+                days: cycle,
+            }
+        })
+        .collect())
+}
+
+/// Create a pattern, or replace an existing one when `id` is set.
+pub async fn save_pattern(db: &DatabaseConnection, id: Option<i32>, data: SaveShiftPattern) -> Result<i32, DbErr> {
+    let txn = db.begin().await?;
+    let before = match id {
+        Some(id) => find_pattern(&txn, id).await?,
+        None => None,
+    };
+
+    let cycle_days = data.days.len() as i32;
+    let pattern_id = match id {
+        Some(id) => {
+            let Some(model) = ShiftPatterns::find_by_id(id).one(&txn).await? else {
+                return Err(DbErr::RecordNotFound(format!("Shift pattern {id}")));
+            };
+            let mut active: shift_patterns::ActiveModel = model.into();
+            active.name = Set(data.name);
+            active.cycle_days = Set(cycle_days);
+            active.update(&txn).await?;
+
+            ShiftPatternDays::delete_many()
+                .filter(shift_pattern_days::Column::PatternId.eq(id))
+                .exec(&txn)
+                .await?;
+            id
+        }
+        None => {
+            shift_patterns::ActiveModel {
+                name: Set(data.name),
+                cycle_days: Set(cycle_days),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?
+            .id
+        }
+    };
+
+    let days: Vec<shift_pattern_days::ActiveModel> = data
+        .days
+        .iter()
+        .enumerate()
+        .filter_map(|(index, shift_id)| {
+            shift_id.map(|shift_id| shift_pattern_days::ActiveModel {
+                pattern_id: Set(pattern_id),
+                day_index: Set(index as i32),
+                shift_id: Set(shift_id),
+            })
+        })
+        .collect();
+    if !days.is_empty() {
+        ShiftPatternDays::insert_many(days).exec(&txn).await?;
+    }
+
+    let after = find_pattern(&txn, pattern_id).await?;
+    let action = if before.is_some() {
+        audit_action::UPDATE
+    } else {
+        audit_action::CREATE
+    };
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::SHIFT_PATTERN,
+        Some(pattern_id.to_string()),
+        action,
+        before.as_ref().and_then(audit::snapshot),
+        after.as_ref().and_then(audit::snapshot),
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(pattern_id)
+}
+
+/// Delete a pattern by ID. Fails while an assignment uses it.
+pub async fn delete_pattern(db: &DatabaseConnection, id: i32) -> Result<bool, DbErr> {
+    let txn = db.begin().await?;
+    let Some(existing) = find_pattern(&txn, id).await? else {
+        return Ok(false);
+    };
+
+    ShiftPatterns::delete_by_id(id).exec(&txn).await?;
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::SHIFT_PATTERN,
+        Some(id.to_string()),
+        audit_action::DELETE,
+        audit::snapshot(&existing),
+        None,
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(true)
+}
+
+/// A pattern with its cycle days.
+async fn find_pattern<C: ConnectionTrait>(db: &C, id: i32) -> Result<Option<ShiftPattern>, DbErr> {
+    Ok(list_patterns(db).await?.into_iter().find(|p| p.id == id))
+}
+
+/// List all assignments, newest start first.
+pub async fn list_assignments(db: &DatabaseConnection) -> Result<Vec<shift_assignments::Model>, DbErr> {
+    ShiftAssignments::find()
+        .order_by_desc(shift_assignments::Column::StartDate)
+        .order_by_desc(shift_assignments::Column::Id)
+        .all(db)
+        .await
+}
+
+/// Assign a pattern to an employee or a department.
+pub async fn create_assignment(
+    db: &DatabaseConnection,
+    data: CreateShiftAssignment,
+) -> Result<shift_assignments::Model, DbErr> {
+    let model = shift_assignments::ActiveModel {
+        employee_id: Set(data.employee_id),
+        department_id: Set(data.department_id),
+        pattern_id: Set(data.pattern_id),
+        start_date: Set(data.start_date),
+        end_date: Set(data.end_date),
+        ..Default::default()
+    };
+
+    let txn = db.begin().await?;
+    let created = model.insert(&txn).await?;
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::SHIFT_ASSIGNMENT,
+        Some(created.id.to_string()),
+        audit_action::CREATE,
+        None,
+        audit::snapshot(&created),
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(created)
+}
+
+/// Delete an assignment by ID.
+pub async fn delete_assignment(db: &DatabaseConnection, id: i32) -> Result<bool, DbErr> {
+    let txn = db.begin().await?;
+    let Some(existing) = ShiftAssignments::find_by_id(id).one(&txn).await? else {
+        return Ok(false);
+    };
+
+    ShiftAssignments::delete_by_id(id).exec(&txn).await?;
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::SHIFT_ASSIGNMENT,
+        Some(id.to_string()),
+        audit_action::DELETE,
+        audit::snapshot(&existing),
+        None,
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(true)
+}
+
+/// Set the roster entry for one employee and day, replacing any existing one.
+pub async fn set_roster_entry(db: &DatabaseConnection, data: SetRosterEntry) -> Result<(), DbErr> {
+    let txn = db.begin().await?;
+    let existing = find_roster_entry(&txn, data.employee_id, data.work_date).await?;
+
+    let model = shift_rosters::ActiveModel {
+        employee_id: Set(data.employee_id),
+        work_date: Set(data.work_date),
+        shift_id: Set(data.shift_id),
+        note: Set(data.note),
+        ..Default::default()
+    };
+    let saved = ShiftRosters::insert(model)
+        .on_conflict(
+            OnConflict::columns([shift_rosters::Column::EmployeeId, shift_rosters::Column::WorkDate])
+                .update_columns([shift_rosters::Column::ShiftId, shift_rosters::Column::Note])
+                .to_owned(),
+        )
+        .exec_with_returning(&txn)
+        .await?;
+    let action = if existing.is_some() {
+        audit_action::UPDATE
+    } else {
+        audit_action::CREATE
+    };
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::ROSTER,
+        Some(saved.id.to_string()),
+        action,
+        existing.as_ref().and_then(audit::snapshot),
+        audit::snapshot(&saved),
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(())
+}
+
+/// Remove the roster entry for one employee and day, reverting to the assignment.
+pub async fn clear_roster_entry(
+    db: &DatabaseConnection,
+    employee_id: i32,
+    work_date: NaiveDate,
+) -> Result<bool, DbErr> {
+    let txn = db.begin().await?;
+    let Some(existing) = find_roster_entry(&txn, employee_id, work_date).await? else {
+        return Ok(false);
+    };
+
+    ShiftRosters::delete_by_id(existing.id).exec(&txn).await?;
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::ROSTER,
+        Some(existing.id.to_string()),
+        audit_action::DELETE,
+        audit::snapshot(&existing),
+        None,
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(true)
+}
+
+/// The roster entry for one employee and day.
+async fn find_roster_entry<C: ConnectionTrait>(
+    db: &C,
+    employee_id: i32,
+    work_date: NaiveDate,
+) -> Result<Option<shift_rosters::Model>, DbErr> {
+    ShiftRosters::find()
+        .filter(shift_rosters::Column::EmployeeId.eq(employee_id))
+        .filter(shift_rosters::Column::WorkDate.eq(work_date))
+        .one(db)
+        .await
+}
+
+/// Load everything needed to resolve shifts for days in `start..=end`.
+pub async fn load_schedule<C: ConnectionTrait>(db: &C, start: NaiveDate, end: NaiveDate) -> Result<Schedule, DbErr> {
+    let shifts = Shifts::find().all(db).await?;
+    let patterns = list_patterns(db).await?;
+    let assignments = ShiftAssignments::find()
+        .filter(shift_assignments::Column::StartDate.lte(end))
+        .filter(
+            Condition::any()
+                .add(shift_assignments::Column::EndDate.is_null())
+                .add(shift_assignments::Column::EndDate.gte(start)),
+        )
+        .all(db)
+        .await?;
+    let rosters = ShiftRosters::find()
+        .filter(shift_rosters::Column::WorkDate.between(start, end))
+        .all(db)
+        .await?;
+    let departments: Vec<departments::Model> = Departments::find().all(db).await?;
+
+    Ok(Schedule::new(shifts, patterns, assignments, rosters, &departments))
+}
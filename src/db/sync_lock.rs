@@ -0,0 +1,99 @@
+//! Per-device sync lock backed by PostgreSQL advisory locks.
+//!
+//! The advisory lock is transaction-scoped, so it is released when the lock
+//! transaction ends, including when the holder's connection dies. The
+//! `app.sync_locks` row only records who holds it for display.
+
+use crate::entities::{prelude::*, sync_locks};
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::*;
+
+/// Advisory lock namespace (first key) shared by all device locks.
+const LOCK_NAMESPACE: i32 = 0x5A4B; // "ZK"
+
+/// Outcome of a lock attempt.
+pub enum LockAttempt {
+    /// Lock taken; it is held until the transaction is committed, rolled back or dropped.
+    Acquired(DatabaseTransaction),
+    /// Another session holds the lock; holder row if one was recorded.
+    Held(Option<sync_locks::Model>),
+}
+
+/// Try to take the advisory lock for a device without waiting.
+pub async fn try_acquire(db: &DatabaseConnection, device: &str) -> Result<LockAttempt, DbErr> {
+    let txn = db.begin().await?;
+
+    let acquired = txn
+        .query_one(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "SELECT pg_try_advisory_xact_lock($1, hashtext($2)) AS acquired",
+            [LOCK_NAMESPACE.into(), device.into()],
+        ))
+        .await?
+        .map(|row| row.try_get::<bool>("", "acquired"))
+        .transpose()?
+        .unwrap_or(false);
+
+    if acquired {
+        Ok(LockAttempt::Acquired(txn))
+    } else {
+        txn.rollback().await?;
+        Ok(LockAttempt::Held(get_holder(db, device).await?))
+    }
+}
+
+/// Record the current lock holder, replacing any stale row.
+pub async fn record_holder(
+    db: &DatabaseConnection,
+    device: &str,
+    holder_host: &str,
+    holder_pid: i32,
+    operation: &str,
+) -> Result<(), DbErr> {
+    let now = chrono::Utc::now();
+    let model = sync_locks::ActiveModel {
+        device: Set(device.to_string()),
+        holder_host: Set(holder_host.to_string()),
+        holder_pid: Set(holder_pid),
+        operation: Set(operation.to_string()),
+        acquired_at: Set(now.into()),
+        heartbeat_at: Set(now.into()),
+    };
+
+    SyncLocks::insert(model)
+        .on_conflict(
+            OnConflict::column(sync_locks::Column::Device)
+                .update_columns([
+                    sync_locks::Column::HolderHost,
+                    sync_locks::Column::HolderPid,
+                    sync_locks::Column::Operation,
+                    sync_locks::Column::AcquiredAt,
+                    sync_locks::Column::HeartbeatAt,
+                ])
+                .to_owned(),
+        )
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+/// Refresh the heartbeat of the holder row.
+pub async fn heartbeat(db: &DatabaseConnection, device: &str) -> Result<(), DbErr> {
+    SyncLocks::update_many()
+        .col_expr(sync_locks::Column::HeartbeatAt, Expr::current_timestamp().into())
+        .filter(sync_locks::Column::Device.eq(device))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+/// Remove the holder row for a device.
+pub async fn clear_holder(db: &DatabaseConnection, device: &str) -> Result<(), DbErr> {
+    SyncLocks::delete_by_id(device.to_string()).exec(db).await?;
+    Ok(())
+}
+
+/// Get the recorded holder for a device.
+pub async fn get_holder(db: &DatabaseConnection, device: &str) -> Result<Option<sync_locks::Model>, DbErr> {
+    SyncLocks::find_by_id(device.to_string()).one(db).await
+}
@@ -0,0 +1,51 @@
+//! Sync run repository for persisted sync history.
+
+use crate::entities::{prelude::*, sync_runs};
+use crate::models::sync_run::{CreateSyncRun, SyncRunFilter, sync_status};
+use sea_orm::*;
+
+/// Record a finished sync run.
+pub async fn create(db: &DatabaseConnection, data: CreateSyncRun) -> Result<sync_runs::Model, DbErr> {
+    let model = sync_runs::ActiveModel {
+        device: Set(data.device),
+        started_at: Set(data.started_at.into()),
+        finished_at: Set(data.finished_at.into()),
+        status: Set(data.status),
+        downloaded: Set(data.downloaded),
+        inserted: Set(data.inserted),
+        skipped: Set(data.skipped),
+        device_cleared: Set(data.device_cleared),
+        duration_secs: Set(data.duration_secs),
+        clock_drift_secs: Set(data.clock_drift_secs),
+        error_message: Set(data.error_message),
+        ..Default::default()
+    };
+    model.insert(db).await
+}
+
+/// List sync runs matching the filter, newest first.
+pub async fn list(db: &DatabaseConnection, filter: &SyncRunFilter) -> Result<Vec<sync_runs::Model>, DbErr> {
+    let mut query = SyncRuns::find();
+
+    if let Some(status) = &filter.status {
+        query = query.filter(sync_runs::Column::Status.eq(status.as_str()));
+    }
+    if let Some(since) = filter.since {
+        query = query.filter(sync_runs::Column::StartedAt.gte(since));
+    }
+
+    query
+        .order_by_desc(sync_runs::Column::StartedAt)
+        .limit(filter.limit)
+        .all(db)
+        .await
+}
+
+/// Get the most recent successful sync run.
+pub async fn get_last_success(db: &DatabaseConnection) -> Result<Option<sync_runs::Model>, DbErr> {
+    SyncRuns::find()
+        .filter(sync_runs::Column::Status.eq(sync_status::SUCCESS))
+        .order_by_desc(sync_runs::Column::StartedAt)
+        .one(db)
+        .await
+}
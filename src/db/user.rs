@@ -0,0 +1,178 @@
+//! User account repository and sign-in.
+
+use chrono::Utc;
+use sea_orm::sea_query::{Expr, Func};
+use sea_orm::*;
+use serde_json::Value;
+
+use super::audit;
+use crate::entities::{prelude::*, users};
+use crate::models::audit::{audit_action, audit_entity};
+use crate::models::user::{CreateUser, UpdateUser, hash_password, user_role, validate_password, verify_password};
+
+/// Number of user accounts, active or not.
+pub async fn count(db: &DatabaseConnection) -> Result<u64, DbErr> {
+    Users::find().count(db).await
+}
+
+/// List all user accounts ordered by username.
+pub async fn list_all(db: &DatabaseConnection) -> Result<Vec<users::Model>, DbErr> {
+    Users::find().order_by_asc(users::Column::Username).all(db).await
+}
+
+/// Get an account by username, ignoring case.
+pub async fn get_by_username(db: &DatabaseConnection, username: &str) -> Result<Option<users::Model>, DbErr> {
+    find_by_username(db, username.trim()).await
+}
+
+/// Create a new user account.
+pub async fn create(db: &DatabaseConnection, data: CreateUser) -> Result<users::Model, DbErr> {
+    let username = data.username.trim().to_string();
+    if username.is_empty() {
+        return Err(DbErr::Custom("Username is required".to_string()));
+    }
+    check_role(&data.role, data.department_id)?;
+    validate_password(&data.password).map_err(DbErr::Custom)?;
+    let password_hash = hash_password(&data.password).map_err(DbErr::Custom)?;
+
+    let txn = db.begin().await?;
+    if find_by_username(&txn, &username).await?.is_some() {
+        return Err(DbErr::Custom(format!("Username '{username}' is already taken")));
+    }
+
+    let created = users::ActiveModel {
+        username: Set(username),
+        display_name: Set(data.display_name),
+        password_hash: Set(password_hash),
+        role: Set(data.role),
+        department_id: Set(data.department_id),
+        is_active: Set(true),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::USER,
+        Some(created.id.to_string()),
+        audit_action::CREATE,
+        None,
+        snapshot(&created),
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(created)
+}
+
+/// Update a user account, optionally resetting its password.
+///
+/// Refuses to demote or deactivate the last active admin.
+pub async fn update(db: &DatabaseConnection, id: i32, data: UpdateUser) -> Result<Option<users::Model>, DbErr> {
+    let txn = db.begin().await?;
+    let Some(model) = Users::find_by_id(id).one(&txn).await? else {
+        return Ok(None);
+    };
+
+    let role = data.role.clone().unwrap_or_else(|| model.role.clone());
+    let department_id = data.department_id.unwrap_or(model.department_id);
+    check_role(&role, department_id)?;
+
+    let stays_admin = role == user_role::ADMIN && data.is_active.unwrap_or(model.is_active);
+    if model.role == user_role::ADMIN && model.is_active && !stays_admin {
+        let other_admins = Users::find()
+            .filter(users::Column::Role.eq(user_role::ADMIN))
+            .filter(users::Column::IsActive.eq(true))
+            .filter(users::Column::Id.ne(id))
+            .count(&txn)
+            .await?;
+        if other_admins == 0 {
+            return Err(DbErr::Custom(
+                "At least one active admin account is required".to_string(),
+            ));
+        }
+    }
+
+    let before = snapshot(&model);
+    let mut active: users::ActiveModel = model.into();
+    if let Some(display_name) = data.display_name {
+        active.display_name = Set(display_name);
+    }
+    active.role = Set(role);
+    active.department_id = Set(department_id);
+    if let Some(is_active) = data.is_active {
+        active.is_active = Set(is_active);
+    }
+    let password_reset = data.password.is_some();
+    if let Some(password) = data.password {
+        validate_password(&password).map_err(DbErr::Custom)?;
+        active.password_hash = Set(hash_password(&password).map_err(DbErr::Custom)?);
+    }
+
+    let updated = active.update(&txn).await?;
+    let mut after = snapshot(&updated);
+    if password_reset && let Some(Value::Object(fields)) = &mut after {
+        // The hash itself is never logged; mark the reset so it shows as a change
+        fields.insert("password".to_string(), Value::from("reset"));
+    }
+    audit::record(
+        &txn,
+        &audit::actor(),
+        audit_entity::USER,
+        Some(id.to_string()),
+        audit_action::UPDATE,
+        before,
+        after,
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(Some(updated))
+}
+
+/// Check a username and password, returning the account when sign-in is allowed.
+///
+/// Unknown usernames, wrong passwords and inactive accounts all return `None`.
+pub async fn authenticate(
+    db: &DatabaseConnection,
+    username: &str,
+    password: &str,
+) -> Result<Option<users::Model>, DbErr> {
+    let Some(user) = find_by_username(db, username.trim()).await? else {
+        return Ok(None);
+    };
+    if !user.is_active || !verify_password(password, &user.password_hash) {
+        return Ok(None);
+    }
+
+    let mut active: users::ActiveModel = user.into();
+    active.last_login_at = Set(Some(Utc::now().fixed_offset()));
+    active.update(db).await.map(Some)
+}
+
+/// Find an account by username, ignoring case.
+async fn find_by_username<C: ConnectionTrait>(db: &C, username: &str) -> Result<Option<users::Model>, DbErr> {
+    Users::find()
+        .filter(Expr::expr(Func::lower(Expr::col(users::Column::Username))).eq(username.to_lowercase()))
+        .one(db)
+        .await
+}
+
+/// Reject unknown roles and managers without a department.
+fn check_role(role: &str, department_id: Option<i32>) -> Result<(), DbErr> {
+    if !user_role::ALL.contains(&role) {
+        return Err(DbErr::Custom(format!("Unknown role '{role}'")));
+    }
+    if role == user_role::MANAGER && department_id.is_none() {
+        return Err(DbErr::Custom("A department manager needs a department".to_string()));
+    }
+    Ok(())
+}
+
+/// Audit snapshot of an account without its password hash.
+fn snapshot(user: &users::Model) -> Option<Value> {
+    let mut value = audit::snapshot(user)?;
+    if let Value::Object(fields) = &mut value {
+        fields.remove("password_hash");
+    }
+    Some(value)
+}
@@ -13,7 +13,9 @@ pub struct Model {
     pub verify_type: i32,
     pub status: i32,
     pub source: String,
+    pub is_duplicate: bool,
     pub created_at: DateTimeWithTimeZone,
+    pub is_voided: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
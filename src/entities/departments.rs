@@ -26,8 +26,18 @@ pub enum Relation {
         on_delete = "SetNull"
     )]
     SelfRef,
+    #[sea_orm(has_many = "super::employee_assignments::Entity")]
+    EmployeeAssignments,
     #[sea_orm(has_many = "super::employees::Entity")]
     Employees,
+    #[sea_orm(has_many = "super::users::Entity")]
+    Users,
+}
+
+impl Related<super::employee_assignments::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::EmployeeAssignments.def()
+    }
 }
 
 impl Related<super::employees::Entity> for Entity {
@@ -36,4 +46,10 @@ impl Related<super::employees::Entity> for Entity {
     }
 }
 
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Users.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -3,5 +3,22 @@
 pub mod prelude;
 
 pub mod attendance_logs;
+pub mod audit_log;
 pub mod departments;
+pub mod employee_assignments;
 pub mod employees;
+pub mod holidays;
+pub mod leave_entitlements;
+pub mod leave_requests;
+pub mod leave_types;
+pub mod punch_corrections;
+pub mod schema_version;
+pub mod settings;
+pub mod shift_assignments;
+pub mod shift_pattern_days;
+pub mod shift_patterns;
+pub mod shift_rosters;
+pub mod shifts;
+pub mod sync_locks;
+pub mod sync_runs;
+pub mod users;
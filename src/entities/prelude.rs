@@ -1,5 +1,22 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
 
 pub use super::attendance_logs::Entity as AttendanceLogs;
+pub use super::audit_log::Entity as AuditLog;
 pub use super::departments::Entity as Departments;
+pub use super::employee_assignments::Entity as EmployeeAssignments;
 pub use super::employees::Entity as Employees;
+pub use super::holidays::Entity as Holidays;
+pub use super::leave_entitlements::Entity as LeaveEntitlements;
+pub use super::leave_requests::Entity as LeaveRequests;
+pub use super::leave_types::Entity as LeaveTypes;
+pub use super::punch_corrections::Entity as PunchCorrections;
+pub use super::schema_version::Entity as SchemaVersion;
+pub use super::settings::Entity as Settings;
+pub use super::shift_assignments::Entity as ShiftAssignments;
+pub use super::shift_pattern_days::Entity as ShiftPatternDays;
+pub use super::shift_patterns::Entity as ShiftPatterns;
+pub use super::shift_rosters::Entity as ShiftRosters;
+pub use super::shifts::Entity as Shifts;
+pub use super::sync_locks::Entity as SyncLocks;
+pub use super::sync_runs::Entity as SyncRuns;
+pub use super::users::Entity as Users;
@@ -48,6 +48,21 @@ pub enum AppError {
     /// ZK TCP protocol error
     #[error("ZK protocol error: {0}")]
     ZkProtocol(#[from] crate::zk::ZkError),
+
+    /// Operation cancelled by the user
+    #[error("Cancelled")]
+    Cancelled,
+
+    /// Device is locked by another workstation
+    #[error("{0}")]
+    DeviceLocked(String),
+
+    /// Database schema was migrated by a newer application version
+    #[error(
+        "Database schema version {found} is newer than this application supports ({supported}). \
+         Please update Gianged Attendance."
+    )]
+    SchemaTooNew { found: i32, supported: i32 },
 }
 
 /// Result type alias for AppError
@@ -73,4 +88,9 @@ impl AppError {
     pub fn not_found(msg: impl Into<String>) -> Self {
         Self::NotFound(msg.into())
     }
+
+    /// Check whether this error is a user cancellation
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self, Self::Cancelled | Self::ZkProtocol(crate::zk::ZkError::Cancelled))
+    }
 }
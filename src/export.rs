@@ -2,12 +2,17 @@
 
 use crate::entities::{departments, employees};
 use crate::models::attendance::{AttendanceDetail, DailyAttendance};
-use chrono::Local;
-use rust_xlsxwriter::{Color, Format, FormatBorder, Workbook, XlsxError};
+use crate::models::day_status::{DailyStatus, DayStatus};
+use crate::models::overtime::monthly_totals;
+use crate::models::punch_session::format_sessions;
+use crate::timezone;
+use chrono::{Datelike, Months, NaiveDate};
+use rust_xlsxwriter::{Color, Format, FormatAlign, FormatBorder, Workbook, XlsxError};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 /// Export daily attendance summary to Excel file.
-/// Shows first check, last check, and work hours per employee per day.
+/// Shows first check, last check, worked hours and in/out sessions per employee per day.
 pub fn export_attendance_summary_to_excel(data: &[DailyAttendance], path: &Path) -> Result<(), XlsxError> {
     let mut workbook = Workbook::new();
     let worksheet = workbook.add_worksheet();
@@ -33,6 +38,12 @@ pub fn export_attendance_summary_to_excel(data: &[DailyAttendance], path: &Path)
         "First Check",
         "Last Check",
         "Work Hours",
+        "Sessions",
+        "Missing Punch",
+        "OT Day",
+        "OT Hours",
+        "Night OT Hours",
+        "Paid OT Hours",
     ];
 
     for (col, header) in headers.iter().enumerate() {
@@ -47,6 +58,12 @@ pub fn export_attendance_summary_to_excel(data: &[DailyAttendance], path: &Path)
     worksheet.set_column_width(4, 10)?; // First Check
     worksheet.set_column_width(5, 10)?; // Last Check
     worksheet.set_column_width(6, 12)?; // Work Hours
+    worksheet.set_column_width(7, 30)?; // Sessions
+    worksheet.set_column_width(8, 14)?; // Missing Punch
+    worksheet.set_column_width(9, 10)?; // OT Day
+    worksheet.set_column_width(10, 10)?; // OT Hours
+    worksheet.set_column_width(11, 15)?; // Night OT Hours
+    worksheet.set_column_width(12, 14)?; // Paid OT Hours
 
     // Data rows
     for (idx, record) in data.iter().enumerate() {
@@ -58,21 +75,30 @@ pub fn export_attendance_summary_to_excel(data: &[DailyAttendance], path: &Path)
         worksheet.write_string(row, 3, record.work_date.to_string())?;
 
         // Convert UTC to local time for display
-        let first_local = record.first_check.with_timezone(&Local);
-        let last_local = record.last_check.with_timezone(&Local);
+        let first_local = timezone::to_business(&record.first_check);
+        let last_local = timezone::to_business(&record.last_check);
 
         worksheet.write_string(row, 4, first_local.format("%H:%M:%S").to_string())?;
         worksheet.write_string(row, 5, last_local.format("%H:%M:%S").to_string())?;
 
-        // Use pre-calculated work_hours if available, otherwise calculate
-        let hours = record.work_hours.unwrap_or_else(|| record.calculate_work_hours());
-        worksheet.write_number_with_format(row, 6, hours, &hours_format)?;
+        // Sum of in/out sessions, so breaks are not counted
+        worksheet.write_number_with_format(row, 6, record.worked_hours(), &hours_format)?;
+        worksheet.write_string(row, 7, format_sessions(&record.sessions))?;
+        if record.missing_punch() {
+            worksheet.write_string(row, 8, "Yes")?;
+        }
+
+        let overtime = &record.overtime;
+        worksheet.write_string(row, 9, overtime.day.label())?;
+        worksheet.write_number_with_format(row, 10, overtime.hours(), &hours_format)?;
+        worksheet.write_number_with_format(row, 11, overtime.night_hours(), &hours_format)?;
+        worksheet.write_number_with_format(row, 12, overtime.paid_hours, &hours_format)?;
     }
 
     // Autofilter
     if !data.is_empty() {
         let last_row = data.len() as u32;
-        worksheet.autofilter(0, 0, last_row, 6)?;
+        worksheet.autofilter(0, 0, last_row, 12)?;
     }
 
     // Freeze top row
@@ -82,6 +108,132 @@ pub fn export_attendance_summary_to_excel(data: &[DailyAttendance], path: &Path)
     Ok(())
 }
 
+/// Export monthly overtime totals per employee to Excel file.
+/// A second sheet lists the days with overtime behind the totals.
+pub fn export_overtime_to_excel(data: &[DailyAttendance], path: &Path) -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.set_name("Overtime")?;
+
+    let header_format = Format::new()
+        .set_bold()
+        .set_background_color(Color::RGB(0x4472C4))
+        .set_font_color(Color::White)
+        .set_border(FormatBorder::Thin);
+    let hours_format = Format::new().set_num_format("0.00");
+
+    let headers = [
+        "Employee Code",
+        "Full Name",
+        "Department",
+        "Month",
+        "OT Days",
+        "Workday OT",
+        "Weekend OT",
+        "Holiday OT",
+        "Total OT",
+        "Night OT",
+        "Paid OT Hours",
+    ];
+
+    for (col, header) in headers.iter().enumerate() {
+        worksheet.write_string_with_format(0, col as u16, *header, &header_format)?;
+    }
+
+    worksheet.set_column_width(0, 15)?; // Employee Code
+    worksheet.set_column_width(1, 30)?; // Full Name
+    worksheet.set_column_width(2, 25)?; // Department
+    worksheet.set_column_width(3, 10)?; // Month
+    for col in 4..=10 {
+        worksheet.set_column_width(col, 13)?;
+    }
+
+    let totals = monthly_totals(data);
+    for (idx, total) in totals.iter().enumerate() {
+        let row = (idx + 1) as u32;
+        let hours = |minutes: i64| minutes as f64 / 60.0;
+
+        worksheet.write_string(row, 0, &total.employee_code)?;
+        worksheet.write_string(row, 1, &total.full_name)?;
+        worksheet.write_string(row, 2, total.department_name.as_deref().unwrap_or(""))?;
+        worksheet.write_string(row, 3, total.month.format("%Y-%m").to_string())?;
+        worksheet.write_number(row, 4, total.days as f64)?;
+        worksheet.write_number_with_format(row, 5, hours(total.workday_minutes), &hours_format)?;
+        worksheet.write_number_with_format(row, 6, hours(total.weekend_minutes), &hours_format)?;
+        worksheet.write_number_with_format(row, 7, hours(total.holiday_minutes), &hours_format)?;
+        worksheet.write_number_with_format(row, 8, hours(total.minutes()), &hours_format)?;
+        worksheet.write_number_with_format(row, 9, hours(total.night_minutes), &hours_format)?;
+        worksheet.write_number_with_format(row, 10, total.paid_hours, &hours_format)?;
+    }
+
+    if !totals.is_empty() {
+        worksheet.autofilter(0, 0, totals.len() as u32, 10)?;
+    }
+    worksheet.set_freeze_panes(1, 0)?;
+
+    // Days behind the totals
+    let mut days: Vec<&DailyAttendance> = data.iter().filter(|r| r.overtime.minutes > 0).collect();
+    days.sort_by(|a, b| {
+        a.employee_code
+            .cmp(&b.employee_code)
+            .then(a.work_date.cmp(&b.work_date))
+    });
+
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name("Days")?;
+
+    let headers = [
+        "Employee Code",
+        "Full Name",
+        "Date",
+        "Day",
+        "Sessions",
+        "Worked Hours",
+        "OT Hours",
+        "Night OT Hours",
+        "Multiplier",
+        "Paid OT Hours",
+    ];
+
+    for (col, header) in headers.iter().enumerate() {
+        worksheet.write_string_with_format(0, col as u16, *header, &header_format)?;
+    }
+
+    worksheet.set_column_width(0, 15)?; // Employee Code
+    worksheet.set_column_width(1, 30)?; // Full Name
+    worksheet.set_column_width(2, 12)?; // Date
+    worksheet.set_column_width(3, 10)?; // Day
+    worksheet.set_column_width(4, 30)?; // Sessions
+    for col in 5..=9 {
+        worksheet.set_column_width(col, 14)?;
+    }
+
+    for (idx, record) in days.iter().enumerate() {
+        let row = (idx + 1) as u32;
+        let overtime = &record.overtime;
+
+        worksheet.write_string(row, 0, &record.employee_code)?;
+        worksheet.write_string(row, 1, &record.full_name)?;
+        worksheet.write_string(row, 2, record.work_date.to_string())?;
+        worksheet.write_string(row, 3, overtime.day.label())?;
+        worksheet.write_string(row, 4, format_sessions(&record.sessions))?;
+        worksheet.write_number_with_format(row, 5, record.worked_hours(), &hours_format)?;
+        worksheet.write_number_with_format(row, 6, overtime.hours(), &hours_format)?;
+        worksheet.write_number_with_format(row, 7, overtime.night_hours(), &hours_format)?;
+        worksheet.write_number(row, 8, overtime.multiplier)?;
+        worksheet.write_number_with_format(row, 9, overtime.paid_hours, &hours_format)?;
+    }
+
+    if !days.is_empty() {
+        worksheet.autofilter(0, 0, days.len() as u32, 9)?;
+    }
+    worksheet.set_freeze_panes(1, 0)?;
+
+    workbook.save(path)?;
+    Ok(())
+}
+
 /// Export detailed attendance records to Excel file.
 /// Shows every individual check time for each employee.
 pub fn export_attendance_detail_to_excel(data: &[AttendanceDetail], path: &Path) -> Result<(), XlsxError> {
@@ -105,6 +257,11 @@ pub fn export_attendance_detail_to_excel(data: &[AttendanceDetail], path: &Path)
         "Date",
         "Time",
         "Verify Type",
+        "Double Punch",
+        "Punch",
+        "Source",
+        "Voided",
+        "Correction",
     ];
 
     for (col, header) in headers.iter().enumerate() {
@@ -118,6 +275,14 @@ pub fn export_attendance_detail_to_excel(data: &[AttendanceDetail], path: &Path)
     worksheet.set_column_width(3, 12)?; // Date
     worksheet.set_column_width(4, 10)?; // Time
     worksheet.set_column_width(5, 12)?; // Verify Type
+    worksheet.set_column_width(6, 13)?; // Double Punch
+    worksheet.set_column_width(7, 18)?; // Punch
+    worksheet.set_column_width(8, 10)?; // Source
+    worksheet.set_column_width(9, 8)?; // Voided
+    worksheet.set_column_width(10, 40)?; // Correction
+
+    // Manual punches stand out from device punches
+    let manual_format = Format::new().set_background_color(Color::RGB(0xDDEBF7));
 
     // Data rows
     for (idx, record) in data.iter().enumerate() {
@@ -128,26 +293,459 @@ pub fn export_attendance_detail_to_excel(data: &[AttendanceDetail], path: &Path)
         worksheet.write_string(row, 2, record.department_name.as_deref().unwrap_or(""))?;
 
         // Convert UTC to local time for display
-        let local_time = record.check_time.with_timezone(&Local);
+        let local_time = timezone::to_business(&record.check_time);
 
         worksheet.write_string(row, 3, local_time.format("%Y-%m-%d").to_string())?;
         worksheet.write_string(row, 4, local_time.format("%H:%M:%S").to_string())?;
         worksheet.write_string(row, 5, &record.verify_type_name)?;
+        if record.is_duplicate {
+            worksheet.write_string(row, 6, "Yes")?;
+        }
+        if let Some(role) = record.punch_role {
+            worksheet.write_string(row, 7, role.label())?;
+        }
+        if record.is_manual() {
+            worksheet.write_string_with_format(row, 8, &record.source, &manual_format)?;
+        } else {
+            worksheet.write_string(row, 8, &record.source)?;
+        }
+        if record.is_voided {
+            worksheet.write_string(row, 9, "Yes")?;
+        }
+        if let (Some(reason), Some(by)) = (&record.correction_reason, &record.corrected_by) {
+            worksheet.write_string(row, 10, format!("{reason} ({by})"))?;
+        }
+    }
+
+    // Autofilter
+    if !data.is_empty() {
+        let last_row = data.len() as u32;
+        worksheet.autofilter(0, 0, last_row, 10)?;
+    }
+
+    // Freeze top row
+    worksheet.set_freeze_panes(1, 0)?;
+
+    workbook.save(path)?;
+    Ok(())
+}
+
+/// Export late arrival, early departure, absence and leave status to Excel file.
+/// First sheet lists every employee-day, second sheet totals per employee.
+pub fn export_attendance_status_to_excel(data: &[DailyStatus], path: &Path) -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Header format
+    let header_format = Format::new()
+        .set_bold()
+        .set_background_color(Color::RGB(0x4472C4))
+        .set_font_color(Color::White)
+        .set_border(FormatBorder::Thin);
+
+    // Highlight for days that need attention
+    let exception_format = Format::new().set_font_color(Color::RGB(0xC00000));
+
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name("Late & Absence")?;
+
+    // Headers
+    let headers = [
+        "Employee Code",
+        "Full Name",
+        "Department",
+        "Date",
+        "Shift",
+        "Scheduled",
+        "First Check",
+        "Last Check",
+        "Status",
+        "Late (min)",
+        "Early (min)",
+        "Leave",
+    ];
+
+    for (col, header) in headers.iter().enumerate() {
+        worksheet.write_string_with_format(0, col as u16, *header, &header_format)?;
+    }
+
+    // Column widths
+    worksheet.set_column_width(0, 15)?; // Employee Code
+    worksheet.set_column_width(1, 30)?; // Full Name
+    worksheet.set_column_width(2, 25)?; // Department
+    worksheet.set_column_width(3, 12)?; // Date
+    worksheet.set_column_width(4, 15)?; // Shift
+    worksheet.set_column_width(5, 13)?; // Scheduled
+    worksheet.set_column_width(6, 10)?; // First Check
+    worksheet.set_column_width(7, 10)?; // Last Check
+    worksheet.set_column_width(8, 18)?; // Status
+    worksheet.set_column_width(9, 10)?; // Late
+    worksheet.set_column_width(10, 11)?; // Early
+    worksheet.set_column_width(11, 20)?; // Leave
+
+    // Data rows
+    for (idx, record) in data.iter().enumerate() {
+        let row = (idx + 1) as u32;
+
+        worksheet.write_string(row, 0, &record.employee_code)?;
+        worksheet.write_string(row, 1, &record.full_name)?;
+        worksheet.write_string(row, 2, record.department_name.as_deref().unwrap_or(""))?;
+        worksheet.write_string(row, 3, record.work_date.to_string())?;
+        worksheet.write_string(row, 4, record.shift_name.as_deref().unwrap_or(""))?;
+        if let (Some(start), Some(end)) = (record.shift_start, record.shift_end) {
+            worksheet.write_string(row, 5, format!("{}-{}", start.format("%H:%M"), end.format("%H:%M")))?;
+        }
+
+        // Convert UTC to local time for display
+        if let Some(first) = record.first_check {
+            worksheet.write_string(row, 6, timezone::to_business(&first).format("%H:%M:%S").to_string())?;
+        }
+        if let Some(last) = record.last_check {
+            worksheet.write_string(row, 7, timezone::to_business(&last).format("%H:%M:%S").to_string())?;
+        }
+
+        if record.is_exception() {
+            worksheet.write_string_with_format(row, 8, record.label(), &exception_format)?;
+        } else {
+            worksheet.write_string(row, 8, record.label())?;
+        }
+        if record.late_minutes > 0 {
+            worksheet.write_number(row, 9, record.late_minutes as f64)?;
+        }
+        if record.early_minutes > 0 {
+            worksheet.write_number(row, 10, record.early_minutes as f64)?;
+        }
+        if let Some(leave) = record.leave_label() {
+            worksheet.write_string(row, 11, leave)?;
+        }
     }
 
     // Autofilter
     if !data.is_empty() {
         let last_row = data.len() as u32;
-        worksheet.autofilter(0, 0, last_row, 5)?;
+        worksheet.autofilter(0, 0, last_row, 11)?;
     }
 
     // Freeze top row
     worksheet.set_freeze_panes(1, 0)?;
 
+    // Totals per employee
+    let mut totals: HashMap<i32, (&DailyStatus, StatusTotals)> = HashMap::new();
+    for record in data {
+        totals
+            .entry(record.employee_id)
+            .or_insert_with(|| (record, StatusTotals::default()))
+            .1
+            .add(record);
+    }
+    let mut totals: Vec<_> = totals.into_values().collect();
+    totals.sort_by(|a, b| a.0.employee_code.cmp(&b.0.employee_code));
+
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name("Totals")?;
+
+    let headers = [
+        "Employee Code",
+        "Full Name",
+        "Department",
+        "Scheduled Days",
+        "Present",
+        "Absent",
+        "Late Days",
+        "Late (min)",
+        "Early Days",
+        "Early (min)",
+        "Leave Days",
+    ];
+
+    for (col, header) in headers.iter().enumerate() {
+        worksheet.write_string_with_format(0, col as u16, *header, &header_format)?;
+    }
+
+    worksheet.set_column_width(0, 15)?; // Employee Code
+    worksheet.set_column_width(1, 30)?; // Full Name
+    worksheet.set_column_width(2, 25)?; // Department
+    for col in 3..=10 {
+        worksheet.set_column_width(col, 12)?;
+    }
+
+    for (idx, (record, total)) in totals.iter().enumerate() {
+        let row = (idx + 1) as u32;
+
+        worksheet.write_string(row, 0, &record.employee_code)?;
+        worksheet.write_string(row, 1, &record.full_name)?;
+        worksheet.write_string(row, 2, record.department_name.as_deref().unwrap_or(""))?;
+        worksheet.write_number(row, 3, total.scheduled as f64)?;
+        worksheet.write_number(row, 4, total.present as f64)?;
+        worksheet.write_number(row, 5, total.absent as f64)?;
+        worksheet.write_number(row, 6, total.late_days as f64)?;
+        worksheet.write_number(row, 7, total.late_minutes as f64)?;
+        worksheet.write_number(row, 8, total.early_days as f64)?;
+        worksheet.write_number(row, 9, total.early_minutes as f64)?;
+        worksheet.write_number(row, 10, total.leave_days)?;
+    }
+
+    if !totals.is_empty() {
+        worksheet.autofilter(0, 0, totals.len() as u32, 10)?;
+    }
+    worksheet.set_freeze_panes(1, 0)?;
+
+    workbook.save(path)?;
+    Ok(())
+}
+
+/// Export a monthly timesheet: one row per employee grouped by department and
+/// one column per day, set up to print landscape on one page wide for sign-off.
+///
+/// `data` is the month's daily status (see `db::attendance::get_daily_status`);
+/// `month` is any day of the month.
+pub fn export_timesheet_to_excel(data: &[DailyStatus], month: NaiveDate, path: &Path) -> Result<(), XlsxError> {
+    const HEADER_ROW: u32 = 3;
+    const FIRST_DAY_COL: u16 = 2;
+
+    let (first, last) = month_bounds(month);
+    let days: Vec<NaiveDate> = first.iter_days().take_while(|d| *d <= last).collect();
+    let totals_col = FIRST_DAY_COL + days.len() as u16;
+    let total_headers = [
+        "Days Worked",
+        "Hours",
+        "Late",
+        "Absent",
+        "Leave Days",
+        "OT Hours",
+        "Paid OT",
+    ];
+    let last_col = totals_col + total_headers.len() as u16 - 1;
+
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name("Timesheet")?;
+
+    let title_format = Format::new().set_bold().set_font_size(14);
+    let header_format = Format::new()
+        .set_bold()
+        .set_align(FormatAlign::Center)
+        .set_align(FormatAlign::VerticalCenter)
+        .set_text_wrap()
+        .set_background_color(Color::RGB(0x4472C4))
+        .set_font_color(Color::White)
+        .set_border(FormatBorder::Thin);
+    let weekend_header_format = header_format.clone().set_background_color(Color::RGB(0x8EA9DB));
+    let department_format = Format::new()
+        .set_bold()
+        .set_background_color(Color::RGB(0xD9E1F2))
+        .set_border(FormatBorder::Thin);
+    let cell_format = Format::new().set_border(FormatBorder::Thin);
+    let day_format = cell_format.clone().set_align(FormatAlign::Center);
+    let hours_format = day_format.clone().set_num_format("0.0");
+    let weekend_format = day_format.clone().set_background_color(Color::RGB(0xF2F2F2));
+    let weekend_hours_format = weekend_format.clone().set_num_format("0.0");
+    let late_format = hours_format.clone().set_background_color(Color::RGB(0xFFE699));
+    let absent_format = day_format.clone().set_font_color(Color::RGB(0xC00000)).set_bold();
+    let total_format = cell_format.clone().set_num_format("0.##");
+
+    worksheet.write_string_with_format(0, 0, format!("Timesheet - {}", first.format("%B %Y")), &title_format)?;
+    worksheet.write_string(1, 0, format!("Period: {} to {}", first, last))?;
+
+    // Two header rows: day number over weekday, other headers merged across both
+    worksheet.merge_range(HEADER_ROW, 0, HEADER_ROW + 1, 0, "Code", &header_format)?;
+    worksheet.merge_range(HEADER_ROW, 1, HEADER_ROW + 1, 1, "Full Name", &header_format)?;
+    for (idx, date) in days.iter().enumerate() {
+        let col = FIRST_DAY_COL + idx as u16;
+        let format = if is_weekend(*date) {
+            &weekend_header_format
+        } else {
+            &header_format
+        };
+        worksheet.write_number_with_format(HEADER_ROW, col, date.day() as f64, format)?;
+        worksheet.write_string_with_format(HEADER_ROW + 1, col, &date.format("%a").to_string()[..2], format)?;
+        worksheet.set_column_width(col, 5)?;
+    }
+    for (idx, header) in total_headers.iter().enumerate() {
+        let col = totals_col + idx as u16;
+        worksheet.merge_range(HEADER_ROW, col, HEADER_ROW + 1, col, header, &header_format)?;
+        worksheet.set_column_width(col, 9)?;
+    }
+    worksheet.set_column_width(0, 10)?; // Code
+    worksheet.set_column_width(1, 26)?; // Full Name
+    worksheet.set_row_height(HEADER_ROW, 30)?;
+
+    // Employees grouped by department, then by code; an employee transferred
+    // during the month gets a row in each department for the days spent there
+    let mut employees: HashMap<(i32, Option<i32>), Vec<&DailyStatus>> = HashMap::new();
+    for record in data {
+        employees
+            .entry((record.employee_id, record.department_id))
+            .or_default()
+            .push(record);
+    }
+    let mut employees: Vec<Vec<&DailyStatus>> = employees.into_values().collect();
+    employees.sort_by(|a, b| {
+        (&a[0].department_name, &a[0].employee_code).cmp(&(&b[0].department_name, &b[0].employee_code))
+    });
+
+    let mut row = HEADER_ROW + 2;
+    let mut department: Option<Option<&str>> = None;
+    for records in &employees {
+        let employee = records[0];
+        let employee_department = employee.department_name.as_deref();
+        if department != Some(employee_department) {
+            department = Some(employee_department);
+            worksheet.merge_range(
+                row,
+                0,
+                row,
+                last_col,
+                employee_department.unwrap_or("No department"),
+                &department_format,
+            )?;
+            row += 1;
+        }
+
+        worksheet.write_string_with_format(row, 0, &employee.employee_code, &cell_format)?;
+        worksheet.write_string_with_format(row, 1, &employee.full_name, &cell_format)?;
+
+        let by_date: HashMap<NaiveDate, &DailyStatus> = records.iter().map(|r| (r.work_date, *r)).collect();
+        for (idx, date) in days.iter().enumerate() {
+            let col = FIRST_DAY_COL + idx as u16;
+            let weekend = is_weekend(*date);
+            match by_date.get(date) {
+                Some(record) if record.status == DayStatus::Present && record.worked_hours > 0.0 => {
+                    let format = if record.code() == "L" {
+                        &late_format
+                    } else if weekend {
+                        &weekend_hours_format
+                    } else {
+                        &hours_format
+                    };
+                    worksheet.write_number_with_format(row, col, record.worked_hours, format)?;
+                }
+                Some(record) => {
+                    let format = match record.status {
+                        DayStatus::Absent => &absent_format,
+                        _ if weekend => &weekend_format,
+                        _ => &day_format,
+                    };
+                    worksheet.write_string_with_format(row, col, record.code(), format)?;
+                }
+                None => {
+                    let format = if weekend { &weekend_format } else { &day_format };
+                    worksheet.write_blank(row, col, format)?;
+                }
+            }
+        }
+
+        let mut totals = StatusTotals::default();
+        let mut hours = 0.0;
+        let mut overtime_hours = 0.0;
+        let mut paid_overtime = 0.0;
+        for record in records {
+            totals.add(record);
+            hours += record.worked_hours;
+            overtime_hours += record.overtime.hours();
+            paid_overtime += record.overtime.paid_hours;
+        }
+        let values = [
+            totals.present as f64,
+            hours,
+            totals.late_days as f64,
+            totals.absent as f64,
+            totals.leave_days,
+            overtime_hours,
+            paid_overtime,
+        ];
+        for (idx, value) in values.iter().enumerate() {
+            worksheet.write_number_with_format(row, totals_col + idx as u16, *value, &total_format)?;
+        }
+
+        row += 1;
+    }
+
+    // Legend and signature block
+    row += 1;
+    worksheet.write_string(
+        row,
+        0,
+        "Numbers are hours worked (highlighted: late or early leave). \
+         P present, L late or early leave, A absent, H holiday, Leave approved leave.",
+    )?;
+
+    row += 2;
+    let signature_format = Format::new().set_bold().set_align(FormatAlign::Center);
+    let note_format = Format::new().set_italic().set_align(FormatAlign::Center);
+    let block_width = (last_col + 1) / 3;
+    for (idx, title) in ["Prepared by", "Checked by", "Approved by"].iter().enumerate() {
+        let first_col = idx as u16 * block_width;
+        let last = first_col + block_width - 1;
+        worksheet.merge_range(row, first_col, row, last, title, &signature_format)?;
+        worksheet.merge_range(
+            row + 1,
+            first_col,
+            row + 1,
+            last,
+            "(Signature and full name)",
+            &note_format,
+        )?;
+    }
+
+    // Print setup: A4 landscape, one page wide, header rows on every page
+    worksheet.set_landscape();
+    worksheet.set_paper_size(9);
+    worksheet.set_print_fit_to_pages(1, 0);
+    worksheet.set_print_center_horizontally(true);
+    worksheet.set_repeat_rows(HEADER_ROW, HEADER_ROW + 1)?;
+    worksheet.set_footer("&CPage &P of &N");
+    worksheet.set_freeze_panes(HEADER_ROW + 2, FIRST_DAY_COL)?;
+
     workbook.save(path)?;
     Ok(())
 }
 
+/// First and last day of the month containing `date`.
+pub fn month_bounds(date: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let first = date.with_day(1).unwrap_or(date);
+    let next = first.checked_add_months(Months::new(1)).unwrap_or(first);
+    (first, next.pred_opt().unwrap_or(first))
+}
+
+fn is_weekend(date: NaiveDate) -> bool {
+    date.weekday().number_from_monday() > 5
+}
+
+/// Per-employee counters for the status totals sheet.
+#[derive(Default)]
+struct StatusTotals {
+    scheduled: u32,
+    present: u32,
+    absent: u32,
+    late_days: u32,
+    late_minutes: i64,
+    early_days: u32,
+    early_minutes: i64,
+    leave_days: f64,
+}
+
+impl StatusTotals {
+    fn add(&mut self, record: &DailyStatus) {
+        if record.shift_name.is_some() && record.status != DayStatus::Holiday {
+            self.scheduled += 1;
+        }
+        match record.status {
+            DayStatus::Present => self.present += 1,
+            DayStatus::Absent => self.absent += 1,
+            DayStatus::OffDay | DayStatus::Holiday | DayStatus::Leave => {}
+        }
+        self.leave_days += record.leave_days();
+        if record.late_minutes > 0 {
+            self.late_days += 1;
+            self.late_minutes += record.late_minutes;
+        }
+        if record.early_minutes > 0 {
+            self.early_days += 1;
+            self.early_minutes += record.early_minutes;
+        }
+    }
+}
+
 /// Open save file dialog and return selected path.
 pub fn show_save_dialog(default_name: &str) -> Option<PathBuf> {
     rfd::FileDialog::new()
@@ -158,7 +756,7 @@ pub fn show_save_dialog(default_name: &str) -> Option<PathBuf> {
 
 /// Generate default filename for export.
 pub fn generate_export_filename(prefix: &str) -> String {
-    let now = Local::now();
+    let now = timezone::now();
     format!("{prefix}_{ts}.xlsx", ts = now.format("%Y%m%d_%H%M%S"))
 }
 
@@ -185,9 +783,12 @@ pub fn export_employees_to_excel(
         "Employee Code",
         "Full Name",
         "Department",
+        "Position",
         "Gender",
         "Birth Date",
         "Start Date",
+        "End Date",
+        "Termination Reason",
         "Active",
     ];
 
@@ -199,10 +800,13 @@ pub fn export_employees_to_excel(
     worksheet.set_column_width(0, 15)?; // Employee Code
     worksheet.set_column_width(1, 30)?; // Full Name
     worksheet.set_column_width(2, 25)?; // Department
-    worksheet.set_column_width(3, 10)?; // Gender
-    worksheet.set_column_width(4, 12)?; // Birth Date
-    worksheet.set_column_width(5, 12)?; // Start Date
-    worksheet.set_column_width(6, 8)?; // Active
+    worksheet.set_column_width(3, 20)?; // Position
+    worksheet.set_column_width(4, 10)?; // Gender
+    worksheet.set_column_width(5, 12)?; // Birth Date
+    worksheet.set_column_width(6, 12)?; // Start Date
+    worksheet.set_column_width(7, 12)?; // End Date
+    worksheet.set_column_width(8, 25)?; // Termination Reason
+    worksheet.set_column_width(9, 8)?; // Active
 
     // Data rows
     for (idx, emp) in employees.iter().enumerate() {
@@ -219,23 +823,26 @@ pub fn export_employees_to_excel(
             .unwrap_or("");
         worksheet.write_string(row, 2, dept_name)?;
 
-        worksheet.write_string(row, 3, emp.gender.as_deref().unwrap_or(""))?;
+        worksheet.write_string(row, 3, emp.position.as_deref().unwrap_or(""))?;
+        worksheet.write_string(row, 4, emp.gender.as_deref().unwrap_or(""))?;
 
         // Birth date
         if let Some(date) = emp.birth_date {
-            worksheet.write_string(row, 4, date.to_string())?;
+            worksheet.write_string(row, 5, date.to_string())?;
         } else {
-            worksheet.write_string(row, 4, "")?;
+            worksheet.write_string(row, 5, "")?;
         }
 
-        worksheet.write_string(row, 5, emp.start_date.to_string())?;
-        worksheet.write_string(row, 6, if emp.is_active { "Yes" } else { "No" })?;
+        worksheet.write_string(row, 6, emp.start_date.to_string())?;
+        worksheet.write_string(row, 7, emp.end_date.map(|d| d.to_string()).unwrap_or_default())?;
+        worksheet.write_string(row, 8, emp.termination_reason.as_deref().unwrap_or(""))?;
+        worksheet.write_string(row, 9, if emp.is_active { "Yes" } else { "No" })?;
     }
 
     // Autofilter
     if !employees.is_empty() {
         let last_row = employees.len() as u32;
-        worksheet.autofilter(0, 0, last_row, 6)?;
+        worksheet.autofilter(0, 0, last_row, 9)?;
     }
 
     // Freeze top row
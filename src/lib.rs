@@ -1,3 +1,4 @@
+pub mod cli;
 pub mod client;
 pub mod config;
 pub mod db;
@@ -5,7 +6,10 @@ pub mod entities;
 pub mod error;
 pub mod export;
 pub mod models;
+pub mod serve;
+pub mod spool;
 pub mod sync;
+pub mod timezone;
 pub mod ui;
 pub mod zk;
 
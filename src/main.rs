@@ -6,8 +6,10 @@ use clap::Parser;
 use eframe::egui;
 use gianged_attendance as app;
 
+use app::cli::{self, Command};
 use app::config::{AppConfig, ConfigLoadResult};
 use app::db;
+use app::timezone;
 use app::ui::{App, SetupApp, SetupWizard};
 
 /// Get the directory containing the executable.
@@ -19,16 +21,18 @@ fn get_exe_dir() -> PathBuf {
 }
 
 /// Initialize logging based on build type.
-/// - Debug: console output at INFO level
-/// - Release: file output at WARN level
-fn init_logging(exe_dir: &Path) {
+/// - Debug: console (stderr) output at INFO level
+/// - Release: file output at WARN level, or INFO for the background service
+fn init_logging(exe_dir: &Path, service: bool) {
     let log_dir = exe_dir.join("logs");
     std::fs::create_dir_all(&log_dir).ok();
 
     #[cfg(debug_assertions)]
     {
-        // Dev mode: console only
+        // Dev mode: console only, on stderr so CLI JSON output stays clean
+        let _ = service;
         tracing_subscriber::fmt()
+            .with_writer(std::io::stderr)
             .with_env_filter(
                 tracing_subscriber::EnvFilter::from_default_env().add_directive(tracing::Level::INFO.into()),
             )
@@ -37,14 +41,17 @@ fn init_logging(exe_dir: &Path) {
 
     #[cfg(not(debug_assertions))]
     {
-        // Release mode: file only, WARN level
+        // Release mode: file only
+        let level = if service {
+            tracing::Level::INFO
+        } else {
+            tracing::Level::WARN
+        };
         let file_appender = tracing_appender::rolling::daily(&log_dir, "app");
         tracing_subscriber::fmt()
             .with_writer(file_appender)
             .with_ansi(false)
-            .with_env_filter(
-                tracing_subscriber::EnvFilter::from_default_env().add_directive(tracing::Level::WARN.into()),
-            )
+            .with_env_filter(tracing_subscriber::EnvFilter::from_default_env().add_directive(level.into()))
             .init();
     }
 }
@@ -86,11 +93,18 @@ fn cleanup_old_logs(log_dir: &Path, keep_days: i64) {
 #[command(name = "gianged-attendance")]
 struct Cli {
     /// Use config.toml from current directory (dev mode)
-    #[arg(long)]
+    #[arg(long, global = true)]
     dev: bool,
+
+    /// Run a command without the GUI
+    #[command(subcommand)]
+    command: Option<Command>,
 }
 
 /// Application launch mode.
+///
+/// Built once at startup, so the size difference between variants does not matter.
+#[allow(clippy::large_enum_variant)]
 enum LaunchMode {
     /// Normal operation with valid config.
     Normal(AppConfig),
@@ -103,7 +117,7 @@ fn main() -> eframe::Result<()> {
     let exe_dir = get_exe_dir();
 
     // Initialize logging
-    init_logging(&exe_dir);
+    init_logging(&exe_dir, matches!(cli.command, Some(Command::Serve)));
 
     // Cleanup logs older than 10 days
     cleanup_old_logs(&exe_dir.join("logs"), 10);
@@ -119,9 +133,23 @@ fn main() -> eframe::Result<()> {
     };
     tracing::info!("Config path: {:?}", config_path);
 
+    // Changes are attributed to the OS user until someone signs in
+    db::audit::init_actor();
+
+    if let Some(command) = cli.command {
+        std::process::exit(cli::run(command, &config_path));
+    }
+
     let launch_mode = match AppConfig::try_load(&config_path) {
         ConfigLoadResult::Loaded(config) => {
             tracing::info!("Config loaded successfully");
+            if let Some(tz) = config.general.timezone() {
+                timezone::init(tz);
+                tracing::info!("Business timezone: {tz}");
+            }
+            if let Some(day_start) = config.general.day_start() {
+                timezone::init_day_start(day_start);
+            }
             LaunchMode::Normal(config)
         }
         ConfigLoadResult::Missing => {
@@ -177,6 +205,12 @@ fn run_main_app(config: AppConfig) -> eframe::Result<()> {
             .await
             .expect("Failed to connect to database");
 
+        match db::migration::migrate(&conn).await {
+            Ok(applied) if !applied.is_empty() => tracing::info!("Applied schema migrations: {:?}", applied),
+            Ok(_) => {}
+            Err(e) => return Err(e),
+        }
+
         // Log connection info
         if let Ok(version) = db::get_version(&conn).await {
             tracing::info!("PostgreSQL: {}", version);
@@ -191,9 +225,23 @@ fn run_main_app(config: AppConfig) -> eframe::Result<()> {
             );
         }
 
-        conn
+        Ok(conn)
     });
 
+    // Refuse to run against a schema we cannot migrate (or one from a newer build)
+    let pool = match pool {
+        Ok(pool) => pool,
+        Err(e) => {
+            tracing::error!("Schema migration failed: {e}");
+            rfd::MessageDialog::new()
+                .set_level(rfd::MessageLevel::Error)
+                .set_title("Gianged Attendance - Database")
+                .set_description(e.to_string())
+                .show();
+            return Ok(());
+        }
+    };
+
     eframe::run_native(
         "Gianged Attendance",
         options,
@@ -1,9 +1,24 @@
 //! Data models for departments, employees, and attendance logs.
 
 pub mod attendance;
+pub mod audit;
+pub mod day_status;
 pub mod department;
 pub mod employee;
+#[cfg(test)]
+mod fixtures;
+pub mod holiday;
+pub mod leave;
+pub mod overtime;
+pub mod punch_correction;
+pub mod punch_session;
+pub mod shift;
+pub mod sync_run;
+pub mod user;
 
 pub use attendance::{CreateAttendanceLog, DailyAttendance, verify_type};
+pub use day_status::{DailyStatus, DayStatus};
 pub use department::{CreateDepartment, UpdateDepartment};
 pub use employee::{CreateEmployee, UpdateEmployee};
+pub use shift::{CreateShift, Schedule, UpdateShift};
+pub use sync_run::{CreateSyncRun, SyncRunFilter, sync_status};
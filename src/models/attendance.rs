@@ -4,6 +4,10 @@ use chrono::{DateTime, NaiveDate, Utc};
 use sea_orm::FromQueryResult;
 use serde::{Deserialize, Serialize};
 
+use super::overtime::Overtime;
+use super::punch_session::{PunchRole, PunchSession, worked_minutes};
+use super::shift::Schedule;
+
 /// DTO for creating an attendance log.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateAttendanceLog {
@@ -14,7 +18,16 @@ pub struct CreateAttendanceLog {
     pub source: String,
 }
 
-/// Daily attendance summary from v_daily_attendance view.
+/// Outcome of inserting a batch of attendance logs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InsertedBatch {
+    /// Records sent to the database; a prefix of the batch when cancelled.
+    pub processed: usize,
+    /// Records actually stored; the rest of the processed ones were duplicates.
+    pub inserted: usize,
+}
+
+/// Daily attendance summary from the v_daily_attendance view (`system.fn_daily_attendance` for a range).
 #[derive(Debug, Clone, Serialize, Deserialize, FromQueryResult)]
 pub struct DailyAttendance {
     pub employee_id: i32,
@@ -27,6 +40,19 @@ pub struct DailyAttendance {
     pub last_check: DateTime<Utc>,
     pub check_count: i64,
     pub work_hours: Option<f64>,
+    /// In/out sessions paired from the day's punches (filled after loading).
+    #[sea_orm(skip)]
+    #[serde(default)]
+    pub sessions: Vec<PunchSession>,
+    /// Overtime under the configured rules (filled after loading).
+    #[sea_orm(skip)]
+    #[serde(default)]
+    pub overtime: Overtime,
+    /// Scheduled break taken without punching out (filled after loading, see
+    /// [`DailyAttendance::unpunched_break_minutes`]).
+    #[sea_orm(skip)]
+    #[serde(default)]
+    pub break_minutes: i64,
 }
 
 /// Attendance detail from v_attendance_details view.
@@ -43,11 +69,42 @@ pub struct AttendanceDetail {
     pub check_time: DateTime<Utc>,
     pub verify_type: i32,
     pub verify_type_name: String,
+    pub status: i32,
     pub source: String,
+    pub is_duplicate: bool,
+    /// Work day the punch is attributed to (`None` for unknown scanner UIDs).
+    pub work_date: Option<NaiveDate>,
+    /// Role of the punch after pairing its work day (filled after loading).
+    #[sea_orm(skip)]
+    #[serde(default)]
+    pub punch_role: Option<PunchRole>,
+    /// Voided or replaced by a correction.
+    pub is_voided: bool,
+    /// Reason of the latest correction that added or voided the punch.
+    pub correction_reason: Option<String>,
+    pub corrected_by: Option<String>,
+}
+
+impl AttendanceDetail {
+    /// Whether the punch was entered by hand.
+    pub fn is_manual(&self) -> bool {
+        self.source == "manual"
+    }
+}
+
+/// Orphan scanner UID from v_unmatched_punches view.
+#[derive(Debug, Clone, Serialize, Deserialize, FromQueryResult)]
+pub struct UnmatchedPunches {
+    pub scanner_uid: i32,
+    pub punch_count: i64,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
 }
 
 /// Verify type constants matching database CHECK constraint.
 pub mod verify_type {
+    /// No verification, for punches entered by hand.
+    pub const NONE: i32 = 0;
     /// Fingerprint verification (device code: 2).
     pub const FINGERPRINT: i32 = 2;
     /// Card verification (device code: 101).
@@ -56,6 +113,7 @@ pub mod verify_type {
     /// Get human-readable name for verify type code.
     pub fn name(code: i32) -> &'static str {
         match code {
+            NONE => "manual",
             FINGERPRINT => "fingerprint",
             CARD => "card",
             _ => "unknown",
@@ -65,8 +123,107 @@ pub mod verify_type {
 
 impl DailyAttendance {
     /// Calculate work duration in hours from first and last check times.
+    ///
+    /// Both fall within one work day, so an overnight shift is measured from
+    /// its evening punch to its morning punch.
     pub fn calculate_work_hours(&self) -> f64 {
         let duration = self.last_check - self.first_check;
         duration.num_minutes() as f64 / 60.0
     }
+
+    /// Hours worked: the sum of complete in/out sessions, net of breaks.
+    ///
+    /// Breaks punched out for fall between sessions; a scheduled break taken
+    /// without punching is deducted via `break_minutes`. Falls back to the
+    /// first-to-last span when sessions were not loaded.
+    pub fn worked_hours(&self) -> f64 {
+        let gross = if self.sessions.is_empty() {
+            self.work_hours.unwrap_or_else(|| self.calculate_work_hours())
+        } else {
+            worked_minutes(&self.sessions) as f64 / 60.0
+        };
+        (gross - self.break_minutes as f64 / 60.0).max(0.0)
+    }
+
+    /// Minutes of the scheduled shift's break not covered by punches.
+    ///
+    /// The break counts as taken when the day has at most one complete
+    /// session, i.e. the employee did not punch out and back in for it.
+    /// Used for both worked hours and overtime so they agree.
+    pub fn unpunched_break_minutes(&self, schedule: &Schedule) -> i64 {
+        let complete = self.sessions.iter().filter(|s| s.is_complete()).count();
+        match schedule.shift_for(self.employee_id, self.department_id, self.work_date) {
+            Some((shift, _)) if complete <= 1 => i64::from(shift.break_minutes),
+            _ => 0,
+        }
+    }
+
+    /// Whether a check-in or check-out is missing.
+    pub fn missing_punch(&self) -> bool {
+        self.sessions.iter().any(|s| !s.is_complete())
+    }
+}
+
+/// Punch state codes selected on the device (stored in `attendance_logs.status`).
+///
+/// Devices without state keys report every punch as a check-in.
+pub mod punch_state {
+    pub const CHECK_IN: i32 = 0;
+    pub const CHECK_OUT: i32 = 1;
+    pub const BREAK_OUT: i32 = 2;
+    pub const BREAK_IN: i32 = 3;
+    pub const OVERTIME_IN: i32 = 4;
+    pub const OVERTIME_OUT: i32 = 5;
+
+    /// Get human-readable name for punch state code.
+    pub fn name(code: i32) -> &'static str {
+        match code {
+            CHECK_IN => "check-in",
+            CHECK_OUT => "check-out",
+            BREAK_OUT => "break-out",
+            BREAK_IN => "break-in",
+            OVERTIME_IN => "overtime-in",
+            OVERTIME_OUT => "overtime-out",
+            _ => "unknown",
+        }
+    }
+
+    /// Whether the state marks leaving work (check-out, break-out or overtime-out).
+    pub fn is_out(code: i32) -> bool {
+        matches!(code, CHECK_OUT | BREAK_OUT | OVERTIME_OUT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_worked_hours_net_of_unpunched_break() {
+        let at = |hour| Utc.with_ymd_and_hms(2025, 11, 10, hour, 0, 0).unwrap();
+        let mut record = DailyAttendance {
+            employee_id: 1,
+            employee_code: "E1".to_string(),
+            full_name: "Employee 1".to_string(),
+            department_id: None,
+            department_name: None,
+            work_date: NaiveDate::from_ymd_opt(2025, 11, 10).unwrap(),
+            first_check: at(1),
+            last_check: at(10),
+            check_count: 2,
+            work_hours: Some(9.0),
+            sessions: vec![PunchSession {
+                check_in: Some(at(1)),
+                check_out: Some(at(10)),
+            }],
+            overtime: Overtime::default(),
+            break_minutes: 60,
+        };
+        assert_eq!(record.worked_hours(), 8.0);
+
+        // Not loaded: the first-to-last span, still net of the break
+        record.sessions.clear();
+        assert_eq!(record.worked_hours(), 8.0);
+    }
 }
@@ -0,0 +1,126 @@
+//! Audit log constants, filter and change summaries.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Audit action constants matching database CHECK constraint.
+pub mod audit_action {
+    /// A record was created.
+    pub const CREATE: &str = "create";
+    /// A record was changed.
+    pub const UPDATE: &str = "update";
+    /// A record, or a range of records, was deleted.
+    pub const DELETE: &str = "delete";
+    /// A punch was voided.
+    pub const VOID: &str = "void";
+    /// Device attendance memory was cleared.
+    pub const CLEAR: &str = "clear";
+
+    /// All actions, for filter dropdowns.
+    pub const ALL: [&str; 5] = [CREATE, UPDATE, DELETE, VOID, CLEAR];
+}
+
+/// Audited entity constants.
+pub mod audit_entity {
+    pub const EMPLOYEE: &str = "employee";
+    pub const DEPARTMENT: &str = "department";
+    /// A single attendance punch.
+    pub const PUNCH: &str = "punch";
+    /// Attendance punches changed in bulk.
+    pub const ATTENDANCE: &str = "attendance";
+    /// The fingerprint device, identified by its address.
+    pub const DEVICE: &str = "device";
+    /// An application user account.
+    pub const USER: &str = "user";
+    /// The settings shared by every workstation.
+    pub const SETTINGS: &str = "settings";
+    pub const HOLIDAY: &str = "holiday";
+    pub const SHIFT: &str = "shift";
+    /// A rotation pattern with its cycle days.
+    pub const SHIFT_PATTERN: &str = "shift_pattern";
+    /// A pattern assigned to an employee or department.
+    pub const SHIFT_ASSIGNMENT: &str = "shift_assignment";
+    /// A one-day roster override for an employee.
+    pub const ROSTER: &str = "roster";
+    pub const LEAVE_TYPE: &str = "leave_type";
+    /// An employee's leave entitlement for a year.
+    pub const LEAVE_ENTITLEMENT: &str = "leave_entitlement";
+    pub const LEAVE_REQUEST: &str = "leave_request";
+
+    /// All entities, for filter dropdowns.
+    pub const ALL: [&str; 15] = [
+        EMPLOYEE,
+        DEPARTMENT,
+        PUNCH,
+        ATTENDANCE,
+        DEVICE,
+        USER,
+        SETTINGS,
+        HOLIDAY,
+        SHIFT,
+        SHIFT_PATTERN,
+        SHIFT_ASSIGNMENT,
+        ROSTER,
+        LEAVE_TYPE,
+        LEAVE_ENTITLEMENT,
+        LEAVE_REQUEST,
+    ];
+}
+
+/// Filter for querying the audit log.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditFilter {
+    /// Only entries for this entity (see [`audit_entity`]).
+    pub entity: Option<String>,
+    /// Only entries for this record of the entity.
+    pub entity_id: Option<String>,
+    /// Only entries with this action (see [`audit_action`]).
+    pub action: Option<String>,
+    /// Only entries whose actor contains this text (case-insensitive).
+    pub actor: Option<String>,
+    /// Only entries at or after this time.
+    pub since: Option<DateTime<Utc>>,
+    /// Only entries before this time.
+    pub until: Option<DateTime<Utc>>,
+    /// Maximum number of entries to return.
+    pub limit: Option<u64>,
+}
+
+/// Fields whose values differ between two record snapshots.
+///
+/// Bookkeeping timestamps are left out; fields missing from `after` come last.
+pub fn changed_fields(before: &Value, after: &Value) -> Vec<String> {
+    let (Some(before), Some(after)) = (before.as_object(), after.as_object()) else {
+        return Vec::new();
+    };
+
+    let removed = before.keys().filter(|key| !after.contains_key(*key));
+    after
+        .iter()
+        .filter(|(key, value)| before.get(*key) != Some(value))
+        .map(|(key, _)| key)
+        .chain(removed)
+        .filter(|key| !IGNORED_FIELDS.contains(&key.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// Fields that change on every update and say nothing about the change.
+const IGNORED_FIELDS: [&str; 2] = ["created_at", "updated_at"];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_changed_fields() {
+        let before = json!({ "id": 1, "department_id": 2, "is_active": true, "updated_at": "a" });
+        let after = json!({ "id": 1, "department_id": 3, "is_active": true, "updated_at": "b", "note": null });
+        assert_eq!(changed_fields(&before, &after), ["department_id", "note"]);
+
+        assert!(changed_fields(&before, &before).is_empty());
+        assert!(changed_fields(&Value::Null, &after).is_empty());
+    }
+}
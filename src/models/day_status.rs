@@ -0,0 +1,359 @@
+//! Per-employee daily status (present, late, early leave, absent, leave, off) against the schedule.
+
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::attendance::DailyAttendance;
+use super::employee::EmploymentHistory;
+use super::holiday::HolidayCalendar;
+use super::leave::{LeaveDays, is_leave_day};
+use super::overtime::Overtime;
+use super::shift::{Schedule, shift_window};
+use crate::entities::{employees, shifts};
+use crate::timezone;
+
+/// Classification of one employee-day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DayStatus {
+    /// Punched in; see the late and early minutes for deviations.
+    Present,
+    /// Scheduled to work but no punches.
+    Absent,
+    /// No shift scheduled and no punches.
+    OffDay,
+    /// Public holiday and no punches.
+    Holiday,
+    /// Approved leave and no punches.
+    Leave,
+}
+
+/// Status of one employee on one day.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyStatus {
+    pub employee_id: i32,
+    pub employee_code: String,
+    pub full_name: String,
+    pub department_id: Option<i32>,
+    pub department_name: Option<String>,
+    pub work_date: NaiveDate,
+    pub shift_name: Option<String>,
+    /// Scheduled start and end in business wall-clock time.
+    pub shift_start: Option<NaiveDateTime>,
+    pub shift_end: Option<NaiveDateTime>,
+    pub first_check: Option<DateTime<Utc>>,
+    pub last_check: Option<DateTime<Utc>>,
+    pub check_count: i64,
+    pub status: DayStatus,
+    /// Minutes after the shift start, when beyond the late grace period.
+    pub late_minutes: i64,
+    /// Minutes before the shift end, when beyond the early grace period.
+    pub early_minutes: i64,
+    /// Approved leave type, e.g. "Annual".
+    pub leave_type: Option<String>,
+    /// The approved leave is half a day.
+    pub leave_half_day: bool,
+    /// Hours worked, net of breaks (see [`DailyAttendance::worked_hours`]).
+    pub worked_hours: f64,
+    pub overtime: Overtime,
+}
+
+impl DailyStatus {
+    /// Short label for reports, e.g. "Late" or "Late, early leave".
+    pub fn label(&self) -> &'static str {
+        match self.status {
+            DayStatus::Absent => "Absent",
+            DayStatus::OffDay => "Off",
+            DayStatus::Holiday => "Holiday",
+            DayStatus::Leave => "Leave",
+            DayStatus::Present => match (self.late_minutes > 0, self.early_minutes > 0) {
+                (true, true) => "Late, early leave",
+                (true, false) => "Late",
+                (false, true) => "Early leave",
+                (false, false) => "Present",
+            },
+        }
+    }
+
+    /// Timesheet code: P present, L late or early leave, A absent, H holiday,
+    /// Leave, or empty for a day off.
+    pub fn code(&self) -> &'static str {
+        match self.status {
+            DayStatus::Present if self.late_minutes > 0 || self.early_minutes > 0 => "L",
+            DayStatus::Present => "P",
+            DayStatus::Absent => "A",
+            DayStatus::Holiday => "H",
+            DayStatus::Leave => "Leave",
+            DayStatus::OffDay => "",
+        }
+    }
+
+    /// Leave taken on the day, e.g. "Annual" or "Annual (half day)".
+    pub fn leave_label(&self) -> Option<String> {
+        self.leave_type.as_ref().map(|leave_type| {
+            if self.leave_half_day {
+                format!("{leave_type} (half day)")
+            } else {
+                leave_type.clone()
+            }
+        })
+    }
+
+    /// Days of leave taken: 1, 0.5 for half a day, or 0.
+    pub fn leave_days(&self) -> f64 {
+        match (&self.leave_type, self.leave_half_day) {
+            (None, _) => 0.0,
+            (Some(_), true) => 0.5,
+            (Some(_), false) => 1.0,
+        }
+    }
+
+    /// Whether the day needs attention (late, early leave or absent).
+    pub fn is_exception(&self) -> bool {
+        self.status == DayStatus::Absent || self.late_minutes > 0 || self.early_minutes > 0
+    }
+}
+
+/// First and last punch of a day in business wall-clock time, with the punch count.
+#[derive(Debug, Clone, Copy)]
+pub struct DayPunches {
+    pub first: NaiveDateTime,
+    pub last: NaiveDateTime,
+    pub count: i64,
+}
+
+/// Classify a day, returning the status with late and early minutes.
+///
+/// Lateness and early leave only apply to scheduled, non-holiday days without
+/// approved leave, so half a day of leave excuses the missing half. Once a
+/// grace period is exceeded the full deviation is counted, so 20 minutes late
+/// with a 5 minute grace is 20 minutes late, not 15. A single punch says nothing
+/// about when the employee left, so it is never counted as early leave.
+pub fn classify(
+    shift: Option<&shifts::Model>,
+    date: NaiveDate,
+    holiday: bool,
+    on_leave: bool,
+    punches: Option<DayPunches>,
+) -> (DayStatus, i64, i64) {
+    let Some(punches) = punches else {
+        let status = if holiday {
+            DayStatus::Holiday
+        } else if on_leave {
+            DayStatus::Leave
+        } else if shift.is_some() {
+            DayStatus::Absent
+        } else {
+            DayStatus::OffDay
+        };
+        return (status, 0, 0);
+    };
+
+    let Some(shift) = shift.filter(|_| !holiday && !on_leave) else {
+        return (DayStatus::Present, 0, 0);
+    };
+
+    let (start, end) = shift_window(shift, date);
+    let beyond = |deviation: TimeDelta, grace: i32| {
+        let minutes = deviation.num_minutes();
+        if minutes > i64::from(grace) { minutes } else { 0 }
+    };
+
+    let late = beyond(punches.first - start, shift.late_grace_minutes);
+    let early = if punches.count > 1 {
+        beyond(end - punches.last, shift.early_grace_minutes)
+    } else {
+        0
+    };
+
+    (DayStatus::Present, late, early)
+}
+
+/// Build the status of every employee for every day in `dates`.
+///
+/// Employees appear on the days they are employed (see
+/// [`EmploymentHistory::is_employed_on`]) and on any day they punched. Each day
+/// is attributed to the department the employee was in that day, and holidays
+/// apply per department, so a department-only day off is not an absence.
+/// Approved leave excuses the days it counts as leave days (see
+/// [`is_leave_day`]). Rows are ordered newest day first, then by employee code.
+pub fn daily_statuses(
+    employees: &[employees::Model],
+    history: &EmploymentHistory,
+    schedule: &Schedule,
+    attendance: &[DailyAttendance],
+    holidays: &HolidayCalendar,
+    leave: &LeaveDays,
+    dates: RangeInclusive<NaiveDate>,
+) -> Vec<DailyStatus> {
+    let (start, end) = dates.into_inner();
+    let punches: HashMap<(i32, NaiveDate), &DailyAttendance> =
+        attendance.iter().map(|a| ((a.employee_id, a.work_date), a)).collect();
+
+    let mut employees: Vec<&employees::Model> = employees.iter().collect();
+    employees.sort_by(|a, b| a.employee_code.cmp(&b.employee_code));
+
+    let mut rows = Vec::new();
+    let mut date = end;
+    while date >= start {
+        for emp in &employees {
+            let day = punches.get(&(emp.id, date)).copied();
+            if day.is_none() && !history.is_employed_on(emp, date) {
+                continue;
+            }
+
+            let department_id = history.department_on(emp, date);
+            let holiday = holidays.is_holiday(department_id, date);
+            let leave_day = leave
+                .get(emp.id, date)
+                .filter(|_| is_leave_day(schedule, holidays, emp.id, department_id, date));
+            let shift = schedule.shift_for(emp.id, department_id, date).map(|(s, _)| s);
+            let day_punches = day.map(|a| DayPunches {
+                first: timezone::to_business(&a.first_check).naive_local(),
+                last: timezone::to_business(&a.last_check).naive_local(),
+                count: a.check_count,
+            });
+            let (status, late_minutes, early_minutes) =
+                classify(shift, date, holiday, leave_day.is_some(), day_punches);
+            let window = shift.map(|s| shift_window(s, date));
+
+            rows.push(DailyStatus {
+                employee_id: emp.id,
+                employee_code: emp.employee_code.clone(),
+                full_name: emp.full_name.clone(),
+                department_id,
+                department_name: department_id
+                    .and_then(|id| history.department_name(id))
+                    .map(str::to_string),
+                work_date: date,
+                shift_name: shift.map(|s| s.name.clone()),
+                shift_start: window.map(|(s, _)| s),
+                shift_end: window.map(|(_, e)| e),
+                first_check: day.map(|a| a.first_check),
+                last_check: day.map(|a| a.last_check),
+                check_count: day.map_or(0, |a| a.check_count),
+                status,
+                late_minutes,
+                early_minutes,
+                leave_type: leave_day.map(|l| l.leave_type.clone()),
+                leave_half_day: leave_day.is_some_and(|l| l.half_day),
+                worked_hours: day.map_or(0.0, DailyAttendance::worked_hours),
+                overtime: day.map(|a| a.overtime).unwrap_or_default(),
+            });
+        }
+
+        let Some(previous) = date.pred_opt() else {
+            break;
+        };
+        date = previous;
+    }
+
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::shift::crosses_midnight;
+    use chrono::NaiveTime;
+
+    fn date(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 11, d).unwrap()
+    }
+
+    fn at(d: u32, h: u32, m: u32) -> NaiveDateTime {
+        date(d).and_time(NaiveTime::from_hms_opt(h, m, 0).unwrap())
+    }
+
+    fn day_shift() -> shifts::Model {
+        let now = Utc::now().fixed_offset();
+        let start = NaiveTime::from_hms_opt(8, 0, 0).unwrap();
+        let end = NaiveTime::from_hms_opt(17, 0, 0).unwrap();
+        shifts::Model {
+            id: 1,
+            name: "Day".to_string(),
+            start_time: start,
+            end_time: end,
+            break_minutes: 60,
+            late_grace_minutes: 5,
+            early_grace_minutes: 10,
+            crosses_midnight: crosses_midnight(start, end),
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn punches(first: NaiveDateTime, last: NaiveDateTime, count: i64) -> Option<DayPunches> {
+        Some(DayPunches { first, last, count })
+    }
+
+    #[test]
+    fn test_classify_without_punches() {
+        let shift = day_shift();
+        assert_eq!(
+            classify(Some(&shift), date(10), false, false, None),
+            (DayStatus::Absent, 0, 0)
+        );
+        assert_eq!(classify(None, date(10), false, false, None), (DayStatus::OffDay, 0, 0));
+        assert_eq!(
+            classify(Some(&shift), date(10), true, false, None),
+            (DayStatus::Holiday, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_classify_grace_periods() {
+        let shift = day_shift();
+        let classify = |p| classify(Some(&shift), date(10), false, false, p);
+
+        // Within both grace periods
+        assert_eq!(
+            classify(punches(at(10, 8, 5), at(10, 16, 50), 2)),
+            (DayStatus::Present, 0, 0)
+        );
+        // Beyond grace counts the full deviation
+        assert_eq!(
+            classify(punches(at(10, 8, 20), at(10, 16, 30), 2)),
+            (DayStatus::Present, 20, 30)
+        );
+        // A single punch is never early leave
+        assert_eq!(
+            classify(punches(at(10, 8, 20), at(10, 8, 20), 1)),
+            (DayStatus::Present, 20, 0)
+        );
+    }
+
+    #[test]
+    fn test_classify_unscheduled_work() {
+        let shift = day_shift();
+        let p = punches(at(10, 9, 0), at(10, 12, 0), 2);
+        assert_eq!(classify(None, date(10), false, false, p), (DayStatus::Present, 0, 0));
+        assert_eq!(
+            classify(Some(&shift), date(10), true, false, p),
+            (DayStatus::Present, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_classify_leave() {
+        let shift = day_shift();
+        assert_eq!(
+            classify(Some(&shift), date(10), false, true, None),
+            (DayStatus::Leave, 0, 0)
+        );
+        assert_eq!(
+            classify(Some(&shift), date(10), true, true, None),
+            (DayStatus::Holiday, 0, 0)
+        );
+
+        // Morning off on half a day of leave is not late
+        let p = punches(at(10, 13, 0), at(10, 17, 0), 2);
+        assert_eq!(
+            classify(Some(&shift), date(10), false, true, p),
+            (DayStatus::Present, 0, 0)
+        );
+    }
+}
@@ -1,7 +1,11 @@
 //! Department DTOs for create and update operations.
 
+use std::collections::HashSet;
+
 use serde::{Deserialize, Serialize};
 
+use crate::entities::departments;
+
 /// DTO for creating a department.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateDepartment {
@@ -18,3 +22,45 @@ pub struct UpdateDepartment {
     pub display_order: Option<i32>,
     pub is_active: Option<bool>,
 }
+
+/// IDs of `root` and every department below it.
+pub fn department_subtree(departments: &[departments::Model], root: i32) -> HashSet<i32> {
+    let mut subtree = HashSet::from([root]);
+    // Keep adding children until a pass finds none; a parent cycle cannot loop forever
+    loop {
+        let before = subtree.len();
+        for dept in departments {
+            if dept.parent_id.is_some_and(|parent| subtree.contains(&parent)) {
+                subtree.insert(dept.id);
+            }
+        }
+        if subtree.len() == before {
+            return subtree;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::fixtures::department;
+
+    #[test]
+    fn test_department_subtree() {
+        // 1 -> 2 -> 4, 1 -> 3, 5 on its own
+        let departments = [
+            department(4, Some(2)),
+            department(1, None),
+            department(2, Some(1)),
+            department(3, Some(1)),
+            department(5, None),
+        ];
+
+        assert_eq!(department_subtree(&departments, 1), HashSet::from([1, 2, 3, 4]));
+        assert_eq!(department_subtree(&departments, 2), HashSet::from([2, 4]));
+        assert_eq!(department_subtree(&departments, 5), HashSet::from([5]));
+
+        let cycle = [department(1, Some(2)), department(2, Some(1))];
+        assert_eq!(department_subtree(&cycle, 1), HashSet::from([1, 2]));
+    }
+}
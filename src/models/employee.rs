@@ -1,14 +1,19 @@
-//! Employee DTOs for create and update operations.
+//! Employee DTOs for create and update operations, and employment history.
+
+use std::collections::HashMap;
 
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
+use crate::entities::{departments, employee_assignments, employees};
+
 /// DTO for creating an employee.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateEmployee {
     pub employee_code: String,
     pub full_name: String,
     pub department_id: Option<i32>,
+    pub position: Option<String>,
     pub scanner_uid: Option<i32>,
     pub gender: Option<String>,
     pub birth_date: Option<NaiveDate>,
@@ -21,9 +26,378 @@ pub struct UpdateEmployee {
     pub employee_code: Option<String>,
     pub full_name: Option<String>,
     pub department_id: Option<Option<i32>>,
+    pub position: Option<Option<String>>,
+    /// First day of a department or position change; today when not set.
+    pub effective_from: Option<NaiveDate>,
     pub scanner_uid: Option<Option<i32>>,
     pub gender: Option<Option<String>>,
     pub birth_date: Option<Option<NaiveDate>>,
     pub start_date: Option<NaiveDate>,
     pub is_active: Option<bool>,
 }
+
+/// DTO for offboarding a leaver.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OffboardEmployee {
+    /// Last working day.
+    pub end_date: NaiveDate,
+    pub reason: Option<String>,
+}
+
+/// DTO for rehiring a leaver.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RehireEmployee {
+    /// First working day back, after the last working day.
+    pub start_date: NaiveDate,
+}
+
+/// How a department or position change is recorded in the assignments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reassignment {
+    /// Correct the department and position of this assignment in place.
+    Amend(i32),
+    /// End `close` (if any) the day before the change and add an assignment
+    /// from the change until `effective_to`.
+    Split {
+        close: Option<i32>,
+        effective_to: Option<NaiveDate>,
+    },
+}
+
+/// Decide how a change from `effective_from` is recorded in an employee's
+/// assignments.
+///
+/// An assignment starting that day is corrected rather than duplicated, so a
+/// second transfer on the same day, or one after an offboarding, keeps one
+/// assignment per start date. A change dated before the start of the open
+/// assignment corrects it too. Otherwise the assignment covering the day is
+/// split, the new part keeping its end.
+pub fn plan_reassignment(assignments: &[employee_assignments::Model], effective_from: NaiveDate) -> Reassignment {
+    if let Some(same_day) = assignments.iter().find(|a| a.effective_from == effective_from) {
+        return Reassignment::Amend(same_day.id);
+    }
+    let open = assignments
+        .iter()
+        .filter(|a| a.effective_to.is_none())
+        .max_by_key(|a| a.effective_from);
+    if let Some(open) = open
+        && open.effective_from > effective_from
+    {
+        return Reassignment::Amend(open.id);
+    }
+
+    let covering = assignments
+        .iter()
+        .filter(|a| a.effective_from <= effective_from && a.effective_to.is_none_or(|to| effective_from <= to))
+        .max_by_key(|a| a.effective_from);
+    Reassignment::Split {
+        close: covering.map(|a| a.id),
+        effective_to: covering.and_then(|a| a.effective_to),
+    }
+}
+
+/// Decide which assignment moves with a changed start date.
+///
+/// The earliest assignment starts on the start date, so it is moved with it.
+/// Returns `None` when nothing needs to move, and an error when the new start
+/// date would pass the end of that assignment or the start of the next one.
+pub fn plan_start_date(
+    assignments: &[employee_assignments::Model],
+    start_date: NaiveDate,
+) -> Result<Option<i32>, String> {
+    let mut sorted: Vec<&employee_assignments::Model> = assignments.iter().collect();
+    sorted.sort_by_key(|a| a.effective_from);
+    let Some(first) = sorted.first() else {
+        return Ok(None);
+    };
+    if first.effective_from == start_date {
+        return Ok(None);
+    }
+    if let Some(next) = sorted.get(1)
+        && start_date >= next.effective_from
+    {
+        return Err(format!(
+            "Start date must be before the transfer on {}",
+            next.effective_from
+        ));
+    }
+    if let Some(to) = first.effective_to
+        && start_date > to
+    {
+        return Err(format!(
+            "Start date must not be after the first assignment ends on {to}"
+        ));
+    }
+    Ok(Some(first.id))
+}
+
+/// How a rehire is recorded in the assignments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rehire {
+    /// Reopen this assignment, ended by the offboarding.
+    Reopen(i32),
+    /// Open a new assignment from this date with the current department.
+    Open(NaiveDate),
+}
+
+/// Decide how rehiring an employee who left on `previous_end` from
+/// `start_date` is recorded.
+///
+/// Coming back the day after leaving continues the last assignment, which
+/// also undoes an offboarding. After a gap a new assignment starts on
+/// `start_date`, so the gap is not employment.
+pub fn plan_rehire(
+    assignments: &[employee_assignments::Model],
+    previous_end: NaiveDate,
+    start_date: NaiveDate,
+) -> Rehire {
+    let latest = assignments.iter().max_by_key(|a| a.effective_from);
+    match latest {
+        Some(latest) if previous_end.succ_opt() == Some(start_date) => Rehire::Reopen(latest.id),
+        _ => Rehire::Open(start_date),
+    }
+}
+
+/// Loaded employee assignments for resolving employment and the department on
+/// a given day.
+///
+/// Matches `system.fn_department_on`: among assignments covering the day the
+/// latest start wins, and without one the employee's current department is used.
+/// Only days covered by the loaded range are answered correctly (see
+/// [`crate::db::employee::load_history`]).
+#[derive(Debug, Clone, Default)]
+pub struct EmploymentHistory {
+    assignments: HashMap<i32, Vec<employee_assignments::Model>>,
+    department_names: HashMap<i32, String>,
+}
+
+impl EmploymentHistory {
+    pub fn new(assignments: Vec<employee_assignments::Model>, departments: &[departments::Model]) -> Self {
+        let mut by_employee: HashMap<i32, Vec<employee_assignments::Model>> = HashMap::new();
+        for assignment in assignments {
+            by_employee.entry(assignment.employee_id).or_default().push(assignment);
+        }
+        // Latest start first so the first match wins
+        for list in by_employee.values_mut() {
+            list.sort_by_key(|a| std::cmp::Reverse(a.effective_from));
+        }
+
+        Self {
+            assignments: by_employee,
+            department_names: departments.iter().map(|d| (d.id, d.name.clone())).collect(),
+        }
+    }
+
+    /// Assignment of `employee_id` covering `date`.
+    fn covering(&self, employee_id: i32, date: NaiveDate) -> Option<&employee_assignments::Model> {
+        self.assignments.get(&employee_id).and_then(|list| {
+            list.iter()
+                .find(|a| a.effective_from <= date && a.effective_to.is_none_or(|to| date <= to))
+        })
+    }
+
+    /// Department `employee` was in on `date`.
+    pub fn department_on(&self, employee: &employees::Model, date: NaiveDate) -> Option<i32> {
+        self.covering(employee.id, date)
+            .map_or(employee.department_id, |a| a.department_id)
+    }
+
+    /// Whether `employee` is expected at work on `date`.
+    ///
+    /// An employee is employed on the days an assignment covers, which ends on
+    /// the last working day and leaves out the gap before a rehire. A
+    /// deactivated employee without an end date is treated as gone, since when
+    /// they left is unknown.
+    pub fn is_employed_on(&self, employee: &employees::Model, date: NaiveDate) -> bool {
+        (employee.is_active || employee.end_date.is_some()) && self.covering(employee.id, date).is_some()
+    }
+
+    /// Name of a department, if it exists.
+    pub fn department_name(&self, department_id: i32) -> Option<&str> {
+        self.department_names.get(&department_id).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn date(m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, m, d).unwrap()
+    }
+
+    fn assignment(
+        id: i32,
+        department_id: Option<i32>,
+        from: NaiveDate,
+        to: Option<NaiveDate>,
+    ) -> employee_assignments::Model {
+        let now = Utc::now().fixed_offset();
+        employee_assignments::Model {
+            id,
+            employee_id: 1,
+            department_id,
+            position: None,
+            effective_from: from,
+            effective_to: to,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn employee() -> employees::Model {
+        let now = Utc::now().fixed_offset();
+        employees::Model {
+            id: 1,
+            employee_code: "E1".to_string(),
+            full_name: "Employee 1".to_string(),
+            department_id: Some(3),
+            scanner_uid: None,
+            gender: None,
+            birth_date: None,
+            start_date: date(1, 1),
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+            position: None,
+            end_date: None,
+            termination_reason: None,
+        }
+    }
+
+    #[test]
+    fn test_department_on() {
+        let employee = employee();
+        let history = EmploymentHistory::new(
+            vec![
+                assignment(1, Some(2), date(1, 1), Some(date(3, 31))),
+                assignment(2, None, date(4, 1), Some(date(4, 30))),
+                assignment(3, Some(3), date(5, 1), None),
+            ],
+            &[],
+        );
+
+        assert_eq!(history.department_on(&employee, date(3, 31)), Some(2));
+        assert_eq!(history.department_on(&employee, date(4, 15)), None);
+        assert_eq!(history.department_on(&employee, date(5, 1)), Some(3));
+        // Before the first assignment the current department applies
+        assert_eq!(
+            history.department_on(&employee, NaiveDate::from_ymd_opt(2024, 12, 31).unwrap()),
+            Some(3)
+        );
+        assert_eq!(
+            EmploymentHistory::default().department_on(&employee, date(2, 1)),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn test_plan_reassignment() {
+        let transferred = vec![
+            assignment(1, Some(2), date(1, 1), Some(date(5, 31))),
+            assignment(2, Some(3), date(6, 1), None),
+        ];
+        assert_eq!(
+            plan_reassignment(&transferred, date(7, 1)),
+            Reassignment::Split {
+                close: Some(2),
+                effective_to: None
+            }
+        );
+        // Dated before the open assignment starts: corrects it
+        assert_eq!(plan_reassignment(&transferred, date(3, 1)), Reassignment::Amend(2));
+        // Within a closed assignment the new one keeps its end
+        let left = vec![assignment(1, Some(2), date(1, 1), Some(date(6, 30)))];
+        assert_eq!(
+            plan_reassignment(&left, date(3, 1)),
+            Reassignment::Split {
+                close: Some(1),
+                effective_to: Some(date(6, 30))
+            }
+        );
+        assert_eq!(
+            plan_reassignment(&[], date(3, 1)),
+            Reassignment::Split {
+                close: None,
+                effective_to: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_plan_reassignment_same_day() {
+        // Second transfer on the day of the first
+        let transferred = vec![
+            assignment(1, Some(2), date(1, 1), Some(date(5, 31))),
+            assignment(2, Some(3), date(6, 1), None),
+        ];
+        assert_eq!(plan_reassignment(&transferred, date(6, 1)), Reassignment::Amend(2));
+
+        // Transfer dated the day an offboarded employee's last assignment started
+        let offboarded = vec![
+            assignment(1, Some(2), date(1, 1), Some(date(5, 31))),
+            assignment(2, Some(3), date(6, 1), Some(date(6, 1))),
+        ];
+        assert_eq!(plan_reassignment(&offboarded, date(6, 1)), Reassignment::Amend(2));
+    }
+
+    #[test]
+    fn test_plan_start_date() {
+        let transferred = vec![
+            assignment(2, Some(3), date(6, 1), None),
+            assignment(1, Some(2), date(1, 1), Some(date(5, 31))),
+        ];
+        assert_eq!(plan_start_date(&transferred, date(1, 1)), Ok(None));
+        assert_eq!(plan_start_date(&transferred, date(2, 1)), Ok(Some(1)));
+        assert_eq!(
+            plan_start_date(&transferred, NaiveDate::from_ymd_opt(2024, 12, 1).unwrap()),
+            Ok(Some(1))
+        );
+        assert!(plan_start_date(&transferred, date(6, 1)).is_err());
+
+        let left = vec![assignment(1, Some(2), date(1, 1), Some(date(3, 31)))];
+        assert!(plan_start_date(&left, date(4, 1)).is_err());
+        assert_eq!(plan_start_date(&[], date(4, 1)), Ok(None));
+    }
+
+    #[test]
+    fn test_plan_rehire() {
+        let offboarded = vec![
+            assignment(1, Some(2), date(1, 1), Some(date(5, 31))),
+            assignment(2, Some(3), date(6, 1), Some(date(6, 30))),
+        ];
+        // Back the next day: the last assignment continues
+        assert_eq!(plan_rehire(&offboarded, date(6, 30), date(7, 1)), Rehire::Reopen(2));
+        // Rehired after a gap: a new assignment from the rehire date
+        assert_eq!(
+            plan_rehire(&offboarded, date(6, 30), date(9, 1)),
+            Rehire::Open(date(9, 1))
+        );
+        assert_eq!(plan_rehire(&[], date(6, 30), date(7, 1)), Rehire::Open(date(7, 1)));
+    }
+
+    #[test]
+    fn test_is_employed_on() {
+        let mut employee = employee();
+        let history = EmploymentHistory::new(
+            vec![
+                assignment(1, Some(2), date(1, 1), Some(date(3, 31))),
+                assignment(2, Some(3), date(6, 1), None),
+            ],
+            &[],
+        );
+        assert!(!history.is_employed_on(&employee, NaiveDate::from_ymd_opt(2024, 12, 31).unwrap()));
+        assert!(history.is_employed_on(&employee, date(3, 31)));
+        // Left at the end of March and rehired in June
+        assert!(!history.is_employed_on(&employee, date(4, 15)));
+        assert!(history.is_employed_on(&employee, date(6, 1)));
+
+        // A deactivated employee without an end date is gone
+        employee.is_active = false;
+        assert!(!history.is_employed_on(&employee, date(6, 1)));
+        // A leaver stays in reports up to the end date
+        employee.end_date = Some(date(3, 31));
+        assert!(history.is_employed_on(&employee, date(3, 31)));
+    }
+}
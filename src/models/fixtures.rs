@@ -0,0 +1,19 @@
+//! Model factories shared by the unit tests.
+
+use chrono::Utc;
+
+use crate::entities::departments;
+
+/// An active department named after its id.
+pub fn department(id: i32, parent_id: Option<i32>) -> departments::Model {
+    let now = Utc::now().fixed_offset();
+    departments::Model {
+        id,
+        name: format!("Dept {id}"),
+        parent_id,
+        display_order: 0,
+        is_active: true,
+        created_at: now,
+        updated_at: now,
+    }
+}
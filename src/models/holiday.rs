@@ -0,0 +1,179 @@
+//! Holiday DTOs, the bundled public holiday list and calendar resolution.
+
+use std::collections::HashMap;
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::entities::{departments, holidays};
+
+/// Vietnamese public holidays bundled with the application (`date,name` lines).
+const VIETNAM_PUBLIC_HOLIDAYS: &str = include_str!("../../database/holidays/vn.csv");
+
+/// DTO for creating a holiday.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateHoliday {
+    pub holiday_date: NaiveDate,
+    pub name: String,
+    pub is_paid: bool,
+    /// Department (and its sub-departments) the day off applies to; `None` for the whole company.
+    pub department_id: Option<i32>,
+}
+
+/// DTO for updating a holiday.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateHoliday {
+    pub holiday_date: Option<NaiveDate>,
+    pub name: Option<String>,
+    pub is_paid: Option<bool>,
+    pub department_id: Option<Option<i32>>,
+}
+
+/// Parse a holiday list of `date,name` lines, skipping blank, `#` comment and
+/// malformed lines.
+pub fn parse_holiday_list(text: &str) -> Vec<(NaiveDate, String)> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (date, name) = line.split_once(',')?;
+            let date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").ok()?;
+            Some((date, name.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Bundled Vietnamese public holidays in `year`, as paid company-wide days off.
+pub fn public_holidays(year: i32) -> Vec<CreateHoliday> {
+    parse_holiday_list(VIETNAM_PUBLIC_HOLIDAYS)
+        .into_iter()
+        .filter(|(date, _)| date.year() == year)
+        .map(|(holiday_date, name)| CreateHoliday {
+            holiday_date,
+            name,
+            is_paid: true,
+            department_id: None,
+        })
+        .collect()
+}
+
+/// Loaded holidays for resolving whether a department has a day off.
+///
+/// A department-scoped holiday also applies to the department's sub-departments.
+#[derive(Debug, Clone, Default)]
+pub struct HolidayCalendar {
+    holidays: HashMap<NaiveDate, Vec<holidays::Model>>,
+    department_parents: HashMap<i32, Option<i32>>,
+}
+
+impl HolidayCalendar {
+    pub fn new(holidays: Vec<holidays::Model>, departments: &[departments::Model]) -> Self {
+        let mut by_date: HashMap<NaiveDate, Vec<holidays::Model>> = HashMap::new();
+        for holiday in holidays {
+            by_date.entry(holiday.holiday_date).or_default().push(holiday);
+        }
+
+        Self {
+            holidays: by_date,
+            department_parents: departments.iter().map(|d| (d.id, d.parent_id)).collect(),
+        }
+    }
+
+    /// Holiday on `date` for an employee of `department_id`, if any.
+    pub fn holiday(&self, department_id: Option<i32>, date: NaiveDate) -> Option<&holidays::Model> {
+        let holidays = self.holidays.get(&date)?;
+        if let Some(holiday) = holidays.iter().find(|h| h.department_id.is_none()) {
+            return Some(holiday);
+        }
+
+        // Walk up the department tree; the visited cap guards against cycles
+        let mut current = department_id;
+        let mut visited = 0;
+        while let Some(dept) = current {
+            if let Some(holiday) = holidays.iter().find(|h| h.department_id == Some(dept)) {
+                return Some(holiday);
+            }
+            visited += 1;
+            if visited > self.department_parents.len() {
+                break;
+            }
+            current = self.department_parents.get(&dept).copied().flatten();
+        }
+        None
+    }
+
+    /// Whether `date` is a day off for an employee of `department_id`.
+    pub fn is_holiday(&self, department_id: Option<i32>, date: NaiveDate) -> bool {
+        self.holiday(department_id, date).is_some()
+    }
+
+    /// Company-wide holiday on `date`, if any.
+    pub fn company_holiday(&self, date: NaiveDate) -> Option<&holidays::Model> {
+        self.holidays.get(&date)?.iter().find(|h| h.department_id.is_none())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::fixtures::department;
+    use chrono::Utc;
+
+    fn date(m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, m, d).unwrap()
+    }
+
+    fn holiday(id: i32, holiday_date: NaiveDate, department_id: Option<i32>) -> holidays::Model {
+        let now = Utc::now().fixed_offset();
+        holidays::Model {
+            id,
+            holiday_date,
+            name: format!("Holiday {id}"),
+            is_paid: true,
+            department_id,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_parse_holiday_list() {
+        let text =
+            "# comment\n\n2026-01-01,New Year's Day\n2026-13-01,Bad date\nno comma\n 2026-04-30 , Reunification Day \n";
+        assert_eq!(
+            parse_holiday_list(text),
+            vec![
+                (date(1, 1), "New Year's Day".to_string()),
+                (date(4, 30), "Reunification Day".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bundled_public_holidays() {
+        let holidays = public_holidays(2026);
+        assert!(holidays.iter().any(|h| h.holiday_date == date(2, 17)));
+        assert!(
+            holidays
+                .iter()
+                .all(|h| h.holiday_date.year() == 2026 && h.department_id.is_none())
+        );
+        assert!(public_holidays(1999).is_empty());
+    }
+
+    #[test]
+    fn test_calendar_department_scope() {
+        // Department 10 (parent of 11) has a company trip on 3 March
+        let calendar = HolidayCalendar::new(
+            vec![holiday(1, date(1, 1), None), holiday(2, date(3, 3), Some(10))],
+            &[department(10, None), department(11, Some(10)), department(12, None)],
+        );
+
+        assert!(calendar.is_holiday(None, date(1, 1)));
+        assert!(calendar.is_holiday(Some(12), date(1, 1)));
+        assert!(calendar.is_holiday(Some(11), date(3, 3)));
+        assert!(!calendar.is_holiday(Some(12), date(3, 3)));
+        assert!(!calendar.is_holiday(None, date(3, 3)));
+        assert!(calendar.company_holiday(date(3, 3)).is_none());
+    }
+}
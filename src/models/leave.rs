@@ -0,0 +1,361 @@
+//! Leave DTOs, leave day counting, balances and approved leave lookup.
+
+use std::collections::HashMap;
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use super::holiday::HolidayCalendar;
+use super::shift::Schedule;
+use crate::entities::{employees, leave_entitlements, leave_requests, leave_types};
+
+/// Leave request status constants matching database CHECK constraint.
+pub mod leave_status {
+    /// Waiting for a decision.
+    pub const PENDING: &str = "pending";
+    /// Approved; the days are excused in attendance.
+    pub const APPROVED: &str = "approved";
+    /// Turned down by the approver.
+    pub const REJECTED: &str = "rejected";
+    /// Withdrawn, before or after approval.
+    pub const CANCELLED: &str = "cancelled";
+
+    /// All statuses, for filter dropdowns.
+    pub const ALL: [&str; 4] = [PENDING, APPROVED, REJECTED, CANCELLED];
+}
+
+/// DTO for creating a leave type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateLeaveType {
+    pub name: String,
+    pub is_paid: bool,
+    /// Yearly entitlement when an employee has none set; `None` tracks no balance.
+    pub default_days: Option<f64>,
+}
+
+/// DTO for updating a leave type.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateLeaveType {
+    pub name: Option<String>,
+    pub is_paid: Option<bool>,
+    pub default_days: Option<Option<f64>>,
+    pub is_active: Option<bool>,
+}
+
+/// DTO for setting an employee's entitlement for a year, replacing any existing one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetLeaveEntitlement {
+    pub employee_id: i32,
+    pub leave_type_id: i32,
+    pub year: i32,
+    pub days: f64,
+}
+
+/// DTO for creating a leave request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateLeaveRequest {
+    pub employee_id: i32,
+    pub leave_type_id: i32,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    /// Half a day of leave; only valid when `start_date == end_date`.
+    pub half_day: bool,
+    pub note: Option<String>,
+}
+
+/// DTO for updating a pending leave request.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateLeaveRequest {
+    pub leave_type_id: Option<i32>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub half_day: Option<bool>,
+    pub note: Option<Option<String>>,
+}
+
+/// Whether an employee would be working on `date` and so uses a leave day.
+///
+/// Holidays are never leave days. Planned days follow the schedule, so a
+/// rostered day off is not one; employees with nothing planned work Monday
+/// to Friday.
+pub fn is_leave_day(
+    schedule: &Schedule,
+    holidays: &HolidayCalendar,
+    employee_id: i32,
+    department_id: Option<i32>,
+    date: NaiveDate,
+) -> bool {
+    !holidays.is_holiday(department_id, date) && schedule.is_working_day(employee_id, department_id, date)
+}
+
+/// Number of leave days a request for `start..=end` takes (see [`is_leave_day`]).
+pub fn count_leave_days(
+    schedule: &Schedule,
+    holidays: &HolidayCalendar,
+    employee_id: i32,
+    department_id: Option<i32>,
+    start: NaiveDate,
+    end: NaiveDate,
+    half_day: bool,
+) -> f64 {
+    let days = start
+        .iter_days()
+        .take_while(|date| *date <= end)
+        .filter(|date| is_leave_day(schedule, holidays, employee_id, department_id, *date))
+        .count() as f64;
+    if half_day { days * 0.5 } else { days }
+}
+
+/// Leave balance of one employee for one leave type in a year.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeaveBalance {
+    pub employee_id: i32,
+    pub leave_type_id: i32,
+    /// Days entitled; `None` when the type tracks no balance.
+    pub entitled: Option<f64>,
+    /// Approved days.
+    pub taken: f64,
+    /// Days awaiting approval.
+    pub pending: f64,
+}
+
+impl LeaveBalance {
+    /// Days left after approved leave, if the type tracks a balance.
+    pub fn remaining(&self) -> Option<f64> {
+        self.entitled.map(|entitled| entitled - self.taken)
+    }
+}
+
+/// Balances of every employee for every active leave type in `year`.
+///
+/// The entitlement is the employee's own for the year, else the type's
+/// default. A request counts towards the year it starts in.
+pub fn leave_balances(
+    employees: &[employees::Model],
+    types: &[leave_types::Model],
+    entitlements: &[leave_entitlements::Model],
+    requests: &[leave_requests::Model],
+    year: i32,
+) -> Vec<LeaveBalance> {
+    let entitled: HashMap<(i32, i32), f64> = entitlements
+        .iter()
+        .filter(|e| e.year == year)
+        .map(|e| ((e.employee_id, e.leave_type_id), e.days))
+        .collect();
+
+    let mut used: HashMap<(i32, i32), (f64, f64)> = HashMap::new();
+    for request in requests.iter().filter(|r| r.start_date.year() == year) {
+        let entry = used.entry((request.employee_id, request.leave_type_id)).or_default();
+        match request.status.as_str() {
+            leave_status::APPROVED => entry.0 += request.days,
+            leave_status::PENDING => entry.1 += request.days,
+            _ => {}
+        }
+    }
+
+    employees
+        .iter()
+        .flat_map(|emp| {
+            types.iter().filter(|t| t.is_active).map(|leave_type| {
+                let key = (emp.id, leave_type.id);
+                let (taken, pending) = used.get(&key).copied().unwrap_or_default();
+                LeaveBalance {
+                    employee_id: emp.id,
+                    leave_type_id: leave_type.id,
+                    entitled: entitled.get(&key).copied().or(leave_type.default_days),
+                    taken,
+                    pending,
+                }
+            })
+        })
+        .collect()
+}
+
+/// Approved leave on one day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeaveDay {
+    pub leave_type: String,
+    pub half_day: bool,
+}
+
+/// Approved leave by employee and date, for excusing days in attendance.
+#[derive(Debug, Clone, Default)]
+pub struct LeaveDays {
+    days: HashMap<(i32, NaiveDate), LeaveDay>,
+}
+
+impl LeaveDays {
+    /// Build from leave requests; only approved requests are kept.
+    pub fn new(requests: &[leave_requests::Model], types: &[leave_types::Model]) -> Self {
+        let type_names: HashMap<i32, &str> = types.iter().map(|t| (t.id, t.name.as_str())).collect();
+
+        let mut days = HashMap::new();
+        for request in requests.iter().filter(|r| r.status == leave_status::APPROVED) {
+            let leave_type = type_names.get(&request.leave_type_id).copied().unwrap_or("Leave");
+            for date in request.start_date.iter_days().take_while(|d| *d <= request.end_date) {
+                days.insert(
+                    (request.employee_id, date),
+                    LeaveDay {
+                        leave_type: leave_type.to_string(),
+                        half_day: request.half_day,
+                    },
+                );
+            }
+        }
+
+        Self { days }
+    }
+
+    /// Approved leave of an employee on `date`, if any.
+    pub fn get(&self, employee_id: i32, date: NaiveDate) -> Option<&LeaveDay> {
+        self.days.get(&(employee_id, date))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::holidays;
+    use chrono::Utc;
+
+    fn date(m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, m, d).unwrap()
+    }
+
+    fn leave_type(id: i32, name: &str, default_days: Option<f64>) -> leave_types::Model {
+        let now = Utc::now().fixed_offset();
+        leave_types::Model {
+            id,
+            name: name.to_string(),
+            is_paid: true,
+            default_days,
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn request(
+        id: i32,
+        leave_type_id: i32,
+        start: NaiveDate,
+        end: NaiveDate,
+        days: f64,
+        status: &str,
+    ) -> leave_requests::Model {
+        let now = Utc::now().fixed_offset();
+        leave_requests::Model {
+            id,
+            employee_id: 1,
+            leave_type_id,
+            start_date: start,
+            end_date: end,
+            half_day: false,
+            days,
+            status: status.to_string(),
+            approver: None,
+            note: None,
+            decided_at: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn employee(id: i32) -> employees::Model {
+        let now = Utc::now().fixed_offset();
+        employees::Model {
+            id,
+            employee_code: format!("E{id}"),
+            full_name: format!("Employee {id}"),
+            department_id: None,
+            scanner_uid: None,
+            gender: None,
+            birth_date: None,
+            start_date: date(1, 1),
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+            position: None,
+            end_date: None,
+            termination_reason: None,
+        }
+    }
+
+    #[test]
+    fn test_count_leave_days() {
+        let now = Utc::now().fixed_offset();
+        let holidays = HolidayCalendar::new(
+            vec![holidays::Model {
+                id: 1,
+                holiday_date: date(9, 2),
+                name: "National Day".to_string(),
+                is_paid: true,
+                department_id: None,
+                created_at: now,
+                updated_at: now,
+            }],
+            &[],
+        );
+        let schedule = Schedule::default();
+
+        // Fri 29 Aug to Wed 3 Sep: weekend and National Day are not leave days
+        assert_eq!(
+            count_leave_days(&schedule, &holidays, 1, None, date(8, 29), date(9, 3), false),
+            3.0
+        );
+        assert_eq!(
+            count_leave_days(&schedule, &holidays, 1, None, date(9, 3), date(9, 3), true),
+            0.5
+        );
+        assert_eq!(
+            count_leave_days(&schedule, &holidays, 1, None, date(9, 6), date(9, 7), false),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_leave_balances() {
+        let types = [leave_type(1, "Annual", Some(12.0)), leave_type(2, "Sick", None)];
+        let now = Utc::now().fixed_offset();
+        let entitlements = [leave_entitlements::Model {
+            id: 1,
+            employee_id: 2,
+            leave_type_id: 1,
+            year: 2025,
+            days: 14.0,
+            created_at: now,
+            updated_at: now,
+        }];
+        let requests = [
+            request(1, 1, date(3, 3), date(3, 5), 3.0, leave_status::APPROVED),
+            request(2, 1, date(4, 1), date(4, 1), 1.0, leave_status::PENDING),
+            request(3, 1, date(5, 5), date(5, 6), 2.0, leave_status::REJECTED),
+            request(4, 2, date(6, 2), date(6, 2), 1.0, leave_status::APPROVED),
+        ];
+
+        let balances = leave_balances(&[employee(1), employee(2)], &types, &entitlements, &requests, 2025);
+        assert_eq!(balances.len(), 4);
+
+        let annual = &balances[0];
+        assert_eq!((annual.entitled, annual.taken, annual.pending), (Some(12.0), 3.0, 1.0));
+        assert_eq!(annual.remaining(), Some(9.0));
+        assert_eq!(balances[1].remaining(), None);
+        assert_eq!(balances[1].taken, 1.0);
+        assert_eq!(balances[2].remaining(), Some(14.0));
+    }
+
+    #[test]
+    fn test_leave_days_approved_only() {
+        let types = [leave_type(1, "Annual", Some(12.0))];
+        let requests = [
+            request(1, 1, date(3, 3), date(3, 4), 2.0, leave_status::APPROVED),
+            request(2, 1, date(3, 10), date(3, 10), 1.0, leave_status::PENDING),
+        ];
+        let leave = LeaveDays::new(&requests, &types);
+
+        assert_eq!(leave.get(1, date(3, 4)).map(|d| d.leave_type.as_str()), Some("Annual"));
+        assert!(leave.get(1, date(3, 5)).is_none());
+        assert!(leave.get(1, date(3, 10)).is_none());
+        assert!(leave.get(2, date(3, 3)).is_none());
+    }
+}
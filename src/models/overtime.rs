@@ -0,0 +1,392 @@
+//! Overtime rules and per-day overtime calculation.
+//!
+//! Rules are shared settings stored in the database (see
+//! [`crate::db::settings::overtime_rules`]), so every workstation computes
+//! overtime the same way.
+
+use std::collections::HashMap;
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use serde::{Deserialize, Serialize};
+
+use super::attendance::DailyAttendance;
+use super::holiday::HolidayCalendar;
+use super::shift::Schedule;
+use crate::timezone;
+
+/// Shared overtime rules.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OvertimeRules {
+    /// Minutes worked on a working day before overtime starts.
+    pub daily_threshold_minutes: i64,
+    pub weekday_multiplier: f64,
+    pub weekend_multiplier: f64,
+    pub holiday_multiplier: f64,
+    /// Night window (business wall-clock time); equal times disable it.
+    pub night_start: NaiveTime,
+    pub night_end: NaiveTime,
+    /// Extra pay per overtime hour inside the night window, as a fraction of an hour.
+    pub night_premium: f64,
+    /// Overtime shorter than this is not counted.
+    pub min_block_minutes: i64,
+    /// Overtime is rounded down to a multiple of this (0 disables).
+    pub rounding_minutes: i64,
+}
+
+impl Default for OvertimeRules {
+    fn default() -> Self {
+        Self {
+            daily_threshold_minutes: 8 * 60,
+            weekday_multiplier: 1.5,
+            weekend_multiplier: 2.0,
+            holiday_multiplier: 3.0,
+            night_start: NaiveTime::from_hms_opt(22, 0, 0).unwrap_or(NaiveTime::MIN),
+            night_end: NaiveTime::from_hms_opt(6, 0, 0).unwrap_or(NaiveTime::MIN),
+            night_premium: 0.3,
+            min_block_minutes: 30,
+            rounding_minutes: 15,
+        }
+    }
+}
+
+impl OvertimeRules {
+    /// Check the rules are usable.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0..=24 * 60).contains(&self.daily_threshold_minutes) {
+            return Err("Overtime threshold must be between 0 and 24 hours".to_string());
+        }
+        let rates = [
+            self.weekday_multiplier,
+            self.weekend_multiplier,
+            self.holiday_multiplier,
+            self.night_premium,
+        ];
+        if rates.iter().any(|rate| !rate.is_finite() || *rate < 0.0) {
+            return Err("Overtime multipliers and night premium cannot be negative".to_string());
+        }
+        if !(0..=480).contains(&self.min_block_minutes) {
+            return Err("Overtime minimum block must be between 0 and 480 minutes".to_string());
+        }
+        if !(0..=60).contains(&self.rounding_minutes) {
+            return Err("Overtime rounding cannot exceed 60 minutes".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Kind of day, which decides the threshold and multiplier.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OvertimeDay {
+    /// Scheduled working day: overtime after the daily threshold.
+    #[default]
+    Workday,
+    /// Weekend or scheduled day off: all time worked is overtime.
+    Weekend,
+    /// Public or company holiday: all time worked is overtime.
+    Holiday,
+}
+
+impl OvertimeDay {
+    pub fn label(&self) -> &'static str {
+        match self {
+            OvertimeDay::Workday => "Workday",
+            OvertimeDay::Weekend => "Weekend",
+            OvertimeDay::Holiday => "Holiday",
+        }
+    }
+}
+
+/// Overtime of one employee-day.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Overtime {
+    pub day: OvertimeDay,
+    /// Overtime minutes after the minimum block and rounding.
+    pub minutes: i64,
+    /// Part of `minutes` inside the night window.
+    pub night_minutes: i64,
+    pub multiplier: f64,
+    /// Hours to pay: overtime times the multiplier, plus the night premium.
+    pub paid_hours: f64,
+}
+
+impl Overtime {
+    pub fn hours(&self) -> f64 {
+        self.minutes as f64 / 60.0
+    }
+
+    pub fn night_hours(&self) -> f64 {
+        self.night_minutes as f64 / 60.0
+    }
+}
+
+/// Compute overtime from a day's complete sessions in business wall-clock time.
+///
+/// `unpunched_break` is the scheduled break when the employee did not punch
+/// out for it; it is taken off the worked time before overtime starts. On a
+/// working day the first `daily_threshold_minutes` are regular time and the
+/// rest, the latest minutes of the day, is overtime.
+pub fn calculate(
+    rules: &OvertimeRules,
+    day: OvertimeDay,
+    sessions: &[(NaiveDateTime, NaiveDateTime)],
+    unpunched_break: i64,
+) -> Overtime {
+    let (multiplier, regular) = match day {
+        OvertimeDay::Workday => (rules.weekday_multiplier, rules.daily_threshold_minutes),
+        OvertimeDay::Weekend => (rules.weekend_multiplier, 0),
+        OvertimeDay::Holiday => (rules.holiday_multiplier, 0),
+    };
+    let mut skip = regular + unpunched_break.max(0);
+
+    let mut minutes = 0;
+    let mut night_minutes = 0;
+    for &(start, end) in sessions {
+        let length = (end - start).num_minutes().max(0);
+        if length <= skip {
+            skip -= length;
+            continue;
+        }
+        let overtime_start = start + TimeDelta::minutes(skip);
+        skip = 0;
+        minutes += (end - overtime_start).num_minutes();
+        night_minutes += night_overlap(rules, overtime_start, end);
+    }
+
+    if minutes < rules.min_block_minutes.max(1) {
+        return Overtime {
+            day,
+            multiplier,
+            ..Overtime::default()
+        };
+    }
+    let minutes = round_down(minutes, rules.rounding_minutes);
+    let night_minutes = round_down(night_minutes, rules.rounding_minutes).min(minutes);
+
+    Overtime {
+        day,
+        minutes,
+        night_minutes,
+        multiplier,
+        paid_hours: (minutes as f64 * multiplier + night_minutes as f64 * rules.night_premium) / 60.0,
+    }
+}
+
+/// Overtime of a summary row against the schedule and holiday calendar.
+///
+/// The unpunched scheduled break is deducted as in
+/// [`DailyAttendance::unpunched_break_minutes`].
+pub fn day_overtime(
+    rules: &OvertimeRules,
+    schedule: &Schedule,
+    holidays: &HolidayCalendar,
+    record: &DailyAttendance,
+) -> Overtime {
+    let (employee_id, department_id, date) = (record.employee_id, record.department_id, record.work_date);
+    let day = if holidays.is_holiday(department_id, date) {
+        OvertimeDay::Holiday
+    } else if schedule.is_working_day(employee_id, department_id, date) {
+        OvertimeDay::Workday
+    } else {
+        OvertimeDay::Weekend
+    };
+
+    let sessions: Vec<_> = record
+        .sessions
+        .iter()
+        .filter_map(|s| {
+            let check_in = timezone::to_business(&s.check_in?).naive_local();
+            let check_out = timezone::to_business(&s.check_out?).naive_local();
+            Some((check_in, check_out))
+        })
+        .collect();
+    calculate(rules, day, &sessions, record.unpunched_break_minutes(schedule))
+}
+
+/// Minutes of `start..end` inside the night window, which may cross midnight.
+fn night_overlap(rules: &OvertimeRules, start: NaiveDateTime, end: NaiveDateTime) -> i64 {
+    if rules.night_start == rules.night_end || end <= start {
+        return 0;
+    }
+
+    let first = start.date().pred_opt().unwrap_or(start.date());
+    first
+        .iter_days()
+        .take_while(|date| *date <= end.date())
+        .map(|date| {
+            let (window_start, window_end) = night_window(rules, date);
+            (end.min(window_end) - start.max(window_start)).num_minutes().max(0)
+        })
+        .sum()
+}
+
+/// Night window starting on `date`.
+fn night_window(rules: &OvertimeRules, date: NaiveDate) -> (NaiveDateTime, NaiveDateTime) {
+    let end_date = if rules.night_end <= rules.night_start {
+        date.succ_opt().unwrap_or(date)
+    } else {
+        date
+    };
+    (date.and_time(rules.night_start), end_date.and_time(rules.night_end))
+}
+
+fn round_down(minutes: i64, step: i64) -> i64 {
+    if step > 0 { minutes - minutes % step } else { minutes }
+}
+
+/// Overtime totals of one employee for one month.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OvertimeTotals {
+    pub employee_id: i32,
+    pub employee_code: String,
+    pub full_name: String,
+    pub department_name: Option<String>,
+    /// First day of the month.
+    pub month: NaiveDate,
+    pub days: u32,
+    pub workday_minutes: i64,
+    pub weekend_minutes: i64,
+    pub holiday_minutes: i64,
+    pub night_minutes: i64,
+    pub paid_hours: f64,
+}
+
+impl OvertimeTotals {
+    pub fn minutes(&self) -> i64 {
+        self.workday_minutes + self.weekend_minutes + self.holiday_minutes
+    }
+}
+
+/// Sum overtime per employee and month, ordered by month then employee code.
+///
+/// Days without overtime are skipped.
+pub fn monthly_totals(data: &[DailyAttendance]) -> Vec<OvertimeTotals> {
+    let mut totals: HashMap<(i32, NaiveDate), OvertimeTotals> = HashMap::new();
+    for record in data.iter().filter(|r| r.overtime.minutes > 0) {
+        let month = record.work_date.with_day(1).unwrap_or(record.work_date);
+        let total = totals
+            .entry((record.employee_id, month))
+            .or_insert_with(|| OvertimeTotals {
+                employee_id: record.employee_id,
+                employee_code: record.employee_code.clone(),
+                full_name: record.full_name.clone(),
+                department_name: record.department_name.clone(),
+                month,
+                ..OvertimeTotals::default()
+            });
+
+        let overtime = &record.overtime;
+        total.days += 1;
+        match overtime.day {
+            OvertimeDay::Workday => total.workday_minutes += overtime.minutes,
+            OvertimeDay::Weekend => total.weekend_minutes += overtime.minutes,
+            OvertimeDay::Holiday => total.holiday_minutes += overtime.minutes,
+        }
+        total.night_minutes += overtime.night_minutes;
+        total.paid_hours += overtime.paid_hours;
+    }
+
+    let mut totals: Vec<_> = totals.into_values().collect();
+    totals.sort_by(|a, b| {
+        a.month
+            .cmp(&b.month)
+            .then_with(|| a.employee_code.cmp(&b.employee_code))
+    });
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 11, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_workday_threshold_and_unpunched_break() {
+        let rules = OvertimeRules::default();
+
+        // 08:00-17:00 with an unpunched hour of break is exactly 8 hours
+        let ot = calculate(&rules, OvertimeDay::Workday, &[(at(10, 8, 0), at(10, 17, 0))], 60);
+        assert_eq!(ot.minutes, 0);
+
+        // Leaving at 19:10 is 2h10 over, rounded down to 2h00
+        let ot = calculate(&rules, OvertimeDay::Workday, &[(at(10, 8, 0), at(10, 19, 10))], 60);
+        assert_eq!((ot.minutes, ot.night_minutes), (120, 0));
+        assert_eq!(ot.paid_hours, 3.0);
+
+        // Punched break: two sessions of 4h and 5h40
+        let sessions = [(at(10, 8, 0), at(10, 12, 0)), (at(10, 13, 0), at(10, 18, 40))];
+        let ot = calculate(&rules, OvertimeDay::Workday, &sessions, 0);
+        assert_eq!(ot.minutes, 90);
+    }
+
+    #[test]
+    fn test_minimum_block() {
+        let rules = OvertimeRules::default();
+
+        // 25 minutes over is below the 30 minute block
+        let ot = calculate(&rules, OvertimeDay::Workday, &[(at(10, 8, 0), at(10, 16, 25))], 0);
+        assert_eq!(
+            ot,
+            Overtime {
+                multiplier: 1.5,
+                ..Overtime::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_weekend_and_holiday_count_all_time() {
+        let rules = OvertimeRules::default();
+        let sessions = [(at(15, 8, 0), at(15, 12, 0))];
+
+        let ot = calculate(&rules, OvertimeDay::Weekend, &sessions, 0);
+        assert_eq!((ot.minutes, ot.paid_hours), (240, 8.0));
+
+        let ot = calculate(&rules, OvertimeDay::Holiday, &sessions, 0);
+        assert_eq!((ot.minutes, ot.paid_hours), (240, 12.0));
+    }
+
+    #[test]
+    fn test_night_window_across_midnight() {
+        let rules = OvertimeRules::default();
+
+        // Night shift 18:00-04:00: overtime from 02:00, all of it at night
+        let ot = calculate(&rules, OvertimeDay::Workday, &[(at(10, 18, 0), at(11, 4, 0))], 0);
+        assert_eq!((ot.minutes, ot.night_minutes), (120, 120));
+        assert!((ot.paid_hours - (3.0 + 0.6)).abs() < 1e-9);
+
+        // 14:00-23:30: overtime 22:00-23:30, all of it at night
+        let ot = calculate(&rules, OvertimeDay::Workday, &[(at(10, 14, 0), at(10, 23, 30))], 0);
+        assert_eq!((ot.minutes, ot.night_minutes), (90, 90));
+
+        // Equal start and end disable the night window
+        let rules = OvertimeRules {
+            night_end: rules.night_start,
+            ..rules
+        };
+        let ot = calculate(&rules, OvertimeDay::Weekend, &[(at(10, 20, 0), at(11, 2, 0))], 0);
+        assert_eq!((ot.minutes, ot.night_minutes), (360, 0));
+    }
+
+    #[test]
+    fn test_validate_rules() {
+        assert!(OvertimeRules::default().validate().is_ok());
+
+        let rules = OvertimeRules {
+            weekend_multiplier: -1.0,
+            ..OvertimeRules::default()
+        };
+        assert!(rules.validate().is_err());
+
+        let rules = OvertimeRules {
+            rounding_minutes: 90,
+            ..OvertimeRules::default()
+        };
+        assert!(rules.validate().is_err());
+    }
+}
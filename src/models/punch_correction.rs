@@ -0,0 +1,41 @@
+//! Manual punch entry and correction DTOs.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Correction action constants matching database CHECK constraint.
+pub mod correction_action {
+    /// A forgotten punch added by hand.
+    pub const ADD: &str = "add";
+    /// A punch replaced by a manual one with another time or state.
+    pub const EDIT: &str = "edit";
+    /// A punch voided without replacement.
+    pub const VOID: &str = "void";
+}
+
+/// DTO for adding a forgotten punch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddPunch {
+    pub employee_id: i32,
+    pub check_time: DateTime<Utc>,
+    /// Punch state code (see `punch_state`).
+    pub status: i32,
+    pub reason: String,
+    pub acted_by: String,
+}
+
+/// DTO for correcting a punch's time or state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorrectPunch {
+    pub check_time: DateTime<Utc>,
+    pub status: i32,
+    pub reason: String,
+    pub acted_by: String,
+}
+
+/// DTO for voiding a punch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoidPunch {
+    pub reason: String,
+    pub acted_by: String,
+}
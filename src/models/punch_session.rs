@@ -0,0 +1,202 @@
+//! Pairing a work day's punches into in/out sessions.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::attendance::punch_state;
+use crate::timezone;
+
+/// One punch of a work day.
+#[derive(Debug, Clone, Copy)]
+pub struct Punch {
+    pub id: i64,
+    pub check_time: DateTime<Utc>,
+    /// Punch state code (see [`punch_state`]).
+    pub state: i32,
+}
+
+/// An in/out interval. One side is `None` when a punch is missing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PunchSession {
+    pub check_in: Option<DateTime<Utc>>,
+    pub check_out: Option<DateTime<Utc>>,
+}
+
+impl PunchSession {
+    /// Whether both punches are present.
+    pub fn is_complete(&self) -> bool {
+        self.check_in.is_some() && self.check_out.is_some()
+    }
+
+    /// Worked minutes, if both punches are present.
+    pub fn minutes(&self) -> Option<i64> {
+        Some((self.check_out? - self.check_in?).num_minutes())
+    }
+}
+
+/// Role of a punch within its work day's sessions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PunchRole {
+    In,
+    Out,
+    /// Check-in with no matching check-out.
+    InMissingOut,
+    /// Check-out with no matching check-in.
+    OutMissingIn,
+}
+
+impl PunchRole {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PunchRole::In => "In",
+            PunchRole::Out => "Out",
+            PunchRole::InMissingOut => "In (missing out)",
+            PunchRole::OutMissingIn => "Out (missing in)",
+        }
+    }
+
+    pub fn is_missing(&self) -> bool {
+        matches!(self, PunchRole::InMissingOut | PunchRole::OutMissingIn)
+    }
+}
+
+/// Pair punches ordered by time into in/out couples.
+///
+/// When any punch carries an out state the device states decide direction, so
+/// two check-ins in a row leave the first without a check-out. Otherwise (no
+/// state keys on the device) punches alternate in, out, in, out.
+fn pair(punches: &[Punch]) -> Vec<(Option<&Punch>, Option<&Punch>)> {
+    let use_states = punches.iter().any(|p| punch_state::is_out(p.state));
+
+    let mut pairs = Vec::new();
+    let mut open: Option<&Punch> = None;
+    for punch in punches {
+        let is_out = if use_states {
+            punch_state::is_out(punch.state)
+        } else {
+            open.is_some()
+        };
+
+        if is_out {
+            pairs.push((open.take(), Some(punch)));
+        } else if let Some(previous) = open.replace(punch) {
+            pairs.push((Some(previous), None));
+        }
+    }
+    if let Some(last) = open {
+        pairs.push((Some(last), None));
+    }
+
+    pairs
+}
+
+/// Turn a work day's punches, ordered by time, into in/out sessions.
+pub fn pair_punches(punches: &[Punch]) -> Vec<PunchSession> {
+    pair(punches)
+        .into_iter()
+        .map(|(check_in, check_out)| PunchSession {
+            check_in: check_in.map(|p| p.check_time),
+            check_out: check_out.map(|p| p.check_time),
+        })
+        .collect()
+}
+
+/// Role of each punch by ID after pairing a work day's punches.
+pub fn punch_roles(punches: &[Punch]) -> Vec<(i64, PunchRole)> {
+    pair(punches)
+        .into_iter()
+        .flat_map(|pair| match pair {
+            (Some(check_in), Some(check_out)) => vec![(check_in.id, PunchRole::In), (check_out.id, PunchRole::Out)],
+            (Some(check_in), None) => vec![(check_in.id, PunchRole::InMissingOut)],
+            (None, Some(check_out)) => vec![(check_out.id, PunchRole::OutMissingIn)],
+            (None, None) => Vec::new(),
+        })
+        .collect()
+}
+
+/// Total minutes of the complete sessions.
+pub fn worked_minutes(sessions: &[PunchSession]) -> i64 {
+    sessions.iter().filter_map(PunchSession::minutes).sum()
+}
+
+/// Sessions as business wall-clock ranges, e.g. "08:00-12:00, 13:00-?".
+pub fn format_sessions(sessions: &[PunchSession]) -> String {
+    let time = |t: Option<DateTime<Utc>>| {
+        t.map_or_else(
+            || "?".to_string(),
+            |t| timezone::to_business(&t).format("%H:%M").to_string(),
+        )
+    };
+    sessions
+        .iter()
+        .map(|s| format!("{}-{}", time(s.check_in), time(s.check_out)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn punch(id: i64, hour: u32, minute: u32, state: i32) -> Punch {
+        Punch {
+            id,
+            check_time: Utc.with_ymd_and_hms(2025, 11, 10, hour, minute, 0).unwrap(),
+            state,
+        }
+    }
+
+    #[test]
+    fn test_pair_by_alternation() {
+        // Lunch break out of the building is not worked time
+        let punches = [
+            punch(1, 1, 0, 0),
+            punch(2, 5, 0, 0),
+            punch(3, 6, 0, 0),
+            punch(4, 10, 0, 0),
+        ];
+        let sessions = pair_punches(&punches);
+        assert_eq!(sessions.len(), 2);
+        assert!(sessions.iter().all(PunchSession::is_complete));
+        assert_eq!(worked_minutes(&sessions), 8 * 60);
+
+        // Odd count leaves the last check-in open
+        let sessions = pair_punches(&punches[..3]);
+        assert_eq!(sessions.len(), 2);
+        assert!(!sessions[1].is_complete());
+        assert_eq!(worked_minutes(&sessions), 4 * 60);
+        assert_eq!(punch_roles(&punches[..3])[2], (3, PunchRole::InMissingOut));
+    }
+
+    #[test]
+    fn test_pair_by_state() {
+        use punch_state::*;
+
+        // Forgot to punch in after the break
+        let punches = [
+            punch(1, 1, 0, CHECK_IN),
+            punch(2, 5, 0, BREAK_OUT),
+            punch(3, 10, 0, CHECK_OUT),
+        ];
+        let sessions = pair_punches(&punches);
+        assert_eq!(
+            sessions,
+            vec![
+                PunchSession {
+                    check_in: Some(punches[0].check_time),
+                    check_out: Some(punches[1].check_time),
+                },
+                PunchSession {
+                    check_in: None,
+                    check_out: Some(punches[2].check_time),
+                },
+            ]
+        );
+        assert_eq!(worked_minutes(&sessions), 4 * 60);
+        assert_eq!(
+            punch_roles(&punches),
+            vec![(1, PunchRole::In), (2, PunchRole::Out), (3, PunchRole::OutMissingIn)]
+        );
+    }
+}
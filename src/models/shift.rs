@@ -0,0 +1,436 @@
+//! Shift DTOs and schedule resolution.
+
+use std::collections::HashMap;
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use serde::{Deserialize, Serialize};
+
+use crate::entities::{departments, shift_assignments, shift_rosters, shifts};
+
+/// DTO for creating a shift.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateShift {
+    pub name: String,
+    pub start_time: NaiveTime,
+    pub end_time: NaiveTime,
+    pub break_minutes: i32,
+    pub late_grace_minutes: i32,
+    pub early_grace_minutes: i32,
+}
+
+/// DTO for updating a shift.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateShift {
+    pub name: Option<String>,
+    pub start_time: Option<NaiveTime>,
+    pub end_time: Option<NaiveTime>,
+    pub break_minutes: Option<i32>,
+    pub late_grace_minutes: Option<i32>,
+    pub early_grace_minutes: Option<i32>,
+    pub is_active: Option<bool>,
+}
+
+/// A rotation pattern with the shift for each day of its cycle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShiftPattern {
+    pub id: i32,
+    pub name: String,
+    /// Shift ID per cycle day (`None` = day off); length is the cycle length.
+    pub days: Vec<Option<i32>>,
+}
+
+impl ShiftPattern {
+    /// Shift worked on `date` when the pattern starts (day 0) on `anchor`.
+    pub fn shift_on(&self, anchor: NaiveDate, date: NaiveDate) -> Option<i32> {
+        let offset = (date - anchor).num_days();
+        if offset < 0 || self.days.is_empty() {
+            return None;
+        }
+        self.days[offset as usize % self.days.len()]
+    }
+}
+
+/// DTO for creating or replacing a pattern.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveShiftPattern {
+    pub name: String,
+    /// Shift ID per cycle day (`None` = day off); length is the cycle length.
+    pub days: Vec<Option<i32>>,
+}
+
+/// DTO for assigning a pattern to an employee or a department.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateShiftAssignment {
+    /// Exactly one of `employee_id` and `department_id` is set.
+    pub employee_id: Option<i32>,
+    pub department_id: Option<i32>,
+    pub pattern_id: i32,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+}
+
+/// DTO for setting one roster day.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetRosterEntry {
+    pub employee_id: i32,
+    pub work_date: NaiveDate,
+    /// Shift worked (`None` = day off).
+    pub shift_id: Option<i32>,
+    pub note: Option<String>,
+}
+
+/// Whether a shift with these times ends on the following day.
+pub fn crosses_midnight(start: NaiveTime, end: NaiveTime) -> bool {
+    end <= start
+}
+
+/// Start and end of a shift starting on `date`, in business wall-clock time.
+pub fn shift_window(shift: &shifts::Model, date: NaiveDate) -> (NaiveDateTime, NaiveDateTime) {
+    let start = date.and_time(shift.start_time);
+    let end_date = if shift.crosses_midnight {
+        date + TimeDelta::days(1)
+    } else {
+        date
+    };
+    (start, end_date.and_time(shift.end_time))
+}
+
+/// Scheduled working minutes of a shift, excluding the break.
+pub fn scheduled_minutes(shift: &shifts::Model) -> i64 {
+    let (start, end) = shift_window(shift, NaiveDate::MIN);
+    ((end - start).num_minutes() - i64::from(shift.break_minutes)).max(0)
+}
+
+/// Work day a punch at `local` (business wall-clock time) belongs to.
+///
+/// `previous_shift` is the shift scheduled the day before. If it crosses
+/// midnight, punches up to the middle of the gap between its end and the next
+/// start belong to that day (before 14:00 for a 22:00-06:00 shift). Otherwise
+/// punches before `day_start` belong to the previous day. Mirrors
+/// `system.fn_work_date` used by `v_daily_attendance`.
+pub fn work_date(local: NaiveDateTime, previous_shift: Option<&shifts::Model>, day_start: NaiveTime) -> NaiveDate {
+    let date = local.date();
+    let previous = date.pred_opt().unwrap_or(date);
+
+    let cutoff = match previous_shift.filter(|s| s.crosses_midnight) {
+        Some(shift) => {
+            let gap = shift.start_time - shift.end_time;
+            shift.end_time + gap / 2
+        }
+        None => day_start,
+    };
+    if local.time() < cutoff { previous } else { date }
+}
+
+/// Where a scheduled shift comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduleSource {
+    /// Per-day roster entry.
+    Roster,
+    /// Pattern assigned to the employee.
+    Employee,
+    /// Pattern assigned to the employee's department or a parent department.
+    Department,
+}
+
+/// Loaded shifts, patterns, assignments and roster entries for resolving who
+/// works which shift on which day.
+///
+/// Precedence: roster entry, then the employee's assignment, then the nearest
+/// department assignment walking up the department tree. Among overlapping
+/// assignments for the same target, the one starting latest wins.
+#[derive(Debug, Clone, Default)]
+pub struct Schedule {
+    shifts: HashMap<i32, shifts::Model>,
+    patterns: HashMap<i32, ShiftPattern>,
+    assignments: Vec<shift_assignments::Model>,
+    rosters: HashMap<(i32, NaiveDate), Option<i32>>,
+    department_parents: HashMap<i32, Option<i32>>,
+}
+
+impl Schedule {
+    pub fn new(
+        shifts: Vec<shifts::Model>,
+        patterns: Vec<ShiftPattern>,
+        mut assignments: Vec<shift_assignments::Model>,
+        rosters: Vec<shift_rosters::Model>,
+        departments: &[departments::Model],
+    ) -> Self {
+        // Latest start first so the first match wins
+        assignments.sort_by(|a, b| b.start_date.cmp(&a.start_date).then(b.id.cmp(&a.id)));
+
+        Self {
+            shifts: shifts.into_iter().map(|s| (s.id, s)).collect(),
+            patterns: patterns.into_iter().map(|p| (p.id, p)).collect(),
+            assignments,
+            rosters: rosters
+                .into_iter()
+                .map(|r| ((r.employee_id, r.work_date), r.shift_id))
+                .collect(),
+            department_parents: departments.iter().map(|d| (d.id, d.parent_id)).collect(),
+        }
+    }
+
+    /// Shift by ID.
+    pub fn shift(&self, id: i32) -> Option<&shifts::Model> {
+        self.shifts.get(&id)
+    }
+
+    /// Roster entry for a day: `Some(None)` is a rostered day off.
+    pub fn roster_entry(&self, employee_id: i32, date: NaiveDate) -> Option<Option<i32>> {
+        self.rosters.get(&(employee_id, date)).copied()
+    }
+
+    /// Shift an employee works starting on `date`, with its source.
+    pub fn shift_for(
+        &self,
+        employee_id: i32,
+        department_id: Option<i32>,
+        date: NaiveDate,
+    ) -> Option<(&shifts::Model, ScheduleSource)> {
+        if let Some(entry) = self.roster_entry(employee_id, date) {
+            return entry
+                .and_then(|id| self.shift(id))
+                .map(|shift| (shift, ScheduleSource::Roster));
+        }
+        self.planned_shift(employee_id, department_id, date)
+    }
+
+    /// Shift from assignments alone, ignoring roster entries.
+    pub fn planned_shift(
+        &self,
+        employee_id: i32,
+        department_id: Option<i32>,
+        date: NaiveDate,
+    ) -> Option<(&shifts::Model, ScheduleSource)> {
+        let (assignment, source) = self.assignment(employee_id, department_id, date)?;
+        self.pattern_shift(assignment, date).map(|s| (s, source))
+    }
+
+    /// Whether a roster entry or an assignment covers `date`, even as a day off.
+    ///
+    /// `false` means nothing is planned for the employee, as opposed to a
+    /// planned day off.
+    pub fn is_planned(&self, employee_id: i32, department_id: Option<i32>, date: NaiveDate) -> bool {
+        self.roster_entry(employee_id, date).is_some() || self.assignment(employee_id, department_id, date).is_some()
+    }
+
+    /// Whether the employee is due to work on `date`, holidays aside.
+    ///
+    /// Planned days follow the schedule; with nothing planned the employee
+    /// works Monday to Friday.
+    pub fn is_working_day(&self, employee_id: i32, department_id: Option<i32>, date: NaiveDate) -> bool {
+        if self.is_planned(employee_id, department_id, date) {
+            self.shift_for(employee_id, department_id, date).is_some()
+        } else {
+            date.weekday().number_from_monday() <= 5
+        }
+    }
+
+    /// Work day a punch at `local` belongs to; see [`work_date`].
+    pub fn work_date(
+        &self,
+        employee_id: i32,
+        department_id: Option<i32>,
+        local: NaiveDateTime,
+        day_start: NaiveTime,
+    ) -> NaiveDate {
+        let previous = local.date().pred_opt().unwrap_or(local.date());
+        let shift = self.shift_for(employee_id, department_id, previous).map(|(s, _)| s);
+        work_date(local, shift, day_start)
+    }
+
+    /// Assignment in effect for an employee on `date`, with its source.
+    fn assignment(
+        &self,
+        employee_id: i32,
+        department_id: Option<i32>,
+        date: NaiveDate,
+    ) -> Option<(&shift_assignments::Model, ScheduleSource)> {
+        if let Some(assignment) = self.assignment_for(|a| a.employee_id == Some(employee_id), date) {
+            return Some((assignment, ScheduleSource::Employee));
+        }
+
+        // Walk up the department tree; the visited cap guards against cycles
+        let mut current = department_id;
+        let mut visited = 0;
+        while let Some(dept) = current {
+            if let Some(assignment) = self.assignment_for(|a| a.department_id == Some(dept), date) {
+                return Some((assignment, ScheduleSource::Department));
+            }
+            visited += 1;
+            if visited > self.department_parents.len() {
+                break;
+            }
+            current = self.department_parents.get(&dept).copied().flatten();
+        }
+        None
+    }
+
+    fn assignment_for(
+        &self,
+        target: impl Fn(&shift_assignments::Model) -> bool,
+        date: NaiveDate,
+    ) -> Option<&shift_assignments::Model> {
+        self.assignments
+            .iter()
+            .find(|a| target(a) && a.start_date <= date && a.end_date.is_none_or(|end| date <= end))
+    }
+
+    fn pattern_shift(&self, assignment: &shift_assignments::Model, date: NaiveDate) -> Option<&shifts::Model> {
+        self.patterns
+            .get(&assignment.pattern_id)?
+            .shift_on(assignment.start_date, date)
+            .and_then(|id| self.shift(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::fixtures::department;
+    use chrono::Utc;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    fn shift(id: i32, start: NaiveTime, end: NaiveTime, break_minutes: i32) -> shifts::Model {
+        let now = Utc::now().fixed_offset();
+        shifts::Model {
+            id,
+            name: format!("Shift {id}"),
+            start_time: start,
+            end_time: end,
+            break_minutes,
+            late_grace_minutes: 0,
+            early_grace_minutes: 0,
+            crosses_midnight: crosses_midnight(start, end),
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn assignment(
+        id: i32,
+        employee_id: Option<i32>,
+        department_id: Option<i32>,
+        start: NaiveDate,
+        end: Option<NaiveDate>,
+    ) -> shift_assignments::Model {
+        let now = Utc::now().fixed_offset();
+        shift_assignments::Model {
+            id,
+            employee_id,
+            department_id,
+            pattern_id: if employee_id.is_some() { 2 } else { 1 },
+            start_date: start,
+            end_date: end,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_shift_window_and_minutes() {
+        let day = shift(1, time(8, 0), time(17, 0), 60);
+        assert!(!day.crosses_midnight);
+        assert_eq!(scheduled_minutes(&day), 8 * 60);
+
+        let night = shift(2, time(22, 0), time(6, 0), 30);
+        assert!(night.crosses_midnight);
+        let (start, end) = shift_window(&night, date(2025, 11, 10));
+        assert_eq!(start, date(2025, 11, 10).and_time(time(22, 0)));
+        assert_eq!(end, date(2025, 11, 11).and_time(time(6, 0)));
+        assert_eq!(scheduled_minutes(&night), 7 * 60 + 30);
+    }
+
+    #[test]
+    fn test_pattern_rotation() {
+        // Two days on, one day off
+        let pattern = ShiftPattern {
+            id: 1,
+            name: "2-on-1-off".to_string(),
+            days: vec![Some(1), Some(1), None],
+        };
+        let anchor = date(2025, 11, 10);
+
+        assert_eq!(pattern.shift_on(anchor, date(2025, 11, 9)), None);
+        assert_eq!(pattern.shift_on(anchor, date(2025, 11, 10)), Some(1));
+        assert_eq!(pattern.shift_on(anchor, date(2025, 11, 12)), None);
+        assert_eq!(pattern.shift_on(anchor, date(2025, 11, 13)), Some(1));
+    }
+
+    #[test]
+    fn test_schedule_precedence() {
+        let shifts = vec![
+            shift(1, time(8, 0), time(17, 0), 60),
+            shift(2, time(22, 0), time(6, 0), 0),
+        ];
+        let patterns = vec![
+            ShiftPattern {
+                id: 1,
+                name: "Day".to_string(),
+                days: vec![Some(1)],
+            },
+            ShiftPattern {
+                id: 2,
+                name: "Night".to_string(),
+                days: vec![Some(2)],
+            },
+        ];
+        // Department 10 (parent) has the day pattern; employee 7 has nights in November only
+        let assignments = vec![
+            assignment(1, None, Some(10), date(2025, 1, 1), None),
+            assignment(2, Some(7), None, date(2025, 11, 1), Some(date(2025, 11, 30))),
+        ];
+        let now = Utc::now().fixed_offset();
+        let rosters = vec![shift_rosters::Model {
+            id: 1,
+            employee_id: 7,
+            work_date: date(2025, 11, 15),
+            shift_id: None,
+            note: None,
+            created_at: now,
+            updated_at: now,
+        }];
+        let departments = vec![department(10, None), department(11, Some(10))];
+        let schedule = Schedule::new(shifts, patterns, assignments, rosters, &departments);
+
+        let resolved = |employee, date| schedule.shift_for(employee, Some(11), date).map(|(s, src)| (s.id, src));
+
+        assert_eq!(resolved(7, date(2025, 10, 31)), Some((1, ScheduleSource::Department)));
+        assert_eq!(resolved(7, date(2025, 11, 14)), Some((2, ScheduleSource::Employee)));
+        assert_eq!(resolved(7, date(2025, 11, 15)), None);
+        assert_eq!(resolved(8, date(2025, 11, 15)), Some((1, ScheduleSource::Department)));
+        assert_eq!(schedule.shift_for(9, None, date(2025, 11, 15)), None);
+
+        // A rostered day off is planned; no assignment at all is not
+        assert!(schedule.is_planned(7, Some(11), date(2025, 11, 15)));
+        assert!(!schedule.is_planned(9, None, date(2025, 11, 15)));
+    }
+
+    #[test]
+    fn test_work_date() {
+        let night = shift(2, time(22, 0), time(6, 0), 0);
+        let day = shift(1, time(8, 0), time(17, 0), 60);
+        let at = |d, h, m| date(2025, 11, d).and_time(time(h, m));
+        let midnight = NaiveTime::MIN;
+
+        // A 22:00-06:00 guard's shift is one work day
+        assert_eq!(work_date(at(10, 21, 55), None, midnight), date(2025, 11, 10));
+        assert_eq!(work_date(at(11, 6, 5), Some(&night), midnight), date(2025, 11, 10));
+        assert_eq!(work_date(at(11, 13, 59), Some(&night), midnight), date(2025, 11, 10));
+        assert_eq!(work_date(at(11, 14, 0), Some(&night), midnight), date(2025, 11, 11));
+
+        // Without an overnight shift the day start decides
+        assert_eq!(work_date(at(11, 3, 0), Some(&day), midnight), date(2025, 11, 11));
+        assert_eq!(work_date(at(11, 3, 0), Some(&day), time(4, 0)), date(2025, 11, 10));
+        assert_eq!(work_date(at(11, 4, 0), None, time(4, 0)), date(2025, 11, 11));
+    }
+}
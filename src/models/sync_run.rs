@@ -0,0 +1,51 @@
+//! Sync run DTOs for recording and filtering sync history.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// DTO for recording a completed sync run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateSyncRun {
+    pub device: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub status: String,
+    pub downloaded: i32,
+    pub inserted: i32,
+    pub skipped: i32,
+    pub device_cleared: bool,
+    pub duration_secs: f64,
+    pub clock_drift_secs: Option<i32>,
+    pub error_message: Option<String>,
+}
+
+/// Filter for querying sync history.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncRunFilter {
+    /// Only runs with this status (see [`sync_status`]).
+    pub status: Option<String>,
+    /// Only runs started at or after this time.
+    pub since: Option<DateTime<Utc>>,
+    /// Maximum number of runs to return.
+    pub limit: Option<u64>,
+}
+
+/// Sync run status constants matching database CHECK constraint.
+pub mod sync_status {
+    /// Sync completed and records were stored.
+    pub const SUCCESS: &str = "success";
+    /// Sync aborted with an error.
+    pub const FAILED: &str = "failed";
+    /// Sync stopped by the user.
+    pub const CANCELLED: &str = "cancelled";
+}
+
+/// Device lock operation constants matching database CHECK constraint.
+pub mod lock_operation {
+    /// Downloading attendance (may auto-clear the device).
+    pub const SYNC: &str = "sync";
+    /// Clearing device attendance memory.
+    pub const CLEAR: &str = "clear";
+    /// Removing a leaver from the device.
+    pub const REMOVE_USER: &str = "remove_user";
+}
@@ -0,0 +1,158 @@
+//! User accounts, roles and what each role may do.
+
+use argon2::Argon2;
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use serde::{Deserialize, Serialize};
+
+/// Shortest password accepted for an account.
+pub const MIN_PASSWORD_LEN: usize = 8;
+
+/// User role constants matching database CHECK constraint.
+pub mod user_role {
+    /// Everything, including device clearing, settings and user accounts.
+    pub const ADMIN: &str = "admin";
+    /// Staff, leave and attendance management.
+    pub const HR: &str = "hr";
+    /// Read-only access to their own department and its sub-departments.
+    pub const MANAGER: &str = "manager";
+    /// Read-only access to everything.
+    pub const VIEWER: &str = "viewer";
+
+    /// All roles, for dropdowns.
+    pub const ALL: [&str; 4] = [ADMIN, HR, MANAGER, VIEWER];
+
+    /// Display label for a role.
+    pub fn label(role: &str) -> &'static str {
+        match role {
+            ADMIN => "Admin",
+            HR => "HR",
+            MANAGER => "Department Manager",
+            VIEWER => "Viewer",
+            _ => "Unknown",
+        }
+    }
+}
+
+/// Something a role may or may not do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permission {
+    /// Create, edit and delete departments, employees, shifts and holidays.
+    EditStaff,
+    /// Enter, decide and delete leave requests and entitlements.
+    ManageLeave,
+    /// Add, correct and void punches.
+    CorrectPunches,
+    /// Download attendance from the device and link unmatched punches.
+    SyncDevice,
+    /// Delete the attendance memory of the device.
+    ClearDevice,
+    /// Change the device and application settings.
+    EditSettings,
+    /// Read the audit log.
+    ViewAudit,
+    /// Create and edit user accounts.
+    ManageUsers,
+}
+
+impl Permission {
+    /// Whether `role` has this permission.
+    pub fn allowed_for(self, role: &str) -> bool {
+        match self {
+            Permission::EditStaff
+            | Permission::ManageLeave
+            | Permission::CorrectPunches
+            | Permission::SyncDevice
+            | Permission::ViewAudit => matches!(role, user_role::ADMIN | user_role::HR),
+            Permission::ClearDevice | Permission::EditSettings | Permission::ManageUsers => role == user_role::ADMIN,
+        }
+    }
+}
+
+/// DTO for creating a user account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateUser {
+    pub username: String,
+    pub display_name: Option<String>,
+    /// Plain password; only its hash is stored
+    pub password: String,
+    pub role: String,
+    /// Department a manager is responsible for
+    pub department_id: Option<i32>,
+}
+
+/// DTO for updating a user account.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateUser {
+    pub display_name: Option<Option<String>>,
+    pub role: Option<String>,
+    pub department_id: Option<Option<i32>>,
+    pub is_active: Option<bool>,
+    /// New plain password, when resetting it
+    pub password: Option<String>,
+}
+
+/// Check a new password against the account rules.
+pub fn validate_password(password: &str) -> Result<(), String> {
+    if password.chars().count() < MIN_PASSWORD_LEN {
+        return Err(format!("Password must be at least {MIN_PASSWORD_LEN} characters"));
+    }
+    Ok(())
+}
+
+/// Hash a password with argon2 and a random salt.
+pub fn hash_password(password: &str) -> Result<String, String> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| format!("Failed to hash password: {e}"))
+}
+
+/// Whether `password` matches a stored hash; a malformed hash never matches.
+pub fn verify_password(password: &str, hash: &str) -> bool {
+    PasswordHash::new(hash).is_ok_and(|parsed| Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_and_verify_password() {
+        let hash = hash_password("correct horse").unwrap();
+        assert!(hash.starts_with("$argon2"));
+        assert!(verify_password("correct horse", &hash));
+        assert!(!verify_password("Correct horse", &hash));
+        assert!(!verify_password("correct horse", "not a hash"));
+
+        // Salted: the same password hashes differently each time
+        assert_ne!(hash, hash_password("correct horse").unwrap());
+    }
+
+    #[test]
+    fn test_validate_password() {
+        assert!(validate_password("1234567").is_err());
+        assert!(validate_password("12345678").is_ok());
+    }
+
+    #[test]
+    fn test_permissions() {
+        for permission in [
+            Permission::ClearDevice,
+            Permission::EditSettings,
+            Permission::ManageUsers,
+        ] {
+            assert!(permission.allowed_for(user_role::ADMIN));
+            assert!(!permission.allowed_for(user_role::HR));
+        }
+
+        assert!(Permission::EditStaff.allowed_for(user_role::HR));
+        assert!(Permission::CorrectPunches.allowed_for(user_role::HR));
+        for role in [user_role::MANAGER, user_role::VIEWER, "unknown"] {
+            assert!(!Permission::EditStaff.allowed_for(role));
+            assert!(!Permission::SyncDevice.allowed_for(role));
+            assert!(!Permission::ClearDevice.allowed_for(role));
+        }
+    }
+}
@@ -0,0 +1,249 @@
+//! Background service mode.
+//!
+//! Runs scheduled syncs, spool replay and the daily summary report as a
+//! long-lived process without the GUI, until SIGTERM or Ctrl+C.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use sea_orm::DatabaseConnection;
+use tokio::time::{Instant, MissedTickBehavior, interval, interval_at};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+use crate::config::AppConfig;
+use crate::db;
+use crate::error::{AppError, Result};
+use crate::export;
+use crate::spool::Spool;
+use crate::sync::SyncService;
+use crate::timezone;
+
+/// Delay between database connection attempts at startup.
+const CONNECT_RETRY_SECS: u64 = 60;
+
+/// Interval for retrying spooled batches between syncs.
+const SPOOL_RETRY_SECS: u64 = 5 * 60;
+
+/// Interval for checking whether the daily report is due.
+const REPORT_CHECK_SECS: u64 = 60;
+
+/// Counters reported when the service stops.
+#[derive(Debug, Clone, Default)]
+pub struct ServeStats {
+    pub syncs: usize,
+    pub failed_syncs: usize,
+    pub inserted: usize,
+    pub spool_replayed: usize,
+    pub reports: usize,
+}
+
+/// Token cancelled on Ctrl+C or, on Unix, SIGTERM.
+///
+/// Must be called from within a tokio runtime.
+pub fn shutdown_on_signal() -> CancellationToken {
+    let token = CancellationToken::new();
+    let trigger = token.clone();
+
+    tokio::spawn(async move {
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{SignalKind, signal};
+            match signal(SignalKind::terminate()) {
+                Ok(mut sigterm) => {
+                    tokio::select! {
+                        _ = tokio::signal::ctrl_c() => {}
+                        _ = sigterm.recv() => {}
+                    }
+                }
+                Err(e) => {
+                    warn!("Failed to install SIGTERM handler: {e}");
+                    let _ = tokio::signal::ctrl_c().await;
+                }
+            }
+        }
+
+        #[cfg(not(unix))]
+        let _ = tokio::signal::ctrl_c().await;
+
+        info!("Shutdown requested");
+        trigger.cancel();
+    });
+
+    token
+}
+
+/// Run the service until `shutdown` is cancelled.
+///
+/// A sync in progress at shutdown is cancelled between chunks, the same as
+/// the GUI cancel button.
+pub async fn run(config: AppConfig, shutdown: CancellationToken) -> Result<ServeStats> {
+    let mut stats = ServeStats::default();
+
+    let Some(pool) = connect_with_retry(&config, &shutdown).await? else {
+        return Ok(stats);
+    };
+
+    let report_time = config
+        .serve
+        .report_time()
+        .ok_or_else(|| AppError::config("Report time must be HH:MM"))?;
+    let report_dir = Some(config.serve.report_dir.trim())
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from);
+
+    let sync_period = Duration::from_secs(u64::from(config.sync.interval_minutes) * 60);
+    info!(
+        "Service started: sync every {} min, daily report {}",
+        config.sync.interval_minutes,
+        match &report_dir {
+            Some(dir) => format!("at {report_time} to {}", dir.display()),
+            None => "disabled".to_string(),
+        }
+    );
+
+    let service = SyncService::new(config, pool.clone());
+
+    let mut sync_tick = interval(sync_period);
+    sync_tick.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let spool_period = Duration::from_secs(SPOOL_RETRY_SECS);
+    let mut spool_tick = interval_at(Instant::now() + spool_period, spool_period);
+    let mut report_tick = interval(Duration::from_secs(REPORT_CHECK_SECS));
+
+    loop {
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            _ = sync_tick.tick() => run_sync(&service, &shutdown, &mut stats).await,
+            _ = spool_tick.tick() => replay_spool(&service, &mut stats).await,
+            _ = report_tick.tick() => {
+                if let Some(dir) = &report_dir {
+                    write_daily_report(&pool, dir, report_time, &mut stats).await;
+                }
+            }
+        }
+    }
+
+    info!(
+        "Service stopped: {} syncs ({} failed), {} records inserted, {} replayed from spool, {} reports",
+        stats.syncs, stats.failed_syncs, stats.inserted, stats.spool_replayed, stats.reports
+    );
+
+    Ok(stats)
+}
+
+/// Connect to the database and apply pending migrations, retrying until it is
+/// reachable or shutdown is requested.
+///
+/// A schema newer than this build is fatal rather than retried.
+async fn connect_with_retry(config: &AppConfig, shutdown: &CancellationToken) -> Result<Option<DatabaseConnection>> {
+    loop {
+        let result = async {
+            let pool = db::connect(&config.database.connection_string()).await?;
+            db::migration::migrate(&pool).await?;
+            Ok::<_, AppError>(pool)
+        }
+        .await;
+
+        match result {
+            Ok(pool) => return Ok(Some(pool)),
+            Err(e @ AppError::SchemaTooNew { .. }) => return Err(e),
+            Err(e) => error!("Database unavailable, retrying in {CONNECT_RETRY_SECS}s: {e}"),
+        }
+
+        tokio::select! {
+            _ = shutdown.cancelled() => return Ok(None),
+            _ = tokio::time::sleep(Duration::from_secs(CONNECT_RETRY_SECS)) => {}
+        }
+    }
+}
+
+async fn run_sync(service: &SyncService, shutdown: &CancellationToken, stats: &mut ServeStats) {
+    stats.syncs += 1;
+    match service.sync_with_progress(shutdown, |_, _| {}).await {
+        Ok(result) => {
+            stats.inserted += result.inserted;
+            info!("Scheduled sync: {}", result.summary());
+        }
+        Err(e) if e.is_cancelled() => warn!("Scheduled sync cancelled by shutdown"),
+        Err(e) => {
+            stats.failed_syncs += 1;
+            error!("Scheduled sync failed: {e}");
+        }
+    }
+}
+
+async fn replay_spool(service: &SyncService, stats: &mut ServeStats) {
+    if !matches!(Spool::default_location().pending(), Ok(batches) if !batches.is_empty()) {
+        return;
+    }
+
+    match service.replay_spool().await {
+        Ok(replayed) => {
+            stats.spool_replayed += replayed;
+            info!("Replayed {replayed} spooled records");
+        }
+        Err(e) => warn!("Spooled records not replayed yet: {e}"),
+    }
+}
+
+/// Day whose summary report is due at `now`, if the report time has passed.
+fn report_day(now: NaiveDateTime, report_time: NaiveTime) -> Option<NaiveDate> {
+    (now.time() >= report_time).then(|| now.date() - TimeDelta::days(1))
+}
+
+/// Report file for a day.
+fn report_path(dir: &Path, day: NaiveDate) -> PathBuf {
+    dir.join(format!("attendance_summary_{}.xlsx", day.format("%Y%m%d")))
+}
+
+/// Write the previous day's summary report once the report time has passed.
+///
+/// An existing file means the report was already written, so restarts do not
+/// repeat it.
+async fn write_daily_report(pool: &DatabaseConnection, dir: &Path, report_time: NaiveTime, stats: &mut ServeStats) {
+    let Some(day) = report_day(timezone::now().naive_local(), report_time) else {
+        return;
+    };
+    let path = report_path(dir, day);
+    if path.exists() {
+        return;
+    }
+
+    let result = async {
+        std::fs::create_dir_all(dir)?;
+        let data = db::attendance::get_all_daily_summary_for_export(pool, day, day, None).await?;
+        export::export_attendance_summary_to_excel(&data, &path).map_err(|e| AppError::Export(e.to_string()))?;
+        Ok::<_, AppError>(data.len())
+    }
+    .await;
+
+    match result {
+        Ok(rows) => {
+            stats.reports += 1;
+            info!("Daily report for {day}: {rows} rows written to {}", path.display());
+        }
+        Err(e) => error!("Daily report for {day} failed: {e}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_day() {
+        let report_time = NaiveTime::from_hms_opt(7, 0, 0).unwrap();
+        let day = NaiveDate::from_ymd_opt(2025, 11, 10).unwrap();
+
+        assert_eq!(report_day(day.and_hms_opt(6, 59, 0).unwrap(), report_time), None);
+        assert_eq!(
+            report_day(day.and_hms_opt(7, 0, 0).unwrap(), report_time),
+            day.pred_opt()
+        );
+        assert_eq!(
+            report_path(Path::new("reports"), day),
+            Path::new("reports").join("attendance_summary_20251110.xlsx")
+        );
+    }
+}
@@ -0,0 +1,150 @@
+//! Local durable spool for downloaded attendance records.
+//!
+//! Each sync writes its converted records to a JSON-lines batch file before
+//! inserting them, so a database outage (or an auto-clear followed by one)
+//! cannot lose data. Batches are deleted once inserted and replayed in
+//! filename (chronological) order otherwise.
+
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+
+use crate::config::AppConfig;
+use crate::models::attendance::CreateAttendanceLog;
+
+/// Extension of committed batch files.
+const BATCH_EXT: &str = "jsonl";
+
+/// Directory-backed spool of pending attendance batches.
+#[derive(Debug, Clone)]
+pub struct Spool {
+    dir: PathBuf,
+}
+
+impl Spool {
+    /// Create a spool in the given directory (created on first write).
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Spool in the `spool` directory next to the config file.
+    pub fn default_location() -> Self {
+        Self::new(AppConfig::default_path().with_file_name("spool"))
+    }
+
+    /// Durably write a batch and return its path.
+    ///
+    /// The file is written under a temporary name, synced, then renamed, so a
+    /// crash never leaves a partial batch behind.
+    pub fn write_batch(&self, records: &[CreateAttendanceLog]) -> io::Result<PathBuf> {
+        fs::create_dir_all(&self.dir)?;
+
+        let name = format!("batch_{}", Utc::now().format("%Y%m%d_%H%M%S_%f"));
+        let tmp_path = self.dir.join(format!("{name}.tmp"));
+        let path = self.dir.join(format!("{name}.{BATCH_EXT}"));
+
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        for record in records {
+            serde_json::to_writer(&mut writer, record)?;
+            writer.write_all(b"\n")?;
+        }
+        writer.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+        fs::rename(&tmp_path, &path)?;
+
+        Ok(path)
+    }
+
+    /// List pending batch files, oldest first.
+    pub fn pending(&self) -> io::Result<Vec<PathBuf>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+
+        let mut batches: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == BATCH_EXT))
+            .collect();
+        batches.sort();
+        Ok(batches)
+    }
+
+    /// Count records across all pending batches.
+    pub fn pending_records(&self) -> io::Result<usize> {
+        let mut count = 0;
+        for path in self.pending()? {
+            let reader = BufReader::new(File::open(path)?);
+            count += reader
+                .lines()
+                .filter(|line| line.as_ref().is_ok_and(|l| !l.is_empty()))
+                .count();
+        }
+        Ok(count)
+    }
+
+    /// Read all records from a batch file.
+    pub fn read_batch(path: &Path) -> io::Result<Vec<CreateAttendanceLog>> {
+        let reader = BufReader::new(File::open(path)?);
+        let mut records = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+            records.push(serde_json::from_str(&line)?);
+        }
+        Ok(records)
+    }
+
+    /// Remove a batch once its records are stored.
+    pub fn remove(path: &Path) -> io::Result<()> {
+        fs::remove_file(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_spool_round_trip() {
+        let dir = std::env::temp_dir().join(format!("gianged_spool_test_{}", std::process::id()));
+        let spool = Spool::new(&dir);
+        assert!(spool.pending().unwrap().is_empty());
+
+        let records = vec![
+            CreateAttendanceLog {
+                scanner_uid: 1,
+                check_time: Utc.with_ymd_and_hms(2025, 11, 10, 1, 0, 0).unwrap(),
+                verify_type: 2,
+                status: 0,
+                source: "device".to_string(),
+            },
+            CreateAttendanceLog {
+                scanner_uid: 2,
+                check_time: Utc.with_ymd_and_hms(2025, 11, 10, 1, 5, 0).unwrap(),
+                verify_type: 101,
+                status: 0,
+                source: "device".to_string(),
+            },
+        ];
+
+        let path = spool.write_batch(&records).unwrap();
+        assert_eq!(spool.pending().unwrap(), vec![path.clone()]);
+        assert_eq!(spool.pending_records().unwrap(), 2);
+
+        let read = Spool::read_batch(&path).unwrap();
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[1].scanner_uid, 2);
+        assert_eq!(read[1].check_time, records[1].check_time);
+
+        Spool::remove(&path).unwrap();
+        assert!(spool.pending().unwrap().is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
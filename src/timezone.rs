@@ -0,0 +1,107 @@
+//! Business timezone.
+//!
+//! Attendance days, device clocks, reports and displayed times all use one
+//! configured timezone (`general.timezone`) instead of the workstation's, so a
+//! server or PC set to UTC does not split a workday in two. The database
+//! session timezone is set to the same zone on every connection.
+//!
+//! The work day start (`general.day_start`) lives here too: it decides which
+//! day a punch shortly after midnight belongs to, and is passed to the
+//! database session alongside the timezone.
+
+use std::sync::OnceLock;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// Timezone used when none is configured.
+pub const DEFAULT_TIMEZONE: &str = "Asia/Ho_Chi_Minh";
+
+static BUSINESS_TZ: OnceLock<Tz> = OnceLock::new();
+static DAY_START: OnceLock<NaiveTime> = OnceLock::new();
+
+/// Set the business timezone for this process.
+///
+/// Called once at startup from the loaded config; later calls are ignored.
+pub fn init(tz: Tz) {
+    if BUSINESS_TZ.set(tz).is_err() && BUSINESS_TZ.get() != Some(&tz) {
+        tracing::warn!("Business timezone already set, ignoring {tz}");
+    }
+}
+
+/// The business timezone.
+pub fn business() -> Tz {
+    *BUSINESS_TZ.get_or_init(|| chrono_tz::Asia::Ho_Chi_Minh)
+}
+
+/// Set the work day start for this process.
+///
+/// Called once at startup from the loaded config; later calls are ignored.
+pub fn init_day_start(time: NaiveTime) {
+    if DAY_START.set(time).is_err() && DAY_START.get() != Some(&time) {
+        tracing::warn!("Work day start already set, ignoring {time}");
+    }
+}
+
+/// Time of day a work day starts (midnight unless configured).
+pub fn day_start() -> NaiveTime {
+    *DAY_START.get_or_init(|| NaiveTime::MIN)
+}
+
+/// Current time in the business timezone.
+pub fn now() -> DateTime<Tz> {
+    Utc::now().with_timezone(&business())
+}
+
+/// Current date in the business timezone.
+pub fn today() -> NaiveDate {
+    now().date_naive()
+}
+
+/// Convert a stored instant to the business timezone.
+pub fn to_business<T: TimeZone>(time: &DateTime<T>) -> DateTime<Tz> {
+    time.with_timezone(&business())
+}
+
+/// Interpret a wall-clock time in the business timezone.
+///
+/// Ambiguous times (DST fall-back) resolve to the earlier instant; times that
+/// do not exist (DST spring-forward gap) return `None`.
+pub fn from_wall_clock(naive: &NaiveDateTime) -> Option<DateTime<Tz>> {
+    business().from_local_datetime(naive).earliest()
+}
+
+/// Instant a calendar day starts in the business timezone.
+///
+/// A midnight skipped by a DST change resolves to the first whole hour after it.
+pub fn start_of_day(date: NaiveDate) -> DateTime<Tz> {
+    let midnight = date.and_time(NaiveTime::MIN);
+    (0..24)
+        .find_map(|hour| from_wall_clock(&(midnight + TimeDelta::hours(hour))))
+        .unwrap_or_else(|| business().from_utc_datetime(&midnight))
+}
+
+/// Half-open instant range `[start, end)` covering the calendar days
+/// `start_date..=end_date` in the business timezone.
+pub fn day_range(start_date: NaiveDate, end_date: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+    let end = end_date.succ_opt().unwrap_or(end_date);
+    (
+        start_of_day(start_date).with_timezone(&Utc),
+        start_of_day(end).with_timezone(&Utc),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_range_uses_business_timezone() {
+        let date = NaiveDate::from_ymd_opt(2025, 11, 10).unwrap();
+        let (start, end) = day_range(date, date);
+
+        assert_eq!(start.with_timezone(&business()).date_naive(), date);
+        assert_eq!(start.with_timezone(&business()).time(), NaiveTime::MIN);
+        assert_eq!(end - start, TimeDelta::days(1));
+    }
+}
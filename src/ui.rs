@@ -1,14 +1,20 @@
 //! GUI panels and application state.
 
 pub mod app;
+pub mod audit_panel;
 pub mod components;
 pub mod dashboard;
 pub mod department_panel;
+pub mod leave_panel;
+pub mod login_panel;
 pub mod reports_panel;
 pub mod settings_panel;
 pub mod setup_wizard;
+pub mod shift_panel;
 pub mod staff_panel;
 pub mod sync_panel;
+pub mod unmatched_panel;
+pub mod users_panel;
 
 pub use app::App;
 pub use setup_wizard::{SetupApp, SetupWizard};
@@ -1,21 +1,47 @@
 //! Main application UI.
 
-use chrono::{DateTime, Local, NaiveDate};
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime};
+use chrono_tz::Tz;
 use eframe::egui::{self, Align, Layout, ProgressBar};
 use sea_orm::DatabaseConnection;
 use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 
 use crate::config::AppConfig;
 use crate::db;
-use crate::entities::{departments, employees};
-use crate::models::attendance::{AttendanceDetail, DailyAttendance};
-use crate::models::department::{CreateDepartment, UpdateDepartment};
-use crate::models::employee::{CreateEmployee, UpdateEmployee};
-use crate::sync::{SyncResult, SyncService, run_sync_background};
+use crate::entities::{
+    audit_log, departments, employee_assignments, employees, holidays, leave_requests, leave_types, shift_assignments,
+    shifts, sync_runs, users,
+};
+use crate::models::Schedule;
+use crate::models::attendance::{AttendanceDetail, DailyAttendance, UnmatchedPunches};
+use crate::models::audit::AuditFilter;
+use crate::models::day_status::DailyStatus;
+use crate::models::department::{CreateDepartment, UpdateDepartment, department_subtree};
+use crate::models::employee::{CreateEmployee, OffboardEmployee, RehireEmployee, UpdateEmployee};
+use crate::models::holiday::{CreateHoliday, HolidayCalendar, UpdateHoliday};
+use crate::models::leave::{
+    CreateLeaveRequest, CreateLeaveType, LeaveBalance, SetLeaveEntitlement, UpdateLeaveRequest, UpdateLeaveType,
+};
+use crate::models::overtime::OvertimeRules;
+use crate::models::punch_correction::{AddPunch, CorrectPunch, VoidPunch};
+use crate::models::shift::{
+    CreateShift, CreateShiftAssignment, SaveShiftPattern, SetRosterEntry, ShiftPattern, UpdateShift,
+};
+use crate::models::sync_run::SyncRunFilter;
+use crate::models::user::{CreateUser, Permission, UpdateUser, user_role};
+use crate::spool::Spool;
+use crate::sync::{SyncPreview, SyncResult, SyncService, run_sync_background};
+use crate::timezone;
 use crate::zk::DeviceCapacity;
 
 use super::components::colors;
-use super::{dashboard, department_panel, reports_panel, settings_panel, staff_panel, sync_panel};
+use super::{
+    audit_panel, dashboard, department_panel, leave_panel, login_panel, reports_panel, settings_panel, shift_panel,
+    staff_panel, sync_panel, unmatched_panel, users_panel,
+};
 
 /// Current panel being displayed.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -24,9 +50,14 @@ pub enum Panel {
     Dashboard,
     Departments,
     Employees,
+    Shifts,
+    Leave,
     Sync,
     Reports,
     Settings,
+    Unmatched,
+    Audit,
+    Users,
 }
 
 impl Panel {
@@ -36,9 +67,14 @@ impl Panel {
             Panel::Dashboard => "Dashboard",
             Panel::Departments => "Departments",
             Panel::Employees => "Employees",
+            Panel::Shifts => "Shifts",
+            Panel::Leave => "Leave",
             Panel::Sync => "Sync",
             Panel::Reports => "Reports",
             Panel::Settings => "Settings",
+            Panel::Unmatched => "Unmatched Punches",
+            Panel::Audit => "Audit Log",
+            Panel::Users => "Users",
         }
     }
 }
@@ -72,7 +108,8 @@ pub enum SyncState {
 pub enum SyncProgress {
     Started,
     Progress { percent: f32, message: String },
-    Completed { records: u32, timestamp: DateTime<Local> },
+    Completed { records: u32, timestamp: DateTime<Tz> },
+    Cancelled,
     Error(String),
 }
 
@@ -81,23 +118,74 @@ pub enum UiMessage {
     // Data loading
     DepartmentsLoaded(Vec<departments::Model>),
     EmployeesLoaded(Vec<employees::Model>),
+    /// Assignment history of the employee being edited.
+    EmployeeAssignmentsLoaded(i32, Vec<employee_assignments::Model>),
+    ShiftsLoaded(Vec<shifts::Model>),
+    ShiftPatternsLoaded(Vec<ShiftPattern>),
+    ShiftAssignmentsLoaded(Vec<shift_assignments::Model>),
+    RosterLoaded(Schedule, HolidayCalendar),
+    HolidaysLoaded(Vec<holidays::Model>),
+    TodayHolidayLoaded(Option<String>),
+    /// Shared settings: double-punch window and overtime rules.
+    SharedSettingsLoaded(u32, OvertimeRules),
+    LeaveTypesLoaded(Vec<leave_types::Model>),
+    LeaveRequestsLoaded(Vec<leave_requests::Model>),
+    LeaveBalancesLoaded(Vec<LeaveBalance>),
     AttendanceLoaded(Vec<DailyAttendance>),
     AttendanceDetailsLoaded(Vec<AttendanceDetail>),
+    AttendanceStatusLoaded(Vec<DailyStatus>),
+    UnmatchedPunchesLoaded(Vec<UnmatchedPunches>),
+    UsersLoaded(Vec<users::Model>),
     // Pagination counts
     AttendanceCountLoaded(u64),
     AttendanceDetailsCountLoaded(u64),
     LoadError(String),
 
+    // Sign-in
+    UserCountLoaded(u64),
+    SignedIn(Session),
+    SignInFailed(String),
+
     // Sync
     SyncProgress(f32, String),
     SyncCompleted(SyncResult),
     SyncFailed(String),
+    SyncRunsLoaded(Vec<sync_runs::Model>),
+    AuditLogLoaded(Vec<audit_log::Model>),
+    LastSyncLoaded(Option<DateTime<Tz>>),
+    DoublePunchesFlagged(u64),
+    OvertimeRulesSaved,
+    SpoolReplayed(usize),
+    SpoolReplayFailed(String),
+    SyncPreviewLoaded(SyncPreview),
+    SyncPreviewFailed(String),
+    SyncPreviewCommitted(SyncResult),
 
     // CRUD operations
     DepartmentSaved(departments::Model),
     DepartmentDeleted(i32),
     EmployeeSaved(employees::Model),
+    /// Leaver offboarded, with the outcome of removing them from the device if requested.
+    EmployeeOffboarded(employees::Model, Option<Result<bool, String>>),
+    EmployeeRehired(employees::Model),
     EmployeeDeleted(i32),
+    ShiftSaved(shifts::Model),
+    ShiftDeleted(i32),
+    ShiftPatternSaved(String),
+    ShiftPatternDeleted(i32),
+    ShiftAssignmentSaved,
+    ShiftAssignmentDeleted(i32),
+    RosterSaved,
+    HolidaySaved(holidays::Model),
+    HolidayDeleted(i32),
+    HolidaysImported(i32, usize),
+    LeaveTypeSaved(leave_types::Model),
+    LeaveRequestSaved(leave_requests::Model),
+    LeaveRequestDecided(leave_requests::Model),
+    LeaveRequestDeleted(i32),
+    LeaveEntitlementSaved,
+    PunchCorrected(&'static str),
+    UserSaved(users::Model),
     OperationFailed(String),
 
     // Export
@@ -154,13 +242,24 @@ pub struct EmployeeForm {
     pub employee_code: String,
     pub full_name: String,
     pub department_id: Option<i32>,
+    pub position: String,
     pub scanner_uid: String,
     pub gender: Option<String>,
     pub birth_date: Option<NaiveDate>,
     pub start_date: Option<NaiveDate>,
     /// Persistent input string for start date (allows partial typing without clearing)
     pub start_date_input: String,
+    /// First day of a department or position change
+    pub effective_from: Option<NaiveDate>,
+    pub effective_from_input: String,
+    /// Department and position when the form was opened
+    pub saved_department_id: Option<i32>,
+    pub saved_position: String,
+    /// Assignment history, latest first
+    pub assignments: Vec<employee_assignments::Model>,
+    /// Last working day, changed by offboarding and rehiring only
     pub end_date: Option<NaiveDate>,
+    pub termination_reason: String,
     pub is_active: bool,
     pub is_open: bool,
     pub is_editing: bool,
@@ -174,22 +273,456 @@ impl EmployeeForm {
 
     /// Create a form pre-filled for editing an existing employee.
     pub fn edit(emp: &employees::Model) -> Self {
+        // A leaver's transfers take effect by the last working day
+        let effective_from = emp
+            .end_date
+            .map_or(timezone::today(), |end_date| end_date.min(timezone::today()));
         Self {
             id: Some(emp.id),
             employee_code: emp.employee_code.clone(),
             full_name: emp.full_name.clone(),
             department_id: emp.department_id,
+            position: emp.position.clone().unwrap_or_default(),
             scanner_uid: emp.scanner_uid.map(|u| u.to_string()).unwrap_or_default(),
             gender: emp.gender.clone(),
             birth_date: emp.birth_date,
             start_date: Some(emp.start_date),
             start_date_input: emp.start_date.format("%Y-%m-%d").to_string(),
-            end_date: None,
+            effective_from: Some(effective_from),
+            effective_from_input: effective_from.format("%Y-%m-%d").to_string(),
+            saved_department_id: emp.department_id,
+            saved_position: emp.position.clone().unwrap_or_default(),
+            assignments: Vec::new(),
+            end_date: emp.end_date,
+            termination_reason: emp.termination_reason.clone().unwrap_or_default(),
             is_active: emp.is_active,
             is_open: true,
             is_editing: true,
         }
     }
+
+    /// Whether the department or position differs from the saved one.
+    pub fn is_reassigned(&self) -> bool {
+        self.department_id != self.saved_department_id || self.position.trim() != self.saved_position
+    }
+}
+
+/// Form state for offboarding a leaver.
+#[derive(Default, Clone)]
+pub struct OffboardForm {
+    pub employee_id: Option<i32>,
+    pub full_name: String,
+    pub scanner_uid: Option<i32>,
+    /// Last working day
+    pub end_date: Option<NaiveDate>,
+    pub end_date_input: String,
+    pub reason: String,
+    pub remove_from_device: bool,
+    pub is_open: bool,
+}
+
+impl OffboardForm {
+    /// Reset the form to default values.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Create a form for offboarding `emp`, ending today.
+    pub fn new(emp: &employees::Model) -> Self {
+        let today = timezone::today();
+        Self {
+            employee_id: Some(emp.id),
+            full_name: emp.full_name.clone(),
+            scanner_uid: emp.scanner_uid,
+            end_date: Some(today),
+            end_date_input: today.format("%Y-%m-%d").to_string(),
+            reason: String::new(),
+            remove_from_device: emp.scanner_uid.is_some(),
+            is_open: true,
+        }
+    }
+}
+
+/// Form state for rehiring a leaver.
+#[derive(Default, Clone)]
+pub struct RehireForm {
+    pub employee_id: Option<i32>,
+    pub full_name: String,
+    /// Last working day before leaving
+    pub previous_end: Option<NaiveDate>,
+    /// First working day back
+    pub start_date: Option<NaiveDate>,
+    pub start_date_input: String,
+    pub is_open: bool,
+}
+
+impl RehireForm {
+    /// Reset the form to default values.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Create a form for rehiring `emp`, back today or the day after they left.
+    pub fn new(emp: &employees::Model) -> Self {
+        let next_day = emp.end_date.and_then(|end_date| end_date.succ_opt());
+        let start_date = next_day.map_or(timezone::today(), |next_day| next_day.max(timezone::today()));
+        Self {
+            employee_id: Some(emp.id),
+            full_name: emp.full_name.clone(),
+            previous_end: emp.end_date,
+            start_date: Some(start_date),
+            start_date_input: start_date.format("%Y-%m-%d").to_string(),
+            is_open: true,
+        }
+    }
+}
+
+/// Form state for the shared overtime rules.
+#[derive(Clone)]
+pub struct OvertimeForm {
+    pub daily_threshold_hours: f64,
+    pub weekday_multiplier: f64,
+    pub weekend_multiplier: f64,
+    pub holiday_multiplier: f64,
+    /// Night window start input (HH:MM)
+    pub night_start: String,
+    /// Night window end input (HH:MM)
+    pub night_end: String,
+    pub night_premium: f64,
+    pub min_block_minutes: u32,
+    pub rounding_minutes: u32,
+}
+
+impl Default for OvertimeForm {
+    fn default() -> Self {
+        Self::new(&OvertimeRules::default())
+    }
+}
+
+impl OvertimeForm {
+    /// Create a form pre-filled with `rules`.
+    pub fn new(rules: &OvertimeRules) -> Self {
+        Self {
+            daily_threshold_hours: rules.daily_threshold_minutes as f64 / 60.0,
+            weekday_multiplier: rules.weekday_multiplier,
+            weekend_multiplier: rules.weekend_multiplier,
+            holiday_multiplier: rules.holiday_multiplier,
+            night_start: rules.night_start.format("%H:%M").to_string(),
+            night_end: rules.night_end.format("%H:%M").to_string(),
+            night_premium: rules.night_premium,
+            min_block_minutes: rules.min_block_minutes.max(0) as u32,
+            rounding_minutes: rules.rounding_minutes.max(0) as u32,
+        }
+    }
+
+    /// Parsed and validated rules.
+    pub fn rules(&self) -> Result<OvertimeRules, String> {
+        let parse = |value: &str| {
+            NaiveTime::parse_from_str(value.trim(), "%H:%M").map_err(|_| "Night window times must be HH:MM".to_string())
+        };
+        let rules = OvertimeRules {
+            daily_threshold_minutes: (self.daily_threshold_hours * 60.0).round() as i64,
+            weekday_multiplier: self.weekday_multiplier,
+            weekend_multiplier: self.weekend_multiplier,
+            holiday_multiplier: self.holiday_multiplier,
+            night_start: parse(&self.night_start)?,
+            night_end: parse(&self.night_end)?,
+            night_premium: self.night_premium,
+            min_block_minutes: i64::from(self.min_block_minutes),
+            rounding_minutes: i64::from(self.rounding_minutes),
+        };
+        rules.validate()?;
+        Ok(rules)
+    }
+}
+
+/// Sub-view of the shifts panel.
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum ShiftTab {
+    #[default]
+    Shifts,
+    Patterns,
+    Assignments,
+    Roster,
+    Holidays,
+}
+
+/// Form state for shift CRUD.
+#[derive(Default, Clone)]
+pub struct ShiftForm {
+    pub id: Option<i32>,
+    pub name: String,
+    /// Start time input (HH:MM)
+    pub start_time: String,
+    /// End time input (HH:MM)
+    pub end_time: String,
+    pub break_minutes: String,
+    pub late_grace_minutes: String,
+    pub early_grace_minutes: String,
+    pub is_active: bool,
+    pub is_open: bool,
+    pub is_editing: bool,
+}
+
+impl ShiftForm {
+    /// Reset the form to default values.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Create a form pre-filled for editing an existing shift.
+    pub fn edit(shift: &shifts::Model) -> Self {
+        Self {
+            id: Some(shift.id),
+            name: shift.name.clone(),
+            start_time: shift.start_time.format("%H:%M").to_string(),
+            end_time: shift.end_time.format("%H:%M").to_string(),
+            break_minutes: shift.break_minutes.to_string(),
+            late_grace_minutes: shift.late_grace_minutes.to_string(),
+            early_grace_minutes: shift.early_grace_minutes.to_string(),
+            is_active: shift.is_active,
+            is_open: true,
+            is_editing: true,
+        }
+    }
+}
+
+/// Form state for rotation pattern CRUD.
+#[derive(Default, Clone)]
+pub struct PatternForm {
+    pub id: Option<i32>,
+    pub name: String,
+    /// Shift ID per cycle day (`None` = day off)
+    pub days: Vec<Option<i32>>,
+    pub is_open: bool,
+    pub is_editing: bool,
+}
+
+impl PatternForm {
+    /// Reset the form to default values.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Create a form pre-filled for editing an existing pattern.
+    pub fn edit(pattern: &ShiftPattern) -> Self {
+        Self {
+            id: Some(pattern.id),
+            name: pattern.name.clone(),
+            days: pattern.days.clone(),
+            is_open: true,
+            is_editing: true,
+        }
+    }
+}
+
+/// Form state for creating a shift assignment.
+#[derive(Default, Clone)]
+pub struct AssignmentForm {
+    /// Assign to an employee (`true`) or a department (`false`)
+    pub for_employee: bool,
+    pub employee_id: Option<i32>,
+    pub department_id: Option<i32>,
+    pub pattern_id: Option<i32>,
+    pub start_date_input: String,
+    /// Empty = open-ended
+    pub end_date_input: String,
+    pub is_open: bool,
+}
+
+impl AssignmentForm {
+    /// Reset the form to default values.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Form state for holiday CRUD.
+#[derive(Default, Clone)]
+pub struct HolidayForm {
+    pub id: Option<i32>,
+    /// Date input (YYYY-MM-DD)
+    pub date_input: String,
+    pub name: String,
+    pub is_paid: bool,
+    /// `None` = whole company
+    pub department_id: Option<i32>,
+    pub is_open: bool,
+    pub is_editing: bool,
+}
+
+impl HolidayForm {
+    /// Reset the form to default values.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Create a form pre-filled for editing an existing holiday.
+    pub fn edit(holiday: &holidays::Model) -> Self {
+        Self {
+            id: Some(holiday.id),
+            date_input: holiday.holiday_date.format("%Y-%m-%d").to_string(),
+            name: holiday.name.clone(),
+            is_paid: holiday.is_paid,
+            department_id: holiday.department_id,
+            is_open: true,
+            is_editing: true,
+        }
+    }
+}
+
+/// Sub-view of the leave panel.
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum LeaveTab {
+    #[default]
+    Requests,
+    Balances,
+    Types,
+}
+
+/// Form state for leave request CRUD.
+#[derive(Default, Clone)]
+pub struct LeaveRequestForm {
+    pub id: Option<i32>,
+    pub employee_id: Option<i32>,
+    pub leave_type_id: Option<i32>,
+    /// Start date input (YYYY-MM-DD)
+    pub start_date_input: String,
+    /// End date input (YYYY-MM-DD)
+    pub end_date_input: String,
+    pub half_day: bool,
+    pub note: String,
+    pub is_open: bool,
+    pub is_editing: bool,
+}
+
+impl LeaveRequestForm {
+    /// Reset the form to default values.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Create a form pre-filled for editing an existing request.
+    pub fn edit(request: &leave_requests::Model) -> Self {
+        Self {
+            id: Some(request.id),
+            employee_id: Some(request.employee_id),
+            leave_type_id: Some(request.leave_type_id),
+            start_date_input: request.start_date.format("%Y-%m-%d").to_string(),
+            end_date_input: request.end_date.format("%Y-%m-%d").to_string(),
+            half_day: request.half_day,
+            note: request.note.clone().unwrap_or_default(),
+            is_open: true,
+            is_editing: true,
+        }
+    }
+}
+
+/// Form state for leave type CRUD.
+#[derive(Default, Clone)]
+pub struct LeaveTypeForm {
+    pub id: Option<i32>,
+    pub name: String,
+    pub is_paid: bool,
+    /// Yearly entitlement input; empty = no balance tracked
+    pub default_days: String,
+    pub is_active: bool,
+    pub is_open: bool,
+    pub is_editing: bool,
+}
+
+impl LeaveTypeForm {
+    /// Reset the form to default values.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Create a form pre-filled for editing an existing leave type.
+    pub fn edit(leave_type: &leave_types::Model) -> Self {
+        Self {
+            id: Some(leave_type.id),
+            name: leave_type.name.clone(),
+            is_paid: leave_type.is_paid,
+            default_days: leave_type.default_days.map(|d| d.to_string()).unwrap_or_default(),
+            is_active: leave_type.is_active,
+            is_open: true,
+            is_editing: true,
+        }
+    }
+}
+
+/// Form state for setting an employee's yearly entitlement.
+#[derive(Default, Clone)]
+pub struct EntitlementForm {
+    pub employee_id: i32,
+    pub leave_type_id: i32,
+    /// Days input; empty reverts to the leave type default
+    pub days: String,
+    pub is_open: bool,
+}
+
+impl EntitlementForm {
+    /// Reset the form to default values.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// What the punch dialog does.
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub enum PunchAction {
+    #[default]
+    Add,
+    Edit,
+    Void,
+}
+
+/// Form state for adding, correcting or voiding a punch.
+#[derive(Default, Clone)]
+pub struct PunchForm {
+    pub action: PunchAction,
+    /// Punch being corrected or voided
+    pub punch_id: Option<i64>,
+    pub employee_id: Option<i32>,
+    /// Work date input (YYYY-MM-DD)
+    pub date_input: String,
+    /// Time input (HH:MM or HH:MM:SS)
+    pub time_input: String,
+    /// Punch state code
+    pub status: i32,
+    pub reason: String,
+    pub is_open: bool,
+}
+
+impl PunchForm {
+    /// Reset the form to default values.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Create a form for adding a punch on `date`.
+    pub fn add(date: NaiveDate) -> Self {
+        Self {
+            date_input: date.format("%Y-%m-%d").to_string(),
+            is_open: true,
+            ..Self::default()
+        }
+    }
+
+    /// Create a form pre-filled for correcting or voiding a punch.
+    pub fn change(action: PunchAction, punch: &AttendanceDetail) -> Self {
+        let check_local = timezone::to_business(&punch.check_time);
+        Self {
+            action,
+            punch_id: Some(punch.id),
+            employee_id: punch.employee_id,
+            date_input: check_local.format("%Y-%m-%d").to_string(),
+            time_input: check_local.format("%H:%M:%S").to_string(),
+            status: punch.status,
+            reason: String::new(),
+            is_open: true,
+        }
+    }
 }
 
 /// Report type: Summary (daily totals) or Detail (every check).
@@ -198,6 +731,8 @@ pub enum ReportType {
     #[default]
     Summary,
     Detail,
+    /// Late arrival, early departure and absence per employee-day
+    Status,
 }
 
 /// Page size for paginated report queries.
@@ -215,6 +750,8 @@ pub struct ReportFilter {
     pub end_date_input: String,
     pub department_id: Option<i32>,
     pub employee_id: Option<i32>,
+    /// Include punches flagged as double punches (detail report only)
+    pub include_duplicates: bool,
     // Pagination state
     pub current_page: u64,
     pub total_records: u64,
@@ -222,7 +759,7 @@ pub struct ReportFilter {
 
 impl Default for ReportFilter {
     fn default() -> Self {
-        let today = Local::now().date_naive();
+        let today = timezone::today();
         let start_date = today - chrono::Duration::days(30);
         Self {
             report_type: ReportType::default(),
@@ -232,6 +769,7 @@ impl Default for ReportFilter {
             end_date_input: today.format("%Y-%m-%d").to_string(),
             department_id: None,
             employee_id: None,
+            include_duplicates: false,
             current_page: 0,
             total_records: 0,
         }
@@ -261,48 +799,247 @@ impl ReportFilter {
     }
 }
 
-/// Log level for UI messages.
-#[derive(Clone, Copy, Debug)]
-pub enum LogLevel {
-    Info,
-    Success,
-    Warning,
-    Error,
-}
+/// Maximum number of sync runs shown in the history table.
+pub const SYNC_HISTORY_LIMIT: u64 = 200;
 
-/// Log entry for display in the UI.
+/// Filter state for the sync history table.
 #[derive(Clone)]
-pub struct LogEntry {
-    pub timestamp: DateTime<Local>,
-    pub message: String,
-    pub level: LogLevel,
+pub struct SyncHistoryFilter {
+    /// Status filter: None = all, Some(status) = only runs with that status
+    pub status: Option<&'static str>,
+    /// Look-back window in days: None = all time
+    pub days: Option<i64>,
 }
 
-/// Target for delete confirmation dialog.
-#[derive(Clone)]
-pub enum DeleteTarget {
-    Department(i32, String),
-    Employee(i32, String),
+impl Default for SyncHistoryFilter {
+    fn default() -> Self {
+        Self {
+            status: None,
+            days: Some(30),
+        }
+    }
 }
 
-/// Main application state.
-pub struct App {
-    // Runtime and database
-    pub rt: tokio::runtime::Runtime,
-    pub pool: DatabaseConnection,
+impl SyncHistoryFilter {
+    /// Build the repository filter for the current selection.
+    pub fn to_query(&self) -> SyncRunFilter {
+        SyncRunFilter {
+            status: self.status.map(str::to_string),
+            since: self.days.map(|days| chrono::Utc::now() - chrono::Duration::days(days)),
+            limit: Some(SYNC_HISTORY_LIMIT),
+        }
+    }
+}
 
-    // Message channel for async communication
-    pub tx: mpsc::UnboundedSender<UiMessage>,
-    pub rx: mpsc::UnboundedReceiver<UiMessage>,
+/// Maximum number of audit entries shown.
+pub const AUDIT_LOG_LIMIT: u64 = 500;
 
-    // Navigation
-    pub current_panel: Panel,
+/// Filter state for the audit log panel.
+#[derive(Clone)]
+pub struct AuditLogFilter {
+    /// Entity filter: None = all
+    pub entity: Option<&'static str>,
+    /// Action filter: None = all
+    pub action: Option<&'static str>,
+    /// Actor search text
+    pub actor: String,
+    /// Record ID of the entity, e.g. an employee ID
+    pub entity_id: String,
+    /// Look-back window in days: None = all time
+    pub days: Option<i64>,
+}
 
-    // Cached data
-    pub departments: Vec<departments::Model>,
+impl Default for AuditLogFilter {
+    fn default() -> Self {
+        Self {
+            entity: None,
+            action: None,
+            actor: String::new(),
+            entity_id: String::new(),
+            days: Some(30),
+        }
+    }
+}
+
+impl AuditLogFilter {
+    /// Build the repository filter for the current selection.
+    pub fn to_query(&self) -> AuditFilter {
+        AuditFilter {
+            entity: self.entity.map(str::to_string),
+            entity_id: Some(self.entity_id.trim().to_string()).filter(|id| !id.is_empty()),
+            action: self.action.map(str::to_string),
+            actor: Some(self.actor.trim().to_string()).filter(|a| !a.is_empty()),
+            since: self.days.map(|days| chrono::Utc::now() - chrono::Duration::days(days)),
+            until: None,
+            limit: Some(AUDIT_LOG_LIMIT),
+        }
+    }
+}
+
+/// Signed-in user and the departments they may see.
+#[derive(Clone)]
+pub struct Session {
+    pub user: users::Model,
+    /// Department subtree of a manager; `None` = all departments
+    pub scope: Option<HashSet<i32>>,
+}
+
+impl Session {
+    /// Whether the signed-in user has `permission`.
+    pub fn can(&self, permission: Permission) -> bool {
+        permission.allowed_for(&self.user.role)
+    }
+
+    /// Whether records of `department_id` are visible to this user.
+    pub fn in_scope(&self, department_id: Option<i32>) -> bool {
+        match &self.scope {
+            None => true,
+            Some(scope) => department_id.is_some_and(|id| scope.contains(&id)),
+        }
+    }
+
+    /// Name shown for the user.
+    pub fn display_name(&self) -> &str {
+        self.user.display_name.as_deref().unwrap_or(&self.user.username)
+    }
+}
+
+/// Form state for the sign-in screen, also used to create the first admin.
+#[derive(Default, Clone)]
+pub struct LoginForm {
+    pub username: String,
+    pub password: String,
+    /// Only used when creating the first admin
+    pub confirm_password: String,
+    /// Only used when creating the first admin
+    pub display_name: String,
+    pub error: Option<String>,
+    pub is_busy: bool,
+}
+
+impl LoginForm {
+    /// Reset the form to default values.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Form state for user account CRUD.
+#[derive(Default, Clone)]
+pub struct UserForm {
+    pub id: Option<i32>,
+    pub username: String,
+    pub display_name: String,
+    pub role: String,
+    /// Department of a manager
+    pub department_id: Option<i32>,
+    /// Required for new accounts; empty keeps the current password when editing
+    pub password: String,
+    pub confirm_password: String,
+    pub is_active: bool,
+    pub is_open: bool,
+    pub is_editing: bool,
+}
+
+impl UserForm {
+    /// Reset the form to default values.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Create a form for a new account.
+    pub fn add() -> Self {
+        Self {
+            role: user_role::VIEWER.to_string(),
+            is_active: true,
+            is_open: true,
+            ..Self::default()
+        }
+    }
+
+    /// Create a form pre-filled for editing an existing account.
+    pub fn edit(user: &users::Model) -> Self {
+        Self {
+            id: Some(user.id),
+            username: user.username.clone(),
+            display_name: user.display_name.clone().unwrap_or_default(),
+            role: user.role.clone(),
+            department_id: user.department_id,
+            password: String::new(),
+            confirm_password: String::new(),
+            is_active: user.is_active,
+            is_open: true,
+            is_editing: true,
+        }
+    }
+}
+
+/// Log level for UI messages.
+#[derive(Clone, Copy, Debug)]
+pub enum LogLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+/// Log entry for display in the UI.
+#[derive(Clone)]
+pub struct LogEntry {
+    pub timestamp: DateTime<Tz>,
+    pub message: String,
+    pub level: LogLevel,
+}
+
+/// Target for delete confirmation dialog.
+#[derive(Clone)]
+pub enum DeleteTarget {
+    Department(i32, String),
+    Employee(i32, String),
+    Shift(i32, String),
+    ShiftPattern(i32, String),
+    ShiftAssignment(i32, String),
+    Holiday(i32, String),
+    LeaveRequest(i32, String),
+}
+
+/// Main application state.
+pub struct App {
+    // Runtime and database
+    pub rt: tokio::runtime::Runtime,
+    pub pool: DatabaseConnection,
+
+    // Message channel for async communication
+    pub tx: mpsc::UnboundedSender<UiMessage>,
+    pub rx: mpsc::UnboundedReceiver<UiMessage>,
+
+    // Navigation
+    pub current_panel: Panel,
+
+    // Session
+    /// Signed-in user; `None` shows the sign-in screen
+    pub session: Option<Session>,
+    pub login_form: LoginForm,
+    /// Number of user accounts, `None` until counted; zero offers creating the first admin
+    pub user_count: Option<u64>,
+
+    // Cached data
+    pub departments: Vec<departments::Model>,
     pub employees: Vec<employees::Model>,
     pub attendance: Vec<DailyAttendance>,
     pub attendance_details: Vec<AttendanceDetail>,
+    pub attendance_status: Vec<DailyStatus>,
+    pub unmatched_punches: Vec<UnmatchedPunches>,
+    pub shifts: Vec<shifts::Model>,
+    pub shift_patterns: Vec<ShiftPattern>,
+    pub shift_assignments: Vec<shift_assignments::Model>,
+    pub holidays: Vec<holidays::Model>,
+    /// Name of today's company-wide holiday, if any
+    pub today_holiday: Option<String>,
+    pub leave_types: Vec<leave_types::Model>,
+    pub leave_requests: Vec<leave_requests::Model>,
+    pub leave_balances: Vec<LeaveBalance>,
+    pub users: Vec<users::Model>,
 
     // Loading states
     pub is_loading: bool,
@@ -312,16 +1049,68 @@ pub struct App {
     pub department_form: DepartmentForm,
     pub employee_form: EmployeeForm,
     pub report_filter: ReportFilter,
+    pub shift_form: ShiftForm,
+    pub pattern_form: PatternForm,
+    pub assignment_form: AssignmentForm,
+    pub holiday_form: HolidayForm,
+    pub leave_request_form: LeaveRequestForm,
+    pub leave_type_form: LeaveTypeForm,
+    pub entitlement_form: EntitlementForm,
+    pub punch_form: PunchForm,
+    pub user_form: UserForm,
+    pub offboard_form: OffboardForm,
+    pub rehire_form: RehireForm,
+
+    // Shifts panel state
+    pub shift_tab: ShiftTab,
+    /// Monday of the week shown in the roster grid
+    pub roster_week_start: NaiveDate,
+    pub roster_dept_filter: Option<i32>,
+    /// Resolved schedule for the roster week
+    pub roster_schedule: Option<Schedule>,
+    /// Holidays in the roster week
+    pub roster_holidays: HolidayCalendar,
+    /// Year shown in the holiday calendar
+    pub holiday_year: i32,
+
+    // Leave panel state
+    pub leave_tab: LeaveTab,
+    /// Year shown in the leave panel
+    pub leave_year: i32,
+    /// Status filter for the request list: None = all
+    pub leave_status_filter: Option<&'static str>,
 
     // Sync state
     pub sync_progress: f32,
     pub sync_status: String,
     pub is_syncing: bool,
-    pub last_sync_time: Option<DateTime<Local>>,
+    pub last_sync_time: Option<DateTime<Tz>>,
 
     // Sync state (used by dashboard and sync panel)
     pub sync_state: SyncState,
     sync_progress_rx: Option<mpsc::UnboundedReceiver<SyncProgress>>,
+    sync_cancel: Option<CancellationToken>,
+
+    /// Downloaded records waiting in the local spool for the database
+    pub spool_pending: usize,
+
+    // Sync history
+    pub sync_runs: Vec<sync_runs::Model>,
+    pub sync_history_filter: SyncHistoryFilter,
+
+    // Audit log
+    pub audit_entries: Vec<audit_log::Model>,
+    pub audit_filter: AuditLogFilter,
+    /// Entry whose before/after values are shown
+    pub audit_selected: Option<i64>,
+
+    // Unmatched punches: selected link target (employee id) per scanner UID
+    pub unmatched_link_targets: HashMap<i32, i32>,
+
+    // Sync preview (dry run)
+    pub sync_preview: Option<SyncPreview>,
+    pub sync_preview_loading: bool,
+    pub sync_preview_committing: bool,
 
     // Log messages
     pub log_messages: Vec<LogEntry>,
@@ -329,6 +1118,14 @@ pub struct App {
     // Configuration
     pub config: AppConfig,
     pub config_modified: bool,
+    /// Double-punch window being edited (a shared database setting)
+    pub double_punch_window: u32,
+    /// Double-punch window the stored flags were computed with, once loaded
+    applied_double_punch_window: Option<u32>,
+    /// Overtime rules being edited (shared database settings)
+    pub overtime_form: OvertimeForm,
+    /// Overtime rules last loaded or saved
+    applied_overtime_rules: Option<OvertimeRules>,
 
     // Search/filter state
     pub employee_search: String,
@@ -374,22 +1171,71 @@ impl App {
             tx,
             rx,
             current_panel: Panel::default(),
+            session: None,
+            login_form: LoginForm::default(),
+            user_count: None,
             departments: Vec::new(),
             employees: Vec::new(),
             attendance: Vec::new(),
             attendance_details: Vec::new(),
+            attendance_status: Vec::new(),
+            unmatched_punches: Vec::new(),
+            shifts: Vec::new(),
+            shift_patterns: Vec::new(),
+            shift_assignments: Vec::new(),
+            holidays: Vec::new(),
+            today_holiday: None,
+            leave_types: Vec::new(),
+            leave_requests: Vec::new(),
+            leave_balances: Vec::new(),
+            users: Vec::new(),
             is_loading: false,
             loading_message: String::new(),
             department_form: DepartmentForm::default(),
             employee_form: EmployeeForm::default(),
             report_filter: ReportFilter::default(),
+            shift_form: ShiftForm::default(),
+            pattern_form: PatternForm::default(),
+            assignment_form: AssignmentForm::default(),
+            holiday_form: HolidayForm::default(),
+            leave_request_form: LeaveRequestForm::default(),
+            leave_type_form: LeaveTypeForm::default(),
+            entitlement_form: EntitlementForm::default(),
+            punch_form: PunchForm::default(),
+            user_form: UserForm::default(),
+            offboard_form: OffboardForm::default(),
+            rehire_form: RehireForm::default(),
+            shift_tab: ShiftTab::default(),
+            roster_week_start: week_start(timezone::today()),
+            roster_dept_filter: None,
+            roster_schedule: None,
+            roster_holidays: HolidayCalendar::default(),
+            holiday_year: timezone::today().year(),
+            leave_tab: LeaveTab::default(),
+            leave_year: timezone::today().year(),
+            leave_status_filter: None,
             sync_progress: 0.0,
             sync_status: "Ready".to_string(),
             is_syncing: false,
             last_sync_time: None,
             sync_state: SyncState::default(),
             sync_progress_rx: None,
+            sync_cancel: None,
+            spool_pending: 0,
+            sync_runs: Vec::new(),
+            sync_history_filter: SyncHistoryFilter::default(),
+            audit_entries: Vec::new(),
+            audit_filter: AuditLogFilter::default(),
+            audit_selected: None,
+            unmatched_link_targets: HashMap::new(),
+            sync_preview: None,
+            sync_preview_loading: false,
+            sync_preview_committing: false,
             log_messages: Vec::new(),
+            double_punch_window: 0,
+            applied_double_punch_window: None,
+            overtime_form: OvertimeForm::default(),
+            applied_overtime_rules: None,
             config,
             config_modified: false,
             employee_search: String::new(),
@@ -413,17 +1259,176 @@ impl App {
             device_clearing: false,
         };
 
-        // Load initial data
-        app.load_departments();
-        app.load_employees();
+        // Data is loaded once someone signs in; spooled punches are stored regardless
+        app.load_user_count();
+        app.replay_spool();
 
         app
     }
 
+    /// Whether the signed-in user has `permission`.
+    pub fn can(&self, permission: Permission) -> bool {
+        self.session.as_ref().is_some_and(|s| s.can(permission))
+    }
+
+    /// Whether records of `department_id` are visible to the signed-in user.
+    pub fn in_scope(&self, department_id: Option<i32>) -> bool {
+        self.session.as_ref().is_some_and(|s| s.in_scope(department_id))
+    }
+
+    /// Username recorded as approver and on punch corrections.
+    pub fn acting_user(&self) -> String {
+        self.session
+            .as_ref()
+            .map(|s| s.user.username.clone())
+            .unwrap_or_default()
+    }
+
+    /// Whether records of the employee are visible to the signed-in user.
+    ///
+    /// Loaded employees are already limited to the user's departments.
+    fn employee_in_scope(&self, employee_id: i32) -> bool {
+        match self.session.as_ref().map(|s| &s.scope) {
+            Some(None) => true,
+            Some(Some(_)) => self.employees.iter().any(|e| e.id == employee_id),
+            None => false,
+        }
+    }
+
+    /// Load the data shown after signing in.
+    fn load_initial_data(&mut self) {
+        self.load_departments();
+        self.load_employees();
+        self.load_shifts();
+        self.load_shift_patterns();
+        self.load_shift_assignments();
+        self.load_holidays();
+        self.load_today_holiday();
+        self.load_shared_settings();
+        self.load_leave_types();
+        self.load_sync_history();
+        self.load_last_sync_time();
+        self.load_unmatched_punches();
+    }
+
+    /// Count user accounts to decide between sign-in and first admin setup.
+    pub fn load_user_count(&mut self) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            match db::user::count(&pool).await {
+                Ok(count) => {
+                    let _ = tx.send(UiMessage::UserCountLoaded(count));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::SignInFailed(format!("Database error: {e}")));
+                }
+            }
+        });
+    }
+
+    /// Sign in with the username and password on the sign-in form.
+    pub fn sign_in(&mut self) {
+        let username = self.login_form.username.trim().to_string();
+        let password = self.login_form.password.clone();
+        if username.is_empty() || password.is_empty() {
+            self.login_form.error = Some("Enter your username and password".to_string());
+            return;
+        }
+
+        self.login_form.is_busy = true;
+        self.login_form.error = None;
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            let result = match db::user::authenticate(&pool, &username, &password).await {
+                Ok(Some(user)) => start_session(&pool, user).await,
+                Ok(None) => Err("Wrong username or password".to_string()),
+                Err(e) => Err(format!("Database error: {e}")),
+            };
+            let _ = match result {
+                Ok(session) => tx.send(UiMessage::SignedIn(session)),
+                Err(e) => tx.send(UiMessage::SignInFailed(e)),
+            };
+        });
+    }
+
+    /// Create the first admin account from the sign-in form and sign in with it.
+    pub fn create_first_admin(&mut self) {
+        let form = &self.login_form;
+        let username = form.username.trim().to_string();
+        if username.is_empty() {
+            self.login_form.error = Some("Enter a username".to_string());
+            return;
+        }
+        if form.password != form.confirm_password {
+            self.login_form.error = Some("Passwords do not match".to_string());
+            return;
+        }
+        let data = CreateUser {
+            username: username.clone(),
+            display_name: Some(form.display_name.trim().to_string()).filter(|n| !n.is_empty()),
+            password: form.password.clone(),
+            role: user_role::ADMIN.to_string(),
+            department_id: None,
+        };
+
+        self.login_form.is_busy = true;
+        self.login_form.error = None;
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            // Someone else may have set up the first account in the meantime
+            let result = match db::user::count(&pool).await {
+                Ok(0) => {
+                    db::audit::set_actor(&username);
+                    match db::user::create(&pool, data).await {
+                        Ok(user) => start_session(&pool, user).await,
+                        Err(e) => Err(e.to_string()),
+                    }
+                }
+                Ok(_) => Err("An account already exists; sign in instead".to_string()),
+                Err(e) => Err(format!("Database error: {e}")),
+            };
+            let _ = match result {
+                Ok(session) => tx.send(UiMessage::SignedIn(session)),
+                Err(e) => tx.send(UiMessage::SignInFailed(e)),
+            };
+        });
+    }
+
+    /// Sign out and return to the sign-in screen, dropping all loaded data.
+    pub fn sign_out(&mut self) {
+        if let Some(session) = self.session.take() {
+            self.log_info(format!("Signed out: {}", session.user.username));
+        }
+        db::audit::init_actor();
+
+        self.current_panel = Panel::Dashboard;
+        self.departments.clear();
+        self.employees.clear();
+        self.attendance.clear();
+        self.attendance_details.clear();
+        self.attendance_status.clear();
+        self.unmatched_punches.clear();
+        self.leave_requests.clear();
+        self.leave_balances.clear();
+        self.users.clear();
+        self.audit_entries.clear();
+        self.report_filter = ReportFilter::default();
+        self.employee_dept_filter = None;
+        self.roster_dept_filter = None;
+        self.login_form.reset();
+        self.load_user_count();
+    }
+
     /// Log a message to the UI log.
     pub fn log(&mut self, level: LogLevel, message: impl Into<String>) {
         self.log_messages.push(LogEntry {
-            timestamp: Local::now(),
+            timestamp: timezone::now(),
             message: message.into(),
             level,
         });
@@ -488,17 +1493,15 @@ impl App {
         });
     }
 
-    /// Load attendance data from database.
-    pub fn load_attendance(&mut self) {
+    /// Load the assignment history of an employee.
+    pub fn load_employee_assignments(&mut self, employee_id: i32) {
         let pool = self.pool.clone();
         let tx = self.tx.clone();
-        let start_date = self.report_filter.start_date;
-        let end_date = self.report_filter.end_date;
 
         self.rt.spawn(async move {
-            match db::attendance::get_daily_summary(&pool, start_date, end_date).await {
-                Ok(attendance) => {
-                    let _ = tx.send(UiMessage::AttendanceLoaded(attendance));
+            match db::employee::list_assignments(&pool, employee_id).await {
+                Ok(assignments) => {
+                    let _ = tx.send(UiMessage::EmployeeAssignmentsLoaded(employee_id, assignments));
                 }
                 Err(e) => {
                     let _ = tx.send(UiMessage::LoadError(e.to_string()));
@@ -507,102 +1510,73 @@ impl App {
         });
     }
 
-    /// Generate report based on current filter settings.
-    /// Uses paginated queries for better performance.
-    pub fn generate_report(&mut self) {
-        self.is_loading = true;
-        self.loading_message = "Generating report...".to_string();
-
+    /// Load shifts from database.
+    pub fn load_shifts(&mut self) {
         let pool = self.pool.clone();
         let tx = self.tx.clone();
-        let filter = self.report_filter.clone();
-        let pagination = db::attendance::Pagination::new(filter.current_page, REPORT_PAGE_SIZE);
-
-        // Load counts first, then data
-        let pool_count = pool.clone();
-        let tx_count = tx.clone();
-        let filter_count = filter.clone();
 
-        // Get summary count
         self.rt.spawn(async move {
-            match db::attendance::count_daily_summary(
-                &pool_count,
-                filter_count.start_date,
-                filter_count.end_date,
-                filter_count.department_id,
-            )
-            .await
-            {
-                Ok(count) => {
-                    let _ = tx_count.send(UiMessage::AttendanceCountLoaded(count));
+            match db::shift::list_shifts(&pool).await {
+                Ok(shifts) => {
+                    let _ = tx.send(UiMessage::ShiftsLoaded(shifts));
                 }
                 Err(e) => {
-                    let _ = tx_count.send(UiMessage::LoadError(e.to_string()));
+                    let _ = tx.send(UiMessage::LoadError(e.to_string()));
                 }
             }
         });
+    }
 
-        // Get details count
-        let pool_detail_count = pool.clone();
-        let tx_detail_count = tx.clone();
-        let filter_detail_count = filter.clone();
+    /// Load rotation patterns from database.
+    pub fn load_shift_patterns(&mut self) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
 
         self.rt.spawn(async move {
-            match db::attendance::count_attendance_details(
-                &pool_detail_count,
-                filter_detail_count.start_date,
-                filter_detail_count.end_date,
-                filter_detail_count.department_id,
-            )
-            .await
-            {
-                Ok(count) => {
-                    let _ = tx_detail_count.send(UiMessage::AttendanceDetailsCountLoaded(count));
+            match db::shift::list_patterns(&pool).await {
+                Ok(patterns) => {
+                    let _ = tx.send(UiMessage::ShiftPatternsLoaded(patterns));
                 }
                 Err(e) => {
-                    let _ = tx_detail_count.send(UiMessage::LoadError(e.to_string()));
+                    let _ = tx.send(UiMessage::LoadError(e.to_string()));
                 }
             }
         });
+    }
 
-        // Load paginated summary data
-        let pool_summary = pool.clone();
-        let tx_summary = tx.clone();
-        let filter_summary = filter.clone();
-        let pagination_summary = pagination;
+    /// Load shift assignments from database.
+    pub fn load_shift_assignments(&mut self) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
 
         self.rt.spawn(async move {
-            match db::attendance::get_daily_summary_paginated(
-                &pool_summary,
-                filter_summary.start_date,
-                filter_summary.end_date,
-                filter_summary.department_id,
-                pagination_summary,
-            )
-            .await
-            {
-                Ok(attendance) => {
-                    let _ = tx_summary.send(UiMessage::AttendanceLoaded(attendance));
+            match db::shift::list_assignments(&pool).await {
+                Ok(assignments) => {
+                    let _ = tx.send(UiMessage::ShiftAssignmentsLoaded(assignments));
                 }
                 Err(e) => {
-                    let _ = tx_summary.send(UiMessage::LoadError(e.to_string()));
+                    let _ = tx.send(UiMessage::LoadError(e.to_string()));
                 }
             }
         });
+    }
+
+    /// Load the resolved schedule for the roster week.
+    pub fn load_roster(&mut self) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+        let start = self.roster_week_start;
+        let end = start + chrono::Duration::days(6);
 
-        // Load paginated detail data
         self.rt.spawn(async move {
-            match db::attendance::get_attendance_details_paginated(
-                &pool,
-                filter.start_date,
-                filter.end_date,
-                filter.department_id,
-                pagination,
-            )
-            .await
-            {
-                Ok(details) => {
-                    let _ = tx.send(UiMessage::AttendanceDetailsLoaded(details));
+            let result = async {
+                let schedule = db::shift::load_schedule(&pool, start, end).await?;
+                let holidays = db::holiday::load_calendar(&pool, start, end).await?;
+                Ok::<_, sea_orm::DbErr>((schedule, holidays))
+            };
+            match result.await {
+                Ok((schedule, holidays)) => {
+                    let _ = tx.send(UiMessage::RosterLoaded(schedule, holidays));
                 }
                 Err(e) => {
                     let _ = tx.send(UiMessage::LoadError(e.to_string()));
@@ -611,16 +1585,363 @@ impl App {
         });
     }
 
-    /// Navigate to next page of report results.
-    pub fn next_page(&mut self) {
-        let total_pages = self.report_filter.total_pages();
-        if self.report_filter.current_page + 1 < total_pages {
-            self.report_filter.current_page += 1;
-            self.generate_report();
-        }
-    }
+    /// Load holidays of the selected calendar year.
+    pub fn load_holidays(&mut self) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+        let year = self.holiday_year;
 
-    /// Navigate to previous page of report results.
+        self.rt.spawn(async move {
+            match db::holiday::list_holidays(&pool, year).await {
+                Ok(holidays) => {
+                    let _ = tx.send(UiMessage::HolidaysLoaded(holidays));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::LoadError(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Load leave types from database.
+    pub fn load_leave_types(&mut self) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            match db::leave::list_leave_types(&pool).await {
+                Ok(types) => {
+                    let _ = tx.send(UiMessage::LeaveTypesLoaded(types));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::LoadError(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Load leave requests overlapping the selected leave year.
+    pub fn load_leave_requests(&mut self) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+        let year = self.leave_year;
+
+        self.rt.spawn(async move {
+            let (Some(start), Some(end)) = (
+                NaiveDate::from_ymd_opt(year, 1, 1),
+                NaiveDate::from_ymd_opt(year, 12, 31),
+            ) else {
+                return;
+            };
+            match db::leave::list_leave_requests(&pool, start, end).await {
+                Ok(requests) => {
+                    let _ = tx.send(UiMessage::LeaveRequestsLoaded(requests));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::LoadError(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Load leave balances for the selected leave year.
+    pub fn load_leave_balances(&mut self) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+        let year = self.leave_year;
+
+        self.rt.spawn(async move {
+            match db::leave::get_leave_balances(&pool, year).await {
+                Ok(balances) => {
+                    let _ = tx.send(UiMessage::LeaveBalancesLoaded(balances));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::LoadError(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Load today's company-wide holiday for the dashboard.
+    pub fn load_today_holiday(&mut self) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+        let today = timezone::today();
+
+        self.rt.spawn(async move {
+            match db::holiday::load_calendar(&pool, today, today).await {
+                Ok(calendar) => {
+                    let name = calendar.company_holiday(today).map(|h| h.name.clone());
+                    let _ = tx.send(UiMessage::TodayHolidayLoaded(name));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::LoadError(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Load the shared settings for the settings panel.
+    pub fn load_shared_settings(&mut self) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            let result = match db::settings::double_punch_window(&pool).await {
+                Ok(window) => db::settings::overtime_rules(&pool).await.map(|rules| (window, rules)),
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok((window, rules)) => {
+                    let _ = tx.send(UiMessage::SharedSettingsLoaded(window, rules));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::LoadError(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Load attendance data from database.
+    pub fn load_attendance(&mut self) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+        let start_date = self.report_filter.start_date;
+        let end_date = self.report_filter.end_date;
+
+        self.rt.spawn(async move {
+            match db::attendance::get_daily_summary(&pool, start_date, end_date).await {
+                Ok(attendance) => {
+                    let _ = tx.send(UiMessage::AttendanceLoaded(attendance));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::LoadError(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Load sync history from database using the current history filter.
+    pub fn load_sync_history(&mut self) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+        let filter = self.sync_history_filter.to_query();
+
+        self.rt.spawn(async move {
+            match db::sync_run::list(&pool, &filter).await {
+                Ok(runs) => {
+                    let _ = tx.send(UiMessage::SyncRunsLoaded(runs));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::LoadError(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Load audit log entries matching the current filter.
+    pub fn load_audit_log(&mut self) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+        let filter = self.audit_filter.to_query();
+
+        self.rt.spawn(async move {
+            match db::audit::list(&pool, &filter).await {
+                Ok(entries) => {
+                    let _ = tx.send(UiMessage::AuditLogLoaded(entries));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::LoadError(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Load user accounts from database.
+    pub fn load_users(&mut self) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            match db::user::list_all(&pool).await {
+                Ok(users) => {
+                    let _ = tx.send(UiMessage::UsersLoaded(users));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::LoadError(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Load scanner UIDs that have punches but no employee.
+    pub fn load_unmatched_punches(&mut self) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+        self.rt.spawn(async move {
+            match db::attendance::get_unmatched_punches(&pool).await {
+                Ok(unmatched) => {
+                    let _ = tx.send(UiMessage::UnmatchedPunchesLoaded(unmatched));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::LoadError(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Load the last successful sync time from the sync history.
+    pub fn load_last_sync_time(&mut self) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            match db::sync_run::get_last_success(&pool).await {
+                Ok(run) => {
+                    let time = run.map(|r| timezone::to_business(&r.finished_at));
+                    let _ = tx.send(UiMessage::LastSyncLoaded(time));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::LoadError(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Generate report based on current filter settings.
+    /// Uses paginated queries for better performance.
+    pub fn generate_report(&mut self) {
+        self.is_loading = true;
+        self.loading_message = "Generating report...".to_string();
+
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+        let filter = self.report_filter.clone();
+
+        if filter.report_type == ReportType::Status {
+            // Computed for the whole range at once and paged locally
+            self.rt.spawn(async move {
+                match db::attendance::get_daily_status(&pool, filter.start_date, filter.end_date, filter.department_id)
+                    .await
+                {
+                    Ok(status) => {
+                        let _ = tx.send(UiMessage::AttendanceStatusLoaded(status));
+                    }
+                    Err(e) => {
+                        let _ = tx.send(UiMessage::LoadError(e.to_string()));
+                    }
+                }
+            });
+            return;
+        }
+
+        let pagination = db::attendance::Pagination::new(filter.current_page, REPORT_PAGE_SIZE);
+
+        // Load counts first, then data
+        let pool_count = pool.clone();
+        let tx_count = tx.clone();
+        let filter_count = filter.clone();
+
+        // Get summary count
+        self.rt.spawn(async move {
+            match db::attendance::count_daily_summary(
+                &pool_count,
+                filter_count.start_date,
+                filter_count.end_date,
+                filter_count.department_id,
+            )
+            .await
+            {
+                Ok(count) => {
+                    let _ = tx_count.send(UiMessage::AttendanceCountLoaded(count));
+                }
+                Err(e) => {
+                    let _ = tx_count.send(UiMessage::LoadError(e.to_string()));
+                }
+            }
+        });
+
+        // Get details count
+        let pool_detail_count = pool.clone();
+        let tx_detail_count = tx.clone();
+        let filter_detail_count = filter.clone();
+
+        self.rt.spawn(async move {
+            match db::attendance::count_attendance_details(
+                &pool_detail_count,
+                filter_detail_count.start_date,
+                filter_detail_count.end_date,
+                filter_detail_count.department_id,
+                filter_detail_count.include_duplicates,
+            )
+            .await
+            {
+                Ok(count) => {
+                    let _ = tx_detail_count.send(UiMessage::AttendanceDetailsCountLoaded(count));
+                }
+                Err(e) => {
+                    let _ = tx_detail_count.send(UiMessage::LoadError(e.to_string()));
+                }
+            }
+        });
+
+        // Load paginated summary data
+        let pool_summary = pool.clone();
+        let tx_summary = tx.clone();
+        let filter_summary = filter.clone();
+        let pagination_summary = pagination;
+
+        self.rt.spawn(async move {
+            match db::attendance::get_daily_summary_paginated(
+                &pool_summary,
+                filter_summary.start_date,
+                filter_summary.end_date,
+                filter_summary.department_id,
+                pagination_summary,
+            )
+            .await
+            {
+                Ok(attendance) => {
+                    let _ = tx_summary.send(UiMessage::AttendanceLoaded(attendance));
+                }
+                Err(e) => {
+                    let _ = tx_summary.send(UiMessage::LoadError(e.to_string()));
+                }
+            }
+        });
+
+        // Load paginated detail data
+        self.rt.spawn(async move {
+            match db::attendance::get_attendance_details_paginated(
+                &pool,
+                filter.start_date,
+                filter.end_date,
+                filter.department_id,
+                filter.include_duplicates,
+                pagination,
+            )
+            .await
+            {
+                Ok(details) => {
+                    let _ = tx.send(UiMessage::AttendanceDetailsLoaded(details));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::LoadError(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Navigate to next page of report results.
+    pub fn next_page(&mut self) {
+        let total_pages = self.report_filter.total_pages();
+        if self.report_filter.current_page + 1 < total_pages {
+            self.report_filter.current_page += 1;
+            self.generate_report();
+        }
+    }
+
+    /// Navigate to previous page of report results.
     pub fn prev_page(&mut self) {
         if self.report_filter.current_page > 0 {
             self.report_filter.current_page -= 1;
@@ -628,143 +1949,743 @@ impl App {
         }
     }
 
-    /// Go to first page of report results.
-    pub fn first_page(&mut self) {
-        if self.report_filter.current_page != 0 {
-            self.report_filter.current_page = 0;
-            self.generate_report();
-        }
+    /// Go to first page of report results.
+    pub fn first_page(&mut self) {
+        if self.report_filter.current_page != 0 {
+            self.report_filter.current_page = 0;
+            self.generate_report();
+        }
+    }
+
+    /// Go to last page of report results.
+    pub fn last_page(&mut self) {
+        let total_pages = self.report_filter.total_pages();
+        let last_page = if total_pages > 0 { total_pages - 1 } else { 0 };
+        if self.report_filter.current_page != last_page {
+            self.report_filter.current_page = last_page;
+            self.generate_report();
+        }
+    }
+
+    /// Export summary report to Excel.
+    /// Fetches all data for the date range (not just paginated view).
+    pub fn export_summary_report(&mut self) {
+        if self.is_loading {
+            return;
+        }
+
+        // Show save dialog first (blocking, on main thread)
+        let default_name = crate::export::generate_export_filename("attendance_summary");
+        let Some(path) = crate::export::show_save_dialog(&default_name) else {
+            return; // User cancelled
+        };
+
+        self.is_loading = true;
+        self.loading_message = "Exporting summary report...".to_string();
+
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+        let filter = self.report_filter.clone();
+
+        self.rt.spawn(async move {
+            // Fetch all data for export (not paginated)
+            let result = db::attendance::get_all_daily_summary_for_export(
+                &pool,
+                filter.start_date,
+                filter.end_date,
+                filter.department_id,
+            )
+            .await;
+
+            match result {
+                Ok(data) => {
+                    if data.is_empty() {
+                        let _ = tx.send(UiMessage::ExportFailed(
+                            "No data to export. Generate a report first.".to_string(),
+                        ));
+                        return;
+                    }
+
+                    match crate::export::export_attendance_summary_to_excel(&data, &path) {
+                        Ok(()) => {
+                            let _ = tx.send(UiMessage::ExportCompleted(path.display().to_string()));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(UiMessage::ExportFailed(e.to_string()));
+                        }
+                    }
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::ExportFailed(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Export the monthly timesheet for the month of the report start date.
+    pub fn export_timesheet_report(&mut self) {
+        if self.is_loading {
+            return;
+        }
+
+        // Show save dialog first (blocking, on main thread)
+        let default_name = crate::export::generate_export_filename("timesheet");
+        let Some(path) = crate::export::show_save_dialog(&default_name) else {
+            return; // User cancelled
+        };
+
+        self.is_loading = true;
+        self.loading_message = "Exporting timesheet...".to_string();
+
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+        let (first, last) = crate::export::month_bounds(self.report_filter.start_date);
+        let department_id = self.report_filter.department_id;
+
+        self.rt.spawn(async move {
+            match db::attendance::get_daily_status(&pool, first, last, department_id).await {
+                Ok(data) => {
+                    if data.is_empty() {
+                        let _ = tx.send(UiMessage::ExportFailed(
+                            "No employees to put on the timesheet.".to_string(),
+                        ));
+                        return;
+                    }
+
+                    match crate::export::export_timesheet_to_excel(&data, first, &path) {
+                        Ok(()) => {
+                            let _ = tx.send(UiMessage::ExportCompleted(path.display().to_string()));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(UiMessage::ExportFailed(e.to_string()));
+                        }
+                    }
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::ExportFailed(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Export monthly overtime totals to Excel.
+    /// Fetches all data for the date range (not just paginated view).
+    pub fn export_overtime_report(&mut self) {
+        if self.is_loading {
+            return;
+        }
+
+        // Show save dialog first (blocking, on main thread)
+        let default_name = crate::export::generate_export_filename("overtime");
+        let Some(path) = crate::export::show_save_dialog(&default_name) else {
+            return; // User cancelled
+        };
+
+        self.is_loading = true;
+        self.loading_message = "Exporting overtime report...".to_string();
+
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+        let filter = self.report_filter.clone();
+
+        self.rt.spawn(async move {
+            let result = db::attendance::get_all_daily_summary_for_export(
+                &pool,
+                filter.start_date,
+                filter.end_date,
+                filter.department_id,
+            )
+            .await;
+
+            match result {
+                Ok(data) => {
+                    if data.iter().all(|r| r.overtime.minutes == 0) {
+                        let _ = tx.send(UiMessage::ExportFailed(
+                            "No overtime in the selected range.".to_string(),
+                        ));
+                        return;
+                    }
+
+                    match crate::export::export_overtime_to_excel(&data, &path) {
+                        Ok(()) => {
+                            let _ = tx.send(UiMessage::ExportCompleted(path.display().to_string()));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(UiMessage::ExportFailed(e.to_string()));
+                        }
+                    }
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::ExportFailed(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Export late arrival, early departure and absence status to Excel.
+    /// Computes the status for the whole date range (not just the current page).
+    pub fn export_status_report(&mut self) {
+        if self.is_loading {
+            return;
+        }
+
+        // Show save dialog first (blocking, on main thread)
+        let default_name = crate::export::generate_export_filename("attendance_status");
+        let Some(path) = crate::export::show_save_dialog(&default_name) else {
+            return; // User cancelled
+        };
+
+        self.is_loading = true;
+        self.loading_message = "Exporting late & absence report...".to_string();
+
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+        let filter = self.report_filter.clone();
+
+        self.rt.spawn(async move {
+            let result =
+                db::attendance::get_daily_status(&pool, filter.start_date, filter.end_date, filter.department_id).await;
+
+            match result {
+                Ok(data) => {
+                    if data.is_empty() {
+                        let _ = tx.send(UiMessage::ExportFailed(
+                            "No data to export. Generate a report first.".to_string(),
+                        ));
+                        return;
+                    }
+
+                    match crate::export::export_attendance_status_to_excel(&data, &path) {
+                        Ok(()) => {
+                            let _ = tx.send(UiMessage::ExportCompleted(path.display().to_string()));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(UiMessage::ExportFailed(e.to_string()));
+                        }
+                    }
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::ExportFailed(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Export detail report to Excel.
+    /// Fetches all data for the date range (not just paginated view).
+    pub fn export_detail_report(&mut self) {
+        if self.is_loading {
+            return;
+        }
+
+        // Show save dialog first (blocking, on main thread)
+        let default_name = crate::export::generate_export_filename("attendance_detail");
+        let Some(path) = crate::export::show_save_dialog(&default_name) else {
+            return; // User cancelled
+        };
+
+        self.is_loading = true;
+        self.loading_message = "Exporting detail report...".to_string();
+
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+        let filter = self.report_filter.clone();
+
+        self.rt.spawn(async move {
+            // Fetch all data for export (not paginated)
+            let result = db::attendance::get_all_attendance_details_for_export(
+                &pool,
+                filter.start_date,
+                filter.end_date,
+                filter.department_id,
+                filter.include_duplicates,
+            )
+            .await;
+
+            match result {
+                Ok(data) => {
+                    if data.is_empty() {
+                        let _ = tx.send(UiMessage::ExportFailed(
+                            "No data to export. Generate a report first.".to_string(),
+                        ));
+                        return;
+                    }
+
+                    match crate::export::export_attendance_detail_to_excel(&data, &path) {
+                        Ok(()) => {
+                            let _ = tx.send(UiMessage::ExportCompleted(path.display().to_string()));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(UiMessage::ExportFailed(e.to_string()));
+                        }
+                    }
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::ExportFailed(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Create a new department.
+    pub fn create_department(&mut self, data: CreateDepartment) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            match db::department::create(&pool, data).await {
+                Ok(dept) => {
+                    let _ = tx.send(UiMessage::DepartmentSaved(dept));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Update an existing department.
+    pub fn update_department(&mut self, id: i32, data: UpdateDepartment) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            match db::department::update(&pool, id, data).await {
+                Ok(Some(dept)) => {
+                    let _ = tx.send(UiMessage::DepartmentSaved(dept));
+                }
+                Ok(None) => {
+                    let _ = tx.send(UiMessage::OperationFailed("Department not found".to_string()));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Delete a department.
+    pub fn delete_department(&mut self, id: i32) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            match db::department::delete(&pool, id).await {
+                Ok(true) => {
+                    let _ = tx.send(UiMessage::DepartmentDeleted(id));
+                }
+                Ok(false) => {
+                    let _ = tx.send(UiMessage::OperationFailed("Department not found".to_string()));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Create a new shift.
+    pub fn create_shift(&mut self, data: CreateShift) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            match db::shift::create_shift(&pool, data).await {
+                Ok(shift) => {
+                    let _ = tx.send(UiMessage::ShiftSaved(shift));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Update an existing shift.
+    pub fn update_shift(&mut self, id: i32, data: UpdateShift) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            match db::shift::update_shift(&pool, id, data).await {
+                Ok(Some(shift)) => {
+                    let _ = tx.send(UiMessage::ShiftSaved(shift));
+                }
+                Ok(None) => {
+                    let _ = tx.send(UiMessage::OperationFailed("Shift not found".to_string()));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Delete a shift.
+    pub fn delete_shift(&mut self, id: i32) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            match db::shift::delete_shift(&pool, id).await {
+                Ok(true) => {
+                    let _ = tx.send(UiMessage::ShiftDeleted(id));
+                }
+                Ok(false) => {
+                    let _ = tx.send(UiMessage::OperationFailed("Shift not found".to_string()));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Create a rotation pattern, or replace an existing one when `id` is set.
+    pub fn save_shift_pattern(&mut self, id: Option<i32>, data: SaveShiftPattern) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            let name = data.name.clone();
+            match db::shift::save_pattern(&pool, id, data).await {
+                Ok(_) => {
+                    let _ = tx.send(UiMessage::ShiftPatternSaved(name));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Delete a rotation pattern.
+    pub fn delete_shift_pattern(&mut self, id: i32) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            match db::shift::delete_pattern(&pool, id).await {
+                Ok(true) => {
+                    let _ = tx.send(UiMessage::ShiftPatternDeleted(id));
+                }
+                Ok(false) => {
+                    let _ = tx.send(UiMessage::OperationFailed("Pattern not found".to_string()));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Assign a rotation pattern to an employee or department.
+    pub fn create_shift_assignment(&mut self, data: CreateShiftAssignment) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            match db::shift::create_assignment(&pool, data).await {
+                Ok(_) => {
+                    let _ = tx.send(UiMessage::ShiftAssignmentSaved);
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Create a new holiday.
+    pub fn create_holiday(&mut self, data: CreateHoliday) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            match db::holiday::create_holiday(&pool, data).await {
+                Ok(holiday) => {
+                    let _ = tx.send(UiMessage::HolidaySaved(holiday));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Update an existing holiday.
+    pub fn update_holiday(&mut self, id: i32, data: UpdateHoliday) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            match db::holiday::update_holiday(&pool, id, data).await {
+                Ok(Some(holiday)) => {
+                    let _ = tx.send(UiMessage::HolidaySaved(holiday));
+                }
+                Ok(None) => {
+                    let _ = tx.send(UiMessage::OperationFailed("Holiday not found".to_string()));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Delete a holiday.
+    pub fn delete_holiday(&mut self, id: i32) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            match db::holiday::delete_holiday(&pool, id).await {
+                Ok(true) => {
+                    let _ = tx.send(UiMessage::HolidayDeleted(id));
+                }
+                Ok(false) => {
+                    let _ = tx.send(UiMessage::OperationFailed("Holiday not found".to_string()));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Import the bundled public holidays for the selected calendar year.
+    pub fn import_public_holidays(&mut self) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+        let year = self.holiday_year;
+
+        self.rt.spawn(async move {
+            match db::holiday::import_public_holidays(&pool, year).await {
+                Ok(count) => {
+                    let _ = tx.send(UiMessage::HolidaysImported(year, count));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Create a new leave type.
+    pub fn create_leave_type(&mut self, data: CreateLeaveType) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            match db::leave::create_leave_type(&pool, data).await {
+                Ok(leave_type) => {
+                    let _ = tx.send(UiMessage::LeaveTypeSaved(leave_type));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Update an existing leave type.
+    pub fn update_leave_type(&mut self, id: i32, data: UpdateLeaveType) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            match db::leave::update_leave_type(&pool, id, data).await {
+                Ok(Some(leave_type)) => {
+                    let _ = tx.send(UiMessage::LeaveTypeSaved(leave_type));
+                }
+                Ok(None) => {
+                    let _ = tx.send(UiMessage::OperationFailed("Leave type not found".to_string()));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
+                }
+            }
+        });
     }
 
-    /// Go to last page of report results.
-    pub fn last_page(&mut self) {
-        let total_pages = self.report_filter.total_pages();
-        let last_page = if total_pages > 0 { total_pages - 1 } else { 0 };
-        if self.report_filter.current_page != last_page {
-            self.report_filter.current_page = last_page;
-            self.generate_report();
-        }
+    /// Create a new leave request.
+    pub fn create_leave_request(&mut self, data: CreateLeaveRequest) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            match db::leave::create_leave_request(&pool, data).await {
+                Ok(request) => {
+                    let _ = tx.send(UiMessage::LeaveRequestSaved(request));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
+                }
+            }
+        });
     }
 
-    /// Export summary report to Excel.
-    /// Fetches all data for the date range (not just paginated view).
-    pub fn export_summary_report(&mut self) {
-        if self.is_loading {
-            return;
-        }
+    /// Update an existing leave request.
+    pub fn update_leave_request(&mut self, id: i32, data: UpdateLeaveRequest) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
 
-        // Show save dialog first (blocking, on main thread)
-        let default_name = crate::export::generate_export_filename("attendance_summary");
-        let Some(path) = crate::export::show_save_dialog(&default_name) else {
-            return; // User cancelled
-        };
+        self.rt.spawn(async move {
+            match db::leave::update_leave_request(&pool, id, data).await {
+                Ok(Some(request)) => {
+                    let _ = tx.send(UiMessage::LeaveRequestSaved(request));
+                }
+                Ok(None) => {
+                    let _ = tx.send(UiMessage::OperationFailed("Leave request not found".to_string()));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
+                }
+            }
+        });
+    }
 
-        self.is_loading = true;
-        self.loading_message = "Exporting summary report...".to_string();
+    /// Approve, reject or cancel a leave request as the current approver.
+    pub fn set_leave_status(&mut self, id: i32, status: &'static str) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+        let approver = Some(self.acting_user()).filter(|a| !a.is_empty());
+
+        self.rt.spawn(async move {
+            match db::leave::set_leave_status(&pool, id, status, approver).await {
+                Ok(Some(request)) => {
+                    let _ = tx.send(UiMessage::LeaveRequestDecided(request));
+                }
+                Ok(None) => {
+                    let _ = tx.send(UiMessage::OperationFailed("Leave request not found".to_string()));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
+                }
+            }
+        });
+    }
 
+    /// Delete a leave request.
+    pub fn delete_leave_request(&mut self, id: i32) {
         let pool = self.pool.clone();
         let tx = self.tx.clone();
-        let filter = self.report_filter.clone();
 
         self.rt.spawn(async move {
-            // Fetch all data for export (not paginated)
-            let result = db::attendance::get_all_daily_summary_for_export(
-                &pool,
-                filter.start_date,
-                filter.end_date,
-                filter.department_id,
-            )
-            .await;
+            match db::leave::delete_leave_request(&pool, id).await {
+                Ok(true) => {
+                    let _ = tx.send(UiMessage::LeaveRequestDeleted(id));
+                }
+                Ok(false) => {
+                    let _ = tx.send(UiMessage::OperationFailed("Leave request not found".to_string()));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
+                }
+            }
+        });
+    }
 
-            match result {
-                Ok(data) => {
-                    if data.is_empty() {
-                        let _ = tx.send(UiMessage::ExportFailed(
-                            "No data to export. Generate a report first.".to_string(),
-                        ));
-                        return;
-                    }
+    /// Set an employee's entitlement for a year.
+    pub fn set_leave_entitlement(&mut self, data: SetLeaveEntitlement) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
 
-                    match crate::export::export_attendance_summary_to_excel(&data, &path) {
-                        Ok(()) => {
-                            let _ = tx.send(UiMessage::ExportCompleted(path.display().to_string()));
-                        }
-                        Err(e) => {
-                            let _ = tx.send(UiMessage::ExportFailed(e.to_string()));
-                        }
-                    }
+        self.rt.spawn(async move {
+            match db::leave::set_entitlement(&pool, data).await {
+                Ok(()) => {
+                    let _ = tx.send(UiMessage::LeaveEntitlementSaved);
                 }
                 Err(e) => {
-                    let _ = tx.send(UiMessage::ExportFailed(e.to_string()));
+                    let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
                 }
             }
         });
     }
 
-    /// Export detail report to Excel.
-    /// Fetches all data for the date range (not just paginated view).
-    pub fn export_detail_report(&mut self) {
-        if self.is_loading {
-            return;
-        }
+    /// Remove an employee's entitlement for a year, reverting to the type default.
+    pub fn clear_leave_entitlement(&mut self, employee_id: i32, leave_type_id: i32, year: i32) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
 
-        // Show save dialog first (blocking, on main thread)
-        let default_name = crate::export::generate_export_filename("attendance_detail");
-        let Some(path) = crate::export::show_save_dialog(&default_name) else {
-            return; // User cancelled
-        };
+        self.rt.spawn(async move {
+            match db::leave::clear_entitlement(&pool, employee_id, leave_type_id, year).await {
+                Ok(_) => {
+                    let _ = tx.send(UiMessage::LeaveEntitlementSaved);
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
+                }
+            }
+        });
+    }
 
-        self.is_loading = true;
-        self.loading_message = "Exporting detail report...".to_string();
+    /// Add a forgotten punch.
+    pub fn add_punch(&mut self, data: AddPunch) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            match db::punch_correction::add_punch(&pool, data).await {
+                Ok(_) => {
+                    let _ = tx.send(UiMessage::PunchCorrected("Punch added"));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
+                }
+            }
+        });
+    }
 
+    /// Replace a punch with a manual one.
+    pub fn correct_punch(&mut self, id: i64, data: CorrectPunch) {
         let pool = self.pool.clone();
         let tx = self.tx.clone();
-        let filter = self.report_filter.clone();
 
         self.rt.spawn(async move {
-            // Fetch all data for export (not paginated)
-            let result = db::attendance::get_all_attendance_details_for_export(
-                &pool,
-                filter.start_date,
-                filter.end_date,
-                filter.department_id,
-            )
-            .await;
+            match db::punch_correction::correct_punch(&pool, id, data).await {
+                Ok(Some(_)) => {
+                    let _ = tx.send(UiMessage::PunchCorrected("Punch corrected"));
+                }
+                Ok(None) => {
+                    let _ = tx.send(UiMessage::OperationFailed("Punch not found".to_string()));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
+                }
+            }
+        });
+    }
 
-            match result {
-                Ok(data) => {
-                    if data.is_empty() {
-                        let _ = tx.send(UiMessage::ExportFailed(
-                            "No data to export. Generate a report first.".to_string(),
-                        ));
-                        return;
-                    }
+    /// Void a punch.
+    pub fn void_punch(&mut self, id: i64, data: VoidPunch) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
 
-                    match crate::export::export_attendance_detail_to_excel(&data, &path) {
-                        Ok(()) => {
-                            let _ = tx.send(UiMessage::ExportCompleted(path.display().to_string()));
-                        }
-                        Err(e) => {
-                            let _ = tx.send(UiMessage::ExportFailed(e.to_string()));
-                        }
-                    }
+        self.rt.spawn(async move {
+            match db::punch_correction::void_punch(&pool, id, data).await {
+                Ok(true) => {
+                    let _ = tx.send(UiMessage::PunchCorrected("Punch voided"));
+                }
+                Ok(false) => {
+                    let _ = tx.send(UiMessage::OperationFailed("Punch not found".to_string()));
                 }
                 Err(e) => {
-                    let _ = tx.send(UiMessage::ExportFailed(e.to_string()));
+                    let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
                 }
             }
         });
     }
 
-    /// Create a new department.
-    pub fn create_department(&mut self, data: CreateDepartment) {
+    /// Create a user account.
+    pub fn create_user(&mut self, data: CreateUser) {
         let pool = self.pool.clone();
         let tx = self.tx.clone();
 
         self.rt.spawn(async move {
-            match db::department::create(&pool, data).await {
-                Ok(dept) => {
-                    let _ = tx.send(UiMessage::DepartmentSaved(dept));
+            match db::user::create(&pool, data).await {
+                Ok(user) => {
+                    let _ = tx.send(UiMessage::UserSaved(user));
                 }
                 Err(e) => {
                     let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
@@ -773,18 +2694,80 @@ impl App {
         });
     }
 
-    /// Update an existing department.
-    pub fn update_department(&mut self, id: i32, data: UpdateDepartment) {
+    /// Update a user account.
+    pub fn update_user(&mut self, id: i32, data: UpdateUser) {
         let pool = self.pool.clone();
         let tx = self.tx.clone();
 
         self.rt.spawn(async move {
-            match db::department::update(&pool, id, data).await {
-                Ok(Some(dept)) => {
-                    let _ = tx.send(UiMessage::DepartmentSaved(dept));
+            match db::user::update(&pool, id, data).await {
+                Ok(Some(user)) => {
+                    let _ = tx.send(UiMessage::UserSaved(user));
                 }
                 Ok(None) => {
-                    let _ = tx.send(UiMessage::OperationFailed("Department not found".to_string()));
+                    let _ = tx.send(UiMessage::OperationFailed("User not found".to_string()));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Delete a shift assignment.
+    pub fn delete_shift_assignment(&mut self, id: i32) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            match db::shift::delete_assignment(&pool, id).await {
+                Ok(true) => {
+                    let _ = tx.send(UiMessage::ShiftAssignmentDeleted(id));
+                }
+                Ok(false) => {
+                    let _ = tx.send(UiMessage::OperationFailed("Assignment not found".to_string()));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Set or clear the roster entry for one employee and day.
+    ///
+    /// `None` clears the entry so the assigned pattern applies again.
+    pub fn set_roster_entry(&mut self, employee_id: i32, work_date: NaiveDate, entry: Option<SetRosterEntry>) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            let result = match entry {
+                Some(data) => db::shift::set_roster_entry(&pool, data).await,
+                None => db::shift::clear_roster_entry(&pool, employee_id, work_date)
+                    .await
+                    .map(|_| ()),
+            };
+            match result {
+                Ok(()) => {
+                    let _ = tx.send(UiMessage::RosterSaved);
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Create a new employee.
+    pub fn create_employee(&mut self, data: CreateEmployee) {
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            match db::employee::create(&pool, data).await {
+                Ok(emp) => {
+                    let _ = tx.send(UiMessage::EmployeeSaved(emp));
                 }
                 Err(e) => {
                     let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
@@ -793,18 +2776,18 @@ impl App {
         });
     }
 
-    /// Delete a department.
-    pub fn delete_department(&mut self, id: i32) {
+    /// Update an existing employee.
+    pub fn update_employee(&mut self, id: i32, data: UpdateEmployee) {
         let pool = self.pool.clone();
         let tx = self.tx.clone();
 
         self.rt.spawn(async move {
-            match db::department::delete(&pool, id).await {
-                Ok(true) => {
-                    let _ = tx.send(UiMessage::DepartmentDeleted(id));
+            match db::employee::update(&pool, id, data).await {
+                Ok(Some(emp)) => {
+                    let _ = tx.send(UiMessage::EmployeeSaved(emp));
                 }
-                Ok(false) => {
-                    let _ = tx.send(UiMessage::OperationFailed("Department not found".to_string()));
+                Ok(None) => {
+                    let _ = tx.send(UiMessage::OperationFailed("Employee not found".to_string()));
                 }
                 Err(e) => {
                     let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
@@ -813,15 +2796,32 @@ impl App {
         });
     }
 
-    /// Create a new employee.
-    pub fn create_employee(&mut self, data: CreateEmployee) {
+    /// Offboard a leaver, optionally removing them from the device.
+    pub fn offboard_employee(&mut self, id: i32, data: OffboardEmployee, remove_from_device: bool) {
+        if remove_from_device && !self.can(Permission::SyncDevice) {
+            self.error_message = Some("You are not allowed to change the device".to_string());
+            return;
+        }
+        let config = self.config.clone();
         let pool = self.pool.clone();
         let tx = self.tx.clone();
 
         self.rt.spawn(async move {
-            match db::employee::create(&pool, data).await {
-                Ok(emp) => {
-                    let _ = tx.send(UiMessage::EmployeeSaved(emp));
+            match db::employee::offboard(&pool, id, data).await {
+                Ok(Some(emp)) => {
+                    let device = match emp.scanner_uid.filter(|_| remove_from_device) {
+                        Some(uid) => Some(
+                            SyncService::new(config, pool)
+                                .remove_device_user(uid)
+                                .await
+                                .map_err(|e| e.to_string()),
+                        ),
+                        None => None,
+                    };
+                    let _ = tx.send(UiMessage::EmployeeOffboarded(emp, device));
+                }
+                Ok(None) => {
+                    let _ = tx.send(UiMessage::OperationFailed("Employee not found".to_string()));
                 }
                 Err(e) => {
                     let _ = tx.send(UiMessage::OperationFailed(e.to_string()));
@@ -830,15 +2830,15 @@ impl App {
         });
     }
 
-    /// Update an existing employee.
-    pub fn update_employee(&mut self, id: i32, data: UpdateEmployee) {
+    /// Rehire a leaver.
+    pub fn rehire_employee(&mut self, id: i32, data: RehireEmployee) {
         let pool = self.pool.clone();
         let tx = self.tx.clone();
 
         self.rt.spawn(async move {
-            match db::employee::update(&pool, id, data).await {
+            match db::employee::rehire(&pool, id, data).await {
                 Ok(Some(emp)) => {
-                    let _ = tx.send(UiMessage::EmployeeSaved(emp));
+                    let _ = tx.send(UiMessage::EmployeeRehired(emp));
                 }
                 Ok(None) => {
                     let _ = tx.send(UiMessage::OperationFailed("Employee not found".to_string()));
@@ -933,8 +2933,59 @@ impl App {
         });
     }
 
+    /// Download from the device and preview what a sync would insert.
+    pub fn start_sync_preview(&mut self) {
+        self.sync_preview_loading = true;
+        self.log_info("Building sync preview...");
+
+        let config = self.config.clone();
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            let service = SyncService::new(config, pool);
+            match service.preview().await {
+                Ok(preview) => {
+                    let _ = tx.send(UiMessage::SyncPreviewLoaded(preview));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::SyncPreviewFailed(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Insert the new records from the current sync preview.
+    pub fn commit_sync_preview(&mut self) {
+        let Some(preview) = self.sync_preview.clone() else {
+            return;
+        };
+        self.sync_preview_committing = true;
+        self.log_info(format!("Committing {} previewed records...", preview.new_records.len()));
+
+        let config = self.config.clone();
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            let service = SyncService::new(config, pool);
+            match service.commit_preview(&preview).await {
+                Ok(result) => {
+                    let _ = tx.send(UiMessage::SyncPreviewCommitted(result));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::SyncPreviewFailed(e.to_string()));
+                }
+            }
+        });
+    }
+
     /// Clear all attendance records from device.
     pub fn clear_device(&mut self) {
+        if !self.can(Permission::ClearDevice) {
+            self.error_message = Some("Only an admin can clear the device".to_string());
+            return;
+        }
         self.device_clearing = true;
         self.log_info("Clearing device attendance records...");
 
@@ -982,13 +3033,65 @@ impl App {
 
     /// Save configuration to file.
     pub fn save_config(&mut self) {
+        if !self.can(Permission::EditSettings) {
+            self.error_message = Some("Only an admin can change settings".to_string());
+            return;
+        }
         let config_path = AppConfig::default_path();
 
+        if self.config.general.timezone().is_none() {
+            let message = format!("Unknown timezone '{}'", self.config.general.timezone);
+            self.error_message = Some(message.clone());
+            self.log_error(message);
+            return;
+        }
+        if self.config.general.day_start().is_none() {
+            let message = format!("Work day start '{}' must be HH:MM", self.config.general.day_start);
+            self.error_message = Some(message.clone());
+            self.log_error(message);
+            return;
+        }
+        if self.double_punch_window > db::settings::MAX_DOUBLE_PUNCH_WINDOW_SECS {
+            let message = format!(
+                "Double-punch window cannot exceed {} seconds",
+                db::settings::MAX_DOUBLE_PUNCH_WINDOW_SECS
+            );
+            self.error_message = Some(message.clone());
+            self.log_error(message);
+            return;
+        }
+        let overtime_rules = match self.overtime_form.rules() {
+            Ok(rules) => rules,
+            Err(message) => {
+                self.error_message = Some(message.clone());
+                self.log_error(message);
+                return;
+            }
+        };
+
         match self.config.save(&config_path) {
             Ok(()) => {
                 self.config_modified = false;
                 self.success_message = Some("Settings saved successfully".to_string());
                 self.log_success("Settings saved");
+                if self
+                    .applied_double_punch_window
+                    .is_some_and(|applied| applied != self.double_punch_window)
+                {
+                    self.save_double_punch_window();
+                }
+                if self
+                    .applied_overtime_rules
+                    .is_some_and(|applied| applied != overtime_rules)
+                {
+                    self.save_overtime_rules(overtime_rules);
+                }
+                if self.config.general.timezone() != Some(timezone::business()) {
+                    self.log_warning("Business timezone changes apply after restart");
+                }
+                if self.config.general.day_start() != Some(timezone::day_start()) {
+                    self.log_warning("Work day start changes apply after restart");
+                }
             }
             Err(e) => {
                 self.error_message = Some(format!("Failed to save settings: {e}"));
@@ -997,6 +3100,52 @@ impl App {
         }
     }
 
+    /// Store the edited double-punch window and re-evaluate the flags of all logs.
+    fn save_double_punch_window(&mut self) {
+        let window = self.double_punch_window;
+        self.applied_double_punch_window = Some(window);
+        self.log_info(format!("Re-applying double-punch window ({window}s)..."));
+
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+        self.rt.spawn(async move {
+            let result = match db::settings::set_double_punch_window(&pool, window).await {
+                Ok(_) => db::attendance::flag_double_punches(&pool, window, None).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(changed) => {
+                    let _ = tx.send(UiMessage::DoublePunchesFlagged(changed));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::OperationFailed(format!(
+                        "Failed to apply double-punch window: {e}"
+                    )));
+                }
+            }
+        });
+    }
+
+    /// Store the edited overtime rules.
+    fn save_overtime_rules(&mut self, rules: OvertimeRules) {
+        self.applied_overtime_rules = Some(rules);
+
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+        self.rt.spawn(async move {
+            match db::settings::set_overtime_rules(&pool, &rules).await {
+                Ok(_) => {
+                    let _ = tx.send(UiMessage::OvertimeRulesSaved);
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::OperationFailed(format!(
+                        "Failed to save overtime rules: {e}"
+                    )));
+                }
+            }
+        });
+    }
+
     /// Clear the activity log.
     pub fn clear_log(&mut self) {
         self.log_messages.clear();
@@ -1057,6 +3206,9 @@ impl App {
 
     /// Start sync operation.
     pub fn start_sync(&mut self) {
+        if !self.can(Permission::SyncDevice) {
+            return;
+        }
         let (tx, rx) = mpsc::unbounded_channel();
         self.sync_progress_rx = Some(rx);
         self.sync_state = SyncState::InProgress {
@@ -1064,15 +3216,66 @@ impl App {
             message: "Starting...".to_string(),
         };
 
+        let cancel = CancellationToken::new();
+        self.sync_cancel = Some(cancel.clone());
+
         let config = self.config.clone();
         let db = self.pool.clone();
 
         self.rt.spawn(async move {
             let _ = tx.send(SyncProgress::Started);
-            run_sync_background(config, db, tx).await;
+            run_sync_background(config, db, tx, cancel).await;
+        });
+    }
+
+    /// Insert records spooled by earlier syncs that could not reach the database.
+    pub fn replay_spool(&mut self) {
+        self.refresh_spool_pending();
+        if self.spool_pending == 0 {
+            return;
+        }
+        self.log_info(format!("Replaying {} spooled records...", self.spool_pending));
+
+        let config = self.config.clone();
+        let pool = self.pool.clone();
+        let tx = self.tx.clone();
+
+        self.rt.spawn(async move {
+            let service = SyncService::new(config, pool);
+            match service.replay_spool().await {
+                Ok(replayed) => {
+                    let _ = tx.send(UiMessage::SpoolReplayed(replayed));
+                }
+                Err(e) => {
+                    let _ = tx.send(UiMessage::SpoolReplayFailed(e.to_string()));
+                }
+            }
         });
     }
 
+    /// Recount records waiting in the local spool.
+    fn refresh_spool_pending(&mut self) {
+        match Spool::default_location().pending_records() {
+            Ok(count) => self.spool_pending = count,
+            Err(e) => tracing::warn!("Failed to read sync spool: {e}"),
+        }
+    }
+
+    /// Request cancellation of the running sync.
+    ///
+    /// Takes effect at the next download chunk or insert batch.
+    pub fn cancel_sync(&mut self) {
+        if let Some(cancel) = &self.sync_cancel {
+            cancel.cancel();
+            self.log_info("Cancelling sync...");
+        }
+    }
+
+    /// Check whether a cancellation has been requested for the running sync.
+    pub fn is_sync_cancelling(&self) -> bool {
+        self.sync_cancel.as_ref().is_some_and(CancellationToken::is_cancelled)
+    }
+
     /// Export today's attendance report to Excel.
     /// Fetches data directly from the database (not from cached in-memory data).
     pub fn export_today_report(&mut self) {
@@ -1091,11 +3294,15 @@ impl App {
 
         let pool = self.pool.clone();
         let tx = self.tx.clone();
-        let today = chrono::Local::now().date_naive();
+        let today = timezone::today();
+        let scope = self.session.as_ref().and_then(|s| s.scope.clone());
 
         self.rt.spawn(async move {
             match db::attendance::get_daily_summary(&pool, today, today).await {
-                Ok(data) => {
+                Ok(mut data) => {
+                    if let Some(scope) = &scope {
+                        data.retain(|a| a.department_id.is_some_and(|id| scope.contains(&id)));
+                    }
                     if data.is_empty() {
                         let _ = tx.send(UiMessage::ExportFailed("No attendance data for today".to_string()));
                         return;
@@ -1123,21 +3330,90 @@ impl App {
         while let Ok(msg) = self.rx.try_recv() {
             match msg {
                 UiMessage::DepartmentsLoaded(deps) => {
+                    let deps = deps.into_iter().filter(|d| self.in_scope(Some(d.id))).collect();
                     self.departments = deps;
                     self.is_loading = false;
                 }
                 UiMessage::EmployeesLoaded(emps) => {
+                    let emps = emps.into_iter().filter(|e| self.in_scope(e.department_id)).collect();
                     self.employees = emps;
                     self.is_loading = false;
                 }
+                UiMessage::ShiftsLoaded(shifts) => {
+                    self.shifts = shifts;
+                }
+                UiMessage::ShiftPatternsLoaded(patterns) => {
+                    self.shift_patterns = patterns;
+                }
+                UiMessage::ShiftAssignmentsLoaded(assignments) => {
+                    self.shift_assignments = assignments;
+                }
+                UiMessage::RosterLoaded(schedule, holidays) => {
+                    self.roster_schedule = Some(schedule);
+                    self.roster_holidays = holidays;
+                }
+                UiMessage::HolidaysLoaded(holidays) => {
+                    self.holidays = holidays;
+                }
+                UiMessage::TodayHolidayLoaded(name) => {
+                    self.today_holiday = name;
+                }
+                UiMessage::SharedSettingsLoaded(window, rules) => {
+                    self.double_punch_window = window;
+                    self.applied_double_punch_window = Some(window);
+                    self.overtime_form = OvertimeForm::new(&rules);
+                    self.applied_overtime_rules = Some(rules);
+                }
+                UiMessage::LeaveTypesLoaded(types) => {
+                    self.leave_types = types;
+                }
+                UiMessage::LeaveRequestsLoaded(requests) => {
+                    let requests = requests
+                        .into_iter()
+                        .filter(|r| self.employee_in_scope(r.employee_id))
+                        .collect();
+                    self.leave_requests = requests;
+                }
+                UiMessage::LeaveBalancesLoaded(balances) => {
+                    let balances = balances
+                        .into_iter()
+                        .filter(|b| self.employee_in_scope(b.employee_id))
+                        .collect();
+                    self.leave_balances = balances;
+                }
                 UiMessage::AttendanceLoaded(att) => {
+                    let att = att.into_iter().filter(|a| self.in_scope(a.department_id)).collect();
                     self.attendance = att;
                     self.is_loading = false;
                 }
                 UiMessage::AttendanceDetailsLoaded(details) => {
+                    let details = details.into_iter().filter(|d| self.in_scope(d.department_id)).collect();
                     self.attendance_details = details;
                     self.is_loading = false;
                 }
+                UiMessage::AttendanceStatusLoaded(status) => {
+                    let status: Vec<_> = status.into_iter().filter(|s| self.in_scope(s.department_id)).collect();
+                    self.report_filter.total_records = status.len() as u64;
+                    self.attendance_status = status;
+                    self.is_loading = false;
+                }
+                UiMessage::UnmatchedPunchesLoaded(mut unmatched) => {
+                    // Punches without an employee belong to no department, so only device users see them
+                    if !self.can(Permission::SyncDevice) {
+                        unmatched.clear();
+                    }
+                    self.unmatched_link_targets
+                        .retain(|uid, _| unmatched.iter().any(|u| u.scanner_uid == *uid));
+                    self.unmatched_punches = unmatched;
+                }
+                UiMessage::UsersLoaded(users) => {
+                    self.users = users;
+                }
+                UiMessage::EmployeeAssignmentsLoaded(employee_id, assignments) => {
+                    if self.employee_form.id == Some(employee_id) {
+                        self.employee_form.assignments = assignments;
+                    }
+                }
                 UiMessage::AttendanceCountLoaded(count) => {
                     self.report_filter.total_records = count;
                 }
@@ -1149,13 +3425,37 @@ impl App {
                     self.log_error(e);
                     self.is_loading = false;
                 }
+                UiMessage::UserCountLoaded(count) => {
+                    self.user_count = Some(count);
+                }
+                UiMessage::SignedIn(session) => {
+                    db::audit::set_actor(&session.user.username);
+                    self.log_info(format!(
+                        "Signed in: {name} ({role})",
+                        name = session.display_name(),
+                        role = user_role::label(&session.user.role)
+                    ));
+                    if session.scope.is_some() {
+                        // Managers have no "all departments" view
+                        self.report_filter.department_id = session.user.department_id;
+                    }
+                    self.session = Some(session);
+                    self.login_form.reset();
+                    self.load_initial_data();
+                }
+                UiMessage::SignInFailed(e) => {
+                    self.login_form.is_busy = false;
+                    self.login_form.password.clear();
+                    self.login_form.confirm_password.clear();
+                    self.login_form.error = Some(e);
+                }
                 UiMessage::SyncProgress(progress, message) => {
                     self.sync_progress = progress;
                     self.sync_status = message;
                 }
                 UiMessage::SyncCompleted(result) => {
                     self.is_syncing = false;
-                    self.last_sync_time = Some(Local::now());
+                    self.last_sync_time = Some(timezone::now());
                     self.log_success(format!(
                         "Sync completed: {} inserted, {} skipped",
                         result.inserted, result.skipped
@@ -1166,6 +3466,56 @@ impl App {
                     self.error_message = Some(e.clone());
                     self.log_error(e);
                 }
+                UiMessage::SyncRunsLoaded(runs) => {
+                    self.sync_runs = runs;
+                }
+                UiMessage::AuditLogLoaded(entries) => {
+                    self.audit_entries = entries;
+                }
+                UiMessage::LastSyncLoaded(time) => {
+                    if self.last_sync_time.is_none() {
+                        self.last_sync_time = time;
+                    }
+                }
+                UiMessage::SpoolReplayed(replayed) => {
+                    self.refresh_spool_pending();
+                    self.log_success(format!("Replayed {replayed} spooled records"));
+                    self.load_sync_history();
+                    self.load_unmatched_punches();
+                }
+                UiMessage::SpoolReplayFailed(e) => {
+                    self.refresh_spool_pending();
+                    self.log_warning(format!("Spooled records not replayed yet: {e}"));
+                }
+                UiMessage::DoublePunchesFlagged(changed) => {
+                    self.log_success(format!("Double-punch flags updated ({changed} records changed)"));
+                }
+                UiMessage::OvertimeRulesSaved => {
+                    self.log_success("Overtime rules saved");
+                }
+                UiMessage::SyncPreviewLoaded(preview) => {
+                    self.sync_preview_loading = false;
+                    self.log_info(format!(
+                        "Sync preview: {} new, {} duplicates",
+                        preview.new_records.len(),
+                        preview.duplicates
+                    ));
+                    self.sync_preview = Some(preview);
+                }
+                UiMessage::SyncPreviewFailed(e) => {
+                    self.sync_preview_loading = false;
+                    self.sync_preview_committing = false;
+                    self.error_message = Some(format!("Sync preview failed: {e}"));
+                    self.log_error(format!("Sync preview failed: {e}"));
+                }
+                UiMessage::SyncPreviewCommitted(result) => {
+                    self.sync_preview_committing = false;
+                    self.sync_preview = None;
+                    self.last_sync_time = Some(timezone::now());
+                    self.log_success(result.summary());
+                    self.load_sync_history();
+                    self.load_unmatched_punches();
+                }
                 UiMessage::DepartmentSaved(dept) => {
                     self.success_message = Some(format!("Department '{name}' saved", name = dept.name));
                     self.department_form.reset();
@@ -1180,11 +3530,143 @@ impl App {
                     self.success_message = Some(format!("Employee '{name}' saved", name = emp.full_name));
                     self.employee_form.reset();
                     self.load_employees();
+                    // A new or changed scanner UID may resolve orphan punches
+                    self.load_unmatched_punches();
+                }
+                UiMessage::EmployeeOffboarded(emp, device) => {
+                    let name = emp.full_name;
+                    match device {
+                        Some(Err(e)) => {
+                            let message = format!("'{name}' offboarded, but removing them from the device failed: {e}");
+                            self.error_message = Some(message.clone());
+                            self.log_error(message);
+                        }
+                        device => {
+                            let message = match device {
+                                Some(Ok(true)) => format!("'{name}' offboarded and removed from the device"),
+                                Some(Ok(false)) => format!("'{name}' offboarded; they were not on the device"),
+                                _ => format!("'{name}' offboarded"),
+                            };
+                            self.success_message = Some(message.clone());
+                            self.log_success(message);
+                        }
+                    }
+                    self.offboard_form.reset();
+                    self.load_employees();
+                }
+                UiMessage::EmployeeRehired(emp) => {
+                    let message = format!("'{name}' rehired", name = emp.full_name);
+                    self.success_message = Some(message.clone());
+                    self.log_success(message);
+                    self.rehire_form.reset();
+                    self.load_employees();
                 }
                 UiMessage::EmployeeDeleted(id) => {
                     self.employees.retain(|e| e.id != id);
                     self.success_message = Some("Employee deleted".to_string());
                     self.log_success("Employee deleted");
+                    self.load_unmatched_punches();
+                }
+                UiMessage::ShiftSaved(shift) => {
+                    self.success_message = Some(format!("Shift '{name}' saved", name = shift.name));
+                    self.shift_form.reset();
+                    self.load_shifts();
+                }
+                UiMessage::ShiftDeleted(id) => {
+                    self.shifts.retain(|s| s.id != id);
+                    self.success_message = Some("Shift deleted".to_string());
+                    self.log_success("Shift deleted");
+                }
+                UiMessage::ShiftPatternSaved(name) => {
+                    self.success_message = Some(format!("Pattern '{name}' saved"));
+                    self.pattern_form.reset();
+                    self.load_shift_patterns();
+                    self.load_roster();
+                }
+                UiMessage::ShiftPatternDeleted(id) => {
+                    self.shift_patterns.retain(|p| p.id != id);
+                    self.success_message = Some("Pattern deleted".to_string());
+                    self.log_success("Pattern deleted");
+                }
+                UiMessage::ShiftAssignmentSaved => {
+                    self.success_message = Some("Shift assignment saved".to_string());
+                    self.assignment_form.reset();
+                    self.load_shift_assignments();
+                    self.load_roster();
+                }
+                UiMessage::ShiftAssignmentDeleted(id) => {
+                    self.shift_assignments.retain(|a| a.id != id);
+                    self.success_message = Some("Shift assignment deleted".to_string());
+                    self.log_success("Shift assignment deleted");
+                    self.load_roster();
+                }
+                UiMessage::RosterSaved => {
+                    self.load_roster();
+                }
+                UiMessage::HolidaySaved(holiday) => {
+                    self.success_message = Some(format!("Holiday '{name}' saved", name = holiday.name));
+                    self.holiday_form.reset();
+                    self.load_holidays();
+                    self.load_today_holiday();
+                }
+                UiMessage::HolidayDeleted(id) => {
+                    self.holidays.retain(|h| h.id != id);
+                    self.success_message = Some("Holiday deleted".to_string());
+                    self.log_success("Holiday deleted");
+                    self.load_today_holiday();
+                }
+                UiMessage::HolidaysImported(year, count) => {
+                    let message = format!("Imported {count} public holidays for {year}");
+                    self.success_message = Some(message.clone());
+                    self.log_success(message);
+                    self.load_holidays();
+                    self.load_today_holiday();
+                }
+                UiMessage::LeaveTypeSaved(leave_type) => {
+                    self.success_message = Some(format!("Leave type '{name}' saved", name = leave_type.name));
+                    self.leave_type_form.reset();
+                    self.load_leave_types();
+                    self.load_leave_balances();
+                }
+                UiMessage::LeaveRequestSaved(request) => {
+                    self.success_message = Some(format!("Leave request saved ({days} days)", days = request.days));
+                    self.leave_request_form.reset();
+                    self.load_leave_requests();
+                    self.load_leave_balances();
+                }
+                UiMessage::LeaveRequestDecided(request) => {
+                    let message = format!("Leave request #{id} {status}", id = request.id, status = request.status);
+                    self.success_message = Some(message.clone());
+                    self.log_success(message);
+                    self.load_leave_requests();
+                    self.load_leave_balances();
+                }
+                UiMessage::LeaveRequestDeleted(id) => {
+                    self.leave_requests.retain(|r| r.id != id);
+                    self.success_message = Some("Leave request deleted".to_string());
+                    self.log_success("Leave request deleted");
+                    self.load_leave_balances();
+                }
+                UiMessage::LeaveEntitlementSaved => {
+                    self.success_message = Some("Entitlement saved".to_string());
+                    self.entitlement_form.reset();
+                    self.load_leave_balances();
+                }
+                UiMessage::PunchCorrected(message) => {
+                    self.success_message = Some(message.to_string());
+                    self.log_success(message);
+                    self.punch_form.reset();
+                    self.generate_report();
+                }
+                UiMessage::UserSaved(user) => {
+                    self.success_message = Some(format!("User '{name}' saved", name = user.username));
+                    self.log_success(format!("User saved: {name}", name = user.username));
+                    if let Some(session) = self.session.as_mut().filter(|s| s.user.id == user.id) {
+                        // Role and department changes apply from the next sign-in
+                        session.user.display_name = user.display_name;
+                    }
+                    self.user_form.reset();
+                    self.load_users();
                 }
                 UiMessage::OperationFailed(e) => {
                     self.error_message = Some(e.clone());
@@ -1301,32 +3783,58 @@ impl App {
                         self.last_sync_time = Some(timestamp);
                         done = true;
                     }
+                    SyncProgress::Cancelled => {
+                        self.sync_state = SyncState::Idle;
+                        self.log_warning("Sync cancelled");
+                        done = true;
+                    }
                     SyncProgress::Error(e) => {
                         self.sync_state = SyncState::Error(e);
                         done = true;
                     }
                 }
             }
-            if !done {
+            if done {
+                self.sync_cancel = None;
+                self.refresh_spool_pending();
+                // Refresh history with the run just recorded
+                self.load_sync_history();
+                self.load_unmatched_punches();
+            } else {
                 self.sync_progress_rx = Some(rx);
             }
         }
     }
 
+    /// Whether the signed-in user may open `panel`.
+    pub fn can_open(&self, panel: Panel) -> bool {
+        match panel {
+            Panel::Sync | Panel::Unmatched => self.can(Permission::SyncDevice),
+            Panel::Settings => self.can(Permission::EditSettings),
+            Panel::Audit => self.can(Permission::ViewAudit),
+            Panel::Users => self.can(Permission::ManageUsers),
+            _ => self.session.is_some(),
+        }
+    }
+
     /// Render menu bar.
     fn show_menu_bar(&mut self, ctx: &egui::Context) {
         egui::TopBottomPanel::top("menu_bar").show(ctx, |ui| {
             egui::MenuBar::new().ui(ui, |ui| {
                 ui.menu_button("Tools", |ui| {
-                    if ui.button("Manage Scanner").clicked() {
+                    let can_edit_settings = self.can(Permission::EditSettings);
+                    if ui
+                        .add_enabled(can_edit_settings, egui::Button::new("Manage Scanner"))
+                        .clicked()
+                    {
                         self.scanner_dialog_open = true;
                         self.scanner_url_input = self.config.device.url.clone();
                         self.scanner_test_status = None;
                         ui.close();
                     }
                     ui.separator();
-                    let connect_enabled =
-                        !matches!(self.device_status, DeviceStatus::Connecting | DeviceStatus::Connected);
+                    let connect_enabled = self.can(Permission::SyncDevice)
+                        && !matches!(self.device_status, DeviceStatus::Connecting | DeviceStatus::Connected);
                     if ui
                         .add_enabled(connect_enabled, egui::Button::new("Connect Device"))
                         .clicked()
@@ -1342,10 +3850,36 @@ impl App {
                         self.disconnect_device();
                         ui.close();
                     }
+                    ui.separator();
+                    if ui
+                        .add_enabled(self.can_open(Panel::Audit), egui::Button::new("Audit Log"))
+                        .clicked()
+                    {
+                        self.current_panel = Panel::Audit;
+                        self.load_audit_log();
+                        ui.close();
+                    }
+                    if ui
+                        .add_enabled(self.can_open(Panel::Users), egui::Button::new("Users"))
+                        .clicked()
+                    {
+                        self.current_panel = Panel::Users;
+                        self.load_users();
+                        ui.close();
+                    }
                 });
                 ui.menu_button("Settings", |ui| {
-                    if ui.button("General").clicked() {
-                        // Future expansion
+                    if ui
+                        .add_enabled(self.can_open(Panel::Settings), egui::Button::new("General"))
+                        .clicked()
+                    {
+                        self.current_panel = Panel::Settings;
+                        ui.close();
+                    }
+                });
+                ui.menu_button("Account", |ui| {
+                    if ui.button("Sign Out").clicked() {
+                        self.sign_out();
                         ui.close();
                     }
                 });
@@ -1373,6 +3907,23 @@ impl App {
                     }
                     ui.colored_label(color, format!("Device: {text}"));
 
+                    if self.spool_pending > 0 {
+                        ui.separator();
+                        ui.colored_label(
+                            colors::WARNING,
+                            format!("Spooled: {} records pending", self.spool_pending),
+                        );
+                    }
+
+                    if let Some(session) = &self.session {
+                        ui.separator();
+                        ui.label(format!(
+                            "User: {name} ({role})",
+                            name = session.display_name(),
+                            role = user_role::label(&session.user.role)
+                        ));
+                    }
+
                     // Progress bar (right side)
                     ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
                         if let SyncState::InProgress { progress, message } = &self.sync_state {
@@ -1520,6 +4071,13 @@ impl App {
             let (title, message) = match target {
                 DeleteTarget::Department(_, name) => ("Delete Department", format!("Delete department '{name}'?")),
                 DeleteTarget::Employee(_, name) => ("Delete Employee", format!("Delete employee '{name}'?")),
+                DeleteTarget::Shift(_, name) => ("Delete Shift", format!("Delete shift '{name}'?")),
+                DeleteTarget::ShiftPattern(_, name) => ("Delete Pattern", format!("Delete pattern '{name}'?")),
+                DeleteTarget::ShiftAssignment(_, name) => ("Delete Assignment", format!("Delete assignment '{name}'?")),
+                DeleteTarget::Holiday(_, name) => ("Delete Holiday", format!("Delete holiday '{name}'?")),
+                DeleteTarget::LeaveRequest(_, name) => {
+                    ("Delete Leave Request", format!("Delete leave request '{name}'?"))
+                }
             };
 
             egui::Window::new(title)
@@ -1556,6 +4114,26 @@ impl App {
                     self.log_info(format!("Deleting employee: {name}"));
                     self.delete_employee(id);
                 }
+                DeleteTarget::Shift(id, name) => {
+                    self.log_info(format!("Deleting shift: {name}"));
+                    self.delete_shift(id);
+                }
+                DeleteTarget::ShiftPattern(id, name) => {
+                    self.log_info(format!("Deleting pattern: {name}"));
+                    self.delete_shift_pattern(id);
+                }
+                DeleteTarget::ShiftAssignment(id, name) => {
+                    self.log_info(format!("Deleting assignment: {name}"));
+                    self.delete_shift_assignment(id);
+                }
+                DeleteTarget::Holiday(id, name) => {
+                    self.log_info(format!("Deleting holiday: {name}"));
+                    self.delete_holiday(id);
+                }
+                DeleteTarget::LeaveRequest(id, name) => {
+                    self.log_info(format!("Deleting leave request: {name}"));
+                    self.delete_leave_request(id);
+                }
             }
         }
     }
@@ -1566,10 +4144,21 @@ impl eframe::App for App {
         // Poll async results
         self.poll_async_results();
 
+        // Nothing but the sign-in screen until someone signs in
+        if self.session.is_none() {
+            if self.login_form.is_busy || self.user_count.is_none() {
+                ctx.request_repaint();
+            }
+            egui::CentralPanel::default().show(ctx, |ui| login_panel::show(self, ui));
+            return;
+        }
+
         // Request repaint during async operations
         if matches!(self.device_status, DeviceStatus::Connecting)
             || matches!(self.sync_state, SyncState::InProgress { .. })
             || self.scanner_test_rx.is_some()
+            || self.sync_preview_loading
+            || self.sync_preview_committing
         {
             ctx.request_repaint();
         }
@@ -1583,9 +4172,16 @@ impl eframe::App for App {
         // Scanner dialog
         self.show_scanner_dialog(ctx);
 
+        // Sync preview window
+        sync_panel::show_preview_window(self, ctx);
+
         // Modal dialogs (error, success, delete confirmation)
         self.show_dialogs(ctx);
 
+        if !self.can_open(self.current_panel) {
+            self.current_panel = Panel::Dashboard;
+        }
+
         // Main content
         egui::CentralPanel::default().show(ctx, |ui| match self.current_panel {
             Panel::Dashboard => {
@@ -1603,6 +4199,16 @@ impl eframe::App for App {
                     self.current_panel = Panel::Dashboard;
                 }
             }
+            Panel::Shifts => {
+                if shift_panel::show(self, ui) {
+                    self.current_panel = Panel::Dashboard;
+                }
+            }
+            Panel::Leave => {
+                if leave_panel::show(self, ui) {
+                    self.current_panel = Panel::Dashboard;
+                }
+            }
             Panel::Sync => {
                 if sync_panel::show(self, ui) {
                     self.current_panel = Panel::Dashboard;
@@ -1618,10 +4224,44 @@ impl eframe::App for App {
                     self.current_panel = Panel::Dashboard;
                 }
             }
+            Panel::Unmatched => {
+                if unmatched_panel::show(self, ui) {
+                    self.current_panel = Panel::Dashboard;
+                }
+            }
+            Panel::Audit => {
+                if audit_panel::show(self, ui) {
+                    self.current_panel = Panel::Dashboard;
+                }
+            }
+            Panel::Users => {
+                if users_panel::show(self, ui) {
+                    self.current_panel = Panel::Dashboard;
+                }
+            }
         });
     }
 }
 
+/// Monday of the week containing `date`.
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - chrono::Duration::days(i64::from(date.weekday().num_days_from_monday()))
+}
+
+/// Build the session for a signed-in user, resolving a manager's department subtree.
+async fn start_session(pool: &DatabaseConnection, user: users::Model) -> Result<Session, String> {
+    let scope = match user.department_id {
+        Some(root) if user.role == user_role::MANAGER => {
+            let departments = db::department::list_all(pool)
+                .await
+                .map_err(|e| format!("Database error: {e}"))?;
+            Some(department_subtree(&departments, root))
+        }
+        _ => None,
+    };
+    Ok(Session { user, scope })
+}
+
 /// Test device connection (simple HTTP check).
 async fn test_device_connection(url: &str) -> Result<(), String> {
     if url.is_empty() {
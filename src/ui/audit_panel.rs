@@ -0,0 +1,277 @@
+//! Audit log panel for reviewing who changed what.
+
+use eframe::egui::{self, RichText, ScrollArea, Ui};
+use egui_phosphor::regular::MAGNIFYING_GLASS;
+
+use super::app::{AUDIT_LOG_LIMIT, App};
+use super::components::{back_button, colors, panel_header, styled_button_with_icon};
+use crate::entities::audit_log;
+use crate::models::audit::{audit_action, audit_entity, changed_fields};
+use crate::timezone;
+
+/// Show the audit log panel.
+///
+/// Returns `true` if the back button was clicked.
+pub fn show(app: &mut App, ui: &mut Ui) -> bool {
+    let mut go_back = false;
+
+    if back_button(ui) {
+        go_back = true;
+    }
+
+    panel_header(ui, "Audit Log");
+
+    show_filters(app, ui);
+
+    ui.add_space(10.0);
+
+    if app.audit_entries.len() as u64 >= AUDIT_LOG_LIMIT {
+        ui.label(
+            RichText::new(format!(
+                "Showing the latest {AUDIT_LOG_LIMIT} entries; narrow the filter to see older ones"
+            ))
+            .weak(),
+        );
+    } else {
+        ui.label(format!("{count} entries", count = app.audit_entries.len()));
+    }
+
+    ui.add_space(10.0);
+    ui.separator();
+    ui.add_space(10.0);
+
+    let selected = app
+        .audit_selected
+        .and_then(|id| app.audit_entries.iter().find(|e| e.id == id))
+        .cloned();
+    if let Some(entry) = selected {
+        show_entry(app, ui, &entry);
+        ui.add_space(10.0);
+        ui.separator();
+        ui.add_space(10.0);
+    }
+
+    show_table(app, ui);
+
+    go_back
+}
+
+fn show_filters(app: &mut App, ui: &mut Ui) {
+    let mut filter_changed = false;
+
+    ui.horizontal(|ui| {
+        let filter = &mut app.audit_filter;
+
+        ui.label("Entity:");
+        egui::ComboBox::from_id_salt("audit_entity_filter")
+            .width(110.0)
+            .selected_text(filter.entity.unwrap_or("All"))
+            .show_ui(ui, |ui| {
+                filter_changed |= ui.selectable_value(&mut filter.entity, None, "All").changed();
+                for entity in audit_entity::ALL {
+                    filter_changed |= ui.selectable_value(&mut filter.entity, Some(entity), entity).changed();
+                }
+            });
+
+        ui.add_space(10.0);
+
+        ui.label("Action:");
+        egui::ComboBox::from_id_salt("audit_action_filter")
+            .width(90.0)
+            .selected_text(filter.action.unwrap_or("All"))
+            .show_ui(ui, |ui| {
+                filter_changed |= ui.selectable_value(&mut filter.action, None, "All").changed();
+                for action in audit_action::ALL {
+                    filter_changed |= ui.selectable_value(&mut filter.action, Some(action), action).changed();
+                }
+            });
+
+        ui.add_space(10.0);
+
+        ui.label("Record ID:");
+        ui.add(
+            egui::TextEdit::singleline(&mut filter.entity_id)
+                .desired_width(80.0)
+                .hint_text("Any"),
+        );
+
+        ui.add_space(10.0);
+
+        ui.label("Actor:");
+        ui.add(
+            egui::TextEdit::singleline(&mut filter.actor)
+                .desired_width(120.0)
+                .hint_text("Anyone"),
+        );
+
+        ui.add_space(10.0);
+
+        ui.label("Period:");
+        for (label, days) in [
+            ("7 Days", Some(7)),
+            ("30 Days", Some(30)),
+            ("90 Days", Some(90)),
+            ("All", None),
+        ] {
+            if ui.selectable_label(filter.days == days, label).clicked() {
+                filter.days = days;
+                filter_changed = true;
+            }
+        }
+
+        ui.add_space(20.0);
+
+        if styled_button_with_icon(ui, MAGNIFYING_GLASS, "Search").clicked() {
+            filter_changed = true;
+        }
+    });
+
+    if filter_changed {
+        app.audit_selected = None;
+        app.load_audit_log();
+    }
+}
+
+fn show_table(app: &mut App, ui: &mut Ui) {
+    ScrollArea::both().id_salt("audit_log_scroll").show(ui, |ui| {
+        egui::Grid::new("audit_log_grid")
+            .num_columns(6)
+            .striped(true)
+            .min_col_width(70.0)
+            .spacing([12.0, 8.0])
+            .show(ui, |ui| {
+                ui.strong("Time");
+                ui.strong("Actor");
+                ui.strong("Entity");
+                ui.strong("Record");
+                ui.strong("Action");
+                ui.strong("Changes");
+                ui.end_row();
+
+                for entry in &app.audit_entries {
+                    let time = timezone::to_business(&entry.created_at);
+                    let is_selected = app.audit_selected == Some(entry.id);
+                    if ui
+                        .selectable_label(is_selected, time.format("%Y-%m-%d %H:%M:%S").to_string())
+                        .on_hover_text("Show before and after")
+                        .clicked()
+                    {
+                        app.audit_selected = if is_selected { None } else { Some(entry.id) };
+                    }
+                    ui.label(&entry.actor);
+                    ui.label(&entry.entity);
+                    ui.label(entry.entity_id.as_deref().unwrap_or("-"));
+                    ui.label(RichText::new(&entry.action).color(action_color(&entry.action)));
+                    ui.label(change_summary(entry));
+                    ui.end_row();
+                }
+
+                if app.audit_entries.is_empty() {
+                    ui.label("No changes recorded for this filter.");
+                    ui.end_row();
+                }
+            });
+    });
+}
+
+/// Before and after values of the selected entry side by side.
+fn show_entry(app: &mut App, ui: &mut Ui, entry: &audit_log::Model) {
+    ui.horizontal(|ui| {
+        ui.label(
+            RichText::new(format!(
+                "{} {} {}",
+                entry.action,
+                entry.entity,
+                entry.entity_id.as_deref().unwrap_or("")
+            ))
+            .strong(),
+        );
+        if ui.small_button("Close").clicked() {
+            app.audit_selected = None;
+        }
+    });
+
+    ui.add_space(5.0);
+
+    let changed = match (&entry.before_data, &entry.after_data) {
+        (Some(before), Some(after)) => changed_fields(before, after),
+        _ => Vec::new(),
+    };
+
+    ui.columns(2, |columns| {
+        let sides = [("Before", &entry.before_data), ("After", &entry.after_data)];
+        for (ui, (title, data)) in columns.iter_mut().zip(sides) {
+            show_snapshot(ui, title, data.as_ref(), &changed);
+        }
+    });
+}
+
+/// Fields of a record snapshot, highlighting the changed ones.
+fn show_snapshot(ui: &mut Ui, title: &str, data: Option<&serde_json::Value>, changed: &[String]) {
+    ui.label(RichText::new(title).strong());
+
+    let Some(data) = data else {
+        ui.label(RichText::new("-").weak());
+        return;
+    };
+    let Some(fields) = data.as_object() else {
+        ui.label(data.to_string());
+        return;
+    };
+
+    ScrollArea::vertical().id_salt(title).max_height(180.0).show(ui, |ui| {
+        egui::Grid::new(format!("audit_{title}_grid"))
+            .num_columns(2)
+            .spacing([12.0, 4.0])
+            .show(ui, |ui| {
+                for (key, value) in fields {
+                    let text = match value.as_str() {
+                        Some(text) => text.to_string(),
+                        None => value.to_string(),
+                    };
+                    if changed.contains(key) {
+                        ui.label(RichText::new(key).color(colors::WARNING));
+                        ui.label(RichText::new(text).color(colors::WARNING));
+                    } else {
+                        ui.label(key);
+                        ui.label(text);
+                    }
+                    ui.end_row();
+                }
+            });
+    });
+}
+
+/// One-line description of what an entry changed.
+fn change_summary(entry: &audit_log::Model) -> String {
+    match (&entry.before_data, &entry.after_data) {
+        (Some(before), Some(after)) if before.is_object() && after.is_object() => {
+            let fields = changed_fields(before, after);
+            if fields.is_empty() {
+                "No changes".to_string()
+            } else {
+                fields.join(", ")
+            }
+        }
+        (None, Some(after)) | (Some(after), None) if !after.is_object() => after.to_string(),
+        (None, Some(after)) => name_of(after).unwrap_or_default(),
+        (Some(before), None) => name_of(before).unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+/// Display name of a record snapshot, if it has one.
+fn name_of(data: &serde_json::Value) -> Option<String> {
+    ["full_name", "name"]
+        .iter()
+        .find_map(|key| data.get(*key).and_then(|v| v.as_str()))
+        .map(str::to_string)
+}
+
+fn action_color(action: &str) -> egui::Color32 {
+    match action {
+        audit_action::CREATE => colors::SUCCESS,
+        audit_action::UPDATE => colors::INFO,
+        _ => colors::ERROR,
+    }
+}
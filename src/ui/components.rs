@@ -60,6 +60,7 @@ pub mod colors {
     pub const SUCCESS: Color32 = Color32::from_rgb(100, 200, 100);
     pub const ERROR: Color32 = Color32::from_rgb(255, 100, 100);
     pub const WARNING: Color32 = Color32::from_rgb(255, 200, 100);
+    pub const INFO: Color32 = Color32::from_rgb(100, 170, 255);
     pub const NEUTRAL: Color32 = Color32::from_rgb(150, 150, 150);
 }
 
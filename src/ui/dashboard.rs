@@ -2,12 +2,13 @@
 
 use std::collections::HashSet;
 
-use chrono::Local;
 use eframe::egui::{self, Color32, CornerRadius, Margin, RichText, ScrollArea, Ui};
-use egui_phosphor::regular::{ARROWS_CLOCKWISE, BUILDINGS, CHART_BAR, USERS};
+use egui_phosphor::regular::{ARROWS_CLOCKWISE, BUILDINGS, CALENDAR_CHECK, CHART_BAR, CLOCK, USERS, WARNING};
 
 use super::app::{App, LogLevel, Panel, SyncState};
-use super::components::dashboard_card;
+use super::components::{colors, dashboard_card};
+use crate::models::user::Permission;
+use crate::timezone;
 
 /// Show the dashboard panel.
 ///
@@ -43,19 +44,40 @@ pub fn show(app: &mut App, ui: &mut Ui) -> Option<Panel> {
                 &app.departments.len().to_string(),
                 "Active departments",
             );
+            // Nobody is expected in on a holiday, so say why the count is low
+            let subtitle = match &app.today_holiday {
+                Some(name) => format!("Holiday: {name}"),
+                None => "Employees checked in".to_string(),
+            };
             stat_card(
                 ui,
                 "Today's Attendance",
                 &count_today_attendance(app).to_string(),
-                "Employees checked in",
+                &subtitle,
             );
         });
 
+        // Orphan punches are excluded from summaries, so surface them here
+        if !app.unmatched_punches.is_empty() && app.can_open(Panel::Unmatched) {
+            ui.add_space(10.0);
+            let text = format!(
+                "{WARNING} {count} scanner UIDs have punches but no employee",
+                count = app.unmatched_punches.len()
+            );
+            if ui
+                .link(RichText::new(text).color(colors::WARNING))
+                .on_hover_text("Review unmatched punches")
+                .clicked()
+            {
+                next_panel = Some(Panel::Unmatched);
+            }
+        }
+
         ui.add_space(30.0);
 
         // Navigation cards row
         let available = ui.available_width();
-        let num_cards = 4.0;
+        let num_cards = 6.0;
         let spacing = 30.0;
         let total_spacing = spacing * (num_cards - 1.0);
         let card_width = ((available - total_spacing) / num_cards).clamp(150.0, 250.0);
@@ -79,7 +101,28 @@ pub fn show(app: &mut App, ui: &mut Ui) -> Option<Panel> {
 
             ui.add_space(spacing);
 
-            if dashboard_card(ui, "Device Sync", "Sync attendance data", ARROWS_CLOCKWISE, card_size).clicked() {
+            if dashboard_card(ui, "Manage Shifts", "Schedules & rosters", CLOCK, card_size).clicked() {
+                next_panel = Some(Panel::Shifts);
+            }
+
+            ui.add_space(spacing);
+
+            if dashboard_card(ui, "Leave", "Requests & balances", CALENDAR_CHECK, card_size).clicked() {
+                app.load_leave_requests();
+                app.load_leave_balances();
+                next_panel = Some(Panel::Leave);
+            }
+
+            ui.add_space(spacing);
+
+            let can_sync = app.can_open(Panel::Sync);
+            if ui
+                .add_enabled_ui(can_sync, |ui| {
+                    dashboard_card(ui, "Device Sync", "Sync attendance data", ARROWS_CLOCKWISE, card_size)
+                })
+                .inner
+                .clicked()
+            {
                 next_panel = Some(Panel::Sync);
             }
 
@@ -116,7 +159,7 @@ pub fn show(app: &mut App, ui: &mut Ui) -> Option<Panel> {
 
                     let is_syncing = matches!(app.sync_state, SyncState::InProgress { .. });
 
-                    ui.add_enabled_ui(!is_syncing, |ui| {
+                    ui.add_enabled_ui(!is_syncing && app.can(Permission::SyncDevice), |ui| {
                         if ui.button("Sync Now").clicked() {
                             app.start_sync();
                         }
@@ -130,7 +173,10 @@ pub fn show(app: &mut App, ui: &mut Ui) -> Option<Panel> {
 
                     ui.add_space(5.0);
 
-                    if ui.button("Add Employee").clicked() {
+                    if ui
+                        .add_enabled(app.can(Permission::EditStaff), egui::Button::new("Add Employee"))
+                        .clicked()
+                    {
                         app.employee_form.reset();
                         app.employee_form.is_open = true;
                         next_panel = Some(Panel::Employees);
@@ -251,7 +297,7 @@ fn stat_card(ui: &mut Ui, title: &str, value: &str, subtitle: &str) {
 
 /// Count unique employees who checked in today.
 fn count_today_attendance(app: &App) -> usize {
-    let today = Local::now().date_naive();
+    let today = timezone::today();
     app.attendance
         .iter()
         .filter(|a| a.work_date == today)
@@ -9,11 +9,14 @@ use super::components::{
     styled_button_with_icon,
 };
 use crate::models::department::{CreateDepartment, UpdateDepartment};
+use crate::models::user::Permission;
 
 /// Show the department panel.
 ///
 /// Returns `true` if the back button was clicked.
 pub fn show(app: &mut App, ui: &mut Ui) -> bool {
+    let can_edit = app.can(Permission::EditStaff);
+
     let mut go_back = false;
 
     if back_button(ui) {
@@ -24,7 +27,12 @@ pub fn show(app: &mut App, ui: &mut Ui) -> bool {
 
     // Toolbar
     ui.horizontal(|ui| {
-        if primary_button_with_icon(ui, PLUS, "Add Department").clicked() {
+        if ui
+            .add_enabled_ui(can_edit, |ui| {
+                primary_button_with_icon(ui, PLUS, "Add Department").clicked()
+            })
+            .inner
+        {
             app.department_form = DepartmentForm {
                 is_active: true,
                 is_open: true,
@@ -59,6 +67,8 @@ pub fn show(app: &mut App, ui: &mut Ui) -> bool {
 }
 
 fn show_table(app: &mut App, ui: &mut Ui) {
+    let can_edit = app.can(Permission::EditStaff);
+
     ScrollArea::vertical().show(ui, |ui| {
         egui::Grid::new("departments_grid")
             .num_columns(6)
@@ -93,6 +103,9 @@ fn show_table(app: &mut App, ui: &mut Ui) {
                     ui.label(if dept.is_active { "Yes" } else { "No" });
 
                     ui.horizontal(|ui| {
+                        if !can_edit {
+                            ui.disable();
+                        }
                         if action_button(ui, PENCIL, "Edit").clicked() {
                             app.department_form = DepartmentForm::edit(dept);
                         }
@@ -0,0 +1,787 @@
+//! Leave panel: leave requests and their approval, yearly balances and leave types.
+
+use chrono::NaiveDate;
+use eframe::egui::{self, RichText, ScrollArea, Ui};
+use egui_phosphor::regular::{ARROWS_CLOCKWISE, CARET_LEFT, CARET_RIGHT, CHECK, PENCIL, PLUS, PROHIBIT, TRASH, X};
+
+use super::app::{App, DeleteTarget, EntitlementForm, LeaveRequestForm, LeaveTab, LeaveTypeForm};
+use super::components::{
+    action_button, back_button, colors, danger_action_button, panel_header, primary_button_with_icon, styled_button,
+    styled_button_with_icon,
+};
+use crate::models::leave::{
+    CreateLeaveRequest, CreateLeaveType, SetLeaveEntitlement, UpdateLeaveRequest, UpdateLeaveType, leave_status,
+};
+use crate::models::user::Permission;
+use crate::timezone;
+
+/// Parse an ISO date.
+fn parse_date(input: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(input.trim(), "%Y-%m-%d").ok()
+}
+
+/// Parse a non-negative day count such as `12` or `1.5`; empty input means `None`.
+fn parse_days(input: &str) -> Result<Option<f64>, ()> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(None);
+    }
+    match input.parse::<f64>() {
+        Ok(days) if days >= 0.0 && days.is_finite() => Ok(Some(days)),
+        _ => Err(()),
+    }
+}
+
+/// Format a day count without a trailing `.0`, e.g. "12" or "1.5".
+fn format_days(days: f64) -> String {
+    format!("{days}")
+}
+
+/// Show the leave panel.
+///
+/// Returns `true` if the back button was clicked.
+pub fn show(app: &mut App, ui: &mut Ui) -> bool {
+    let mut go_back = false;
+
+    if back_button(ui) {
+        go_back = true;
+    }
+
+    panel_header(ui, "Leave");
+
+    // Tab selector
+    ui.horizontal(|ui| {
+        for (tab, label) in [
+            (LeaveTab::Requests, "Requests"),
+            (LeaveTab::Balances, "Balances"),
+            (LeaveTab::Types, "Leave Types"),
+        ] {
+            if ui.selectable_label(app.leave_tab == tab, label).clicked() && app.leave_tab != tab {
+                app.leave_tab = tab;
+                match tab {
+                    LeaveTab::Requests => app.load_leave_requests(),
+                    LeaveTab::Balances => app.load_leave_balances(),
+                    LeaveTab::Types => app.load_leave_types(),
+                }
+            }
+        }
+    });
+
+    ui.add_space(10.0);
+
+    match app.leave_tab {
+        LeaveTab::Requests => show_requests(app, ui),
+        LeaveTab::Balances => show_balances(app, ui),
+        LeaveTab::Types => show_types(app, ui),
+    }
+
+    // Form dialogs
+    if app.leave_request_form.is_open {
+        show_request_dialog(app, ui.ctx());
+    }
+    if app.leave_type_form.is_open {
+        show_type_dialog(app, ui.ctx());
+    }
+    if app.entitlement_form.is_open {
+        show_entitlement_dialog(app, ui.ctx());
+    }
+
+    go_back
+}
+
+/// Display name of an employee.
+fn employee_name(app: &App, employee_id: i32) -> String {
+    app.employees
+        .iter()
+        .find(|e| e.id == employee_id)
+        .map(|e| format!("{} - {}", e.employee_code, e.full_name))
+        .unwrap_or_else(|| format!("#{employee_id}"))
+}
+
+/// Display name of a leave type.
+fn leave_type_name(app: &App, leave_type_id: i32) -> String {
+    app.leave_types
+        .iter()
+        .find(|t| t.id == leave_type_id)
+        .map(|t| t.name.clone())
+        .unwrap_or_else(|| format!("#{leave_type_id}"))
+}
+
+/// Year selector shared by the requests and balances tabs.
+fn year_selector(app: &mut App, ui: &mut Ui) -> bool {
+    let mut changed = false;
+    if styled_button_with_icon(ui, CARET_LEFT, "").clicked() {
+        app.leave_year -= 1;
+        changed = true;
+    }
+    ui.strong(app.leave_year.to_string());
+    if styled_button_with_icon(ui, CARET_RIGHT, "").clicked() {
+        app.leave_year += 1;
+        changed = true;
+    }
+    changed
+}
+
+// ============================================================================
+// Requests
+// ============================================================================
+
+fn show_requests(app: &mut App, ui: &mut Ui) {
+    let can_manage = app.can(Permission::ManageLeave);
+
+    ui.horizontal(|ui| {
+        if year_selector(app, ui) {
+            app.load_leave_requests();
+            app.load_leave_balances();
+        }
+
+        ui.add_space(20.0);
+
+        if ui
+            .add_enabled_ui(can_manage, |ui| {
+                primary_button_with_icon(ui, PLUS, "New Request").clicked()
+            })
+            .inner
+        {
+            let today = timezone::today().format("%Y-%m-%d").to_string();
+            app.leave_request_form = LeaveRequestForm {
+                leave_type_id: app.leave_types.iter().find(|t| t.is_active).map(|t| t.id),
+                start_date_input: today.clone(),
+                end_date_input: today,
+                is_open: true,
+                ..Default::default()
+            };
+        }
+
+        ui.add_space(20.0);
+
+        ui.label("Status:");
+        egui::ComboBox::from_id_salt("leave_status_filter")
+            .width(110.0)
+            .selected_text(app.leave_status_filter.unwrap_or("All"))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut app.leave_status_filter, None, "All");
+                for status in leave_status::ALL {
+                    ui.selectable_value(&mut app.leave_status_filter, Some(status), status);
+                }
+            });
+
+        ui.add_space(20.0);
+
+        if styled_button_with_icon(ui, ARROWS_CLOCKWISE, "Refresh").clicked() {
+            app.load_leave_requests();
+        }
+    });
+
+    ui.add_space(10.0);
+    ui.label(
+        RichText::new("Approved leave excuses the employee on working days; holidays and days off are not counted.")
+            .weak(),
+    );
+    ui.add_space(10.0);
+
+    let requests: Vec<_> = app
+        .leave_requests
+        .iter()
+        .filter(|r| app.leave_status_filter.is_none_or(|s| r.status == s))
+        .cloned()
+        .collect();
+    ui.label(format!("{count} requests", count = requests.len()));
+    ui.add_space(10.0);
+
+    ScrollArea::vertical().show(ui, |ui| {
+        egui::Grid::new("leave_requests_grid")
+            .num_columns(9)
+            .striped(true)
+            .min_col_width(60.0)
+            .spacing([15.0, 8.0])
+            .show(ui, |ui| {
+                ui.strong("Employee");
+                ui.strong("Type");
+                ui.strong("From");
+                ui.strong("To");
+                ui.strong("Days");
+                ui.strong("Status");
+                ui.strong("Approver");
+                ui.strong("Note");
+                ui.strong("Actions");
+                ui.end_row();
+
+                for request in &requests {
+                    ui.label(employee_name(app, request.employee_id));
+                    ui.label(leave_type_name(app, request.leave_type_id));
+                    ui.label(request.start_date.format("%a %Y-%m-%d").to_string());
+                    ui.label(request.end_date.format("%a %Y-%m-%d").to_string());
+                    let days = format_days(request.days);
+                    ui.label(if request.half_day {
+                        format!("{days} (half)")
+                    } else {
+                        days
+                    });
+
+                    let color = match request.status.as_str() {
+                        leave_status::APPROVED => colors::SUCCESS,
+                        leave_status::PENDING => colors::WARNING,
+                        leave_status::REJECTED => colors::ERROR,
+                        _ => colors::NEUTRAL,
+                    };
+                    ui.label(RichText::new(&request.status).color(color));
+                    ui.label(request.approver.as_deref().unwrap_or("-"));
+                    ui.label(request.note.as_deref().unwrap_or(""));
+
+                    ui.horizontal(|ui| {
+                        if !can_manage {
+                            ui.disable();
+                        }
+                        match request.status.as_str() {
+                            leave_status::PENDING => {
+                                if action_button(ui, CHECK, "Approve").clicked() {
+                                    app.set_leave_status(request.id, leave_status::APPROVED);
+                                }
+                                if danger_action_button(ui, X, "Reject").clicked() {
+                                    app.set_leave_status(request.id, leave_status::REJECTED);
+                                }
+                                if action_button(ui, PENCIL, "Edit").clicked() {
+                                    app.leave_request_form = LeaveRequestForm::edit(request);
+                                }
+                            }
+                            leave_status::APPROVED => {
+                                if danger_action_button(ui, PROHIBIT, "Cancel").clicked() {
+                                    app.set_leave_status(request.id, leave_status::CANCELLED);
+                                }
+                            }
+                            _ => {
+                                if danger_action_button(ui, TRASH, "Delete").clicked() {
+                                    app.delete_target = Some(DeleteTarget::LeaveRequest(
+                                        request.id,
+                                        format!(
+                                            "{} {} to {}",
+                                            employee_name(app, request.employee_id),
+                                            request.start_date,
+                                            request.end_date
+                                        ),
+                                    ));
+                                    app.show_delete_confirm = true;
+                                }
+                            }
+                        }
+                    });
+
+                    ui.end_row();
+                }
+            });
+    });
+
+    if requests.is_empty() {
+        ui.label("No leave requests");
+    }
+}
+
+fn show_request_dialog(app: &mut App, ctx: &egui::Context) {
+    let title = if app.leave_request_form.is_editing {
+        "Edit Leave Request"
+    } else {
+        "New Leave Request"
+    };
+
+    egui::Window::new(title)
+        .collapsible(false)
+        .resizable(false)
+        .default_width(420.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(10.0);
+
+            egui::Grid::new("leave_request_form_grid")
+                .num_columns(2)
+                .spacing([20.0, 12.0])
+                .show(ui, |ui| {
+                    ui.label("Employee:");
+                    if app.leave_request_form.is_editing {
+                        ui.label(
+                            app.leave_request_form
+                                .employee_id
+                                .map(|id| employee_name(app, id))
+                                .unwrap_or_default(),
+                        );
+                    } else {
+                        let selected = app
+                            .leave_request_form
+                            .employee_id
+                            .map(|id| employee_name(app, id))
+                            .unwrap_or_else(|| "Select employee".to_string());
+                        egui::ComboBox::from_id_salt("leave_employee")
+                            .width(260.0)
+                            .selected_text(selected)
+                            .show_ui(ui, |ui| {
+                                for emp in app.employees.iter().filter(|e| e.is_active) {
+                                    ui.selectable_value(
+                                        &mut app.leave_request_form.employee_id,
+                                        Some(emp.id),
+                                        format!("{} - {}", emp.employee_code, emp.full_name),
+                                    );
+                                }
+                            });
+                    }
+                    ui.end_row();
+
+                    ui.label("Leave type:");
+                    let selected = app
+                        .leave_request_form
+                        .leave_type_id
+                        .map(|id| leave_type_name(app, id))
+                        .unwrap_or_else(|| "Select type".to_string());
+                    egui::ComboBox::from_id_salt("leave_type")
+                        .width(260.0)
+                        .selected_text(selected)
+                        .show_ui(ui, |ui| {
+                            for leave_type in app.leave_types.iter().filter(|t| t.is_active) {
+                                ui.selectable_value(
+                                    &mut app.leave_request_form.leave_type_id,
+                                    Some(leave_type.id),
+                                    &leave_type.name,
+                                );
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("From:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut app.leave_request_form.start_date_input)
+                            .desired_width(120.0)
+                            .hint_text("YYYY-MM-DD"),
+                    );
+                    ui.end_row();
+
+                    ui.label("To:");
+                    ui.add_enabled(
+                        !app.leave_request_form.half_day,
+                        egui::TextEdit::singleline(&mut app.leave_request_form.end_date_input)
+                            .desired_width(120.0)
+                            .hint_text("YYYY-MM-DD"),
+                    );
+                    ui.end_row();
+
+                    ui.label("Half day:");
+                    if ui
+                        .checkbox(&mut app.leave_request_form.half_day, "Half a day")
+                        .changed()
+                        && app.leave_request_form.half_day
+                    {
+                        app.leave_request_form.end_date_input = app.leave_request_form.start_date_input.clone();
+                    }
+                    ui.end_row();
+
+                    ui.label("Note:");
+                    ui.add(egui::TextEdit::multiline(&mut app.leave_request_form.note).desired_width(260.0));
+                    ui.end_row();
+                });
+
+            ui.add_space(15.0);
+            ui.separator();
+            ui.add_space(10.0);
+
+            ui.horizontal(|ui| {
+                if styled_button(ui, "Cancel").clicked() {
+                    app.leave_request_form.reset();
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if primary_button_with_icon(ui, "", "Save").clicked() {
+                        save_request(app);
+                    }
+                });
+            });
+        });
+}
+
+fn save_request(app: &mut App) {
+    let form = &app.leave_request_form;
+
+    let Some(employee_id) = form.employee_id else {
+        app.error_message = Some("Select an employee".to_string());
+        return;
+    };
+    let Some(leave_type_id) = form.leave_type_id else {
+        app.error_message = Some("Select a leave type".to_string());
+        return;
+    };
+    let (Some(start_date), Some(end_date)) = (parse_date(&form.start_date_input), parse_date(&form.end_date_input))
+    else {
+        app.error_message = Some("Dates must be YYYY-MM-DD".to_string());
+        return;
+    };
+    let end_date = if form.half_day { start_date } else { end_date };
+    if end_date < start_date {
+        app.error_message = Some("End date must not be before the start date".to_string());
+        return;
+    }
+
+    // Only one live request per employee-day
+    if let Some(overlap) = app.leave_requests.iter().find(|r| {
+        r.employee_id == employee_id
+            && Some(r.id) != form.id
+            && matches!(r.status.as_str(), leave_status::PENDING | leave_status::APPROVED)
+            && r.start_date <= end_date
+            && start_date <= r.end_date
+    }) {
+        app.error_message = Some(format!(
+            "Overlaps a {} request from {} to {}",
+            overlap.status, overlap.start_date, overlap.end_date
+        ));
+        return;
+    }
+
+    let note = Some(form.note.trim().to_string()).filter(|n| !n.is_empty());
+
+    match form.id {
+        Some(id) => {
+            let data = UpdateLeaveRequest {
+                leave_type_id: Some(leave_type_id),
+                start_date: Some(start_date),
+                end_date: Some(end_date),
+                half_day: Some(form.half_day),
+                note: Some(note),
+            };
+            app.update_leave_request(id, data);
+        }
+        None => {
+            let data = CreateLeaveRequest {
+                employee_id,
+                leave_type_id,
+                start_date,
+                end_date,
+                half_day: form.half_day,
+                note,
+            };
+            app.create_leave_request(data);
+        }
+    }
+}
+
+// ============================================================================
+// Balances
+// ============================================================================
+
+fn show_balances(app: &mut App, ui: &mut Ui) {
+    let can_manage = app.can(Permission::ManageLeave);
+
+    ui.horizontal(|ui| {
+        if year_selector(app, ui) {
+            app.load_leave_requests();
+            app.load_leave_balances();
+        }
+
+        ui.add_space(20.0);
+
+        if styled_button_with_icon(ui, ARROWS_CLOCKWISE, "Refresh").clicked() {
+            app.load_leave_balances();
+        }
+    });
+
+    ui.add_space(10.0);
+    ui.label(
+        RichText::new("Entitlements default to the leave type's yearly days; set one to override it for an employee.")
+            .weak(),
+    );
+    ui.add_space(10.0);
+
+    ScrollArea::vertical().show(ui, |ui| {
+        egui::Grid::new("leave_balances_grid")
+            .num_columns(7)
+            .striped(true)
+            .min_col_width(70.0)
+            .spacing([15.0, 8.0])
+            .show(ui, |ui| {
+                ui.strong("Employee");
+                ui.strong("Type");
+                ui.strong("Entitled");
+                ui.strong("Taken");
+                ui.strong("Pending");
+                ui.strong("Remaining");
+                ui.strong("Actions");
+                ui.end_row();
+
+                let balances = app.leave_balances.clone();
+                for balance in &balances {
+                    ui.label(employee_name(app, balance.employee_id));
+                    ui.label(leave_type_name(app, balance.leave_type_id));
+                    ui.label(balance.entitled.map_or_else(|| "-".to_string(), format_days));
+                    ui.label(format_days(balance.taken));
+                    ui.label(format_days(balance.pending));
+                    match balance.remaining() {
+                        Some(remaining) => {
+                            let color = if remaining < 0.0 {
+                                colors::ERROR
+                            } else {
+                                colors::SUCCESS
+                            };
+                            ui.label(RichText::new(format_days(remaining)).color(color));
+                        }
+                        None => {
+                            ui.label("-");
+                        }
+                    }
+
+                    if ui
+                        .add_enabled_ui(can_manage, |ui| action_button(ui, PENCIL, "Set").clicked())
+                        .inner
+                    {
+                        app.entitlement_form = EntitlementForm {
+                            employee_id: balance.employee_id,
+                            leave_type_id: balance.leave_type_id,
+                            days: balance.entitled.map(format_days).unwrap_or_default(),
+                            is_open: true,
+                        };
+                    }
+
+                    ui.end_row();
+                }
+            });
+    });
+
+    if app.leave_balances.is_empty() {
+        ui.label("No active employees or leave types");
+    }
+}
+
+fn show_entitlement_dialog(app: &mut App, ctx: &egui::Context) {
+    egui::Window::new("Set Entitlement")
+        .collapsible(false)
+        .resizable(false)
+        .default_width(380.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(10.0);
+
+            egui::Grid::new("entitlement_form_grid")
+                .num_columns(2)
+                .spacing([20.0, 12.0])
+                .show(ui, |ui| {
+                    ui.label("Employee:");
+                    ui.label(employee_name(app, app.entitlement_form.employee_id));
+                    ui.end_row();
+
+                    ui.label("Leave type:");
+                    ui.label(leave_type_name(app, app.entitlement_form.leave_type_id));
+                    ui.end_row();
+
+                    ui.label("Year:");
+                    ui.label(app.leave_year.to_string());
+                    ui.end_row();
+
+                    ui.label("Days:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut app.entitlement_form.days)
+                            .desired_width(80.0)
+                            .hint_text("Default"),
+                    )
+                    .on_hover_text("Leave empty to use the leave type default");
+                    ui.end_row();
+                });
+
+            ui.add_space(15.0);
+            ui.separator();
+            ui.add_space(10.0);
+
+            ui.horizontal(|ui| {
+                if styled_button(ui, "Cancel").clicked() {
+                    app.entitlement_form.reset();
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if primary_button_with_icon(ui, "", "Save").clicked() {
+                        save_entitlement(app);
+                    }
+                });
+            });
+        });
+}
+
+fn save_entitlement(app: &mut App) {
+    let form = &app.entitlement_form;
+    let (employee_id, leave_type_id, year) = (form.employee_id, form.leave_type_id, app.leave_year);
+
+    match parse_days(&form.days) {
+        Ok(Some(days)) => app.set_leave_entitlement(SetLeaveEntitlement {
+            employee_id,
+            leave_type_id,
+            year,
+            days,
+        }),
+        Ok(None) => app.clear_leave_entitlement(employee_id, leave_type_id, year),
+        Err(()) => app.error_message = Some("Days must be a non-negative number".to_string()),
+    }
+}
+
+// ============================================================================
+// Leave types
+// ============================================================================
+
+fn show_types(app: &mut App, ui: &mut Ui) {
+    let can_manage = app.can(Permission::ManageLeave);
+
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled_ui(can_manage, |ui| {
+                primary_button_with_icon(ui, PLUS, "Add Leave Type").clicked()
+            })
+            .inner
+        {
+            app.leave_type_form = LeaveTypeForm {
+                is_paid: true,
+                is_active: true,
+                is_open: true,
+                ..Default::default()
+            };
+        }
+
+        ui.add_space(10.0);
+
+        if styled_button_with_icon(ui, ARROWS_CLOCKWISE, "Refresh").clicked() {
+            app.load_leave_types();
+        }
+    });
+
+    ui.add_space(10.0);
+
+    ScrollArea::vertical().show(ui, |ui| {
+        egui::Grid::new("leave_types_grid")
+            .num_columns(5)
+            .striped(true)
+            .min_col_width(80.0)
+            .spacing([15.0, 8.0])
+            .show(ui, |ui| {
+                ui.strong("Name");
+                ui.strong("Paid");
+                ui.strong("Days / Year");
+                ui.strong("Status");
+                ui.strong("Actions");
+                ui.end_row();
+
+                let types = app.leave_types.clone();
+                for leave_type in &types {
+                    ui.label(&leave_type.name);
+                    ui.label(if leave_type.is_paid { "Paid" } else { "Unpaid" });
+                    ui.label(
+                        leave_type
+                            .default_days
+                            .map_or_else(|| "No limit".to_string(), format_days),
+                    );
+                    if leave_type.is_active {
+                        ui.label(RichText::new("Active").color(colors::SUCCESS));
+                    } else {
+                        ui.label(RichText::new("Inactive").color(colors::NEUTRAL));
+                    }
+
+                    if ui
+                        .add_enabled_ui(can_manage, |ui| action_button(ui, PENCIL, "Edit").clicked())
+                        .inner
+                    {
+                        app.leave_type_form = LeaveTypeForm::edit(leave_type);
+                    }
+
+                    ui.end_row();
+                }
+            });
+    });
+
+    if app.leave_types.is_empty() {
+        ui.label("No leave types");
+    }
+}
+
+fn show_type_dialog(app: &mut App, ctx: &egui::Context) {
+    let title = if app.leave_type_form.is_editing {
+        "Edit Leave Type"
+    } else {
+        "Add Leave Type"
+    };
+
+    egui::Window::new(title)
+        .collapsible(false)
+        .resizable(false)
+        .default_width(380.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(10.0);
+
+            egui::Grid::new("leave_type_form_grid")
+                .num_columns(2)
+                .spacing([20.0, 12.0])
+                .show(ui, |ui| {
+                    ui.label("Name:");
+                    ui.add(egui::TextEdit::singleline(&mut app.leave_type_form.name).desired_width(200.0));
+                    ui.end_row();
+
+                    ui.label("Paid:");
+                    ui.checkbox(&mut app.leave_type_form.is_paid, "Paid leave");
+                    ui.end_row();
+
+                    ui.label("Days / year:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut app.leave_type_form.default_days)
+                            .desired_width(80.0)
+                            .hint_text("No limit"),
+                    )
+                    .on_hover_text("Default yearly entitlement; leave empty to track no balance");
+                    ui.end_row();
+
+                    if app.leave_type_form.is_editing {
+                        ui.label("Active:");
+                        ui.checkbox(&mut app.leave_type_form.is_active, "Available for new requests");
+                        ui.end_row();
+                    }
+                });
+
+            ui.add_space(15.0);
+            ui.separator();
+            ui.add_space(10.0);
+
+            ui.horizontal(|ui| {
+                if styled_button(ui, "Cancel").clicked() {
+                    app.leave_type_form.reset();
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if primary_button_with_icon(ui, "", "Save").clicked() {
+                        save_type(app);
+                    }
+                });
+            });
+        });
+}
+
+fn save_type(app: &mut App) {
+    let form = &app.leave_type_form;
+
+    let name = form.name.trim().to_string();
+    if name.is_empty() {
+        app.error_message = Some("Leave type name is required".to_string());
+        return;
+    }
+    let Ok(default_days) = parse_days(&form.default_days) else {
+        app.error_message = Some("Days per year must be a non-negative number".to_string());
+        return;
+    };
+
+    match form.id {
+        Some(id) => {
+            let data = UpdateLeaveType {
+                name: Some(name),
+                is_paid: Some(form.is_paid),
+                default_days: Some(default_days),
+                is_active: Some(form.is_active),
+            };
+            app.update_leave_type(id, data);
+        }
+        None => {
+            let data = CreateLeaveType {
+                name,
+                is_paid: form.is_paid,
+                default_days,
+            };
+            app.create_leave_type(data);
+        }
+    }
+}
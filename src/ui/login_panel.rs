@@ -0,0 +1,135 @@
+//! Sign-in screen, also used to create the first admin account.
+
+use eframe::egui::{self, RichText, Ui};
+use egui_phosphor::regular::SIGN_IN;
+
+use super::app::App;
+use super::components::{card_frame, colors, primary_button_with_icon};
+use crate::models::user::MIN_PASSWORD_LEN;
+
+/// Show the sign-in screen.
+pub fn show(app: &mut App, ui: &mut Ui) {
+    ui.vertical_centered(|ui| {
+        ui.add_space(80.0);
+
+        ui.label(RichText::new("Gianged Attendance").size(32.0).strong());
+        ui.add_space(5.0);
+        ui.label(RichText::new("Staff and Attendance Management").size(14.0).weak());
+
+        ui.add_space(30.0);
+
+        card_frame(ui).show(ui, |ui| {
+            ui.set_width(360.0);
+            match app.user_count {
+                None if app.login_form.error.is_none() => {
+                    ui.spinner();
+                }
+                Some(0) => show_first_admin_form(app, ui),
+                _ => show_sign_in_form(app, ui),
+            }
+
+            if let Some(error) = &app.login_form.error {
+                ui.add_space(10.0);
+                ui.colored_label(colors::ERROR, error);
+            }
+        });
+    });
+}
+
+fn show_sign_in_form(app: &mut App, ui: &mut Ui) {
+    ui.label(RichText::new("Sign In").size(18.0).strong());
+    ui.add_space(15.0);
+
+    let mut submit = false;
+    egui::Grid::new("sign_in_grid")
+        .num_columns(2)
+        .spacing([20.0, 12.0])
+        .show(ui, |ui| {
+            ui.label("Username:");
+            let username = ui.add(egui::TextEdit::singleline(&mut app.login_form.username).desired_width(220.0));
+            if app.login_form.username.is_empty() && !username.has_focus() && ui.memory(|m| m.focused().is_none()) {
+                username.request_focus();
+            }
+            ui.end_row();
+
+            ui.label("Password:");
+            let password = ui.add(
+                egui::TextEdit::singleline(&mut app.login_form.password)
+                    .password(true)
+                    .desired_width(220.0),
+            );
+            submit |= password.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            ui.end_row();
+        });
+
+    ui.add_space(15.0);
+
+    ui.add_enabled_ui(!app.login_form.is_busy, |ui| {
+        submit |= primary_button_with_icon(ui, SIGN_IN, "Sign In").clicked();
+    });
+    if app.login_form.is_busy {
+        ui.spinner();
+    }
+
+    if submit && !app.login_form.is_busy {
+        app.sign_in();
+    }
+}
+
+fn show_first_admin_form(app: &mut App, ui: &mut Ui) {
+    ui.label(RichText::new("Create Admin Account").size(18.0).strong());
+    ui.add_space(5.0);
+    ui.label(
+        RichText::new("No accounts exist yet. The first account can manage everything, including other users.").weak(),
+    );
+    ui.add_space(15.0);
+
+    let form = &mut app.login_form;
+    egui::Grid::new("first_admin_grid")
+        .num_columns(2)
+        .spacing([20.0, 12.0])
+        .show(ui, |ui| {
+            ui.label("Username:");
+            ui.add(egui::TextEdit::singleline(&mut form.username).desired_width(220.0));
+            ui.end_row();
+
+            ui.label("Display Name:");
+            ui.add(
+                egui::TextEdit::singleline(&mut form.display_name)
+                    .desired_width(220.0)
+                    .hint_text("Optional"),
+            );
+            ui.end_row();
+
+            ui.label("Password:");
+            ui.add(
+                egui::TextEdit::singleline(&mut form.password)
+                    .password(true)
+                    .desired_width(220.0)
+                    .hint_text(format!("At least {MIN_PASSWORD_LEN} characters")),
+            );
+            ui.end_row();
+
+            ui.label("Confirm:");
+            ui.add(
+                egui::TextEdit::singleline(&mut form.confirm_password)
+                    .password(true)
+                    .desired_width(220.0),
+            );
+            ui.end_row();
+        });
+
+    ui.add_space(15.0);
+
+    let mut submit = false;
+    ui.add_enabled_ui(!app.login_form.is_busy, |ui| {
+        submit = primary_button_with_icon(ui, SIGN_IN, "Create and Sign In").clicked();
+    });
+    if app.login_form.is_busy {
+        ui.spinner();
+    }
+
+    if submit {
+        app.create_first_admin();
+    }
+}
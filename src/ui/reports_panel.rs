@@ -1,13 +1,23 @@
 //! Attendance reports panel with filters and Excel export.
 
-use chrono::{Datelike, Local, NaiveDate};
+use chrono::{Datelike, NaiveDate, NaiveTime, Utc};
 use eframe::egui::{self, RichText, ScrollArea, Ui};
 use egui_phosphor::regular::{
     ARROWS_CLOCKWISE, CARET_DOUBLE_LEFT, CARET_DOUBLE_RIGHT, CARET_LEFT, CARET_RIGHT, FILE_XLS, MAGNIFYING_GLASS,
+    PENCIL, PLUS, PROHIBIT,
 };
 
-use super::app::{App, REPORT_PAGE_SIZE, ReportType};
-use super::components::{back_button, panel_header, primary_button_with_icon, styled_button, styled_button_with_icon};
+use super::app::{App, PunchAction, PunchForm, REPORT_PAGE_SIZE, ReportType};
+use super::components::{
+    action_button, back_button, colors, danger_action_button, panel_header, primary_button_with_icon, styled_button,
+    styled_button_with_icon,
+};
+use crate::models::attendance::{AttendanceDetail, punch_state};
+use crate::models::day_status::{DailyStatus, DayStatus};
+use crate::models::punch_correction::{AddPunch, CorrectPunch, VoidPunch};
+use crate::models::punch_session::format_sessions;
+use crate::models::user::Permission;
+use crate::timezone;
 
 /// Parse date from multiple formats: "2000-1-1", "2000/1/1", "2000 1 1", "2000.1.1"
 fn parse_flexible_date(input: &str) -> Option<NaiveDate> {
@@ -39,6 +49,13 @@ pub fn show(app: &mut App, ui: &mut Ui) -> bool {
 
     panel_header(ui, "Attendance Reports");
 
+    // A manager only reports on departments in their subtree, one at a time
+    let scoped = app.session.as_ref().is_some_and(|s| s.scope.is_some());
+    if scoped && !app.in_scope(app.report_filter.department_id) {
+        app.report_filter.department_id = app.session.as_ref().and_then(|s| s.user.department_id);
+        app.report_filter.reset_pagination();
+    }
+
     // Report type toggle
     ui.horizontal(|ui| {
         ui.label("Report Type:");
@@ -57,6 +74,14 @@ pub fn show(app: &mut App, ui: &mut Ui) -> bool {
         {
             app.report_filter.report_type = ReportType::Detail;
         }
+
+        if ui
+            .selectable_label(app.report_filter.report_type == ReportType::Status, "Late / Absence")
+            .on_hover_text("Late arrivals, early departures and absences against the assigned shifts")
+            .clicked()
+        {
+            app.report_filter.report_type = ReportType::Status;
+        }
     });
 
     ui.add_space(10.0);
@@ -125,7 +150,7 @@ pub fn show(app: &mut App, ui: &mut Ui) -> bool {
 
         // Quick date buttons (reset pagination when filter changes)
         if styled_button(ui, "Today").clicked() {
-            let today = Local::now().date_naive();
+            let today = timezone::today();
             app.report_filter.start_date = today;
             app.report_filter.end_date = today;
             app.report_filter.sync_date_inputs();
@@ -133,7 +158,7 @@ pub fn show(app: &mut App, ui: &mut Ui) -> bool {
         }
 
         if styled_button(ui, "This Week").clicked() {
-            let today = Local::now().date_naive();
+            let today = timezone::today();
             let weekday = today.weekday().num_days_from_monday();
             app.report_filter.start_date = today - chrono::Duration::days(weekday as i64);
             app.report_filter.end_date = today;
@@ -142,7 +167,7 @@ pub fn show(app: &mut App, ui: &mut Ui) -> bool {
         }
 
         if styled_button(ui, "This Month").clicked() {
-            let today = Local::now().date_naive();
+            let today = timezone::today();
             app.report_filter.start_date = today.with_day(1).unwrap_or(today);
             app.report_filter.end_date = today;
             app.report_filter.sync_date_inputs();
@@ -150,7 +175,7 @@ pub fn show(app: &mut App, ui: &mut Ui) -> bool {
         }
 
         if styled_button(ui, "Last 30 Days").clicked() {
-            let today = Local::now().date_naive();
+            let today = timezone::today();
             app.report_filter.start_date = today - chrono::Duration::days(30);
             app.report_filter.end_date = today;
             app.report_filter.sync_date_inputs();
@@ -176,9 +201,10 @@ pub fn show(app: &mut App, ui: &mut Ui) -> bool {
                     .unwrap_or("All Departments"),
             )
             .show_ui(ui, |ui| {
-                if ui
-                    .selectable_label(app.report_filter.department_id.is_none(), "All Departments")
-                    .clicked()
+                if !scoped
+                    && ui
+                        .selectable_label(app.report_filter.department_id.is_none(), "All Departments")
+                        .clicked()
                 {
                     app.report_filter.department_id = None;
                     app.report_filter.reset_pagination();
@@ -194,6 +220,20 @@ pub fn show(app: &mut App, ui: &mut Ui) -> bool {
                 }
             });
 
+        if app.report_filter.report_type == ReportType::Detail {
+            ui.add_space(10.0);
+            if ui
+                .checkbox(
+                    &mut app.report_filter.include_duplicates,
+                    "Include double and voided punches",
+                )
+                .on_hover_text("Show repeat punches within the configured double-punch window and voided punches")
+                .changed()
+            {
+                app.report_filter.reset_pagination();
+            }
+        }
+
         ui.add_space(20.0);
 
         if primary_button_with_icon(ui, MAGNIFYING_GLASS, "Generate Report").clicked() {
@@ -204,6 +244,16 @@ pub fn show(app: &mut App, ui: &mut Ui) -> bool {
         if styled_button_with_icon(ui, ARROWS_CLOCKWISE, "Refresh").clicked() {
             app.generate_report();
         }
+
+        if app.report_filter.report_type == ReportType::Detail && app.can(Permission::CorrectPunches) {
+            ui.add_space(10.0);
+            if styled_button_with_icon(ui, PLUS, "Add Punch")
+                .on_hover_text("Enter a forgotten punch by hand")
+                .clicked()
+            {
+                app.punch_form = PunchForm::add(app.report_filter.start_date);
+            }
+        }
     });
 
     ui.add_space(10.0);
@@ -220,12 +270,37 @@ pub fn show(app: &mut App, ui: &mut Ui) -> bool {
             app.export_detail_report();
         }
 
+        ui.add_space(10.0);
+
+        if styled_button_with_icon(ui, FILE_XLS, "Export Late / Absence").clicked() {
+            app.export_status_report();
+        }
+
+        ui.add_space(10.0);
+
+        if styled_button_with_icon(ui, FILE_XLS, "Export Overtime")
+            .on_hover_text("Overtime totals per employee and month for the selected dates")
+            .clicked()
+        {
+            app.export_overtime_report();
+        }
+
+        ui.add_space(10.0);
+
+        if styled_button_with_icon(ui, FILE_XLS, "Export Timesheet")
+            .on_hover_text("Monthly timesheet for the month of the start date")
+            .clicked()
+        {
+            app.export_timesheet_report();
+        }
+
         ui.add_space(20.0);
 
         // Show total records and current page info
         let page_count = match app.report_filter.report_type {
             ReportType::Summary => app.attendance.len(),
             ReportType::Detail => app.attendance_details.len(),
+            ReportType::Status => status_page(app).len(),
         };
         let total = app.report_filter.total_records;
         let current_page = app.report_filter.current_page;
@@ -295,6 +370,11 @@ pub fn show(app: &mut App, ui: &mut Ui) -> bool {
     match app.report_filter.report_type {
         ReportType::Summary => show_summary_table(app, ui),
         ReportType::Detail => show_detail_table(app, ui),
+        ReportType::Status => show_status_table(app, ui),
+    }
+
+    if app.punch_form.is_open {
+        show_punch_dialog(app, ui.ctx());
     }
 
     go_back
@@ -303,7 +383,7 @@ pub fn show(app: &mut App, ui: &mut Ui) -> bool {
 fn show_summary_table(app: &App, ui: &mut Ui) {
     ScrollArea::both().show(ui, |ui| {
         egui::Grid::new("attendance_summary_grid")
-            .num_columns(8)
+            .num_columns(11)
             .striped(true)
             .min_col_width(80.0)
             .spacing([12.0, 8.0])
@@ -317,6 +397,9 @@ fn show_summary_table(app: &App, ui: &mut Ui) {
                 ui.strong("Last Check");
                 ui.strong("Count");
                 ui.strong("Hours");
+                ui.strong("Sessions");
+                ui.strong("OT");
+                ui.strong("Paid OT");
                 ui.end_row();
 
                 // Data is already filtered at DB level via pagination
@@ -327,16 +410,39 @@ fn show_summary_table(app: &App, ui: &mut Ui) {
                     ui.label(record.work_date.to_string());
 
                     // Convert to local time for display
-                    let first_local = record.first_check.with_timezone(&Local);
-                    let last_local = record.last_check.with_timezone(&Local);
+                    let first_local = timezone::to_business(&record.first_check);
+                    let last_local = timezone::to_business(&record.last_check);
 
                     ui.label(first_local.format("%H:%M:%S").to_string());
                     ui.label(last_local.format("%H:%M:%S").to_string());
                     ui.label(record.check_count.to_string());
 
-                    // Use pre-calculated work_hours if available
-                    let hours = record.work_hours.unwrap_or_else(|| record.calculate_work_hours());
-                    ui.label(format!("{:.2}", hours));
+                    // Sum of in/out sessions, so breaks are not counted
+                    ui.label(format!("{:.2}", record.worked_hours()));
+                    let sessions = format_sessions(&record.sessions);
+                    if record.missing_punch() {
+                        ui.label(RichText::new(format!("{sessions} (missing punch)")).color(colors::WARNING));
+                    } else {
+                        ui.label(sessions);
+                    }
+
+                    let overtime = &record.overtime;
+                    if overtime.minutes > 0 {
+                        let label = ui.label(format!("{:.2}", overtime.hours()));
+                        if overtime.night_minutes > 0 {
+                            label.on_hover_text(format!(
+                                "{} ({:.2} h at night)",
+                                overtime.day.label(),
+                                overtime.night_hours()
+                            ));
+                        } else {
+                            label.on_hover_text(overtime.day.label());
+                        }
+                        ui.label(format!("{:.2}", overtime.paid_hours));
+                    } else {
+                        ui.label("-");
+                        ui.label("-");
+                    }
 
                     ui.end_row();
                 }
@@ -349,10 +455,13 @@ fn show_summary_table(app: &App, ui: &mut Ui) {
     });
 }
 
-fn show_detail_table(app: &App, ui: &mut Ui) {
+fn show_detail_table(app: &mut App, ui: &mut Ui) {
+    let mut open_form = None;
+    let can_correct = app.can(Permission::CorrectPunches);
+
     ScrollArea::both().show(ui, |ui| {
         egui::Grid::new("attendance_detail_grid")
-            .num_columns(7)
+            .num_columns(9)
             .striped(true)
             .min_col_width(80.0)
             .spacing([12.0, 8.0])
@@ -365,6 +474,8 @@ fn show_detail_table(app: &App, ui: &mut Ui) {
                 ui.strong("Time");
                 ui.strong("Verify Type");
                 ui.strong("Source");
+                ui.strong("Punch");
+                ui.strong("Actions");
                 ui.end_row();
 
                 // Data is already filtered at DB level via pagination
@@ -374,12 +485,36 @@ fn show_detail_table(app: &App, ui: &mut Ui) {
                     ui.label(record.department_name.as_deref().unwrap_or("-"));
 
                     // Convert to local time for display
-                    let check_local = record.check_time.with_timezone(&Local);
+                    let check_local = timezone::to_business(&record.check_time);
 
                     ui.label(check_local.format("%Y-%m-%d").to_string());
                     ui.label(check_local.format("%H:%M:%S").to_string());
                     ui.label(&record.verify_type_name);
-                    ui.label(&record.source);
+                    show_source(ui, record);
+                    match record.punch_role {
+                        Some(role) if role.is_missing() => {
+                            ui.label(RichText::new(role.label()).color(colors::WARNING));
+                        }
+                        Some(role) => {
+                            ui.label(role.label());
+                        }
+                        None => {
+                            ui.label("-");
+                        }
+                    }
+
+                    if record.is_voided || !can_correct {
+                        ui.label("-");
+                    } else {
+                        ui.horizontal(|ui| {
+                            if action_button(ui, PENCIL, "Edit").clicked() {
+                                open_form = Some(PunchForm::change(PunchAction::Edit, record));
+                            }
+                            if danger_action_button(ui, PROHIBIT, "Void").clicked() {
+                                open_form = Some(PunchForm::change(PunchAction::Void, record));
+                            }
+                        });
+                    }
 
                     ui.end_row();
                 }
@@ -390,4 +525,282 @@ fn show_detail_table(app: &App, ui: &mut Ui) {
                 }
             });
     });
+
+    if let Some(form) = open_form {
+        app.punch_form = form;
+    }
+}
+
+/// Source of a punch, marking manual, double and voided punches.
+fn show_source(ui: &mut Ui, record: &AttendanceDetail) {
+    let label = if record.is_voided {
+        ui.label(
+            RichText::new(format!("{} (voided)", record.source))
+                .strikethrough()
+                .weak(),
+        )
+    } else if record.is_duplicate {
+        ui.label(RichText::new(format!("{} (double)", record.source)).color(colors::WARNING))
+    } else if record.is_manual() {
+        ui.label(RichText::new(&record.source).color(colors::INFO))
+    } else {
+        ui.label(&record.source)
+    };
+
+    if let (Some(reason), Some(by)) = (&record.correction_reason, &record.corrected_by) {
+        label.on_hover_text(format!("{reason} ({by})"));
+    }
+}
+
+/// Dialog for adding, correcting or voiding a punch.
+fn show_punch_dialog(app: &mut App, ctx: &egui::Context) {
+    let title = match app.punch_form.action {
+        PunchAction::Add => "Add Punch",
+        PunchAction::Edit => "Correct Punch",
+        PunchAction::Void => "Void Punch",
+    };
+    let editable = app.punch_form.action != PunchAction::Void;
+
+    egui::Window::new(title)
+        .collapsible(false)
+        .resizable(false)
+        .default_width(420.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(10.0);
+
+            egui::Grid::new("punch_form_grid")
+                .num_columns(2)
+                .spacing([20.0, 12.0])
+                .show(ui, |ui| {
+                    ui.label("Employee:");
+                    let selected = app
+                        .punch_form
+                        .employee_id
+                        .and_then(|id| app.employees.iter().find(|e| e.id == id))
+                        .map(|e| format!("{} - {}", e.employee_code, e.full_name));
+                    if app.punch_form.action == PunchAction::Add {
+                        egui::ComboBox::from_id_salt("punch_employee")
+                            .width(260.0)
+                            .selected_text(selected.unwrap_or_else(|| "Select employee".to_string()))
+                            .show_ui(ui, |ui| {
+                                // Punches are keyed by scanner UID; leavers may need their last days fixed
+                                let candidates = app
+                                    .employees
+                                    .iter()
+                                    .filter(|e| (e.is_active || e.end_date.is_some()) && e.scanner_uid.is_some());
+                                for emp in candidates {
+                                    ui.selectable_value(
+                                        &mut app.punch_form.employee_id,
+                                        Some(emp.id),
+                                        format!("{} - {}", emp.employee_code, emp.full_name),
+                                    );
+                                }
+                            });
+                    } else {
+                        ui.label(selected.unwrap_or_else(|| "-".to_string()));
+                    }
+                    ui.end_row();
+
+                    ui.label("Date:");
+                    ui.add_enabled(
+                        editable,
+                        egui::TextEdit::singleline(&mut app.punch_form.date_input)
+                            .desired_width(120.0)
+                            .hint_text("YYYY-MM-DD"),
+                    );
+                    ui.end_row();
+
+                    ui.label("Time:");
+                    ui.add_enabled(
+                        editable,
+                        egui::TextEdit::singleline(&mut app.punch_form.time_input)
+                            .desired_width(120.0)
+                            .hint_text("HH:MM"),
+                    );
+                    ui.end_row();
+
+                    ui.label("Punch:");
+                    ui.add_enabled_ui(editable, |ui| {
+                        egui::ComboBox::from_id_salt("punch_state")
+                            .width(140.0)
+                            .selected_text(punch_state::name(app.punch_form.status))
+                            .show_ui(ui, |ui| {
+                                for code in punch_state::CHECK_IN..=punch_state::OVERTIME_OUT {
+                                    ui.selectable_value(&mut app.punch_form.status, code, punch_state::name(code));
+                                }
+                            });
+                    });
+                    ui.end_row();
+
+                    ui.label("Reason:");
+                    ui.add(
+                        egui::TextEdit::multiline(&mut app.punch_form.reason)
+                            .desired_width(260.0)
+                            .hint_text("Required"),
+                    );
+                    ui.end_row();
+
+                    ui.label("Entered by:");
+                    ui.label(app.acting_user());
+                    ui.end_row();
+                });
+
+            ui.add_space(15.0);
+            ui.separator();
+            ui.add_space(10.0);
+
+            ui.horizontal(|ui| {
+                if styled_button(ui, "Cancel").clicked() {
+                    app.punch_form.reset();
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let label = if editable { "Save" } else { "Void" };
+                    if primary_button_with_icon(ui, "", label).clicked() {
+                        save_punch(app);
+                    }
+                });
+            });
+        });
+}
+
+fn save_punch(app: &mut App) {
+    let form = &app.punch_form;
+
+    let reason = form.reason.trim().to_string();
+    if reason.is_empty() {
+        app.error_message = Some("Enter a reason".to_string());
+        return;
+    }
+    let acted_by = app.acting_user();
+
+    if form.action == PunchAction::Void {
+        if let Some(id) = form.punch_id {
+            app.void_punch(id, VoidPunch { reason, acted_by });
+        }
+        return;
+    }
+
+    let Some(date) = parse_flexible_date(&form.date_input) else {
+        app.error_message = Some("Date must be YYYY-MM-DD".to_string());
+        return;
+    };
+    let time_input = form.time_input.trim();
+    let Ok(time) =
+        NaiveTime::parse_from_str(time_input, "%H:%M:%S").or_else(|_| NaiveTime::parse_from_str(time_input, "%H:%M"))
+    else {
+        app.error_message = Some("Time must be HH:MM".to_string());
+        return;
+    };
+    let Some(check_time) = timezone::from_wall_clock(&date.and_time(time)) else {
+        app.error_message = Some("That time does not exist in the business timezone".to_string());
+        return;
+    };
+    let check_time = check_time.with_timezone(&Utc);
+    let status = form.status;
+
+    match (form.action, form.punch_id) {
+        (PunchAction::Edit, Some(id)) => {
+            let data = CorrectPunch {
+                check_time,
+                status,
+                reason,
+                acted_by,
+            };
+            app.correct_punch(id, data);
+        }
+        _ => {
+            let Some(employee_id) = form.employee_id else {
+                app.error_message = Some("Select an employee".to_string());
+                return;
+            };
+            let data = AddPunch {
+                employee_id,
+                check_time,
+                status,
+                reason,
+                acted_by,
+            };
+            app.add_punch(data);
+        }
+    }
+}
+
+/// Rows of the status report on the current page.
+fn status_page(app: &App) -> &[DailyStatus] {
+    let start = (app.report_filter.current_page * REPORT_PAGE_SIZE) as usize;
+    let end = (start + REPORT_PAGE_SIZE as usize).min(app.attendance_status.len());
+    app.attendance_status.get(start..end).unwrap_or_default()
+}
+
+fn show_status_table(app: &App, ui: &mut Ui) {
+    ScrollArea::both().show(ui, |ui| {
+        egui::Grid::new("attendance_status_grid")
+            .num_columns(11)
+            .striped(true)
+            .min_col_width(70.0)
+            .spacing([12.0, 8.0])
+            .show(ui, |ui| {
+                // Header
+                ui.strong("Code");
+                ui.strong("Name");
+                ui.strong("Department");
+                ui.strong("Date");
+                ui.strong("Shift");
+                ui.strong("First Check");
+                ui.strong("Last Check");
+                ui.strong("Status");
+                ui.strong("Late");
+                ui.strong("Early");
+                ui.strong("Leave");
+                ui.end_row();
+
+                let rows = status_page(app);
+                for record in rows {
+                    ui.label(&record.employee_code);
+                    ui.label(&record.full_name);
+                    ui.label(record.department_name.as_deref().unwrap_or("-"));
+                    ui.label(record.work_date.to_string());
+
+                    match (&record.shift_name, record.shift_start, record.shift_end) {
+                        (Some(name), Some(start), Some(end)) => {
+                            ui.label(name)
+                                .on_hover_text(format!("{}-{}", start.format("%H:%M"), end.format("%H:%M")));
+                        }
+                        _ => {
+                            ui.label("-");
+                        }
+                    }
+
+                    // Convert to local time for display
+                    let time = |check: Option<chrono::DateTime<chrono::Utc>>| {
+                        check
+                            .map(|c| timezone::to_business(&c).format("%H:%M:%S").to_string())
+                            .unwrap_or_else(|| "-".to_string())
+                    };
+                    ui.label(time(record.first_check));
+                    ui.label(time(record.last_check));
+
+                    let color = match record.status {
+                        _ if record.is_exception() => colors::ERROR,
+                        DayStatus::Present => colors::SUCCESS,
+                        _ => colors::NEUTRAL,
+                    };
+                    ui.label(RichText::new(record.label()).color(color));
+
+                    let minutes = |m: i64| if m > 0 { format!("{m} min") } else { "-".to_string() };
+                    ui.label(minutes(record.late_minutes));
+                    ui.label(minutes(record.early_minutes));
+                    ui.label(record.leave_label().unwrap_or_else(|| "-".to_string()));
+
+                    ui.end_row();
+                }
+
+                if rows.is_empty() {
+                    ui.label("No data. Click 'Generate Report' to classify attendance against shifts.");
+                    ui.end_row();
+                }
+            });
+    });
 }
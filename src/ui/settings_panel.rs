@@ -193,6 +193,117 @@ pub fn show(app: &mut App, ui: &mut egui::Ui) -> bool {
                         app.config_modified = true;
                     }
                     ui.end_row();
+
+                    ui.label("Double-punch window (seconds):");
+                    let mut window_str = app.double_punch_window.to_string();
+                    if ui
+                        .text_edit_singleline(&mut window_str)
+                        .on_hover_text("Repeat punches by the same employee within this window are hidden from reports (0 disables); shared by all workstations")
+                        .changed()
+                        && let Ok(window) = window_str.parse()
+                    {
+                        app.double_punch_window = window;
+                        app.config_modified = true;
+                    }
+                    ui.end_row();
+
+                    ui.label("Business timezone:");
+                    if ui
+                        .text_edit_singleline(&mut app.config.general.timezone)
+                        .on_hover_text("IANA name such as Asia/Ho_Chi_Minh; attendance days and reports use it (restart required)")
+                        .changed()
+                    {
+                        app.config_modified = true;
+                    }
+                    ui.end_row();
+
+                    ui.label("Work day starts at (HH:MM):");
+                    if ui
+                        .text_edit_singleline(&mut app.config.general.day_start)
+                        .on_hover_text("Punches before this time count towards the previous day, unless an overnight shift decides (restart required)")
+                        .changed()
+                    {
+                        app.config_modified = true;
+                    }
+                    ui.end_row();
+                });
+        });
+
+        ui.add_space(15.0);
+
+        // Overtime Rules
+        ui.group(|ui| {
+            ui.heading("Overtime Rules");
+            ui.label(RichText::new("Shared by all workstations").weak().italics());
+            ui.add_space(5.0);
+
+            let overtime = &mut app.overtime_form;
+            egui::Grid::new("overtime_settings_grid")
+                .num_columns(2)
+                .spacing([10.0, 8.0])
+                .show(ui, |ui| {
+                    let mut changed = false;
+
+                    ui.label("Overtime after (hours/day):");
+                    changed |= ui
+                        .add(egui::DragValue::new(&mut overtime.daily_threshold_hours).speed(0.25).range(0.0..=24.0))
+                        .changed();
+                    ui.end_row();
+
+                    ui.label("Weekday multiplier:");
+                    changed |= ui
+                        .add(egui::DragValue::new(&mut overtime.weekday_multiplier).speed(0.1).range(0.0..=10.0))
+                        .changed();
+                    ui.end_row();
+
+                    ui.label("Weekend / day off multiplier:");
+                    changed |= ui
+                        .add(egui::DragValue::new(&mut overtime.weekend_multiplier).speed(0.1).range(0.0..=10.0))
+                        .changed();
+                    ui.end_row();
+
+                    ui.label("Holiday multiplier:");
+                    changed |= ui
+                        .add(egui::DragValue::new(&mut overtime.holiday_multiplier).speed(0.1).range(0.0..=10.0))
+                        .changed();
+                    ui.end_row();
+
+                    ui.label("Night window (HH:MM):");
+                    ui.horizontal(|ui| {
+                        changed |= ui
+                            .add(egui::TextEdit::singleline(&mut overtime.night_start).desired_width(60.0))
+                            .changed();
+                        ui.label("to");
+                        changed |= ui
+                            .add(egui::TextEdit::singleline(&mut overtime.night_end).desired_width(60.0))
+                            .on_hover_text("Same as the start disables the night premium")
+                            .changed();
+                    });
+                    ui.end_row();
+
+                    ui.label("Night premium:");
+                    changed |= ui
+                        .add(egui::DragValue::new(&mut overtime.night_premium).speed(0.05).range(0.0..=10.0))
+                        .on_hover_text("Extra fraction of an hour paid per overtime hour in the night window")
+                        .changed();
+                    ui.end_row();
+
+                    ui.label("Minimum block (minutes):");
+                    changed |= ui
+                        .add(egui::DragValue::new(&mut overtime.min_block_minutes).range(0..=480))
+                        .changed();
+                    ui.end_row();
+
+                    ui.label("Round down to (minutes):");
+                    changed |= ui
+                        .add(egui::DragValue::new(&mut overtime.rounding_minutes).range(0..=60))
+                        .on_hover_text("0 disables rounding")
+                        .changed();
+                    ui.end_row();
+
+                    if changed {
+                        app.config_modified = true;
+                    }
                 });
         });
 
@@ -1,6 +1,8 @@
 //! First-run setup wizard for configuration.
 
 use crate::config::AppConfig;
+use crate::db::migration::{self, SchemaStatus};
+use crate::db::provision::{self, ProvisionReport, ProvisionRequest};
 use eframe::egui::{self, Color32, RichText};
 use std::sync::mpsc;
 
@@ -14,14 +16,43 @@ pub enum ConnectionTestState {
     Failed(String),
 }
 
+/// Database provisioning state.
+#[derive(Default, Clone)]
+pub enum ProvisionState {
+    #[default]
+    Idle,
+    Running {
+        progress: f32,
+        message: String,
+    },
+    Done(String),
+    Failed(String),
+}
+
+/// Message from the provisioning task.
+enum ProvisionMessage {
+    Progress(f32, String),
+    Finished(Result<ProvisionReport, String>),
+}
+
 /// Setup wizard state.
 pub struct SetupWizard {
-    /// Current step (0-4).
+    /// Current step (0-5).
     pub current_step: usize,
     /// Configuration being built.
     pub config: AppConfig,
     /// Database connection test state.
     pub db_test_state: ConnectionTestState,
+    /// Schema state found by the last successful database test.
+    pub schema_status: Option<SchemaStatus>,
+    /// Database provisioning state.
+    pub provision_state: ProvisionState,
+    /// Superuser for creating the database and role.
+    pub admin_username: String,
+    admin_password: String,
+    /// Create a default department in an empty database.
+    pub seed_department: bool,
+    pub seed_department_name: String,
     /// Device connection test state.
     pub device_test_state: ConnectionTestState,
     /// Wizard completed flag.
@@ -53,6 +84,12 @@ impl SetupWizard {
             interval_input: config.sync.interval_minutes.to_string(),
             config,
             db_test_state: ConnectionTestState::NotTested,
+            schema_status: None,
+            provision_state: ProvisionState::Idle,
+            admin_username: "postgres".to_string(),
+            admin_password: String::new(),
+            seed_department: true,
+            seed_department_name: "General".to_string(),
             device_test_state: ConnectionTestState::NotTested,
             completed: false,
         }
@@ -62,10 +99,14 @@ impl SetupWizard {
     pub fn can_proceed(&self) -> bool {
         match self.current_step {
             0 => true, // Welcome - always can proceed
-            1 => matches!(self.db_test_state, ConnectionTestState::Success),
-            2 => true, // Device is optional
-            3 => self.validate_sync_step().is_ok(),
-            4 => true, // Confirmation
+            1 => !matches!(self.db_test_state, ConnectionTestState::Testing),
+            2 => {
+                matches!(self.db_test_state, ConnectionTestState::Success)
+                    && !matches!(self.provision_state, ProvisionState::Running { .. })
+            }
+            3 => true, // Device is optional
+            4 => self.validate_sync_step().is_ok(),
+            5 => true, // Confirmation
             _ => false,
         }
     }
@@ -81,6 +122,9 @@ impl SetupWizard {
         if self.config.sync.interval_minutes < 1 {
             return Err("Interval must be at least 1 minute".to_string());
         }
+        if self.config.general.timezone().is_none() {
+            return Err("Timezone must be an IANA name such as Asia/Ho_Chi_Minh".to_string());
+        }
         Ok(())
     }
 
@@ -89,15 +133,16 @@ impl SetupWizard {
         match self.current_step {
             0 => "Welcome",
             1 => "Database Configuration",
-            2 => "Device Configuration",
-            3 => "Sync Settings",
-            4 => "Confirmation",
+            2 => "Database Setup",
+            3 => "Device Configuration",
+            4 => "Sync Settings",
+            5 => "Confirmation",
             _ => "Setup",
         }
     }
 
     /// Total number of steps.
-    const TOTAL_STEPS: usize = 5;
+    const TOTAL_STEPS: usize = 6;
 }
 
 /// Setup wizard application.
@@ -105,7 +150,8 @@ pub struct SetupApp {
     pub wizard: SetupWizard,
     pub initial_error: Option<String>,
     pub rt: tokio::runtime::Runtime,
-    db_test_rx: Option<mpsc::Receiver<Result<(), String>>>,
+    db_test_rx: Option<mpsc::Receiver<Result<SchemaStatus, String>>>,
+    provision_rx: Option<mpsc::Receiver<ProvisionMessage>>,
     device_test_rx: Option<mpsc::Receiver<Result<(), String>>>,
 }
 
@@ -116,6 +162,7 @@ impl SetupApp {
             initial_error,
             rt: tokio::runtime::Runtime::new().expect("Failed to create tokio runtime"),
             db_test_rx: None,
+            provision_rx: None,
             device_test_rx: None,
         }
     }
@@ -133,6 +180,35 @@ impl SetupApp {
         });
     }
 
+    /// Create and initialise the database asynchronously.
+    fn start_provision(&mut self) {
+        let request = ProvisionRequest {
+            database: self.wizard.config.database.clone(),
+            admin_username: self.wizard.admin_username.trim().to_string(),
+            admin_password: self.wizard.admin_password.clone(),
+            maintenance_db: "postgres".to_string(),
+            seed_department: self
+                .wizard
+                .seed_department
+                .then(|| self.wizard.seed_department_name.clone()),
+        };
+        let (tx, rx) = mpsc::channel();
+        self.provision_rx = Some(rx);
+        self.wizard.provision_state = ProvisionState::Running {
+            progress: 0.0,
+            message: "Starting...".to_string(),
+        };
+
+        self.rt.spawn(async move {
+            let result = provision::provision(&request, |progress, message| {
+                let _ = tx.send(ProvisionMessage::Progress(progress, message.to_string()));
+            })
+            .await
+            .map_err(|e| e.to_string());
+            let _ = tx.send(ProvisionMessage::Finished(result));
+        });
+    }
+
     /// Test device connection asynchronously.
     #[allow(dead_code)]
     fn start_device_test(&mut self) {
@@ -153,12 +229,44 @@ impl SetupApp {
             && let Ok(result) = rx.try_recv()
         {
             self.wizard.db_test_state = match result {
-                Ok(()) => ConnectionTestState::Success,
-                Err(e) => ConnectionTestState::Failed(e),
+                Ok(status) => {
+                    self.wizard.schema_status = Some(status);
+                    ConnectionTestState::Success
+                }
+                Err(e) => {
+                    self.wizard.schema_status = None;
+                    ConnectionTestState::Failed(e)
+                }
             };
             self.db_test_rx = None;
         }
 
+        let mut provisioned = false;
+        if let Some(rx) = &self.provision_rx {
+            while let Ok(message) = rx.try_recv() {
+                match message {
+                    ProvisionMessage::Progress(progress, message) => {
+                        self.wizard.provision_state = ProvisionState::Running { progress, message };
+                    }
+                    ProvisionMessage::Finished(Ok(report)) => {
+                        self.wizard.provision_state = ProvisionState::Done(provision_summary(&report));
+                        provisioned = true;
+                    }
+                    ProvisionMessage::Finished(Err(e)) => {
+                        self.wizard.provision_state = ProvisionState::Failed(e);
+                    }
+                }
+            }
+            if !matches!(self.wizard.provision_state, ProvisionState::Running { .. }) {
+                self.provision_rx = None;
+            }
+        }
+
+        // Re-read the schema state with the application credentials
+        if provisioned {
+            self.start_db_test();
+        }
+
         if let Some(rx) = &self.device_test_rx
             && let Ok(result) = rx.try_recv()
         {
@@ -179,6 +287,7 @@ impl eframe::App for SetupApp {
         // Request repaint while testing
         if matches!(self.wizard.db_test_state, ConnectionTestState::Testing)
             || matches!(self.wizard.device_test_state, ConnectionTestState::Testing)
+            || matches!(self.wizard.provision_state, ProvisionState::Running { .. })
         {
             ctx.request_repaint();
         }
@@ -224,6 +333,7 @@ impl eframe::App for SetupApp {
                 ui.add_space(20.0);
 
                 // Step content
+                let mut needs_provision = false;
                 let needs_db_test = match self.wizard.current_step {
                     0 => {
                         show_welcome_step(ui);
@@ -231,14 +341,18 @@ impl eframe::App for SetupApp {
                     }
                     1 => show_database_step(ui, &mut self.wizard),
                     2 => {
-                        show_device_step(ui, &mut self.wizard);
+                        needs_provision = show_provision_step(ui, &mut self.wizard);
                         false
                     }
                     3 => {
-                        show_sync_step(ui, &mut self.wizard);
+                        show_device_step(ui, &mut self.wizard);
                         false
                     }
                     4 => {
+                        show_sync_step(ui, &mut self.wizard);
+                        false
+                    }
+                    5 => {
                         show_confirmation_step(ui, &self.wizard);
                         false
                     }
@@ -248,6 +362,9 @@ impl eframe::App for SetupApp {
                 if needs_db_test {
                     self.start_db_test();
                 }
+                if needs_provision {
+                    self.start_provision();
+                }
 
                 ui.add_space(30.0);
                 ui.separator();
@@ -268,6 +385,12 @@ impl eframe::App for SetupApp {
                             let enabled = self.wizard.can_proceed();
                             if ui.add_enabled(enabled, egui::Button::new(btn_text)).clicked() {
                                 self.wizard.current_step += 1;
+                                // Check the connection on entering the setup step
+                                if self.wizard.current_step == 2
+                                    && !matches!(self.wizard.db_test_state, ConnectionTestState::Success)
+                                {
+                                    self.start_db_test();
+                                }
                             }
                         } else {
                             // Final step - Save & Exit
@@ -368,9 +491,103 @@ fn show_database_step(ui: &mut egui::Ui, wizard: &mut SetupWizard) -> bool {
         }
     });
 
+    if let (ConnectionTestState::Success, Some(status)) = (&wizard.db_test_state, &wizard.schema_status) {
+        ui.add_space(5.0);
+        ui.label(schema_summary(status));
+    }
+
+    ui.add_space(10.0);
+    ui.label(
+        RichText::new("If the database or user does not exist yet, it can be created in the next step.").italics(),
+    );
+
     needs_test
 }
 
+/// Show the provisioning step. Returns true when provisioning was requested.
+fn show_provision_step(ui: &mut egui::Ui, wizard: &mut SetupWizard) -> bool {
+    let mut needs_provision = false;
+    let running = matches!(wizard.provision_state, ProvisionState::Running { .. });
+
+    match (&wizard.db_test_state, &wizard.schema_status) {
+        (ConnectionTestState::Testing, _) => {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label("Checking database...");
+            });
+        }
+        (ConnectionTestState::Success, Some(status)) => {
+            ui.colored_label(Color32::from_rgb(100, 200, 100), schema_summary(status));
+        }
+        (ConnectionTestState::Failed(e), _) => {
+            ui.colored_label(Color32::from_rgb(255, 200, 100), format!("Cannot connect: {e}"));
+        }
+        _ => {
+            ui.label("Database not checked yet.");
+        }
+    }
+
+    ui.add_space(10.0);
+    ui.label(format!(
+        "Create database '{}' and user '{}' if missing, apply the schema and set the search path.",
+        wizard.config.database.name, wizard.config.database.username
+    ));
+    ui.label(RichText::new("Administrator credentials are used once and not saved.").italics());
+    ui.add_space(10.0);
+
+    ui.add_enabled_ui(!running, |ui| {
+        egui::Grid::new("provision_grid")
+            .num_columns(2)
+            .spacing([20.0, 8.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Admin user:");
+                ui.text_edit_singleline(&mut wizard.admin_username);
+                ui.end_row();
+
+                ui.label("Admin password:");
+                ui.add(egui::TextEdit::singleline(&mut wizard.admin_password).password(true));
+                ui.end_row();
+
+                ui.label("Department:");
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut wizard.seed_department, "Create default");
+                    ui.add_enabled(
+                        wizard.seed_department,
+                        egui::TextEdit::singleline(&mut wizard.seed_department_name),
+                    );
+                });
+                ui.end_row();
+            });
+
+        ui.add_space(10.0);
+        let ready =
+            !wizard.admin_username.trim().is_empty() && !matches!(wizard.db_test_state, ConnectionTestState::Testing);
+        if ui
+            .add_enabled(ready, egui::Button::new("Create & Initialize"))
+            .clicked()
+        {
+            needs_provision = true;
+        }
+    });
+
+    ui.add_space(10.0);
+    match &wizard.provision_state {
+        ProvisionState::Idle => {}
+        ProvisionState::Running { progress, message } => {
+            ui.add(egui::ProgressBar::new(*progress).text(message.as_str()));
+        }
+        ProvisionState::Done(summary) => {
+            ui.colored_label(Color32::from_rgb(100, 200, 100), summary);
+        }
+        ProvisionState::Failed(e) => {
+            ui.colored_label(Color32::from_rgb(255, 100, 100), format!("Setup failed: {e}"));
+        }
+    }
+
+    needs_provision
+}
+
 fn show_device_step(ui: &mut egui::Ui, wizard: &mut SetupWizard) {
     ui.label("Configure the ZKTeco fingerprint scanner connection.");
     ui.label(RichText::new("This step is optional - you can configure it later.").italics());
@@ -446,6 +663,10 @@ fn show_sync_step(ui: &mut egui::Ui, wizard: &mut SetupWizard) {
                 wizard.config.sync.interval_minutes = i;
             }
             ui.end_row();
+
+            ui.label("Business timezone:");
+            ui.text_edit_singleline(&mut wizard.config.general.timezone);
+            ui.end_row();
         });
 
     // Validation feedback
@@ -511,13 +732,51 @@ fn show_confirmation_step(ui: &mut egui::Ui, wizard: &SetupWizard) {
     ui.label("You will need to restart the application after setup.");
 }
 
-/// Test database connection.
-async fn test_db_connection(conn_str: &str) -> Result<(), String> {
-    use sea_orm::Database;
+/// Describe the schema state found by the database test.
+fn schema_summary(status: &SchemaStatus) -> String {
+    match status.pending() {
+        0 => format!("Schema is up to date (version {}).", status.current),
+        _ if status.current == 0 => "Database is empty; the schema will be created on first start.".to_string(),
+        pending => format!(
+            "Schema version {}; {pending} migration(s) will be applied on first start.",
+            status.current
+        ),
+    }
+}
+
+/// Describe what provisioning did.
+fn provision_summary(report: &ProvisionReport) -> String {
+    let mut done = Vec::new();
+    if report.database_created {
+        done.push("database created".to_string());
+    }
+    if report.role_created {
+        done.push("user created".to_string());
+    }
+    if !report.migrations.is_empty() {
+        done.push(format!("{} migration(s) applied", report.migrations.len()));
+    }
+    if report.department_seeded {
+        done.push("default department added".to_string());
+    }
+
+    if done.is_empty() {
+        "Database is already set up.".to_string()
+    } else {
+        format!("Database ready: {}.", done.join(", "))
+    }
+}
+
+/// Test database connection and read its schema version.
+///
+/// Fails if the schema is newer than this build supports.
+async fn test_db_connection(conn_str: &str) -> Result<SchemaStatus, String> {
+    let conn = crate::db::connect(conn_str).await.map_err(|e| e.to_string())?;
 
-    let conn = Database::connect(conn_str).await.map_err(|e| e.to_string())?;
+    let status = migration::status(&conn).await.map_err(|e| e.to_string())?;
+    status.ensure_supported().map_err(|e| e.to_string())?;
 
-    conn.ping().await.map_err(|e| e.to_string())
+    Ok(status)
 }
 
 /// Test device connection (simple HTTP check).
@@ -0,0 +1,1175 @@
+//! Shift management panel: shift definitions, rotation patterns, assignments, the weekly roster
+//! and the holiday calendar.
+
+use chrono::{Duration, NaiveDate, NaiveTime};
+use eframe::egui::{self, RichText, ScrollArea, Ui};
+use egui_phosphor::regular::{ARROWS_CLOCKWISE, CALENDAR_PLUS, CARET_LEFT, CARET_RIGHT, PENCIL, PLUS, TRASH};
+
+use super::app::{App, AssignmentForm, DeleteTarget, HolidayForm, PatternForm, ShiftForm, ShiftTab, week_start};
+use super::components::{
+    action_button, back_button, colors, danger_action_button, panel_header, primary_button_with_icon, styled_button,
+    styled_button_with_icon,
+};
+use crate::models::holiday::{CreateHoliday, UpdateHoliday};
+use crate::models::shift::{
+    CreateShift, CreateShiftAssignment, SaveShiftPattern, ScheduleSource, SetRosterEntry, UpdateShift, crosses_midnight,
+};
+use crate::models::user::Permission;
+use crate::timezone;
+
+/// Longest cycle a pattern may have (matches the database check).
+const MAX_CYCLE_DAYS: usize = 366;
+
+/// Parse a wall-clock time such as `08:00` or `8:00`.
+fn parse_time(input: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(input.trim(), "%H:%M").ok()
+}
+
+/// Parse a non-negative minute count; empty input means zero.
+fn parse_minutes(input: &str) -> Option<i32> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Some(0);
+    }
+    input.parse().ok().filter(|m| *m >= 0)
+}
+
+/// Parse an ISO date; empty input means `None`.
+fn parse_date(input: &str) -> Result<Option<NaiveDate>, ()> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(None);
+    }
+    NaiveDate::parse_from_str(input, "%Y-%m-%d").map(Some).map_err(|_| ())
+}
+
+/// Show the shifts panel.
+///
+/// Returns `true` if the back button was clicked.
+pub fn show(app: &mut App, ui: &mut Ui) -> bool {
+    let mut go_back = false;
+
+    if back_button(ui) {
+        go_back = true;
+    }
+
+    panel_header(ui, "Manage Shifts");
+
+    // Tab selector
+    ui.horizontal(|ui| {
+        for (tab, label) in [
+            (ShiftTab::Shifts, "Shifts"),
+            (ShiftTab::Patterns, "Patterns"),
+            (ShiftTab::Assignments, "Assignments"),
+            (ShiftTab::Roster, "Roster"),
+            (ShiftTab::Holidays, "Holidays"),
+        ] {
+            if ui.selectable_label(app.shift_tab == tab, label).clicked() && app.shift_tab != tab {
+                app.shift_tab = tab;
+                match tab {
+                    ShiftTab::Roster => app.load_roster(),
+                    ShiftTab::Holidays => app.load_holidays(),
+                    _ => {}
+                }
+            }
+        }
+    });
+
+    ui.add_space(10.0);
+
+    match app.shift_tab {
+        ShiftTab::Shifts => show_shifts(app, ui),
+        ShiftTab::Patterns => show_patterns(app, ui),
+        ShiftTab::Assignments => show_assignments(app, ui),
+        ShiftTab::Roster => show_roster(app, ui),
+        ShiftTab::Holidays => show_holidays(app, ui),
+    }
+
+    // Form dialogs
+    if app.shift_form.is_open {
+        show_shift_dialog(app, ui.ctx());
+    }
+    if app.pattern_form.is_open {
+        show_pattern_dialog(app, ui.ctx());
+    }
+    if app.assignment_form.is_open {
+        show_assignment_dialog(app, ui.ctx());
+    }
+    if app.holiday_form.is_open {
+        show_holiday_dialog(app, ui.ctx());
+    }
+
+    go_back
+}
+
+/// Display name of a shift, or "Off" for a day off.
+fn shift_name(app: &App, shift_id: Option<i32>) -> String {
+    match shift_id {
+        Some(id) => app
+            .shifts
+            .iter()
+            .find(|s| s.id == id)
+            .map(|s| s.name.clone())
+            .unwrap_or_else(|| format!("#{id}")),
+        None => "Off".to_string(),
+    }
+}
+
+// ============================================================================
+// Shifts
+// ============================================================================
+
+fn show_shifts(app: &mut App, ui: &mut Ui) {
+    let can_edit = app.can(Permission::EditStaff);
+
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled_ui(can_edit, |ui| primary_button_with_icon(ui, PLUS, "Add Shift").clicked())
+            .inner
+        {
+            app.shift_form = ShiftForm {
+                start_time: "08:00".to_string(),
+                end_time: "17:00".to_string(),
+                break_minutes: "60".to_string(),
+                late_grace_minutes: "0".to_string(),
+                early_grace_minutes: "0".to_string(),
+                is_active: true,
+                is_open: true,
+                ..Default::default()
+            };
+        }
+
+        ui.add_space(10.0);
+
+        if styled_button_with_icon(ui, ARROWS_CLOCKWISE, "Refresh").clicked() {
+            app.load_shifts();
+        }
+    });
+
+    ui.add_space(15.0);
+    ui.label(format!("{count} shifts", count = app.shifts.len()));
+    ui.add_space(10.0);
+
+    ScrollArea::vertical().show(ui, |ui| {
+        egui::Grid::new("shifts_grid")
+            .num_columns(8)
+            .striped(true)
+            .min_col_width(60.0)
+            .spacing([15.0, 8.0])
+            .show(ui, |ui| {
+                ui.strong("Name");
+                ui.strong("Start");
+                ui.strong("End");
+                ui.strong("Break");
+                ui.strong("Late Grace");
+                ui.strong("Early Grace");
+                ui.strong("Active");
+                ui.strong("Actions");
+                ui.end_row();
+
+                let shifts = app.shifts.clone();
+                for shift in &shifts {
+                    ui.label(&shift.name);
+                    ui.label(shift.start_time.format("%H:%M").to_string());
+                    let end = shift.end_time.format("%H:%M").to_string();
+                    if shift.crosses_midnight {
+                        ui.label(format!("{end} (+1 day)"));
+                    } else {
+                        ui.label(end);
+                    }
+                    ui.label(format!("{} min", shift.break_minutes));
+                    ui.label(format!("{} min", shift.late_grace_minutes));
+                    ui.label(format!("{} min", shift.early_grace_minutes));
+                    ui.label(if shift.is_active { "Yes" } else { "No" });
+
+                    ui.horizontal(|ui| {
+                        if !can_edit {
+                            ui.disable();
+                        }
+                        if action_button(ui, PENCIL, "Edit").clicked() {
+                            app.shift_form = ShiftForm::edit(shift);
+                        }
+                        if danger_action_button(ui, TRASH, "Delete").clicked() {
+                            app.delete_target = Some(DeleteTarget::Shift(shift.id, shift.name.clone()));
+                            app.show_delete_confirm = true;
+                        }
+                    });
+
+                    ui.end_row();
+                }
+            });
+    });
+}
+
+fn show_shift_dialog(app: &mut App, ctx: &egui::Context) {
+    let title = if app.shift_form.is_editing {
+        "Edit Shift"
+    } else {
+        "Add Shift"
+    };
+
+    egui::Window::new(title)
+        .collapsible(false)
+        .resizable(false)
+        .default_width(400.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(10.0);
+
+            let form = &mut app.shift_form;
+            egui::Grid::new("shift_form_grid")
+                .num_columns(2)
+                .spacing([20.0, 12.0])
+                .show(ui, |ui| {
+                    ui.label("Name:");
+                    ui.add(egui::TextEdit::singleline(&mut form.name).desired_width(250.0));
+                    ui.end_row();
+
+                    ui.label("Start (HH:MM):");
+                    ui.add(egui::TextEdit::singleline(&mut form.start_time).desired_width(80.0));
+                    ui.end_row();
+
+                    ui.label("End (HH:MM):");
+                    ui.horizontal(|ui| {
+                        ui.add(egui::TextEdit::singleline(&mut form.end_time).desired_width(80.0));
+                        if let (Some(start), Some(end)) = (parse_time(&form.start_time), parse_time(&form.end_time))
+                            && crosses_midnight(start, end)
+                        {
+                            ui.label(RichText::new("ends next day").color(colors::WARNING));
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("Break (minutes):");
+                    ui.add(egui::TextEdit::singleline(&mut form.break_minutes).desired_width(80.0));
+                    ui.end_row();
+
+                    ui.label("Late grace (minutes):");
+                    ui.add(egui::TextEdit::singleline(&mut form.late_grace_minutes).desired_width(80.0));
+                    ui.end_row();
+
+                    ui.label("Early grace (minutes):");
+                    ui.add(egui::TextEdit::singleline(&mut form.early_grace_minutes).desired_width(80.0));
+                    ui.end_row();
+
+                    ui.label("Active:");
+                    ui.checkbox(&mut form.is_active, "");
+                    ui.end_row();
+                });
+
+            ui.add_space(15.0);
+            ui.separator();
+            ui.add_space(10.0);
+
+            ui.horizontal(|ui| {
+                if styled_button(ui, "Cancel").clicked() {
+                    app.shift_form.reset();
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if primary_button_with_icon(ui, "", "Save").clicked() {
+                        save_shift(app);
+                    }
+                });
+            });
+        });
+}
+
+fn save_shift(app: &mut App) {
+    let form = &app.shift_form;
+
+    // Validation
+    if form.name.trim().is_empty() {
+        app.error_message = Some("Name is required".to_string());
+        return;
+    }
+    let (Some(start_time), Some(end_time)) = (parse_time(&form.start_time), parse_time(&form.end_time)) else {
+        app.error_message = Some("Start and end must be times like 08:00".to_string());
+        return;
+    };
+    let (Some(break_minutes), Some(late), Some(early)) = (
+        parse_minutes(&form.break_minutes),
+        parse_minutes(&form.late_grace_minutes),
+        parse_minutes(&form.early_grace_minutes),
+    ) else {
+        app.error_message = Some("Break and grace periods must be whole minutes".to_string());
+        return;
+    };
+
+    if form.is_editing {
+        let id = form.id.unwrap();
+        let data = UpdateShift {
+            name: Some(form.name.trim().to_string()),
+            start_time: Some(start_time),
+            end_time: Some(end_time),
+            break_minutes: Some(break_minutes),
+            late_grace_minutes: Some(late),
+            early_grace_minutes: Some(early),
+            is_active: Some(form.is_active),
+        };
+        app.update_shift(id, data);
+    } else {
+        let data = CreateShift {
+            name: form.name.trim().to_string(),
+            start_time,
+            end_time,
+            break_minutes,
+            late_grace_minutes: late,
+            early_grace_minutes: early,
+        };
+        app.create_shift(data);
+    }
+}
+
+// ============================================================================
+// Patterns
+// ============================================================================
+
+fn show_patterns(app: &mut App, ui: &mut Ui) {
+    let can_edit = app.can(Permission::EditStaff);
+
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled_ui(can_edit, |ui| {
+                primary_button_with_icon(ui, PLUS, "Add Pattern").clicked()
+            })
+            .inner
+        {
+            // Weekly by default: first active shift Monday to Friday, weekend off
+            let default_shift = app.shifts.iter().find(|s| s.is_active).map(|s| s.id);
+            app.pattern_form = PatternForm {
+                days: (0..7).map(|i| if i < 5 { default_shift } else { None }).collect(),
+                is_open: true,
+                ..Default::default()
+            };
+        }
+
+        ui.add_space(10.0);
+
+        if styled_button_with_icon(ui, ARROWS_CLOCKWISE, "Refresh").clicked() {
+            app.load_shift_patterns();
+        }
+    });
+
+    ui.add_space(15.0);
+    ui.label(format!("{count} patterns", count = app.shift_patterns.len()));
+    ui.add_space(10.0);
+
+    ScrollArea::vertical().show(ui, |ui| {
+        egui::Grid::new("shift_patterns_grid")
+            .num_columns(4)
+            .striped(true)
+            .min_col_width(80.0)
+            .spacing([15.0, 8.0])
+            .show(ui, |ui| {
+                ui.strong("Name");
+                ui.strong("Cycle");
+                ui.strong("Days");
+                ui.strong("Actions");
+                ui.end_row();
+
+                let patterns = app.shift_patterns.clone();
+                for pattern in &patterns {
+                    ui.label(&pattern.name);
+                    ui.label(format!("{} days", pattern.days.len()));
+
+                    let days: Vec<String> = pattern.days.iter().map(|d| shift_name(app, *d)).collect();
+                    ui.label(days.join(", "));
+
+                    ui.horizontal(|ui| {
+                        if !can_edit {
+                            ui.disable();
+                        }
+                        if action_button(ui, PENCIL, "Edit").clicked() {
+                            app.pattern_form = PatternForm::edit(pattern);
+                        }
+                        if danger_action_button(ui, TRASH, "Delete").clicked() {
+                            app.delete_target = Some(DeleteTarget::ShiftPattern(pattern.id, pattern.name.clone()));
+                            app.show_delete_confirm = true;
+                        }
+                    });
+
+                    ui.end_row();
+                }
+            });
+    });
+}
+
+fn show_pattern_dialog(app: &mut App, ctx: &egui::Context) {
+    let title = if app.pattern_form.is_editing {
+        "Edit Pattern"
+    } else {
+        "Add Pattern"
+    };
+
+    egui::Window::new(title)
+        .collapsible(false)
+        .resizable(false)
+        .default_width(420.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(10.0);
+
+            let shifts = app.shifts.clone();
+            let form = &mut app.pattern_form;
+
+            egui::Grid::new("pattern_form_grid")
+                .num_columns(2)
+                .spacing([20.0, 12.0])
+                .show(ui, |ui| {
+                    ui.label("Name:");
+                    ui.add(egui::TextEdit::singleline(&mut form.name).desired_width(250.0));
+                    ui.end_row();
+
+                    ui.label("Cycle (days):");
+                    let mut cycle = form.days.len();
+                    ui.add(egui::DragValue::new(&mut cycle).range(1..=MAX_CYCLE_DAYS));
+                    form.days.resize(cycle, None);
+                    ui.end_row();
+                });
+
+            ui.add_space(10.0);
+            ui.label(RichText::new("Day 1 is the assignment start date; the cycle then repeats.").weak());
+            ui.add_space(5.0);
+
+            ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                egui::Grid::new("pattern_days_grid")
+                    .num_columns(2)
+                    .spacing([20.0, 6.0])
+                    .show(ui, |ui| {
+                        for (index, day) in form.days.iter_mut().enumerate() {
+                            ui.label(format!("Day {}", index + 1));
+                            let selected = match day {
+                                Some(id) => shifts
+                                    .iter()
+                                    .find(|s| s.id == *id)
+                                    .map(|s| s.name.clone())
+                                    .unwrap_or_else(|| format!("#{id}")),
+                                None => "Off".to_string(),
+                            };
+                            egui::ComboBox::from_id_salt(("pattern_day", index))
+                                .width(200.0)
+                                .selected_text(selected)
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(day, None, "Off");
+                                    let current = *day;
+                                    for shift in shifts.iter().filter(|s| s.is_active || current == Some(s.id)) {
+                                        ui.selectable_value(day, Some(shift.id), &shift.name);
+                                    }
+                                });
+                            ui.end_row();
+                        }
+                    });
+            });
+
+            ui.add_space(15.0);
+            ui.separator();
+            ui.add_space(10.0);
+
+            ui.horizontal(|ui| {
+                if styled_button(ui, "Cancel").clicked() {
+                    app.pattern_form.reset();
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if primary_button_with_icon(ui, "", "Save").clicked() {
+                        save_pattern(app);
+                    }
+                });
+            });
+        });
+}
+
+fn save_pattern(app: &mut App) {
+    let form = &app.pattern_form;
+
+    if form.name.trim().is_empty() {
+        app.error_message = Some("Name is required".to_string());
+        return;
+    }
+    if form.days.is_empty() {
+        app.error_message = Some("A pattern needs at least one day".to_string());
+        return;
+    }
+
+    let data = SaveShiftPattern {
+        name: form.name.trim().to_string(),
+        days: form.days.clone(),
+    };
+    app.save_shift_pattern(form.id, data);
+}
+
+// ============================================================================
+// Assignments
+// ============================================================================
+
+/// Human-readable label for an assignment target.
+fn assignment_target(app: &App, employee_id: Option<i32>, department_id: Option<i32>) -> String {
+    if let Some(id) = employee_id {
+        return app
+            .employees
+            .iter()
+            .find(|e| e.id == id)
+            .map(|e| format!("{} - {}", e.employee_code, e.full_name))
+            .unwrap_or_else(|| format!("Employee #{id}"));
+    }
+    if let Some(id) = department_id {
+        return app
+            .departments
+            .iter()
+            .find(|d| d.id == id)
+            .map(|d| format!("{} (department)", d.name))
+            .unwrap_or_else(|| format!("Department #{id}"));
+    }
+    "-".to_string()
+}
+
+fn show_assignments(app: &mut App, ui: &mut Ui) {
+    let can_edit = app.can(Permission::EditStaff);
+
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled_ui(can_edit, |ui| {
+                primary_button_with_icon(ui, PLUS, "Assign Pattern").clicked()
+            })
+            .inner
+        {
+            app.assignment_form = AssignmentForm {
+                for_employee: false,
+                pattern_id: app.shift_patterns.first().map(|p| p.id),
+                start_date_input: timezone::today().format("%Y-%m-%d").to_string(),
+                is_open: true,
+                ..Default::default()
+            };
+        }
+
+        ui.add_space(10.0);
+
+        if styled_button_with_icon(ui, ARROWS_CLOCKWISE, "Refresh").clicked() {
+            app.load_shift_assignments();
+        }
+    });
+
+    ui.add_space(10.0);
+    ui.label(
+        RichText::new(
+            "Employee assignments override department ones; departments without one inherit from their parent.",
+        )
+        .weak(),
+    );
+    ui.add_space(10.0);
+    ui.label(format!("{count} assignments", count = app.shift_assignments.len()));
+    ui.add_space(10.0);
+
+    ScrollArea::vertical().show(ui, |ui| {
+        egui::Grid::new("shift_assignments_grid")
+            .num_columns(5)
+            .striped(true)
+            .min_col_width(80.0)
+            .spacing([15.0, 8.0])
+            .show(ui, |ui| {
+                ui.strong("Assigned To");
+                ui.strong("Pattern");
+                ui.strong("From");
+                ui.strong("Until");
+                ui.strong("Actions");
+                ui.end_row();
+
+                let assignments = app.shift_assignments.clone();
+                for assignment in &assignments {
+                    let target = assignment_target(app, assignment.employee_id, assignment.department_id);
+                    let pattern = app
+                        .shift_patterns
+                        .iter()
+                        .find(|p| p.id == assignment.pattern_id)
+                        .map(|p| p.name.clone())
+                        .unwrap_or_else(|| format!("#{}", assignment.pattern_id));
+
+                    ui.label(&target);
+                    ui.label(&pattern);
+                    ui.label(assignment.start_date.format("%Y-%m-%d").to_string());
+                    ui.label(
+                        assignment
+                            .end_date
+                            .map(|d| d.format("%Y-%m-%d").to_string())
+                            .unwrap_or_else(|| "Open".to_string()),
+                    );
+
+                    if ui
+                        .add_enabled_ui(can_edit, |ui| danger_action_button(ui, TRASH, "Delete").clicked())
+                        .inner
+                    {
+                        app.delete_target = Some(DeleteTarget::ShiftAssignment(
+                            assignment.id,
+                            format!("{pattern} for {target}"),
+                        ));
+                        app.show_delete_confirm = true;
+                    }
+
+                    ui.end_row();
+                }
+            });
+    });
+}
+
+fn show_assignment_dialog(app: &mut App, ctx: &egui::Context) {
+    egui::Window::new("Assign Pattern")
+        .collapsible(false)
+        .resizable(false)
+        .default_width(420.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(10.0);
+
+            egui::Grid::new("assignment_form_grid")
+                .num_columns(2)
+                .spacing([20.0, 12.0])
+                .show(ui, |ui| {
+                    ui.label("Assign to:");
+                    ui.horizontal(|ui| {
+                        ui.radio_value(&mut app.assignment_form.for_employee, false, "Department");
+                        ui.radio_value(&mut app.assignment_form.for_employee, true, "Employee");
+                    });
+                    ui.end_row();
+
+                    if app.assignment_form.for_employee {
+                        ui.label("Employee:");
+                        let selected = app
+                            .assignment_form
+                            .employee_id
+                            .map(|id| assignment_target(app, Some(id), None))
+                            .unwrap_or_else(|| "Select...".to_string());
+                        egui::ComboBox::from_id_salt("assignment_employee")
+                            .width(250.0)
+                            .selected_text(selected)
+                            .show_ui(ui, |ui| {
+                                for emp in app.employees.iter().filter(|e| e.is_active) {
+                                    ui.selectable_value(
+                                        &mut app.assignment_form.employee_id,
+                                        Some(emp.id),
+                                        format!("{} - {}", emp.employee_code, emp.full_name),
+                                    );
+                                }
+                            });
+                    } else {
+                        ui.label("Department:");
+                        let selected = app
+                            .assignment_form
+                            .department_id
+                            .and_then(|id| app.departments.iter().find(|d| d.id == id))
+                            .map(|d| d.name.clone())
+                            .unwrap_or_else(|| "Select...".to_string());
+                        egui::ComboBox::from_id_salt("assignment_department")
+                            .width(250.0)
+                            .selected_text(selected)
+                            .show_ui(ui, |ui| {
+                                for dept in &app.departments {
+                                    ui.selectable_value(
+                                        &mut app.assignment_form.department_id,
+                                        Some(dept.id),
+                                        &dept.name,
+                                    );
+                                }
+                            });
+                    }
+                    ui.end_row();
+
+                    ui.label("Pattern:");
+                    let selected = app
+                        .assignment_form
+                        .pattern_id
+                        .and_then(|id| app.shift_patterns.iter().find(|p| p.id == id))
+                        .map(|p| p.name.clone())
+                        .unwrap_or_else(|| "Select...".to_string());
+                    egui::ComboBox::from_id_salt("assignment_pattern")
+                        .width(250.0)
+                        .selected_text(selected)
+                        .show_ui(ui, |ui| {
+                            for pattern in &app.shift_patterns {
+                                ui.selectable_value(
+                                    &mut app.assignment_form.pattern_id,
+                                    Some(pattern.id),
+                                    &pattern.name,
+                                );
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("From:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut app.assignment_form.start_date_input)
+                            .desired_width(120.0)
+                            .hint_text("YYYY-MM-DD"),
+                    );
+                    ui.end_row();
+
+                    ui.label("Until:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut app.assignment_form.end_date_input)
+                            .desired_width(120.0)
+                            .hint_text("open-ended"),
+                    );
+                    ui.end_row();
+                });
+
+            ui.add_space(15.0);
+            ui.separator();
+            ui.add_space(10.0);
+
+            ui.horizontal(|ui| {
+                if styled_button(ui, "Cancel").clicked() {
+                    app.assignment_form.reset();
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if primary_button_with_icon(ui, "", "Save").clicked() {
+                        save_assignment(app);
+                    }
+                });
+            });
+        });
+}
+
+fn save_assignment(app: &mut App) {
+    let form = &app.assignment_form;
+
+    let (employee_id, department_id) = if form.for_employee {
+        (form.employee_id, None)
+    } else {
+        (None, form.department_id)
+    };
+    if employee_id.is_none() && department_id.is_none() {
+        app.error_message = Some("Select who the pattern is assigned to".to_string());
+        return;
+    }
+    let Some(pattern_id) = form.pattern_id else {
+        app.error_message = Some("Select a pattern".to_string());
+        return;
+    };
+    let Ok(Some(start_date)) = parse_date(&form.start_date_input) else {
+        app.error_message = Some("Start date must be YYYY-MM-DD".to_string());
+        return;
+    };
+    let Ok(end_date) = parse_date(&form.end_date_input) else {
+        app.error_message = Some("End date must be YYYY-MM-DD or empty".to_string());
+        return;
+    };
+    if end_date.is_some_and(|end| end < start_date) {
+        app.error_message = Some("End date is before the start date".to_string());
+        return;
+    }
+
+    app.create_shift_assignment(CreateShiftAssignment {
+        employee_id,
+        department_id,
+        pattern_id,
+        start_date,
+        end_date,
+    });
+}
+
+// ============================================================================
+// Roster
+// ============================================================================
+
+/// Roster cell choice.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum RosterChoice {
+    /// No roster entry; the assigned pattern applies.
+    Default,
+    /// Rostered day off.
+    Off,
+    /// Rostered shift.
+    Shift(i32),
+}
+
+fn show_roster(app: &mut App, ui: &mut Ui) {
+    let can_edit = app.can(Permission::EditStaff);
+
+    let week_start_date = app.roster_week_start;
+
+    ui.horizontal(|ui| {
+        if styled_button_with_icon(ui, CARET_LEFT, "Previous").clicked() {
+            app.roster_week_start -= Duration::days(7);
+            app.load_roster();
+        }
+        if styled_button(ui, "This Week").clicked() {
+            app.roster_week_start = week_start(timezone::today());
+            app.load_roster();
+        }
+        if styled_button_with_icon(ui, CARET_RIGHT, "Next").clicked() {
+            app.roster_week_start += Duration::days(7);
+            app.load_roster();
+        }
+
+        ui.add_space(10.0);
+        ui.label(format!(
+            "{} to {}",
+            week_start_date.format("%Y-%m-%d"),
+            (week_start_date + Duration::days(6)).format("%Y-%m-%d")
+        ));
+
+        ui.add_space(20.0);
+
+        ui.label("Department:");
+        egui::ComboBox::from_id_salt("roster_dept_filter")
+            .width(180.0)
+            .selected_text(
+                app.roster_dept_filter
+                    .and_then(|id| app.departments.iter().find(|d| d.id == id))
+                    .map(|d| d.name.as_str())
+                    .unwrap_or("All"),
+            )
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut app.roster_dept_filter, None, "All");
+                for dept in &app.departments {
+                    ui.selectable_value(&mut app.roster_dept_filter, Some(dept.id), &dept.name);
+                }
+            });
+    });
+
+    ui.add_space(10.0);
+    ui.label(RichText::new("Cells show the planned shift; pick a shift or Off to override a single day.").weak());
+    ui.add_space(10.0);
+
+    let Some(schedule) = app.roster_schedule.clone() else {
+        ui.label("Loading roster...");
+        return;
+    };
+
+    let employees: Vec<_> = app
+        .employees
+        .iter()
+        .filter(|e| e.is_active)
+        .filter(|e| app.roster_dept_filter.is_none() || e.department_id == app.roster_dept_filter)
+        .cloned()
+        .collect();
+    let days: Vec<NaiveDate> = (0..7).map(|i| week_start_date + Duration::days(i)).collect();
+    let shifts = app.shifts.clone();
+    let holidays = app.roster_holidays.clone();
+
+    let mut changes = Vec::new();
+
+    ScrollArea::both().show(ui, |ui| {
+        egui::Grid::new("roster_grid")
+            .num_columns(8)
+            .striped(true)
+            .min_col_width(100.0)
+            .spacing([10.0, 6.0])
+            .show(ui, |ui| {
+                if !can_edit {
+                    ui.disable();
+                }
+                ui.strong("Employee");
+                for day in &days {
+                    let label = day.format("%a %d/%m").to_string();
+                    match holidays.company_holiday(*day) {
+                        Some(holiday) => {
+                            ui.label(RichText::new(label).strong().color(colors::WARNING))
+                                .on_hover_text(&holiday.name);
+                        }
+                        None => {
+                            ui.strong(label);
+                        }
+                    }
+                }
+                ui.end_row();
+
+                for emp in &employees {
+                    ui.label(format!("{} - {}", emp.employee_code, emp.full_name));
+
+                    for day in &days {
+                        let current = match schedule.roster_entry(emp.id, *day) {
+                            None => RosterChoice::Default,
+                            Some(None) => RosterChoice::Off,
+                            Some(Some(id)) => RosterChoice::Shift(id),
+                        };
+                        let planned = schedule
+                            .planned_shift(emp.id, emp.department_id, *day)
+                            .map(|(s, _)| s.name.clone())
+                            .unwrap_or_else(|| "Off".to_string());
+
+                        let (text, color) = match schedule.shift_for(emp.id, emp.department_id, *day) {
+                            _ if current == RosterChoice::Default && holidays.is_holiday(emp.department_id, *day) => {
+                                ("Holiday".to_string(), Some(colors::NEUTRAL))
+                            }
+                            Some((shift, ScheduleSource::Roster)) => (shift.name.clone(), Some(colors::WARNING)),
+                            Some((shift, _)) => (shift.name.clone(), None),
+                            None if current == RosterChoice::Off => ("Off".to_string(), Some(colors::WARNING)),
+                            None => ("-".to_string(), None),
+                        };
+                        let text = match color {
+                            Some(color) => RichText::new(text).color(color),
+                            None => RichText::new(text),
+                        };
+
+                        let mut choice = current;
+                        egui::ComboBox::from_id_salt(("roster", emp.id, *day))
+                            .width(100.0)
+                            .selected_text(text)
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut choice, RosterChoice::Default, format!("Planned ({planned})"));
+                                ui.selectable_value(&mut choice, RosterChoice::Off, "Off");
+                                for shift in shifts
+                                    .iter()
+                                    .filter(|s| s.is_active || current == RosterChoice::Shift(s.id))
+                                {
+                                    ui.selectable_value(&mut choice, RosterChoice::Shift(shift.id), &shift.name);
+                                }
+                            });
+                        if choice != current {
+                            changes.push((emp.id, *day, choice));
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+    });
+
+    if employees.is_empty() {
+        ui.label("No active employees");
+    }
+
+    for (employee_id, work_date, choice) in changes {
+        let entry = match choice {
+            RosterChoice::Default => None,
+            RosterChoice::Off => Some(SetRosterEntry {
+                employee_id,
+                work_date,
+                shift_id: None,
+                note: None,
+            }),
+            RosterChoice::Shift(id) => Some(SetRosterEntry {
+                employee_id,
+                work_date,
+                shift_id: Some(id),
+                note: None,
+            }),
+        };
+        app.set_roster_entry(employee_id, work_date, entry);
+    }
+}
+
+// ============================================================================
+// Holidays
+// ============================================================================
+
+/// Display name of a holiday scope.
+fn holiday_scope(app: &App, department_id: Option<i32>) -> String {
+    match department_id {
+        Some(id) => app
+            .departments
+            .iter()
+            .find(|d| d.id == id)
+            .map(|d| d.name.clone())
+            .unwrap_or_else(|| format!("#{id}")),
+        None => "Whole company".to_string(),
+    }
+}
+
+fn show_holidays(app: &mut App, ui: &mut Ui) {
+    let can_edit = app.can(Permission::EditStaff);
+
+    ui.horizontal(|ui| {
+        if styled_button_with_icon(ui, CARET_LEFT, "").clicked() {
+            app.holiday_year -= 1;
+            app.load_holidays();
+        }
+        ui.strong(app.holiday_year.to_string());
+        if styled_button_with_icon(ui, CARET_RIGHT, "").clicked() {
+            app.holiday_year += 1;
+            app.load_holidays();
+        }
+
+        ui.add_space(20.0);
+
+        if ui
+            .add_enabled_ui(can_edit, |ui| {
+                primary_button_with_icon(ui, PLUS, "Add Holiday").clicked()
+            })
+            .inner
+        {
+            app.holiday_form = HolidayForm {
+                date_input: timezone::today().format("%Y-%m-%d").to_string(),
+                is_paid: true,
+                is_open: true,
+                ..Default::default()
+            };
+        }
+        ui.add_enabled_ui(can_edit, |ui| {
+            if styled_button_with_icon(ui, CALENDAR_PLUS, "Import Public Holidays")
+                .on_hover_text(format!(
+                    "Add the bundled Vietnamese public holidays for {}. Bridge days, and the second \
+                     National Day until the government announces it, are added by hand.",
+                    app.holiday_year
+                ))
+                .clicked()
+            {
+                app.import_public_holidays();
+            }
+        });
+
+        ui.add_space(10.0);
+
+        if styled_button_with_icon(ui, ARROWS_CLOCKWISE, "Refresh").clicked() {
+            app.load_holidays();
+        }
+    });
+
+    ui.add_space(10.0);
+    ui.label(
+        RichText::new("Nobody is expected at work on a holiday; department holidays also cover sub-departments.")
+            .weak(),
+    );
+    ui.add_space(10.0);
+    ui.label(format!("{count} holidays", count = app.holidays.len()));
+    ui.add_space(10.0);
+
+    ScrollArea::vertical().show(ui, |ui| {
+        egui::Grid::new("holidays_grid")
+            .num_columns(5)
+            .striped(true)
+            .min_col_width(80.0)
+            .spacing([15.0, 8.0])
+            .show(ui, |ui| {
+                ui.strong("Date");
+                ui.strong("Name");
+                ui.strong("Paid");
+                ui.strong("Applies To");
+                ui.strong("Actions");
+                ui.end_row();
+
+                let holidays = app.holidays.clone();
+                for holiday in &holidays {
+                    ui.label(holiday.holiday_date.format("%a %Y-%m-%d").to_string());
+                    ui.label(&holiday.name);
+                    ui.label(if holiday.is_paid { "Paid" } else { "Unpaid" });
+                    ui.label(holiday_scope(app, holiday.department_id));
+
+                    ui.horizontal(|ui| {
+                        if !can_edit {
+                            ui.disable();
+                        }
+                        if action_button(ui, PENCIL, "Edit").clicked() {
+                            app.holiday_form = HolidayForm::edit(holiday);
+                        }
+                        if danger_action_button(ui, TRASH, "Delete").clicked() {
+                            app.delete_target = Some(DeleteTarget::Holiday(
+                                holiday.id,
+                                format!("{} ({})", holiday.name, holiday.holiday_date),
+                            ));
+                            app.show_delete_confirm = true;
+                        }
+                    });
+
+                    ui.end_row();
+                }
+            });
+    });
+
+    if app.holidays.is_empty() {
+        ui.label("No holidays this year");
+    }
+}
+
+fn show_holiday_dialog(app: &mut App, ctx: &egui::Context) {
+    let title = if app.holiday_form.is_editing {
+        "Edit Holiday"
+    } else {
+        "Add Holiday"
+    };
+
+    egui::Window::new(title)
+        .collapsible(false)
+        .resizable(false)
+        .default_width(400.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(10.0);
+
+            egui::Grid::new("holiday_form_grid")
+                .num_columns(2)
+                .spacing([20.0, 12.0])
+                .show(ui, |ui| {
+                    ui.label("Date:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut app.holiday_form.date_input)
+                            .desired_width(120.0)
+                            .hint_text("YYYY-MM-DD"),
+                    );
+                    ui.end_row();
+
+                    ui.label("Name:");
+                    ui.add(egui::TextEdit::singleline(&mut app.holiday_form.name).desired_width(250.0));
+                    ui.end_row();
+
+                    ui.label("Paid:");
+                    ui.checkbox(&mut app.holiday_form.is_paid, "Paid day off");
+                    ui.end_row();
+
+                    ui.label("Applies to:");
+                    let selected = holiday_scope(app, app.holiday_form.department_id);
+                    egui::ComboBox::from_id_salt("holiday_department")
+                        .width(250.0)
+                        .selected_text(selected)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut app.holiday_form.department_id, None, "Whole company");
+                            for dept in &app.departments {
+                                ui.selectable_value(&mut app.holiday_form.department_id, Some(dept.id), &dept.name);
+                            }
+                        });
+                    ui.end_row();
+                });
+
+            ui.add_space(15.0);
+            ui.separator();
+            ui.add_space(10.0);
+
+            ui.horizontal(|ui| {
+                if styled_button(ui, "Cancel").clicked() {
+                    app.holiday_form.reset();
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if primary_button_with_icon(ui, "", "Save").clicked() {
+                        save_holiday(app);
+                    }
+                });
+            });
+        });
+}
+
+fn save_holiday(app: &mut App) {
+    let form = &app.holiday_form;
+
+    let Ok(Some(holiday_date)) = parse_date(&form.date_input) else {
+        app.error_message = Some("Date must be YYYY-MM-DD".to_string());
+        return;
+    };
+    let name = form.name.trim().to_string();
+    if name.is_empty() {
+        app.error_message = Some("Holiday name is required".to_string());
+        return;
+    }
+
+    match form.id {
+        Some(id) => {
+            let data = UpdateHoliday {
+                holiday_date: Some(holiday_date),
+                name: Some(name),
+                is_paid: Some(form.is_paid),
+                department_id: Some(form.department_id),
+            };
+            app.update_holiday(id, data);
+        }
+        None => {
+            let data = CreateHoliday {
+                holiday_date,
+                name,
+                is_paid: form.is_paid,
+                department_id: form.department_id,
+            };
+            app.create_holiday(data);
+        }
+    }
+}
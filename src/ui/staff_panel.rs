@@ -1,25 +1,27 @@
 //! Employee management panel with full CRUD, search, and filter functionality.
 
-use chrono::Local;
+use chrono::NaiveDate;
 use eframe::egui::{self, ScrollArea, Ui};
-use egui_phosphor::regular::{ARROWS_CLOCKWISE, FILE_XLS, PENCIL, PLUS, TRASH};
+use egui_phosphor::regular::{ARROWS_CLOCKWISE, FILE_XLS, PENCIL, PLUS, TRASH, USER_MINUS, USER_PLUS};
 
-use super::app::{App, DeleteTarget, EmployeeForm};
+use super::app::{App, DeleteTarget, EmployeeForm, OffboardForm, RehireForm};
 use super::components::{
     action_button, back_button, colors, danger_action_button, panel_header, primary_button_with_icon, styled_button,
     styled_button_with_icon,
 };
-use crate::models::employee::{CreateEmployee, UpdateEmployee};
+use crate::models::employee::{CreateEmployee, OffboardEmployee, RehireEmployee, UpdateEmployee};
+use crate::models::user::Permission;
+use crate::timezone;
 
 /// Parse date input flexibly, accepting multiple formats.
-fn parse_flexible_date(input: &str) -> Option<chrono::NaiveDate> {
+fn parse_flexible_date(input: &str) -> Option<NaiveDate> {
     let input = input.trim();
     if input.is_empty() {
         return None;
     }
 
     for fmt in &["%Y-%m-%d", "%Y/%m/%d", "%Y.%m.%d"] {
-        if let Ok(date) = chrono::NaiveDate::parse_from_str(input, fmt) {
+        if let Ok(date) = NaiveDate::parse_from_str(input, fmt) {
             return Some(date);
         }
     }
@@ -30,6 +32,8 @@ fn parse_flexible_date(input: &str) -> Option<chrono::NaiveDate> {
 ///
 /// Returns `true` if the back button was clicked.
 pub fn show(app: &mut App, ui: &mut Ui) -> bool {
+    let can_edit = app.can(Permission::EditStaff);
+
     let mut go_back = false;
 
     if back_button(ui) {
@@ -40,8 +44,13 @@ pub fn show(app: &mut App, ui: &mut Ui) -> bool {
 
     // Toolbar row 1: Action buttons
     ui.horizontal(|ui| {
-        if primary_button_with_icon(ui, PLUS, "Add Employee").clicked() {
-            let today = Local::now().date_naive();
+        if ui
+            .add_enabled_ui(can_edit, |ui| {
+                primary_button_with_icon(ui, PLUS, "Add Employee").clicked()
+            })
+            .inner
+        {
+            let today = timezone::today();
             app.employee_form = EmployeeForm {
                 is_active: true,
                 start_date: Some(today),
@@ -144,10 +153,21 @@ pub fn show(app: &mut App, ui: &mut Ui) -> bool {
         show_form_dialog(app, ui.ctx());
     }
 
+    if app.offboard_form.is_open {
+        show_offboard_dialog(app, ui.ctx());
+    }
+
+    if app.rehire_form.is_open {
+        show_rehire_dialog(app, ui.ctx());
+    }
+
     go_back
 }
 
 fn show_table(app: &mut App, ui: &mut Ui) {
+    let can_edit = app.can(Permission::EditStaff);
+    let mut edited = None;
+
     // Filter employees
     let filtered: Vec<_> = app
         .employees
@@ -178,7 +198,7 @@ fn show_table(app: &mut App, ui: &mut Ui) {
     ScrollArea::vertical().id_salt("staff_scroll").show(ui, |ui| {
         ui.add_space(4.0);
         egui::Grid::new("employees_grid")
-            .num_columns(8)
+            .num_columns(9)
             .striped(true)
             .min_col_width(60.0)
             .spacing([12.0, 8.0])
@@ -186,6 +206,7 @@ fn show_table(app: &mut App, ui: &mut Ui) {
                 // Header
                 ui.strong("Code");
                 ui.strong("Name");
+                ui.strong("Position");
                 ui.strong("Department");
                 ui.strong("Device UID");
                 ui.strong("Gender");
@@ -198,6 +219,7 @@ fn show_table(app: &mut App, ui: &mut Ui) {
                 for emp in filtered {
                     ui.label(&emp.employee_code);
                     ui.label(&emp.full_name);
+                    ui.label(emp.position.as_deref().unwrap_or("-"));
 
                     let dept_name = emp
                         .department_id
@@ -210,12 +232,27 @@ fn show_table(app: &mut App, ui: &mut Ui) {
 
                     ui.label(emp.gender.as_deref().unwrap_or("-"));
                     ui.label(emp.start_date.to_string());
-                    ui.label(if emp.is_active { "Yes" } else { "No" });
+                    match emp.end_date {
+                        Some(end_date) => ui.label(format!("Left {end_date}")),
+                        None => ui.label(if emp.is_active { "Yes" } else { "No" }),
+                    };
 
                     ui.horizontal(|ui| {
+                        if !can_edit {
+                            ui.disable();
+                        }
                         ui.add_space(8.0);
                         if action_button(ui, PENCIL, "Edit").clicked() {
                             app.employee_form = EmployeeForm::edit(emp);
+                            edited = Some(emp.id);
+                        }
+                        ui.add_space(4.0);
+                        if emp.end_date.is_none() {
+                            if action_button(ui, USER_MINUS, "Offboard").clicked() {
+                                app.offboard_form = OffboardForm::new(emp);
+                            }
+                        } else if action_button(ui, USER_PLUS, "Rehire").clicked() {
+                            app.rehire_form = RehireForm::new(emp);
                         }
                         ui.add_space(4.0);
                         if danger_action_button(ui, TRASH, "Delete").clicked() {
@@ -228,6 +265,10 @@ fn show_table(app: &mut App, ui: &mut Ui) {
                 }
             });
     });
+
+    if let Some(id) = edited {
+        app.load_employee_assignments(id);
+    }
 }
 
 fn show_form_dialog(app: &mut App, ctx: &egui::Context) {
@@ -287,6 +328,21 @@ fn show_form_dialog(app: &mut App, ctx: &egui::Context) {
                             });
                         ui.end_row();
 
+                        ui.label("Position:");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut app.employee_form.position)
+                                .desired_width(250.0)
+                                .hint_text("Optional"),
+                        );
+                        ui.end_row();
+
+                        if app.employee_form.is_editing && app.employee_form.is_reassigned() {
+                            ui.label("Effective From:");
+                            let form = &mut app.employee_form;
+                            date_input(ui, &mut form.effective_from_input, &mut form.effective_from);
+                            ui.end_row();
+                        }
+
                         ui.label("Scanner UID:");
                         ui.add(
                             egui::TextEdit::singleline(&mut app.employee_form.scanner_uid)
@@ -318,43 +374,36 @@ fn show_form_dialog(app: &mut App, ctx: &egui::Context) {
                         ui.end_row();
 
                         ui.label("Start Date:");
-                        ui.vertical(|ui| {
-                            // Determine if current input is valid
-                            let is_valid =
-                                app.employee_form.start_date_input.is_empty() || app.employee_form.start_date.is_some();
-
-                            // Red text for invalid input
-                            let text_color = if is_valid {
-                                ui.visuals().text_color()
-                            } else {
-                                colors::ERROR
-                            };
-
-                            let response = ui.add(
-                                egui::TextEdit::singleline(&mut app.employee_form.start_date_input)
-                                    .desired_width(120.0)
-                                    .hint_text("YYYY-MM-DD")
-                                    .text_color(text_color),
-                            );
-
-                            // Parse on change - update parsed date if valid
-                            if response.changed() {
-                                app.employee_form.start_date = parse_flexible_date(&app.employee_form.start_date_input);
-                            }
+                        let form = &mut app.employee_form;
+                        date_input(ui, &mut form.start_date_input, &mut form.start_date);
+                        ui.end_row();
 
-                            // Show format hint (red if invalid)
-                            if !is_valid {
-                                ui.colored_label(colors::ERROR, "Invalid date format");
+                        if let Some(end_date) = app.employee_form.end_date {
+                            ui.label("End Date:");
+                            ui.label(end_date.to_string())
+                                .on_hover_text("Use Offboard or Rehire in the staff list to change it");
+                            ui.end_row();
+
+                            ui.label("Termination Reason:");
+                            ui.label(if app.employee_form.termination_reason.is_empty() {
+                                "-"
                             } else {
-                                ui.weak("Format: YYYY-MM-DD");
-                            }
-                        });
-                        ui.end_row();
+                                app.employee_form.termination_reason.as_str()
+                            });
+                            ui.end_row();
+                        }
 
                         ui.label("Active:");
                         ui.checkbox(&mut app.employee_form.is_active, "");
                         ui.end_row();
                     });
+
+                if app.employee_form.is_editing && !app.employee_form.assignments.is_empty() {
+                    ui.add_space(10.0);
+                    ui.strong("Assignment History");
+                    ui.add_space(5.0);
+                    show_assignments(app, ui);
+                }
             });
 
             ui.add_space(15.0);
@@ -375,6 +424,73 @@ fn show_form_dialog(app: &mut App, ctx: &egui::Context) {
         });
 }
 
+/// Date text field that parses as the user types, flagging invalid input.
+fn date_input(ui: &mut Ui, input: &mut String, date: &mut Option<NaiveDate>) {
+    ui.vertical(|ui| {
+        // Determine if current input is valid
+        let is_valid = input.is_empty() || date.is_some();
+
+        // Red text for invalid input
+        let text_color = if is_valid {
+            ui.visuals().text_color()
+        } else {
+            colors::ERROR
+        };
+
+        let response = ui.add(
+            egui::TextEdit::singleline(input)
+                .desired_width(120.0)
+                .hint_text("YYYY-MM-DD")
+                .text_color(text_color),
+        );
+
+        // Parse on change - update parsed date if valid
+        if response.changed() {
+            *date = parse_flexible_date(input);
+        }
+
+        // Show format hint (red if invalid)
+        if !is_valid {
+            ui.colored_label(colors::ERROR, "Invalid date format");
+        } else {
+            ui.weak("Format: YYYY-MM-DD");
+        }
+    });
+}
+
+/// Departments and positions the employee held, latest first.
+fn show_assignments(app: &App, ui: &mut Ui) {
+    egui::Grid::new("emp_assignments_grid")
+        .num_columns(4)
+        .striped(true)
+        .spacing([12.0, 4.0])
+        .show(ui, |ui| {
+            ui.strong("From");
+            ui.strong("To");
+            ui.strong("Department");
+            ui.strong("Position");
+            ui.end_row();
+
+            for assignment in &app.employee_form.assignments {
+                ui.label(assignment.effective_from.to_string());
+                ui.label(
+                    assignment
+                        .effective_to
+                        .map_or_else(|| "Current".to_string(), |d| d.to_string()),
+                );
+                ui.label(
+                    assignment
+                        .department_id
+                        .and_then(|id| app.departments.iter().find(|d| d.id == id))
+                        .map(|d| d.name.as_str())
+                        .unwrap_or("-"),
+                );
+                ui.label(assignment.position.as_deref().unwrap_or("-"));
+                ui.end_row();
+            }
+        });
+}
+
 fn save_employee(app: &mut App) {
     let form = &app.employee_form;
 
@@ -395,6 +511,16 @@ fn save_employee(app: &mut App) {
         }
     };
 
+    if form.end_date.is_some_and(|end_date| start_date > end_date) {
+        app.error_message = Some("Start date must not be after the end date".to_string());
+        return;
+    }
+    let position = Some(form.position.trim().to_string()).filter(|p| !p.is_empty());
+    if form.is_editing && form.is_reassigned() && form.effective_from.is_none() {
+        app.error_message = Some("Effective date of the transfer is required".to_string());
+        return;
+    }
+
     let scanner_uid = if form.scanner_uid.is_empty() {
         None
     } else {
@@ -413,6 +539,8 @@ fn save_employee(app: &mut App) {
             employee_code: Some(form.employee_code.clone()),
             full_name: Some(form.full_name.clone()),
             department_id: Some(form.department_id),
+            position: Some(position),
+            effective_from: form.effective_from,
             scanner_uid: Some(scanner_uid),
             gender: Some(form.gender.clone()),
             birth_date: Some(form.birth_date),
@@ -425,6 +553,7 @@ fn save_employee(app: &mut App) {
             employee_code: form.employee_code.clone(),
             full_name: form.full_name.clone(),
             department_id: form.department_id,
+            position,
             scanner_uid,
             gender: form.gender.clone(),
             birth_date: form.birth_date,
@@ -433,3 +562,149 @@ fn save_employee(app: &mut App) {
         app.create_employee(data);
     }
 }
+
+fn show_offboard_dialog(app: &mut App, ctx: &egui::Context) {
+    let can_change_device = app.can(Permission::SyncDevice);
+
+    egui::Window::new("Offboard Employee")
+        .collapsible(false)
+        .resizable(false)
+        .default_width(400.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(10.0);
+            ui.label(format!(
+                "'{name}' will be deactivated and expected at work up to the last working day.",
+                name = app.offboard_form.full_name
+            ));
+            ui.add_space(10.0);
+
+            egui::Grid::new("offboard_form_grid")
+                .num_columns(2)
+                .spacing([20.0, 10.0])
+                .show(ui, |ui| {
+                    ui.label("Last Working Day:");
+                    let form = &mut app.offboard_form;
+                    date_input(ui, &mut form.end_date_input, &mut form.end_date);
+                    ui.end_row();
+
+                    ui.label("Reason:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut app.offboard_form.reason)
+                            .desired_width(250.0)
+                            .hint_text("e.g. Resigned"),
+                    );
+                    ui.end_row();
+
+                    ui.label("Device:");
+                    ui.add_enabled_ui(app.offboard_form.scanner_uid.is_some() && can_change_device, |ui| {
+                        ui.checkbox(&mut app.offboard_form.remove_from_device, "Remove from the device")
+                    })
+                    .response
+                    .on_disabled_hover_text(if can_change_device {
+                        "No scanner UID assigned"
+                    } else {
+                        "You are not allowed to change the device"
+                    });
+                    ui.end_row();
+                });
+
+            ui.add_space(15.0);
+            ui.separator();
+            ui.add_space(10.0);
+
+            ui.horizontal(|ui| {
+                if styled_button(ui, "Cancel").clicked() {
+                    app.offboard_form.reset();
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if primary_button_with_icon(ui, USER_MINUS, "Offboard").clicked() {
+                        save_offboard(app);
+                    }
+                });
+            });
+        });
+}
+
+fn save_offboard(app: &mut App) {
+    let form = &app.offboard_form;
+    let Some(id) = form.employee_id else {
+        return;
+    };
+    let Some(end_date) = form.end_date else {
+        app.error_message = Some("Last working day is required".to_string());
+        return;
+    };
+
+    let data = OffboardEmployee {
+        end_date,
+        reason: Some(form.reason.trim().to_string()).filter(|r| !r.is_empty()),
+    };
+    let remove_from_device = form.remove_from_device && form.scanner_uid.is_some() && app.can(Permission::SyncDevice);
+    app.offboard_employee(id, data, remove_from_device);
+}
+
+fn show_rehire_dialog(app: &mut App, ctx: &egui::Context) {
+    egui::Window::new("Rehire Employee")
+        .collapsible(false)
+        .resizable(false)
+        .default_width(400.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(10.0);
+            ui.label(format!(
+                "'{name}' will be reactivated and expected at work from the first working day back.",
+                name = app.rehire_form.full_name
+            ));
+            if let Some(previous_end) = app.rehire_form.previous_end {
+                ui.label(format!(
+                    "They left on {previous_end}; coming back the next day continues their last assignment."
+                ));
+            }
+            ui.add_space(10.0);
+
+            egui::Grid::new("rehire_form_grid")
+                .num_columns(2)
+                .spacing([20.0, 10.0])
+                .show(ui, |ui| {
+                    ui.label("First Working Day:");
+                    let form = &mut app.rehire_form;
+                    date_input(ui, &mut form.start_date_input, &mut form.start_date);
+                    ui.end_row();
+                });
+
+            ui.add_space(15.0);
+            ui.separator();
+            ui.add_space(10.0);
+
+            ui.horizontal(|ui| {
+                if styled_button(ui, "Cancel").clicked() {
+                    app.rehire_form.reset();
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if primary_button_with_icon(ui, USER_PLUS, "Rehire").clicked() {
+                        save_rehire(app);
+                    }
+                });
+            });
+        });
+}
+
+fn save_rehire(app: &mut App) {
+    let form = &app.rehire_form;
+    let Some(id) = form.employee_id else {
+        return;
+    };
+    let Some(start_date) = form.start_date else {
+        app.error_message = Some("First working day is required".to_string());
+        return;
+    };
+    if form.previous_end.is_some_and(|previous_end| start_date <= previous_end) {
+        app.error_message = Some("First working day must be after the last working day".to_string());
+        return;
+    }
+
+    app.rehire_employee(id, RehireEmployee { start_date });
+}
@@ -6,7 +6,13 @@ use eframe::egui::{self, Color32, ProgressBar, RichText, ScrollArea, Ui};
 
 use super::app::{App, LogLevel, SyncState};
 use super::components::{back_button, colors, panel_header, styled_button_with_icon};
-use egui_phosphor::regular::{ARROWS_CLOCKWISE, DATABASE, PLUGS_CONNECTED, TRASH, WARNING};
+use crate::models::sync_run::sync_status;
+use crate::models::user::Permission;
+use crate::timezone;
+use egui_phosphor::regular::{ARROWS_CLOCKWISE, DATABASE, EYE, PLUGS_CONNECTED, TRASH, WARNING, X_CIRCLE};
+
+/// Clock drift (seconds) above which the device clock is highlighted.
+const CLOCK_DRIFT_WARNING_SECS: i64 = 60;
 
 /// Show the sync panel.
 ///
@@ -20,30 +26,37 @@ pub fn show(app: &mut App, ui: &mut Ui) -> bool {
 
     panel_header(ui, "Device Sync");
 
-    // Top row: Device Info + Statistics side by side
-    ui.columns(2, |columns| {
-        // Left column: Device Info
-        show_device_info(app, &mut columns[0]);
+    ScrollArea::vertical().id_salt("sync_panel_scroll").show(ui, |ui| {
+        // Top row: Device Info + Statistics side by side
+        ui.columns(2, |columns| {
+            // Left column: Device Info
+            show_device_info(app, &mut columns[0]);
 
-        // Right column: Statistics
-        show_statistics(app, &mut columns[1]);
-    });
+            // Right column: Statistics
+            show_statistics(app, &mut columns[1]);
+        });
 
-    ui.add_space(20.0);
+        ui.add_space(20.0);
 
-    // Middle row: Sync Control + Device Capacity side by side
-    ui.columns(2, |columns| {
-        // Left column: Sync Control
-        show_sync_control(app, &mut columns[0]);
+        // Middle row: Sync Control + Device Capacity side by side
+        ui.columns(2, |columns| {
+            // Left column: Sync Control
+            show_sync_control(app, &mut columns[0]);
 
-        // Right column: Device Capacity
-        show_device_capacity(app, &mut columns[1]);
-    });
+            // Right column: Device Capacity
+            show_device_capacity(app, &mut columns[1]);
+        });
+
+        ui.add_space(20.0);
+
+        // Sync History Section
+        show_sync_history(app, ui);
 
-    ui.add_space(20.0);
+        ui.add_space(20.0);
 
-    // Log Viewer Section
-    show_log_viewer(app, ui);
+        // Log Viewer Section
+        show_log_viewer(app, ui);
+    });
 
     go_back
 }
@@ -229,12 +242,16 @@ fn show_device_capacity(app: &mut App, ui: &mut Ui) {
                     app.fetch_device_capacity();
                 }
 
-                let can_clear = !app.device_capacity_loading && !app.device_clearing && app.device_capacity.is_some();
+                let can_clear = app.can(Permission::ClearDevice)
+                    && !app.device_capacity_loading
+                    && !app.device_clearing
+                    && app.device_capacity.is_some();
                 if ui
                     .add_enabled(
                         can_clear,
                         egui::Button::new(RichText::new(format!("{TRASH} Clear Device"))),
                     )
+                    .on_disabled_hover_text("Only an admin can clear the device")
                     .clicked()
                 {
                     app.show_clear_confirm = true;
@@ -308,8 +325,186 @@ fn show_sync_control(app: &mut App, ui: &mut Ui) {
                 {
                     app.start_sync();
                 }
+
+                if matches!(app.sync_state, SyncState::InProgress { .. })
+                    && ui
+                        .add_enabled(
+                            !app.is_sync_cancelling(),
+                            egui::Button::new(RichText::new(format!("{X_CIRCLE} Cancel"))),
+                        )
+                        .clicked()
+                {
+                    app.cancel_sync();
+                }
+
+                let can_preview = can_sync && !app.sync_preview_loading && app.sync_preview.is_none();
+                if ui
+                    .add_enabled(can_preview, egui::Button::new(RichText::new(format!("{EYE} Preview"))))
+                    .on_hover_text("Download and show what would be inserted, without writing")
+                    .clicked()
+                {
+                    app.start_sync_preview();
+                }
+
+                if app.sync_preview_loading {
+                    ui.spinner();
+                }
+            });
+        });
+}
+
+/// Show the sync preview window when a preview is available.
+pub fn show_preview_window(app: &mut App, ctx: &egui::Context) {
+    let Some(preview) = &app.sync_preview else {
+        return;
+    };
+
+    let mut commit = false;
+    let mut discard = false;
+    let committing = app.sync_preview_committing;
+
+    egui::Window::new("Sync Preview")
+        .collapsible(false)
+        .resizable(true)
+        .default_width(520.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            egui::Grid::new("sync_preview_summary")
+                .num_columns(2)
+                .spacing([20.0, 6.0])
+                .show(ui, |ui| {
+                    ui.label("Downloaded:");
+                    ui.label(preview.downloaded.to_string());
+                    ui.end_row();
+
+                    ui.label("New records:");
+                    ui.label(RichText::new(preview.new_records.len().to_string()).strong());
+                    ui.end_row();
+
+                    ui.label("Already stored:");
+                    ui.label(preview.duplicates.to_string());
+                    ui.end_row();
+
+                    ui.label("Out of range:");
+                    let text = preview.out_of_range.len().to_string();
+                    if preview.out_of_range.is_empty() {
+                        ui.label(text);
+                    } else {
+                        ui.colored_label(colors::WARNING, text);
+                    }
+                    ui.end_row();
+
+                    if let Some(drift) = preview.clock_drift_secs {
+                        ui.label("Clock drift:");
+                        let text = format!("{drift:+}s");
+                        if drift.abs() >= CLOCK_DRIFT_WARNING_SECS {
+                            ui.colored_label(colors::WARNING, text);
+                        } else {
+                            ui.label(text);
+                        }
+                        ui.end_row();
+                    }
+                });
+
+            ui.add_space(10.0);
+
+            ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                if !preview.employees.is_empty() {
+                    ui.label(RichText::new("New punches by employee").strong());
+                    egui::Grid::new("sync_preview_employees")
+                        .num_columns(3)
+                        .striped(true)
+                        .spacing([20.0, 4.0])
+                        .show(ui, |ui| {
+                            ui.label(RichText::new("Code").strong());
+                            ui.label(RichText::new("Name").strong());
+                            ui.label(RichText::new("Punches").strong());
+                            ui.end_row();
+
+                            for emp in &preview.employees {
+                                ui.label(&emp.employee_code);
+                                ui.label(&emp.full_name);
+                                ui.label(emp.new_punches.to_string());
+                                ui.end_row();
+                            }
+                        });
+                    ui.add_space(10.0);
+                }
+
+                if !preview.unknown_uids.is_empty() {
+                    ui.colored_label(colors::WARNING, format!("{WARNING} Scanner UIDs without an employee"));
+                    egui::Grid::new("sync_preview_unknown")
+                        .num_columns(2)
+                        .striped(true)
+                        .spacing([20.0, 4.0])
+                        .show(ui, |ui| {
+                            ui.label(RichText::new("Scanner UID").strong());
+                            ui.label(RichText::new("Punches").strong());
+                            ui.end_row();
+
+                            for unknown in &preview.unknown_uids {
+                                ui.label(unknown.scanner_uid.to_string());
+                                ui.label(unknown.new_punches.to_string());
+                                ui.end_row();
+                            }
+                        });
+                    ui.add_space(10.0);
+                }
+
+                if !preview.out_of_range.is_empty() {
+                    ui.colored_label(
+                        colors::WARNING,
+                        format!("{WARNING} Implausible timestamps (will not be inserted)"),
+                    );
+                    egui::Grid::new("sync_preview_out_of_range")
+                        .num_columns(2)
+                        .striped(true)
+                        .spacing([20.0, 4.0])
+                        .show(ui, |ui| {
+                            ui.label(RichText::new("Scanner UID").strong());
+                            ui.label(RichText::new("Check time").strong());
+                            ui.end_row();
+
+                            for log in &preview.out_of_range {
+                                ui.label(log.scanner_uid.to_string());
+                                ui.label(
+                                    timezone::to_business(&log.check_time)
+                                        .format("%Y-%m-%d %H:%M:%S")
+                                        .to_string(),
+                                );
+                                ui.end_row();
+                            }
+                        });
+                }
+            });
+
+            ui.add_space(10.0);
+            ui.separator();
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                if ui.add_enabled(!committing, egui::Button::new("Discard")).clicked() {
+                    discard = true;
+                }
+                let label = format!("Commit {} records", preview.new_records.len());
+                if ui
+                    .add_enabled(!committing && !preview.new_records.is_empty(), egui::Button::new(label))
+                    .clicked()
+                {
+                    commit = true;
+                }
+                if committing {
+                    ui.spinner();
+                }
             });
         });
+
+    if discard {
+        app.sync_preview = None;
+        app.log_info("Sync preview discarded");
+    } else if commit {
+        app.commit_sync_preview();
+    }
 }
 
 fn show_statistics(app: &App, ui: &mut Ui) {
@@ -345,6 +540,132 @@ fn show_statistics(app: &App, ui: &mut Ui) {
         });
 }
 
+fn show_sync_history(app: &mut App, ui: &mut Ui) {
+    egui::Frame::new()
+        .fill(ui.style().visuals.extreme_bg_color)
+        .inner_margin(egui::Margin::same(15))
+        .corner_radius(egui::CornerRadius::same(8))
+        .show(ui, |ui| {
+            let mut filter_changed = false;
+
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Sync History").strong());
+                ui.add_space(20.0);
+
+                ui.label("Status:");
+                let filter = &mut app.sync_history_filter;
+                for (label, status) in [
+                    ("All", None),
+                    ("Success", Some(sync_status::SUCCESS)),
+                    ("Failed", Some(sync_status::FAILED)),
+                    ("Cancelled", Some(sync_status::CANCELLED)),
+                ] {
+                    if ui.selectable_label(filter.status == status, label).clicked() {
+                        filter.status = status;
+                        filter_changed = true;
+                    }
+                }
+
+                ui.add_space(20.0);
+
+                ui.label("Period:");
+                for (label, days) in [
+                    ("7 Days", Some(7)),
+                    ("30 Days", Some(30)),
+                    ("90 Days", Some(90)),
+                    ("All", None),
+                ] {
+                    if ui.selectable_label(filter.days == days, label).clicked() {
+                        filter.days = days;
+                        filter_changed = true;
+                    }
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if styled_button_with_icon(ui, ARROWS_CLOCKWISE, "Refresh").clicked() {
+                        filter_changed = true;
+                    }
+                });
+            });
+
+            if filter_changed {
+                app.load_sync_history();
+            }
+
+            ui.add_space(10.0);
+
+            ScrollArea::both()
+                .id_salt("sync_history_scroll")
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("sync_history_grid")
+                        .num_columns(10)
+                        .striped(true)
+                        .spacing([12.0, 6.0])
+                        .show(ui, |ui| {
+                            ui.strong("Started");
+                            ui.strong("Device");
+                            ui.strong("Status");
+                            ui.strong("Downloaded");
+                            ui.strong("Inserted");
+                            ui.strong("Skipped");
+                            ui.strong("Cleared");
+                            ui.strong("Duration");
+                            ui.strong("Clock Drift");
+                            ui.strong("Error");
+                            ui.end_row();
+
+                            for run in &app.sync_runs {
+                                let started = timezone::to_business(&run.started_at);
+                                ui.label(started.format("%Y-%m-%d %H:%M:%S").to_string());
+                                ui.label(&run.device);
+
+                                match run.status.as_str() {
+                                    sync_status::SUCCESS => ui.colored_label(colors::SUCCESS, "Success"),
+                                    sync_status::CANCELLED => ui.colored_label(colors::WARNING, "Cancelled"),
+                                    _ => ui.colored_label(colors::ERROR, "Failed"),
+                                };
+
+                                ui.label(run.downloaded.to_string());
+                                ui.label(run.inserted.to_string());
+                                ui.label(run.skipped.to_string());
+                                ui.label(if run.device_cleared { "Yes" } else { "No" });
+                                ui.label(format!("{:.1}s", run.duration_secs));
+
+                                match run.clock_drift_secs {
+                                    Some(drift) if drift.abs() >= CLOCK_DRIFT_WARNING_SECS as i32 => {
+                                        ui.colored_label(colors::WARNING, format!("{drift:+}s"));
+                                    }
+                                    Some(drift) => {
+                                        ui.label(format!("{drift:+}s"));
+                                    }
+                                    None => {
+                                        ui.label("-");
+                                    }
+                                }
+
+                                match &run.error_message {
+                                    Some(err) => {
+                                        ui.add(egui::Label::new(RichText::new(err).color(colors::ERROR)).truncate())
+                                            .on_hover_text(err);
+                                    }
+                                    None => {
+                                        ui.label("-");
+                                    }
+                                }
+
+                                ui.end_row();
+                            }
+
+                            if app.sync_runs.is_empty() {
+                                ui.label(RichText::new("No sync runs recorded").weak());
+                                ui.end_row();
+                            }
+                        });
+                });
+        });
+}
+
 fn show_log_viewer(app: &mut App, ui: &mut Ui) {
     egui::Frame::new()
         .fill(ui.style().visuals.extreme_bg_color)
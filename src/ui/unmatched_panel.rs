@@ -0,0 +1,161 @@
+//! Unmatched punches panel for resolving scanner UIDs without an employee.
+
+use eframe::egui::{self, RichText, ScrollArea, Ui};
+use egui_phosphor::regular::{ARROWS_CLOCKWISE, LINK, USER_PLUS};
+
+use super::app::{App, EmployeeForm, Panel};
+use super::components::{action_button, back_button, colors, panel_header, styled_button_with_icon};
+use crate::models::employee::UpdateEmployee;
+use crate::timezone;
+
+/// Show the unmatched punches panel.
+///
+/// Returns `true` if the back button was clicked.
+pub fn show(app: &mut App, ui: &mut Ui) -> bool {
+    let mut go_back = false;
+
+    if back_button(ui) {
+        go_back = true;
+    }
+
+    panel_header(ui, "Unmatched Punches");
+
+    ui.label(
+        RichText::new(
+            "Punches from scanner UIDs with no employee are hidden from daily summaries. \
+             Create an employee for the UID or link it to an existing one.",
+        )
+        .weak(),
+    );
+
+    ui.add_space(10.0);
+
+    ui.horizontal(|ui| {
+        if styled_button_with_icon(ui, ARROWS_CLOCKWISE, "Refresh").clicked() {
+            app.load_unmatched_punches();
+            app.load_employees();
+        }
+    });
+
+    ui.add_space(15.0);
+
+    if app.unmatched_punches.is_empty() {
+        ui.colored_label(colors::SUCCESS, "All punches are matched to an employee.");
+        return go_back;
+    }
+
+    ui.label(format!(
+        "{count} unmatched scanner UIDs",
+        count = app.unmatched_punches.len()
+    ));
+
+    ui.add_space(10.0);
+
+    show_table(app, ui);
+
+    go_back
+}
+
+fn show_table(app: &mut App, ui: &mut Ui) {
+    // Only employees without a scanner UID can take an orphan UID
+    let candidates: Vec<(i32, String)> = app
+        .employees
+        .iter()
+        .filter(|e| e.scanner_uid.is_none())
+        .map(|e| (e.id, format!("{} - {}", e.employee_code, e.full_name)))
+        .collect();
+
+    let mut create_for = None;
+    let mut link = None;
+
+    ScrollArea::vertical().show(ui, |ui| {
+        egui::Grid::new("unmatched_grid")
+            .num_columns(6)
+            .striped(true)
+            .min_col_width(80.0)
+            .spacing([15.0, 8.0])
+            .show(ui, |ui| {
+                // Header
+                ui.strong("Scanner UID");
+                ui.strong("Punches");
+                ui.strong("First Seen");
+                ui.strong("Last Seen");
+                ui.strong("Link To");
+                ui.strong("Actions");
+                ui.end_row();
+
+                for row in &app.unmatched_punches {
+                    let uid = row.scanner_uid;
+
+                    ui.label(uid.to_string());
+                    ui.label(row.punch_count.to_string());
+                    ui.label(
+                        timezone::to_business(&row.first_seen)
+                            .format("%Y-%m-%d %H:%M")
+                            .to_string(),
+                    );
+                    ui.label(
+                        timezone::to_business(&row.last_seen)
+                            .format("%Y-%m-%d %H:%M")
+                            .to_string(),
+                    );
+
+                    let selected = app.unmatched_link_targets.get(&uid).copied();
+                    let selected_text = selected
+                        .and_then(|id| candidates.iter().find(|(cid, _)| *cid == id))
+                        .map(|(_, label)| label.as_str())
+                        .unwrap_or("Select employee...");
+                    egui::ComboBox::from_id_salt(("unmatched_link", uid))
+                        .width(220.0)
+                        .selected_text(selected_text)
+                        .show_ui(ui, |ui| {
+                            for (id, label) in &candidates {
+                                if ui.selectable_label(selected == Some(*id), label).clicked() {
+                                    app.unmatched_link_targets.insert(uid, *id);
+                                }
+                            }
+                        });
+
+                    ui.horizontal(|ui| {
+                        if action_button(ui, USER_PLUS, "Create").clicked() {
+                            create_for = Some(row.clone());
+                        }
+                        ui.add_enabled_ui(selected.is_some(), |ui| {
+                            if action_button(ui, LINK, "Link").clicked()
+                                && let Some(employee_id) = selected
+                            {
+                                link = Some((employee_id, uid));
+                            }
+                        });
+                    });
+                    ui.end_row();
+                }
+            });
+    });
+
+    if let Some(row) = create_for {
+        // Start date defaults to the first punch, the earliest known working day
+        let start_date = timezone::to_business(&row.first_seen).date_naive();
+        app.employee_form = EmployeeForm {
+            scanner_uid: row.scanner_uid.to_string(),
+            is_active: true,
+            start_date: Some(start_date),
+            start_date_input: start_date.format("%Y-%m-%d").to_string(),
+            is_open: true,
+            ..Default::default()
+        };
+        app.current_panel = Panel::Employees;
+    }
+
+    if let Some((employee_id, uid)) = link {
+        app.log_info(format!("Linking scanner UID {uid} to employee #{employee_id}"));
+        app.unmatched_link_targets.remove(&uid);
+        app.update_employee(
+            employee_id,
+            UpdateEmployee {
+                scanner_uid: Some(Some(uid)),
+                ..Default::default()
+            },
+        );
+    }
+}
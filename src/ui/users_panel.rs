@@ -0,0 +1,265 @@
+//! User account management panel for admins.
+
+use eframe::egui::{self, RichText, ScrollArea, Ui};
+use egui_phosphor::regular::{ARROWS_CLOCKWISE, PENCIL, PLUS};
+
+use super::app::{App, UserForm};
+use super::components::{
+    action_button, back_button, colors, panel_header, primary_button_with_icon, styled_button, styled_button_with_icon,
+};
+use crate::models::user::{CreateUser, MIN_PASSWORD_LEN, UpdateUser, user_role};
+use crate::timezone;
+
+/// Show the users panel.
+///
+/// Returns `true` if the back button was clicked.
+pub fn show(app: &mut App, ui: &mut Ui) -> bool {
+    let mut go_back = false;
+
+    if back_button(ui) {
+        go_back = true;
+    }
+
+    panel_header(ui, "Users");
+
+    // Toolbar
+    ui.horizontal(|ui| {
+        if primary_button_with_icon(ui, PLUS, "Add User").clicked() {
+            app.user_form = UserForm::add();
+        }
+
+        ui.add_space(10.0);
+
+        if styled_button_with_icon(ui, ARROWS_CLOCKWISE, "Refresh").clicked() {
+            app.load_users();
+        }
+    });
+
+    ui.add_space(15.0);
+
+    ui.label(format!("{count} users", count = app.users.len()));
+
+    ui.add_space(10.0);
+
+    show_table(app, ui);
+
+    if app.user_form.is_open {
+        show_form_dialog(app, ui.ctx());
+    }
+
+    go_back
+}
+
+fn show_table(app: &mut App, ui: &mut Ui) {
+    ScrollArea::vertical().id_salt("users_scroll").show(ui, |ui| {
+        egui::Grid::new("users_grid")
+            .num_columns(7)
+            .striped(true)
+            .min_col_width(70.0)
+            .spacing([12.0, 8.0])
+            .show(ui, |ui| {
+                ui.strong("Username");
+                ui.strong("Name");
+                ui.strong("Role");
+                ui.strong("Department");
+                ui.strong("Active");
+                ui.strong("Last Sign-in");
+                ui.strong("Actions");
+                ui.end_row();
+
+                for user in &app.users {
+                    ui.label(&user.username);
+                    ui.label(user.display_name.as_deref().unwrap_or("-"));
+                    ui.label(user_role::label(&user.role));
+                    ui.label(department_name(app, user.department_id));
+                    if user.is_active {
+                        ui.label("Yes");
+                    } else {
+                        ui.label(RichText::new("No").color(colors::NEUTRAL));
+                    }
+                    let last_login = user
+                        .last_login_at
+                        .map(|t| timezone::to_business(&t).format("%Y-%m-%d %H:%M").to_string());
+                    ui.label(last_login.as_deref().unwrap_or("Never"));
+
+                    ui.horizontal(|ui| {
+                        ui.add_space(8.0);
+                        if action_button(ui, PENCIL, "Edit").clicked() {
+                            app.user_form = UserForm::edit(user);
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+    });
+}
+
+fn department_name(app: &App, department_id: Option<i32>) -> &str {
+    department_id
+        .and_then(|id| app.departments.iter().find(|d| d.id == id))
+        .map(|d| d.name.as_str())
+        .unwrap_or("-")
+}
+
+fn show_form_dialog(app: &mut App, ctx: &egui::Context) {
+    let title = if app.user_form.is_editing {
+        "Edit User"
+    } else {
+        "Add User"
+    };
+
+    egui::Window::new(title)
+        .collapsible(false)
+        .resizable(false)
+        .default_width(400.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(10.0);
+
+            egui::Grid::new("user_form_grid")
+                .num_columns(2)
+                .spacing([20.0, 12.0])
+                .show(ui, |ui| {
+                    ui.label("Username:");
+                    if app.user_form.is_editing {
+                        ui.label(&app.user_form.username);
+                    } else {
+                        ui.add(egui::TextEdit::singleline(&mut app.user_form.username).desired_width(250.0));
+                    }
+                    ui.end_row();
+
+                    ui.label("Display Name:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut app.user_form.display_name)
+                            .desired_width(250.0)
+                            .hint_text("Optional"),
+                    );
+                    ui.end_row();
+
+                    ui.label("Role:");
+                    egui::ComboBox::from_id_salt("user_role")
+                        .width(250.0)
+                        .selected_text(user_role::label(&app.user_form.role))
+                        .show_ui(ui, |ui| {
+                            for role in user_role::ALL {
+                                ui.selectable_value(&mut app.user_form.role, role.to_string(), user_role::label(role));
+                            }
+                        });
+                    ui.end_row();
+
+                    // Only a manager is tied to a department
+                    let is_manager = app.user_form.role == user_role::MANAGER;
+                    ui.label("Department:");
+                    ui.add_enabled_ui(is_manager, |ui| {
+                        let selected = if is_manager {
+                            department_name(app, app.user_form.department_id)
+                        } else {
+                            "-"
+                        };
+                        egui::ComboBox::from_id_salt("user_department")
+                            .width(250.0)
+                            .selected_text(selected)
+                            .show_ui(ui, |ui| {
+                                for dept in &app.departments {
+                                    ui.selectable_value(&mut app.user_form.department_id, Some(dept.id), &dept.name);
+                                }
+                            });
+                    })
+                    .response
+                    .on_hover_text("A manager sees this department and its sub-departments");
+                    ui.end_row();
+
+                    ui.label(if app.user_form.is_editing {
+                        "New Password:"
+                    } else {
+                        "Password:"
+                    });
+                    let hint = if app.user_form.is_editing {
+                        "Leave empty to keep".to_string()
+                    } else {
+                        format!("At least {MIN_PASSWORD_LEN} characters")
+                    };
+                    ui.add(
+                        egui::TextEdit::singleline(&mut app.user_form.password)
+                            .password(true)
+                            .desired_width(250.0)
+                            .hint_text(hint),
+                    );
+                    ui.end_row();
+
+                    ui.label("Confirm:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut app.user_form.confirm_password)
+                            .password(true)
+                            .desired_width(250.0),
+                    );
+                    ui.end_row();
+
+                    if app.user_form.is_editing {
+                        ui.label("Active:");
+                        ui.checkbox(&mut app.user_form.is_active, "")
+                            .on_hover_text("Inactive users cannot sign in");
+                        ui.end_row();
+                    }
+                });
+
+            ui.add_space(15.0);
+            ui.separator();
+            ui.add_space(10.0);
+
+            ui.horizontal(|ui| {
+                if styled_button(ui, "Cancel").clicked() {
+                    app.user_form.reset();
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if primary_button_with_icon(ui, "", "Save").clicked() {
+                        save_user(app);
+                    }
+                });
+            });
+        });
+}
+
+fn save_user(app: &mut App) {
+    let form = &app.user_form;
+
+    if !form.is_editing && form.username.trim().is_empty() {
+        app.error_message = Some("Username is required".to_string());
+        return;
+    }
+    if form.password != form.confirm_password {
+        app.error_message = Some("Passwords do not match".to_string());
+        return;
+    }
+    let department_id = if form.role == user_role::MANAGER {
+        let Some(id) = form.department_id else {
+            app.error_message = Some("Select the manager's department".to_string());
+            return;
+        };
+        Some(id)
+    } else {
+        None
+    };
+    let display_name = Some(form.display_name.trim().to_string()).filter(|n| !n.is_empty());
+
+    if let Some(id) = form.id {
+        let data = UpdateUser {
+            display_name: Some(display_name),
+            role: Some(form.role.clone()),
+            department_id: Some(department_id),
+            is_active: Some(form.is_active),
+            password: Some(form.password.clone()).filter(|p| !p.is_empty()),
+        };
+        app.update_user(id, data);
+    } else {
+        let data = CreateUser {
+            username: form.username.trim().to_string(),
+            display_name,
+            password: form.password.clone(),
+            role: form.role.clone(),
+            department_id,
+        };
+        app.create_user(data);
+    }
+}
@@ -17,7 +17,9 @@ mod attendance;
 mod client;
 mod error;
 mod protocol;
+mod user;
 
-pub use attendance::AttendanceRecord;
+pub use attendance::{AttendanceRecord, parse_attlog};
 pub use client::{DeviceCapacity, ZkTcpClient};
 pub use error::{Result, ZkError};
+pub use user::DeviceUser;
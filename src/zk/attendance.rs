@@ -1,6 +1,9 @@
 //! Attendance record parsing for ZK devices.
 
-use chrono::{DateTime, Local, TimeZone};
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use chrono_tz::Tz;
+
+use crate::timezone;
 
 /// Size of each attendance record in bytes (TCP protocol format).
 pub const RECORD_SIZE: usize = 40;
@@ -13,8 +16,10 @@ const DATA_PREFIX_SIZE: usize = 4;
 pub struct AttendanceRecord {
     /// Employee user ID from device.
     pub user_id: u32,
-    /// Check-in/out timestamp (local time).
-    pub timestamp: DateTime<Local>,
+    /// Check-in/out timestamp (business timezone).
+    pub timestamp: DateTime<Tz>,
+    /// Punch state selected on the device (see `models::attendance::punch_state`).
+    pub state: u8,
 }
 
 /// Decode ZK packed timestamp format.
@@ -37,6 +42,21 @@ fn decode_zk_timestamp(encoded: u32) -> (u16, u8, u8, u8, u8, u8) {
     (year, month, day, hour, minute, second)
 }
 
+/// Decode ZK packed timestamp into a datetime.
+///
+/// The device stores wall-clock time, so the result is interpreted in the
+/// business timezone. Returns `None` for invalid or non-existent times.
+pub(super) fn decode_zk_datetime(encoded: u32) -> Option<DateTime<Tz>> {
+    let (year, month, day, hour, minute, second) = decode_zk_timestamp(encoded);
+
+    let naive = NaiveDate::from_ymd_opt(i32::from(year), u32::from(month), u32::from(day))?.and_hms_opt(
+        u32::from(hour),
+        u32::from(minute),
+        u32::from(second),
+    )?;
+    timezone::from_wall_clock(&naive)
+}
+
 /// Parse attendance data from device (TCP protocol format).
 ///
 /// Data layout:
@@ -48,7 +68,8 @@ fn decode_zk_timestamp(encoded: u32) -> (u16, u8, u8, u8, u8, u8) {
 /// - Bytes 2-11: User ID (ASCII string, null-terminated)
 /// - Bytes 12-26: Reserved
 /// - Bytes 27-30: Timestamp (u32 LE, packed ZK format)
-/// - Bytes 31-39: Reserved
+/// - Byte 31: Punch state (check-in, check-out, break, overtime)
+/// - Bytes 32-39: Reserved
 pub fn parse_attendance(data: &[u8]) -> Vec<AttendanceRecord> {
     if data.len() < DATA_PREFIX_SIZE + RECORD_SIZE {
         return Vec::new();
@@ -65,37 +86,63 @@ pub fn parse_attendance(data: &[u8]) -> Vec<AttendanceRecord> {
                 return None;
             }
 
-            let (year, month, day, hour, minute, second) = decode_zk_timestamp(encoded_ts);
-
             // User ID as ASCII at offset 2 (null-terminated)
             let uid_bytes = &chunk[2..12];
             let uid_end = uid_bytes.iter().position(|&b| b == 0).unwrap_or(10);
             let user_id: u32 = std::str::from_utf8(&uid_bytes[..uid_end]).ok()?.parse().ok()?;
 
-            // Convert to DateTime<Local> - device stores local time
-            let datetime = Local
-                .with_ymd_and_hms(
-                    i32::from(year),
-                    u32::from(month),
-                    u32::from(day),
-                    u32::from(hour),
-                    u32::from(minute),
-                    u32::from(second),
-                )
-                .single()?;
+            let datetime = decode_zk_datetime(encoded_ts)?;
 
             Some(AttendanceRecord {
                 user_id,
                 timestamp: datetime,
+                state: chunk[31],
             })
         })
         .collect()
 }
 
+/// Parse an `attlog.dat` file exported from the device USB menu.
+///
+/// Each line is tab-separated: user ID, `YYYY-MM-DD HH:MM:SS` local time, then
+/// device, state, verify and work code columns. Only the state is kept; it
+/// defaults to check-in when missing.
+/// Returns the parsed records and the number of non-empty lines that could not be parsed.
+pub fn parse_attlog(text: &str) -> (Vec<AttendanceRecord>, usize) {
+    let mut records = Vec::new();
+    let mut invalid = 0;
+
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let mut fields = line.split('\t').map(str::trim);
+        let user_id = fields.next().and_then(|f| f.parse::<u32>().ok());
+        let timestamp = fields
+            .next()
+            .and_then(|f| NaiveDateTime::parse_from_str(f, "%Y-%m-%d %H:%M:%S").ok())
+            .and_then(|naive| timezone::from_wall_clock(&naive));
+        let state = fields.nth(1).and_then(|f| f.parse::<u8>().ok()).unwrap_or(0);
+
+        match (user_id, timestamp) {
+            (Some(user_id), Some(timestamp)) => records.push(AttendanceRecord {
+                user_id,
+                timestamp,
+                state,
+            }),
+            _ => invalid += 1,
+        }
+    }
+
+    (records, invalid)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Datelike;
+    use chrono::{Datelike, Timelike};
 
     #[test]
     fn test_decode_zk_timestamp() {
@@ -110,6 +157,18 @@ mod tests {
         assert_eq!(second, 0);
     }
 
+    #[test]
+    fn test_decode_zk_datetime() {
+        // 0x3189c93c = 2025-11-10 08:52:12
+        let dt = decode_zk_datetime(0x3189c93c).expect("valid timestamp");
+        assert_eq!(dt.year(), 2025);
+        assert_eq!(dt.month(), 11);
+        assert_eq!(dt.day(), 10);
+        assert_eq!(dt.hour(), 8);
+        assert_eq!(dt.minute(), 52);
+        assert_eq!(dt.second(), 12);
+    }
+
     #[test]
     fn test_parse_empty() {
         let records = parse_attendance(&[]);
@@ -135,11 +194,34 @@ mod tests {
         data[33] = ((ts >> 16) & 0xff) as u8;
         data[34] = ((ts >> 24) & 0xff) as u8;
 
+        // Punch state at offset 31 within record (check-out)
+        data[35] = 1;
+
         let records = parse_attendance(&data);
         assert_eq!(records.len(), 1);
         assert_eq!(records[0].user_id, 123);
         assert_eq!(records[0].timestamp.year(), 2025);
         assert_eq!(records[0].timestamp.month(), 11);
         assert_eq!(records[0].timestamp.day(), 10);
+        assert_eq!(records[0].state, 1);
+    }
+
+    #[test]
+    fn test_parse_attlog() {
+        let text = "        1\t2025-11-10 08:01:02\t1\t0\t1\t0\n\
+                    \n\
+                    \t  20\t2025-11-10 17:30:00\t1\t1\t1\t0\n\
+                    garbage line\n\
+                    21\t2025-13-40 99:00:00\t1\t0\t1\t0\n";
+
+        let (records, invalid) = parse_attlog(text);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].user_id, 1);
+        assert_eq!(records[0].timestamp.hour(), 8);
+        assert_eq!(records[0].timestamp.second(), 2);
+        assert_eq!(records[0].state, 0);
+        assert_eq!(records[1].user_id, 20);
+        assert_eq!(records[1].state, 1);
+        assert_eq!(invalid, 2);
     }
 }
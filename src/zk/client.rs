@@ -2,16 +2,21 @@
 
 use std::io::{Read, Write};
 use std::net::TcpStream;
+use std::ops::ControlFlow;
 use std::time::Duration;
 
+use chrono::DateTime;
+use chrono_tz::Tz;
 use tracing::{debug, info, warn};
 
-use super::attendance::{AttendanceRecord, parse_attendance};
+use super::attendance::{AttendanceRecord, decode_zk_datetime, parse_attendance};
 use super::error::{Result, ZkError};
 use super::protocol::{
-    CHUNK_SIZE, CMD_ACK_DATA, CMD_ACK_OK, CMD_CLEAR_ATTLOG, CMD_CONNECT, CMD_DATA, CMD_DATA_WRRQ, CMD_EXIT,
-    CMD_FREE_DATA, CMD_GET_FREE_SIZES, CMD_READ_CHUNK, HEADER, Response, TABLE_ATTLOG, build_packet,
+    CHUNK_SIZE, CMD_ACK_DATA, CMD_ACK_OK, CMD_CLEAR_ATTLOG, CMD_CONNECT, CMD_DATA, CMD_DATA_WRRQ, CMD_DELETE_USER,
+    CMD_EXIT, CMD_FREE_DATA, CMD_GET_FREE_SIZES, CMD_GET_TIME, CMD_READ_CHUNK, CMD_REFRESHDATA, HEADER, Response,
+    TABLE_ATTLOG, TABLE_USER, build_packet,
 };
+use super::user::{DeviceUser, parse_users};
 
 /// Device storage capacity information.
 #[derive(Debug, Clone)]
@@ -108,6 +113,28 @@ impl ZkTcpClient {
         Ok(capacity)
     }
 
+    /// Get the device clock time.
+    ///
+    /// The device reports its wall-clock time in the packed ZK timestamp format,
+    /// interpreted in the business timezone.
+    pub fn get_time(&mut self) -> Result<DateTime<Tz>> {
+        debug!("Getting device time");
+
+        let response = self.send_command(CMD_GET_TIME, &[])?;
+
+        if response.data.len() < 4 {
+            return Err(ZkError::InvalidResponse(format!(
+                "Expected 4 bytes for device time, got {}",
+                response.data.len()
+            )));
+        }
+
+        let encoded = u32::from_le_bytes([response.data[0], response.data[1], response.data[2], response.data[3]]);
+
+        decode_zk_datetime(encoded)
+            .ok_or_else(|| ZkError::InvalidResponse(format!("Invalid device time encoding: {encoded:#010x}")))
+    }
+
     /// Clear all attendance records from device.
     pub fn clear_attendance(&mut self) -> Result<()> {
         info!("Clearing attendance records from device");
@@ -128,17 +155,89 @@ impl ZkTcpClient {
     /// Get all attendance records from device.
     ///
     /// Reads the complete ATTLOG table from device flash storage.
-    /// First gets total size from DATA_WRRQ response, then reads chunks
-    /// with exact sizes to avoid requesting beyond available data.
     pub fn get_attendance(&mut self) -> Result<Vec<AttendanceRecord>> {
+        self.get_attendance_with_progress(|_, _| ControlFlow::Continue(()))
+    }
+
+    /// Get all attendance records with per-chunk progress.
+    ///
+    /// Calls `on_chunk(received, total_size)` in bytes after each chunk.
+    /// Returning `ControlFlow::Break` stops the download, frees the device
+    /// buffer and returns `ZkError::Cancelled`.
+    pub fn get_attendance_with_progress<F>(&mut self, on_chunk: F) -> Result<Vec<AttendanceRecord>>
+    where
+        F: FnMut(u32, u32) -> ControlFlow<()>,
+    {
         info!("Fetching attendance records from device");
 
         // Get device info first (required by protocol)
         self.send_command(CMD_GET_FREE_SIZES, &[])?;
         self.send_command(CMD_GET_FREE_SIZES, &[])?;
 
+        let data = self.read_table(&TABLE_ATTLOG, on_chunk)?;
+        info!("Downloaded {} bytes of attendance data", data.len());
+
+        // Parse records
+        let records = parse_attendance(&data);
+        info!("Parsed {} attendance records", records.len());
+
+        Ok(records)
+    }
+
+    /// Get all users enrolled on the device.
+    pub fn get_users(&mut self) -> Result<Vec<DeviceUser>> {
+        info!("Fetching users from device");
+
+        // The user count tells the record sizes apart
+        let sizes = self.send_command(CMD_GET_FREE_SIZES, &[])?;
+        let user_count = match sizes.data.get(16..20) {
+            Some(bytes) => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            None => 0,
+        };
+
+        let data = self.read_table(&TABLE_USER, |_, _| ControlFlow::Continue(()))?;
+        let users = parse_users(&data, user_count);
+        info!("Parsed {} device users", users.len());
+
+        Ok(users)
+    }
+
+    /// Delete the user with `user_id` from the device, with their fingerprints.
+    ///
+    /// Returns `false` if no such user is enrolled.
+    pub fn delete_user(&mut self, user_id: u32) -> Result<bool> {
+        let Some(user) = self.get_users()?.into_iter().find(|u| u.user_id == user_id) else {
+            info!("User {user_id} is not enrolled on the device");
+            return Ok(false);
+        };
+
+        info!("Deleting user {user_id} (slot {}) from device", user.uid);
+
+        let response = self.send_command(CMD_DELETE_USER, &user.uid.to_le_bytes())?;
+        if response.cmd != CMD_ACK_OK {
+            return Err(ZkError::InvalidResponse(format!(
+                "Expected CMD_ACK_OK ({CMD_ACK_OK}) after delete user, got {}",
+                response.cmd
+            )));
+        }
+
+        // Apply the change to the device's in-memory tables
+        self.send_command(CMD_REFRESHDATA, &[])?;
+
+        info!("User {user_id} deleted from device");
+        Ok(true)
+    }
+
+    /// Read a whole device table in chunks.
+    ///
+    /// First gets total size from DATA_WRRQ response, then reads chunks
+    /// with exact sizes to avoid requesting beyond available data.
+    fn read_table<F>(&mut self, table: &[u8], mut on_chunk: F) -> Result<Vec<u8>>
+    where
+        F: FnMut(u32, u32) -> ControlFlow<()>,
+    {
         // Send DATA_WRRQ - device responds with ACK_OK containing total size
-        let wrrq_response = self.send_command(CMD_DATA_WRRQ, &TABLE_ATTLOG)?;
+        let wrrq_response = self.send_command(CMD_DATA_WRRQ, table)?;
 
         // Device sends ACK_OK with total size in data[1..5], not DATA
         if wrrq_response.cmd != CMD_ACK_OK || wrrq_response.data.len() < 5 {
@@ -157,7 +256,7 @@ impl ZkTcpClient {
             wrrq_response.data[4],
         ]);
 
-        info!("Total attendance data size: {total_size} bytes");
+        debug!("Total table data size: {total_size} bytes");
 
         let mut all_data = Vec::new();
         let mut offset: u32 = 0;
@@ -203,18 +302,18 @@ impl ZkTcpClient {
 
             all_data.extend_from_slice(&chunk_data);
             offset += chunk_len;
+
+            if on_chunk(offset.min(total_size), total_size).is_break() {
+                info!("Download cancelled at {offset}/{total_size} bytes");
+                self.send_command(CMD_FREE_DATA, &[])?;
+                return Err(ZkError::Cancelled);
+            }
         }
 
         // Free buffer
         self.send_command(CMD_FREE_DATA, &[])?;
 
-        info!("Downloaded {} bytes of attendance data", all_data.len());
-
-        // Parse records
-        let records = parse_attendance(&all_data);
-        info!("Parsed {} attendance records", records.len());
-
-        Ok(records)
+        Ok(all_data)
     }
 
     /// Send a command to the device and read response.
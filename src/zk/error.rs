@@ -28,6 +28,10 @@ pub enum ZkError {
     /// No data available from device.
     #[error("No data available")]
     NoData,
+
+    /// Operation aborted by the caller.
+    #[error("Operation cancelled")]
+    Cancelled,
 }
 
 /// Result type for ZK protocol operations.
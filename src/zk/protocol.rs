@@ -12,7 +12,10 @@ pub const CHUNK_SIZE: u32 = 65472;
 pub const CMD_CONNECT: u16 = 1000;
 pub const CMD_EXIT: u16 = 1001;
 pub const CMD_CLEAR_ATTLOG: u16 = 15;
+pub const CMD_DELETE_USER: u16 = 18;
 pub const CMD_GET_FREE_SIZES: u16 = 50;
+pub const CMD_GET_TIME: u16 = 201;
+pub const CMD_REFRESHDATA: u16 = 1013;
 pub const CMD_ACK_OK: u16 = 2000; // General device ACK (0x07d0)
 pub const CMD_ACK_DATA: u16 = 1500; // Data transfer ACK (0x05dc)
 pub const CMD_DATA: u16 = 1501; // Data response (0x05dd)
@@ -23,6 +26,9 @@ pub const CMD_READ_CHUNK: u16 = 1504;
 /// ATTLOG table identifier for data request.
 pub const TABLE_ATTLOG: [u8; 11] = [0x01, 0x0d, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
 
+/// User table identifier for data request.
+pub const TABLE_USER: [u8; 11] = [0x01, 0x09, 0x00, 0x05, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+
 /// Parsed response from device.
 #[derive(Debug)]
 #[allow(dead_code)]
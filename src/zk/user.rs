@@ -0,0 +1,117 @@
+//! User table parsing for ZK devices.
+
+/// Size of each user record on current firmware.
+const RECORD_SIZE: usize = 72;
+
+/// Size of each user record on older firmware.
+const LEGACY_RECORD_SIZE: usize = 28;
+
+/// Size of data prefix before records start.
+const DATA_PREFIX_SIZE: usize = 4;
+
+/// User enrolled on the device.
+#[derive(Debug, Clone)]
+pub struct DeviceUser {
+    /// Internal slot number, used to address the user in commands.
+    pub uid: u16,
+    /// Employee user ID, matching `AttendanceRecord::user_id`.
+    pub user_id: u32,
+    /// Name entered on the device.
+    pub name: String,
+}
+
+/// Parse user data from device.
+///
+/// Data layout:
+/// - Bytes 0-3: Total size of the records (u32 LE)
+/// - Bytes 4+: Records, 72 bytes each (28 on older firmware)
+///
+/// `user_count` from the device capacity tells the two record sizes apart.
+///
+/// Record layout (72 bytes):
+/// - Bytes 0-1: Slot number (u16 LE)
+/// - Bytes 11-34: Name (ASCII string, null-terminated)
+/// - Bytes 48-71: User ID (ASCII string, null-terminated)
+///
+/// Record layout (28 bytes):
+/// - Bytes 0-1: Slot number (u16 LE)
+/// - Bytes 8-15: Name (ASCII string, null-terminated)
+/// - Bytes 24-27: User ID (u32 LE)
+pub fn parse_users(data: &[u8], user_count: u32) -> Vec<DeviceUser> {
+    if data.len() < DATA_PREFIX_SIZE {
+        return Vec::new();
+    }
+
+    let total_size = u32::from_le_bytes([data[0], data[1], data[2], data[3]]) as usize;
+    let record_size = match user_count {
+        0 if total_size.is_multiple_of(RECORD_SIZE) => RECORD_SIZE,
+        0 => LEGACY_RECORD_SIZE,
+        count if total_size / count as usize == LEGACY_RECORD_SIZE => LEGACY_RECORD_SIZE,
+        _ => RECORD_SIZE,
+    };
+
+    data[DATA_PREFIX_SIZE..]
+        .chunks_exact(record_size)
+        .filter_map(|chunk| {
+            let uid = u16::from_le_bytes([chunk[0], chunk[1]]);
+            let (name, user_id) = if record_size == RECORD_SIZE {
+                (ascii_field(&chunk[11..35]), ascii_field(&chunk[48..72]).parse().ok()?)
+            } else {
+                (
+                    ascii_field(&chunk[8..16]),
+                    u32::from_le_bytes([chunk[24], chunk[25], chunk[26], chunk[27]]),
+                )
+            };
+
+            Some(DeviceUser { uid, user_id, name })
+        })
+        .collect()
+}
+
+/// Null-terminated ASCII field.
+fn ascii_field(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_users() {
+        // 4-byte prefix + 2 records (72 bytes each)
+        let mut data = vec![0u8; 4 + 2 * RECORD_SIZE];
+        data[0..4].copy_from_slice(&(2 * RECORD_SIZE as u32).to_le_bytes());
+        for (idx, (uid, name, user_id)) in [(1u16, "An", "15"), (2, "Binh", "abc")].iter().enumerate() {
+            let record = &mut data[4 + idx * RECORD_SIZE..4 + (idx + 1) * RECORD_SIZE];
+            record[0..2].copy_from_slice(&uid.to_le_bytes());
+            record[11..11 + name.len()].copy_from_slice(name.as_bytes());
+            record[48..48 + user_id.len()].copy_from_slice(user_id.as_bytes());
+        }
+
+        // Non-numeric user IDs cannot match an employee and are skipped
+        let users = parse_users(&data, 2);
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].uid, 1);
+        assert_eq!(users[0].user_id, 15);
+        assert_eq!(users[0].name, "An");
+    }
+
+    #[test]
+    fn test_parse_legacy_users() {
+        let mut data = vec![0u8; 4 + LEGACY_RECORD_SIZE];
+        data[0..4].copy_from_slice(&(LEGACY_RECORD_SIZE as u32).to_le_bytes());
+        data[4..6].copy_from_slice(&7u16.to_le_bytes());
+        data[12..15].copy_from_slice(b"Cuc");
+        data[28..32].copy_from_slice(&42u32.to_le_bytes());
+
+        let users = parse_users(&data, 1);
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].uid, 7);
+        assert_eq!(users[0].user_id, 42);
+        assert_eq!(users[0].name, "Cuc");
+
+        assert!(parse_users(&[], 0).is_empty());
+    }
+}